@@ -0,0 +1,1092 @@
+use crate::error::AppError;
+use crate::ffmpeg::options::{MergeOptions, MergePreset};
+use crate::shortcuts::ShortcutAction;
+use crate::utils::{PowerAction, app_config_dir, wildcard_match};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 当前配置格式版本，修改格式时递增并在 [`MIGRATIONS`] 末尾追加迁移步骤
+pub const CONFIG_VERSION: u32 = 2;
+
+/// 迁移链：`MIGRATIONS[i]` 把版本 i 的配置升级到版本 i + 1
+const MIGRATIONS: [fn(&mut Value); CONFIG_VERSION as usize] = [migrate_v0_to_v1, migrate_v1_to_v2];
+
+/// 界面主题
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Theme {
+    /// 跟随系统
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::System => "跟随系统",
+            Theme::Light => "浅色",
+            Theme::Dark => "深色",
+        }
+    }
+
+    /// 写到 `<html data-theme>` 上的值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+}
+
+/// 界面语言，目前只有简体中文
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Language {
+    #[default]
+    ZhCn,
+}
+
+impl Language {
+    pub const ALL: [Language; 1] = [Language::ZhCn];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::ZhCn => "简体中文",
+        }
+    }
+
+    /// 完整时间的格式，用于悬停提示和导出
+    pub fn datetime_format(&self) -> &'static str {
+        match self {
+            Language::ZhCn => "%Y-%m-%d %H:%M:%S",
+        }
+    }
+
+    /// 只显示日期时的格式
+    pub fn date_format(&self) -> &'static str {
+        match self {
+            Language::ZhCn => "%Y-%m-%d",
+        }
+    }
+}
+
+/// 文件大小的单位制
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SizeUnits {
+    /// KiB/MiB，1024 进制
+    #[default]
+    Binary,
+    /// KB/MB，1000 进制
+    Decimal,
+}
+
+impl SizeUnits {
+    pub const ALL: [SizeUnits; 2] = [SizeUnits::Binary, SizeUnits::Decimal];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SizeUnits::Binary => "KiB / MiB（1024 进制）",
+            SizeUnits::Decimal => "KB / MB（1000 进制）",
+        }
+    }
+}
+
+/// 向合并列表添加已存在的文件时的处理方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum DuplicatePolicy {
+    /// 自动去重并提示
+    #[default]
+    Skip,
+    /// 询问后允许重复添加，列表中标出重复项
+    Confirm,
+}
+
+impl DuplicatePolicy {
+    pub const ALL: [DuplicatePolicy; 2] = [DuplicatePolicy::Skip, DuplicatePolicy::Confirm];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuplicatePolicy::Skip => "自动跳过",
+            DuplicatePolicy::Confirm => "询问是否重复添加",
+        }
+    }
+}
+
+/// 队列中的任务失败时的处理方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ErrorPolicy {
+    /// 标记失败后继续执行其余任务
+    #[default]
+    Continue,
+    /// 停止队列，其余任务保持等待
+    Stop,
+}
+
+impl ErrorPolicy {
+    pub const ALL: [ErrorPolicy; 2] = [ErrorPolicy::Continue, ErrorPolicy::Stop];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorPolicy::Continue => "继续执行其余任务",
+            ErrorPolicy::Stop => "停止队列",
+        }
+    }
+}
+
+/// 队列任务因暂时性错误（文件被占用、网络共享中断等）失败时的自动重试
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// 最多重试次数，0 表示不重试
+    pub max_retries: u32,
+    /// 第一次重试前等待的秒数，之后每次翻倍
+    pub base_delay_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay_secs: 5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 两次重试之间最长等待时间
+    const MAX_DELAY_SECS: u64 = 300;
+
+    /// 第 `attempt` 次重试（从 1 开始）前的等待时间
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        Duration::from_secs(
+            self.base_delay_secs
+                .saturating_mul(factor)
+                .min(Self::MAX_DELAY_SECS),
+        )
+    }
+}
+
+/// 大任务确认：预计输出或耗时超过阈值时，开始前先确认
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct JobGuard {
+    pub enabled: bool,
+    pub max_output_gb: f64,
+    pub max_hours: f64,
+}
+
+impl Default for JobGuard {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_output_gb: 20.0,
+            max_hours: 2.0,
+        }
+    }
+}
+
+impl JobGuard {
+    /// 估算结果超过阈值时返回超出的项目说明
+    pub fn exceeded(&self, output_bytes: u64, secs: Option<f64>) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if !self.enabled {
+            return reasons;
+        }
+        if output_bytes as f64 > self.max_output_gb * 1024.0 * 1024.0 * 1024.0 {
+            reasons.push(format!("输出超过 {} GB", self.max_output_gb));
+        }
+        if secs.is_some_and(|secs| secs > self.max_hours * 3600.0) {
+            reasons.push(format!("耗时超过 {} 小时", self.max_hours));
+        }
+        reasons
+    }
+}
+
+/// 扫描目录时的过滤规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ScanRules {
+    /// 同时扫描子文件夹
+    pub include_subfolders: bool,
+    /// 跳过以 `.` 开头或带隐藏属性的文件和文件夹
+    pub ignore_hidden: bool,
+    /// 排除的文件或文件夹名，支持 `*` 与 `?` 通配符，不区分大小写
+    pub exclude_patterns: Vec<String>,
+    /// 小于该大小（MB）的文件不列出，0 表示不限制
+    pub min_size_mb: f64,
+}
+
+impl Default for ScanRules {
+    fn default() -> Self {
+        Self {
+            include_subfolders: false,
+            ignore_hidden: true,
+            exclude_patterns: Vec::new(),
+            min_size_mb: 0.0,
+        }
+    }
+}
+
+impl ScanRules {
+    /// 名称是否命中排除规则
+    pub fn excludes(&self, name: &str) -> bool {
+        self.exclude_patterns
+            .iter()
+            .any(|pattern| wildcard_match(pattern.trim(), name))
+    }
+
+    /// 文件大小是否低于下限
+    pub fn too_small(&self, size: u64) -> bool {
+        (size as f64) < self.min_size_mb * 1024.0 * 1024.0
+    }
+}
+
+/// 窗口行为，启动时应用到主窗口
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct WindowSettings {
+    pub start_maximized: bool,
+    /// 关闭时记下窗口大小，下次启动恢复
+    pub remember_size: bool,
+    /// 上次关闭时的窗口大小（逻辑像素）
+    pub last_size: Option<(f64, f64)>,
+    /// 最小窗口大小（逻辑像素）
+    pub min_width: f64,
+    pub min_height: f64,
+    pub always_on_top: bool,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            start_maximized: false,
+            remember_size: true,
+            last_size: None,
+            min_width: 640.0,
+            min_height: 480.0,
+            always_on_top: false,
+        }
+    }
+}
+
+impl WindowSettings {
+    /// 未记住大小时的默认窗口大小
+    pub const DEFAULT_SIZE: (f64, f64) = (900.0, 700.0);
+
+    /// 启动时的窗口大小：优先使用上次的大小，不超过显示器逻辑尺寸的 90%，
+    /// 也不小于最小窗口大小（显示器比最小窗口还小时占满）
+    pub fn initial_size(&self, monitor_width: f64, monitor_height: f64) -> (f64, f64) {
+        let (width, height) = self
+            .last_size
+            .filter(|_| self.remember_size)
+            .unwrap_or(Self::DEFAULT_SIZE);
+        let fit = |preferred: f64, min: f64, available: f64| {
+            preferred.min(available * 0.9).max(min.min(available))
+        };
+        (
+            fit(width, self.min_width, monitor_width),
+            fit(height, self.min_height, monitor_height),
+        )
+    }
+}
+
+/// 输出目录规则：输入文件位于 `input_dir`（含子文件夹）时输出到 `output_dir`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OutputRoute {
+    pub input_dir: PathBuf,
+    pub output_dir: PathBuf,
+}
+
+impl OutputRoute {
+    /// 文件是否位于规则的输入目录下，Windows 上不区分大小写
+    pub fn matches(&self, file: &Path) -> bool {
+        if cfg!(windows) {
+            let lower = |p: &Path| PathBuf::from(p.to_string_lossy().to_lowercase());
+            lower(file).starts_with(lower(&self.input_dir))
+        } else {
+            file.starts_with(&self.input_dir)
+        }
+    }
+}
+
+/// 清理建议的判定规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct CleanupRules {
+    /// 短于该时长（秒）的片段视为误录
+    pub min_duration_secs: f64,
+    /// 修改时间早于该天数的片段建议清理，0 表示不检查
+    pub stale_days: u32,
+    /// 只对匹配的旧片段提出建议，支持 `*` 与 `?` 通配符，留空匹配所有文件
+    pub stale_pattern: String,
+}
+
+impl Default for CleanupRules {
+    fn default() -> Self {
+        Self {
+            min_duration_secs: 1.0,
+            stale_days: 0,
+            stale_pattern: String::new(),
+        }
+    }
+}
+
+impl CleanupRules {
+    /// 文件名是否在旧片段规则的范围内
+    pub fn stale_matches(&self, name: &str) -> bool {
+        let pattern = self.stale_pattern.trim();
+        pattern.is_empty() || wildcard_match(pattern, name)
+    }
+}
+
+/// 扫描列表的排序字段
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScannerSort {
+    Name,
+    #[default]
+    Duration,
+    Size,
+    Modified,
+}
+
+impl ScannerSort {
+    /// 首次按该字段排序时的方向：时长、大小与日期默认从大到小
+    pub fn default_desc(&self) -> bool {
+        !matches!(self, ScannerSort::Name)
+    }
+}
+
+/// 扫描结果的显示方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScannerLayout {
+    #[default]
+    Table,
+    /// 带缩略图的卡片
+    Grid,
+}
+
+/// 扫描列表中可以隐藏的列
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScannerColumn {
+    Resolution,
+    Codec,
+    Duration,
+    Size,
+    Modified,
+}
+
+impl ScannerColumn {
+    pub const ALL: [ScannerColumn; 5] = [
+        ScannerColumn::Resolution,
+        ScannerColumn::Codec,
+        ScannerColumn::Duration,
+        ScannerColumn::Size,
+        ScannerColumn::Modified,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScannerColumn::Resolution => "分辨率",
+            ScannerColumn::Codec => "编码格式",
+            ScannerColumn::Duration => "时长",
+            ScannerColumn::Size => "大小",
+            ScannerColumn::Modified => "修改日期",
+        }
+    }
+}
+
+/// 扫描列表的视图状态，按目录分别保存
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ScannerView {
+    pub layout: ScannerLayout,
+    pub sort: ScannerSort,
+    pub sort_desc: bool,
+    pub page_size: usize,
+    /// 文件名包含的关键字，为空时不筛选
+    pub name_filter: String,
+    /// 只显示该编码格式的文件
+    pub codec_filter: Option<String>,
+    pub hidden_columns: Vec<ScannerColumn>,
+}
+
+impl Default for ScannerView {
+    fn default() -> Self {
+        Self {
+            layout: ScannerLayout::default(),
+            sort: ScannerSort::default(),
+            sort_desc: true,
+            page_size: 20,
+            name_filter: String::new(),
+            codec_filter: None,
+            hidden_columns: Vec::new(),
+        }
+    }
+}
+
+impl ScannerView {
+    pub fn shows(&self, column: ScannerColumn) -> bool {
+        !self.hidden_columns.contains(&column)
+    }
+
+    /// 点击列头排序：同一列切换方向，换列时使用该列的默认方向
+    pub fn toggle_sort(&mut self, sort: ScannerSort) {
+        if self.sort == sort {
+            self.sort_desc = !self.sort_desc;
+        } else {
+            self.sort = sort;
+            self.sort_desc = sort.default_desc();
+        }
+    }
+
+    /// 是否设置了筛选条件
+    pub fn is_filtered(&self) -> bool {
+        !self.name_filter.trim().is_empty() || self.codec_filter.is_some()
+    }
+}
+
+/// 本机 HTTP 接口：只监听 127.0.0.1，请求需携带令牌
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 38080,
+            token: String::new(),
+        }
+    }
+}
+
+// 调试输出中隐去令牌
+impl fmt::Debug for ApiSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiSettings")
+            .field("enabled", &self.enabled)
+            .field("port", &self.port)
+            .field("token", &"***")
+            .finish()
+    }
+}
+
+/// 上传目标类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum UploadKind {
+    #[default]
+    WebDav,
+    /// S3 及兼容接口（MinIO、R2、OSS 等）
+    S3,
+}
+
+impl UploadKind {
+    pub const ALL: [UploadKind; 2] = [UploadKind::WebDav, UploadKind::S3];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            UploadKind::WebDav => "WebDAV",
+            UploadKind::S3 => "S3 兼容存储",
+        }
+    }
+}
+
+/// 合并成功后把输出文件上传到 WebDAV 或 S3 兼容存储
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct UploadSettings {
+    pub enabled: bool,
+    pub kind: UploadKind,
+    /// WebDAV 为上传目录的地址；S3 为服务地址，如 `https://s3.us-east-1.amazonaws.com`
+    pub url: String,
+    /// S3 存储桶，WebDAV 不使用
+    pub bucket: String,
+    /// S3 区域，为空时使用 us-east-1
+    pub region: String,
+    /// WebDAV 用户名或 S3 Access Key
+    pub username: String,
+    /// WebDAV 密码或 S3 Secret Key
+    pub password: String,
+    /// 对外访问地址的前缀，为空时使用上传地址作为链接
+    pub public_url: String,
+    /// 上传完成后把链接复制到剪贴板
+    pub copy_link: bool,
+}
+
+impl Default for UploadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: UploadKind::default(),
+            url: String::new(),
+            bucket: String::new(),
+            region: String::new(),
+            username: String::new(),
+            password: String::new(),
+            public_url: String::new(),
+            copy_link: true,
+        }
+    }
+}
+
+// 调试输出中隐去密码
+impl fmt::Debug for UploadSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadSettings")
+            .field("enabled", &self.enabled)
+            .field("kind", &self.kind)
+            .field("url", &self.url)
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("username", &self.username)
+            .field("password", &"***")
+            .field("public_url", &self.public_url)
+            .field("copy_link", &self.copy_link)
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppConfig {
+    /// 配置格式版本，旧配置没有该字段时视为 0
+    #[serde(default)]
+    pub version: u32,
+    pub output_directory: Option<PathBuf>,
+    pub last_input_directory: Option<PathBuf>,
+    /// 勾选“完成后关机/睡眠”时执行的电源操作
+    #[serde(default)]
+    pub power_action: PowerAction,
+    /// 用户保存的合并选项预设
+    #[serde(default)]
+    pub presets: Vec<MergePreset>,
+    /// 用户修改过的快捷键，未出现的操作使用默认绑定
+    #[serde(default)]
+    pub shortcuts: HashMap<ShortcutAction, String>,
+    /// 有任务执行时关闭窗口只隐藏到托盘
+    #[serde(default)]
+    pub minimize_to_tray: bool,
+    /// 新建合并工作区时使用的默认选项
+    #[serde(default)]
+    pub default_options: MergeOptions,
+    /// 是否已完成首次启动向导
+    #[serde(default)]
+    pub setup_completed: bool,
+    /// 用户手动指定的 FFmpeg 所在目录，启动时加入 PATH
+    #[serde(default)]
+    pub ffmpeg_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub language: Language,
+    #[serde(default)]
+    pub size_units: SizeUnits,
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+    #[serde(default)]
+    pub error_policy: ErrorPolicy,
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
+    #[serde(default)]
+    pub job_guard: JobGuard,
+    /// 合并成功后计算输出与输入的 SHA-256 并写入历史记录
+    #[serde(default)]
+    pub compute_checksums: bool,
+    /// 归档原文件时使用的子文件夹名
+    #[serde(default = "default_archive_folder")]
+    pub archive_folder: String,
+    /// 合并成功后执行的命令，为空时不执行
+    #[serde(default)]
+    pub post_command: String,
+    #[serde(default)]
+    pub api: ApiSettings,
+    #[serde(default)]
+    pub scan_rules: ScanRules,
+    #[serde(default)]
+    pub cleanup_rules: CleanupRules,
+    /// 各目录的扫描列表视图（排序、每页数量、筛选与列）
+    #[serde(default)]
+    pub scanner_views: HashMap<PathBuf, ScannerView>,
+    /// 固定在扫描页的常用目录，按固定的先后排列
+    #[serde(default)]
+    pub pinned_directories: Vec<PathBuf>,
+    /// 切换到固定目录后立即扫描
+    #[serde(default)]
+    pub scan_on_switch: bool,
+    /// 输出目录所在磁盘可用空间低于该值（GB）时提醒，0 表示不提醒
+    #[serde(default = "default_low_space_gb")]
+    pub low_space_gb: f64,
+    /// 在后台为 4K 等高分辨率素材生成低分辨率代理，供剪切分析和预览使用
+    #[serde(default)]
+    pub generate_proxies: bool,
+    /// 按输入所在目录选择输出目录，未命中时使用 `output_directory`
+    #[serde(default)]
+    pub output_routes: Vec<OutputRoute>,
+    /// 上次关闭时所在的标签页，启动时恢复
+    #[serde(default)]
+    pub last_tab: Option<String>,
+    #[serde(default)]
+    pub window: WindowSettings,
+    #[serde(default)]
+    pub upload: UploadSettings,
+}
+
+fn default_archive_folder() -> String {
+    "已合并".to_string()
+}
+
+fn default_low_space_gb() -> f64 {
+    10.0
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            output_directory: None,
+            last_input_directory: None,
+            power_action: PowerAction::default(),
+            presets: Vec::new(),
+            shortcuts: HashMap::new(),
+            minimize_to_tray: false,
+            default_options: MergeOptions::default(),
+            setup_completed: false,
+            ffmpeg_dir: None,
+            theme: Theme::default(),
+            language: Language::default(),
+            size_units: SizeUnits::default(),
+            duplicate_policy: DuplicatePolicy::default(),
+            error_policy: ErrorPolicy::default(),
+            retry_policy: RetryPolicy::default(),
+            job_guard: JobGuard::default(),
+            compute_checksums: false,
+            archive_folder: default_archive_folder(),
+            post_command: String::new(),
+            api: ApiSettings::default(),
+            scan_rules: ScanRules::default(),
+            cleanup_rules: CleanupRules::default(),
+            scanner_views: HashMap::new(),
+            pinned_directories: Vec::new(),
+            scan_on_switch: false,
+            low_space_gb: default_low_space_gb(),
+            generate_proxies: false,
+            output_routes: Vec::new(),
+            last_tab: None,
+            window: WindowSettings::default(),
+            upload: UploadSettings::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Load configuration from file
+    ///
+    /// 旧版本的配置会先备份为 `config.v{版本}.bak.json`，迁移后写回
+    pub fn load() -> Result<Self, AppError> {
+        Self::load_from(&Self::config_path()?)
+    }
+
+    fn load_from(config_path: &Path) -> Result<Self, AppError> {
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content =
+            fs::read_to_string(config_path).map_err(|e| AppError::io("读取配置文件失败", e))?;
+        let mut value: Value =
+            serde_json::from_str(&content).map_err(|e| AppError::Config(e.to_string()))?;
+        let from_version = match migrate(&mut value) {
+            Ok(version) => version,
+            Err(e) => {
+                // 之后回退到默认配置保存时会覆盖原文件，先留一份备份
+                let _ = fs::copy(
+                    config_path,
+                    config_path.with_file_name("config.newer.bak.json"),
+                );
+                return Err(AppError::Config(e));
+            }
+        };
+        let config: AppConfig =
+            serde_json::from_value(value).map_err(|e| AppError::Config(e.to_string()))?;
+
+        if from_version < CONFIG_VERSION {
+            let backup = config_path.with_file_name(format!("config.v{}.bak.json", from_version));
+            fs::copy(config_path, &backup).map_err(|e| AppError::io("备份配置文件失败", e))?;
+            config.save_to(config_path)?;
+        }
+
+        Ok(config)
+    }
+
+    /// 导入其他位置的配置文件（如从旧电脑复制的 config.json），按需迁移
+    pub fn import_from(path: &Path) -> Result<Self, AppError> {
+        let content = fs::read_to_string(path).map_err(|e| AppError::io("读取配置文件失败", e))?;
+        let mut value: Value =
+            serde_json::from_str(&content).map_err(|e| AppError::Config(e.to_string()))?;
+        migrate(&mut value).map_err(AppError::Config)?;
+        serde_json::from_value(value).map_err(|e| AppError::Config(e.to_string()))
+    }
+
+    /// Save configuration to file
+    pub fn save(&self) -> Result<(), AppError> {
+        self.save_to(&Self::config_path()?)
+    }
+
+    fn save_to(&self, config_path: &Path) -> Result<(), AppError> {
+        // Ensure directory exists
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io("创建配置目录失败", e))?;
+        }
+
+        let content =
+            serde_json::to_string_pretty(self).map_err(|e| AppError::Config(e.to_string()))?;
+
+        fs::write(config_path, content).map_err(|e| AppError::io("写入配置文件失败", e))?;
+
+        Ok(())
+    }
+
+    /// Get the configuration file path
+    fn config_path() -> Result<PathBuf, AppError> {
+        let app_config_dir = app_config_dir().map_err(|e| AppError::io("无法定位配置目录", e))?;
+        Ok(app_config_dir.join("config.json"))
+    }
+
+    /// Set output directory and save configuration
+    pub fn set_output_directory(&mut self, path: PathBuf) -> Result<(), AppError> {
+        self.output_directory = Some(path);
+        self.save()
+    }
+
+    /// Get output directory, falling back to current directory if not set
+    pub fn get_output_directory(&self) -> PathBuf {
+        self.output_directory
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+    /// 第一个输入文件命中的输出规则，多条命中时取输入目录最具体的一条
+    pub fn output_route_for(&self, inputs: &[PathBuf]) -> Option<&OutputRoute> {
+        let first = inputs.first()?;
+        self.output_routes
+            .iter()
+            .filter(|route| route.matches(first))
+            .max_by_key(|route| route.input_dir.components().count())
+    }
+
+    /// 合并 `inputs` 时使用的输出目录：命中规则时用规则的目录，否则同 [`Self::get_output_directory`]
+    pub fn output_directory_for(&self, inputs: &[PathBuf]) -> PathBuf {
+        self.output_route_for(inputs)
+            .map(|route| route.output_dir.clone())
+            .unwrap_or_else(|| self.get_output_directory())
+    }
+
+    /// 设置最后一个输入目录并保存配置
+    pub fn set_last_input_directory(&mut self, path: PathBuf) -> Result<(), AppError> {
+        self.last_input_directory = Some(path);
+        self.save()
+    }
+
+    /// 获取最后一个输入目录，如果未设置，则回退到None
+    pub fn get_last_input_directory(&self) -> Option<PathBuf> {
+        self.last_input_directory.clone()
+    }
+    /// 设置需要查询的目录
+    pub fn set_query_directory(&mut self, path: PathBuf) -> Result<(), AppError> {
+        self.last_input_directory = Some(path);
+        self.save()
+    }
+    /// 获取需要查询的目录，如果未设置，则回退到None
+    pub fn get_query_directory(&self) -> Option<PathBuf> {
+        self.last_input_directory.clone()
+    }
+    /// 目录的扫描列表视图，没有保存过时使用默认视图
+    pub fn scanner_view(&self, dir: &Path) -> ScannerView {
+        self.scanner_views.get(dir).cloned().unwrap_or_default()
+    }
+    /// 保存目录的扫描列表视图，与默认视图相同时移除记录
+    pub fn set_scanner_view(&mut self, dir: PathBuf, view: ScannerView) -> Result<(), AppError> {
+        if view == ScannerView::default() {
+            self.scanner_views.remove(&dir);
+        } else {
+            self.scanner_views.insert(dir, view);
+        }
+        self.save()
+    }
+    /// 可用空间是否低于提醒阈值
+    pub fn is_low_space(&self, free_bytes: u64) -> bool {
+        (free_bytes as f64) < self.low_space_gb * 1024.0 * 1024.0 * 1024.0
+    }
+    /// 目录是否已固定
+    pub fn is_pinned(&self, dir: &Path) -> bool {
+        self.pinned_directories.iter().any(|d| d == dir)
+    }
+    /// 固定或取消固定目录并保存配置
+    pub fn toggle_pinned_directory(&mut self, dir: PathBuf) -> Result<(), AppError> {
+        if self.is_pinned(&dir) {
+            self.pinned_directories.retain(|d| *d != dir);
+        } else {
+            self.pinned_directories.push(dir);
+        }
+        self.save()
+    }
+    /// 获取操作当前绑定的快捷键
+    pub fn shortcut(&self, action: ShortcutAction) -> String {
+        self.shortcuts
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_binding().to_string())
+    }
+    /// 修改快捷键并保存配置，与默认绑定相同时移除自定义项
+    pub fn set_shortcut(
+        &mut self,
+        action: ShortcutAction,
+        binding: String,
+    ) -> Result<(), AppError> {
+        if binding == action.default_binding() {
+            self.shortcuts.remove(&action);
+        } else {
+            self.shortcuts.insert(action, binding);
+        }
+        self.save()
+    }
+}
+
+/// 依次执行迁移步骤，把配置升级到当前版本，返回原始版本号
+fn migrate(value: &mut Value) -> Result<u32, String> {
+    let from_version = value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if from_version > CONFIG_VERSION {
+        return Err(format!(
+            "配置文件版本 {} 高于当前程序支持的版本 {}，请升级程序",
+            from_version, CONFIG_VERSION
+        ));
+    }
+    for (version, step) in MIGRATIONS.iter().enumerate().skip(from_version as usize) {
+        step(value);
+        value["version"] = json!(version + 1);
+    }
+    Ok(from_version)
+}
+
+// v0：没有版本号的旧配置。后来新增的字段都有 serde 默认值，这里只把清空目录时留下的空字符串改为 null
+fn migrate_v0_to_v1(value: &mut Value) {
+    let Some(map) = value.as_object_mut() else {
+        return;
+    };
+    for key in ["output_directory", "last_input_directory"] {
+        if map.get(key).and_then(Value::as_str) == Some("") {
+            map.insert(key.to_string(), Value::Null);
+        }
+    }
+}
+
+// v1 → v2：新增首次启动向导。已有配置说明程序用过，不再显示向导
+fn migrate_v1_to_v2(value: &mut Value) {
+    if let Some(map) = value.as_object_mut() {
+        map.insert("setup_completed".to_string(), json!(true));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v0_config_is_migrated() {
+        let mut value = json!({
+            "output_directory": "",
+            "last_input_directory": "/videos"
+        });
+        assert_eq!(migrate(&mut value), Ok(0));
+        assert_eq!(value["version"], json!(CONFIG_VERSION));
+        assert_eq!(value["output_directory"], Value::Null);
+
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.last_input_directory, Some(PathBuf::from("/videos")));
+        assert!(config.presets.is_empty());
+    }
+
+    #[test]
+    fn v0_file_loads_through_migration_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        // 加入版本号之前保存的配置文件原样
+        fs::write(
+            &path,
+            "{\n  \"output_directory\": \"D:\\\\Videos\\\\merged\",\n  \"last_input_directory\": null\n}",
+        )
+        .unwrap();
+
+        let config = AppConfig::load_from(&path).unwrap();
+        assert_eq!(
+            config.output_directory,
+            Some(PathBuf::from("D:\\Videos\\merged"))
+        );
+        assert_eq!(config.power_action, PowerAction::default());
+        assert!(config.setup_completed);
+        assert!(dir.path().join("config.v0.bak.json").is_file());
+
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], json!(CONFIG_VERSION));
+        let reloaded = AppConfig::load_from(&path).unwrap();
+        assert_eq!(
+            serde_json::to_value(reloaded).unwrap(),
+            serde_json::to_value(config).unwrap()
+        );
+    }
+
+    #[test]
+    fn existing_config_skips_setup_wizard() {
+        let mut value = json!({ "version": 1, "output_directory": null });
+        assert_eq!(migrate(&mut value), Ok(1));
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert!(config.setup_completed);
+        assert!(!AppConfig::default().setup_completed);
+    }
+
+    #[test]
+    fn retry_delay_backs_off() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(1), Duration::from_secs(5));
+        assert_eq!(policy.delay(2), Duration::from_secs(10));
+        assert_eq!(policy.delay(3), Duration::from_secs(20));
+        assert_eq!(policy.delay(40), Duration::from_secs(300));
+        assert_eq!(policy.delay(100), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn window_fits_scaled_monitor() {
+        let window = WindowSettings::default();
+        // 1920x1080 在 100% 缩放下使用默认大小
+        assert_eq!(
+            window.initial_size(1920.0, 1080.0),
+            WindowSettings::DEFAULT_SIZE
+        );
+        // 1366x768 在 150% 缩放下约为 911x512 逻辑像素
+        let (width, height) = window.initial_size(1366.0 / 1.5, 768.0 / 1.5);
+        assert!(width <= 1366.0 / 1.5 * 0.9);
+        assert_eq!(height, window.min_height);
+        // 比最小窗口还小的屏幕占满
+        assert_eq!(window.initial_size(600.0, 400.0), (600.0, 400.0));
+
+        let remembered = WindowSettings {
+            last_size: Some((1200.0, 800.0)),
+            ..window
+        };
+        assert_eq!(remembered.initial_size(1920.0, 1080.0), (1200.0, 800.0));
+        let forgotten = WindowSettings {
+            remember_size: false,
+            ..remembered
+        };
+        assert_eq!(
+            forgotten.initial_size(1920.0, 1080.0),
+            WindowSettings::DEFAULT_SIZE
+        );
+    }
+
+    #[test]
+    fn job_guard_flags_huge_jobs() {
+        let guard = JobGuard::default();
+        let gb = 1024 * 1024 * 1024;
+        assert!(guard.exceeded(5 * gb, Some(3600.0)).is_empty());
+        assert_eq!(guard.exceeded(25 * gb, Some(3.0 * 3600.0)).len(), 2);
+        let disabled = JobGuard {
+            enabled: false,
+            ..guard
+        };
+        assert!(disabled.exceeded(25 * gb, None).is_empty());
+    }
+
+    #[test]
+    fn scan_rules_match_names_and_sizes() {
+        let rules = ScanRules {
+            exclude_patterns: vec!["*.tmp.mp4".to_string(), " node_modules ".to_string()],
+            min_size_mb: 1.0,
+            ..ScanRules::default()
+        };
+        assert!(rules.excludes("clip.TMP.mp4"));
+        assert!(rules.excludes("node_modules"));
+        assert!(!rules.excludes("clip.mp4"));
+        assert!(rules.too_small(512 * 1024));
+        assert!(!rules.too_small(2 * 1024 * 1024));
+        assert!(!ScanRules::default().too_small(0));
+    }
+
+    #[test]
+    fn scanner_sort_toggles_direction() {
+        let mut view = ScannerView::default();
+        view.toggle_sort(ScannerSort::Duration);
+        assert!(!view.sort_desc);
+        view.toggle_sort(ScannerSort::Name);
+        assert_eq!(view.sort, ScannerSort::Name);
+        assert!(!view.sort_desc);
+        view.toggle_sort(ScannerSort::Size);
+        assert!(view.sort_desc);
+    }
+
+    #[test]
+    fn most_specific_output_route_wins() {
+        let route = |input: &str, output: &str| OutputRoute {
+            input_dir: PathBuf::from(input),
+            output_dir: PathBuf::from(output),
+        };
+        let config = AppConfig {
+            output_directory: Some(PathBuf::from("/default")),
+            output_routes: vec![
+                route("/videos", "/published"),
+                route("/videos/obs", "/published/obs"),
+            ],
+            ..AppConfig::default()
+        };
+        let dir_for = |file: &str| config.output_directory_for(&[PathBuf::from(file)]);
+        assert_eq!(
+            dir_for("/videos/obs/a.mp4"),
+            PathBuf::from("/published/obs")
+        );
+        assert_eq!(dir_for("/videos/phone/b.mp4"), PathBuf::from("/published"));
+        assert_eq!(dir_for("/videos-old/c.mp4"), PathBuf::from("/default"));
+        assert_eq!(config.output_directory_for(&[]), PathBuf::from("/default"));
+    }
+
+    #[test]
+    fn debug_output_hides_secrets() {
+        let upload = UploadSettings {
+            password: "s3-secret".to_string(),
+            ..Default::default()
+        };
+        let debug = format!("{:?}", upload);
+        assert!(!debug.contains("s3-secret"));
+        assert!(debug.contains("password: \"***\""));
+
+        let api = ApiSettings {
+            token: "bearer-token".to_string(),
+            ..Default::default()
+        };
+        assert!(!format!("{:?}", api).contains("bearer-token"));
+    }
+
+    #[test]
+    fn current_config_is_unchanged() {
+        let original = serde_json::to_value(AppConfig::default()).unwrap();
+        let mut value = original.clone();
+        assert_eq!(migrate(&mut value), Ok(CONFIG_VERSION));
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn newer_config_is_rejected() {
+        let mut value = json!({ "version": CONFIG_VERSION + 1 });
+        assert!(migrate(&mut value).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! 全局错误类型。界面按种类给出针对性的提示与补救（如重新定位丢失的文件），
+//! 无法细分的错误仍保留原始文字，再由 [`ErrorCode::classify`] 识别常见原因
+use crate::ffmpeg::command::CommandError;
+use crate::ffmpeg::error::ErrorCode;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error)]
+pub enum AppError {
+    /// 文件读写失败，`context` 说明当时在做什么
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        #[source]
+        source: Arc<io::Error>,
+    },
+    #[error("文件不存在: {}", .0.display())]
+    FileNotFound(PathBuf),
+    #[error("未找到FFmpeg，请确保已安装并添加到系统PATH中")]
+    FfmpegNotFound,
+    /// FFmpeg 进程异常退出，内容含退出码与最后一行输出
+    #[error("{0}")]
+    Ffmpeg(String),
+    /// 读取视频信息失败
+    #[error("无法读取视频信息 {}: {message}", path.display())]
+    Probe { path: PathBuf, message: String },
+    /// 探测时 FFmpeg 长时间没有返回，文件可能已损坏
+    #[error("读取 {} 时 FFmpeg 超过 {secs} 秒未响应，文件可能已损坏", path.display())]
+    ProbeTimeout { path: PathBuf, secs: u64 },
+    /// 文件内容无法解析
+    #[error("{0}")]
+    Parse(String),
+    #[error("配置文件有误: {0}")]
+    Config(String),
+    #[error("已取消")]
+    Cancelled,
+    /// 其他不需要单独处理的错误
+    #[error("{0}")]
+    Other(String),
+}
+
+impl AppError {
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        AppError::Io {
+            context: context.into(),
+            source: Arc::new(source),
+        }
+    }
+
+    /// 对应的错误码，未识别时返回 None
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            AppError::FfmpegNotFound => Some(ErrorCode::FfmpegMissing),
+            AppError::ProbeTimeout { .. } => Some(ErrorCode::ProbeTimeout),
+            AppError::Io { source, .. } => match source.kind() {
+                io::ErrorKind::PermissionDenied => Some(ErrorCode::PermissionDenied),
+                io::ErrorKind::StorageFull => Some(ErrorCode::DiskFull),
+                _ => ErrorCode::classify(&self.to_string()),
+            },
+            AppError::FileNotFound(_) | AppError::Cancelled => None,
+            _ => ErrorCode::classify(&self.to_string()),
+        }
+    }
+
+    /// 文件被暂时占用、网络共享短暂中断等稍后重试可能成功的错误
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AppError::Io { source, .. }
+                if matches!(
+                    source.kind(),
+                    io::ErrorKind::Interrupted
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::WouldBlock
+                        | io::ErrorKind::ResourceBusy
+                        | io::ErrorKind::ConnectionReset
+                        | io::ErrorKind::ConnectionAborted
+                ) =>
+            {
+                true
+            }
+            AppError::Cancelled | AppError::FfmpegNotFound | AppError::FileNotFound(_) => false,
+            _ => {
+                let lower = self.to_string().to_lowercase();
+                TRANSIENT_PATTERNS.iter().any(|p| lower.contains(p))
+            }
+        }
+    }
+}
+
+// 暂时性错误在错误文本中的特征，按小写匹配
+const TRANSIENT_PATTERNS: &[&str] = &[
+    "being used by another process",
+    "另一个程序正在使用此文件",
+    "resource temporarily unavailable",
+    "device or resource busy",
+    "network name is no longer available",
+    "unexpected network error",
+    "connection reset",
+    "(os error 32)",
+    "(os error 33)",
+    "(os error 59)",
+    "(os error 64)",
+];
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
+
+impl From<CommandError> for AppError {
+    fn from(error: CommandError) -> Self {
+        match error {
+            CommandError::Spawn(e) if e.kind() == io::ErrorKind::NotFound => {
+                AppError::FfmpegNotFound
+            }
+            CommandError::Spawn(e) => AppError::io("启动FFmpeg失败", e),
+            CommandError::Wait(e) => AppError::io("等待FFmpeg进程失败", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_map_to_codes() {
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert_eq!(
+            AppError::io("写入输出文件失败", denied).code(),
+            Some(ErrorCode::PermissionDenied)
+        );
+        let spawn = CommandError::Spawn(io::Error::from(io::ErrorKind::NotFound));
+        assert_eq!(AppError::from(spawn).code(), Some(ErrorCode::FfmpegMissing));
+        let dts = "FFmpeg进程异常退出，退出码: exit code: 1：Non-monotonous DTS".to_string();
+        assert_eq!(AppError::Ffmpeg(dts).code(), Some(ErrorCode::CodecMismatch));
+        let missing = AppError::FileNotFound(PathBuf::from("a.mp4"));
+        assert_eq!(missing.to_string(), "文件不存在: a.mp4");
+        assert_eq!(missing.code(), None);
+    }
+
+    #[test]
+    fn transient_errors_are_retryable() {
+        let busy = io::Error::from(io::ErrorKind::ResourceBusy);
+        assert!(AppError::io("写入输出文件失败", busy).is_transient());
+        let locked = AppError::Ffmpeg(
+            "a.mp4: The process cannot access the file because it is being used by another process. (os error 32)"
+                .to_string(),
+        );
+        assert!(locked.is_transient());
+        assert!(!AppError::Cancelled.is_transient());
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert!(!AppError::io("写入输出文件失败", denied).is_transient());
+        let dts = "FFmpeg进程异常退出，退出码: exit code: 1：Non-monotonous DTS".to_string();
+        assert!(!AppError::Ffmpeg(dts).is_transient());
+    }
+}
@@ -0,0 +1,317 @@
+//! FFmpeg 命令行参数的构建器。
+//!
+//! FFmpeg 对参数位置敏感：`-ss`/`-t`/`-f` 写在 `-i` 之前作用于输入，写在之后作用于输出。
+//! [`FfmpegCommand`] 按输入、滤镜、映射、编码、输出的固定顺序生成参数，调用方无需关心先后。
+use crate::ffmpeg::encoder::NULL_OUTPUT;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// 一个输入文件及其输入参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct FfmpegInput {
+    options: Vec<OsString>,
+    path: OsString,
+}
+
+impl FfmpegInput {
+    pub fn new(path: impl Into<OsString>) -> Self {
+        Self {
+            options: Vec::new(),
+            path: path.into(),
+        }
+    }
+
+    /// 从第 `secs` 秒开始读取
+    pub fn seek(self, secs: f64) -> Self {
+        self.option("-ss", format!("{:.3}", secs))
+    }
+
+    /// 只读取 `secs` 秒
+    pub fn duration(self, secs: f64) -> Self {
+        self.option("-t", format!("{:.3}", secs))
+    }
+
+    /// 指定输入格式，如 concat
+    pub fn format(self, format: &str) -> Self {
+        self.option("-f", format)
+    }
+
+    pub fn option(mut self, key: &str, value: impl Into<OsString>) -> Self {
+        self.options.push(key.into());
+        self.options.push(value.into());
+        self
+    }
+}
+
+impl From<&Path> for FfmpegInput {
+    fn from(path: &Path) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<PathBuf> for FfmpegInput {
+    fn from(path: PathBuf) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&PathBuf> for FfmpegInput {
+    fn from(path: &PathBuf) -> Self {
+        Self::new(path)
+    }
+}
+
+/// 一次 FFmpeg 调用：若干输入，一个输出
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FfmpegCommand {
+    inputs: Vec<FfmpegInput>,
+    filter_complex: Option<String>,
+    maps: Vec<String>,
+    video_filters: Vec<String>,
+    codecs: Vec<OsString>,
+    options: Vec<OsString>,
+    format: Option<String>,
+    output: Option<OsString>,
+}
+
+impl FfmpegCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn input(mut self, input: impl Into<FfmpegInput>) -> Self {
+        self.inputs.push(input.into());
+        self
+    }
+
+    /// 复杂滤镜图，输出标签需再通过 [`map`](Self::map) 选取
+    pub fn filter_complex(mut self, graph: impl Into<String>) -> Self {
+        self.filter_complex = Some(graph.into());
+        self
+    }
+
+    /// 选取输出的流，按调用顺序排列
+    pub fn map(mut self, spec: impl Into<String>) -> Self {
+        self.maps.push(spec.into());
+        self
+    }
+
+    /// 追加简单视频滤镜，多次调用时以逗号串联
+    pub fn video_filter(mut self, filter: impl Into<String>) -> Self {
+        self.video_filters.push(filter.into());
+        self
+    }
+
+    pub fn video_codec(self, codec: &str) -> Self {
+        self.codec("-c:v", codec)
+    }
+
+    pub fn audio_codec(self, codec: &str) -> Self {
+        self.codec("-c:a", codec)
+    }
+
+    /// 所有流直接复制，不重新编码
+    pub fn copy_codecs(self) -> Self {
+        self.codec("-c", "copy")
+    }
+
+    fn codec(mut self, key: &str, codec: &str) -> Self {
+        self.codecs.push(key.into());
+        self.codecs.push(codec.into());
+        self
+    }
+
+    /// 不输出音频
+    pub fn no_audio(self) -> Self {
+        self.flag("-an")
+    }
+
+    /// 不输出视频
+    pub fn no_video(self) -> Self {
+        self.flag("-vn")
+    }
+
+    pub fn metadata(self, key: &str, value: &str) -> Self {
+        self.option("-metadata", format!("{}={}", key, value))
+    }
+
+    /// 其他输出参数，如 `-crf`、`-b:a`
+    pub fn option(mut self, key: &str, value: impl Into<OsString>) -> Self {
+        self.options.push(key.into());
+        self.options.push(value.into());
+        self
+    }
+
+    pub fn flag(mut self, flag: &str) -> Self {
+        self.options.push(flag.into());
+        self
+    }
+
+    /// 输出容器格式，如 mp4
+    pub fn format(mut self, format: &str) -> Self {
+        self.format = Some(format.to_string());
+        self
+    }
+
+    /// 输出文件，已存在时覆盖
+    pub fn output(mut self, path: impl Into<OsString>) -> Self {
+        self.output = Some(path.into());
+        self
+    }
+
+    /// 丢弃输出，用于测速和两遍编码的第一遍
+    pub fn null_output(self) -> Self {
+        self.format("null").output(NULL_OUTPUT)
+    }
+
+    /// 按 FFmpeg 要求的顺序生成参数
+    pub fn build(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = Vec::new();
+        for input in &self.inputs {
+            args.extend(input.options.iter().cloned());
+            args.push("-i".into());
+            args.push(input.path.clone());
+        }
+        if let Some(graph) = &self.filter_complex {
+            args.push("-filter_complex".into());
+            args.push(graph.into());
+        }
+        for spec in &self.maps {
+            args.push("-map".into());
+            args.push(spec.into());
+        }
+        if !self.video_filters.is_empty() {
+            args.push("-vf".into());
+            args.push(self.video_filters.join(",").into());
+        }
+        args.extend(self.codecs.iter().cloned());
+        args.extend(self.options.iter().cloned());
+        if let Some(format) = &self.format {
+            args.push("-f".into());
+            args.push(format.into());
+        }
+        if let Some(output) = &self.output {
+            args.push("-y".into());
+            args.push(output.clone());
+        }
+        args
+    }
+}
+
+/// 可直接粘贴到终端的命令行，含空格或引号的参数会加上引号，用于日志
+impl fmt::Display for FfmpegCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ffmpeg")?;
+        for arg in self.build() {
+            write!(f, " {}", quote(&arg.to_string_lossy()))?;
+        }
+        Ok(())
+    }
+}
+
+fn quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_.,:/\\=+@%[]?".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(command: &FfmpegCommand) -> Vec<String> {
+        command
+            .build()
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn arguments_follow_ffmpeg_order() {
+        // 调用顺序与生成顺序无关：输入参数总在 -i 之前，输出路径总在最后
+        let command = FfmpegCommand::new()
+            .output("out.mp4")
+            .format("mp4")
+            .video_codec("libx264")
+            .map("[v]")
+            .filter_complex("[0:v]scale=1280:720[v]")
+            .input(
+                FfmpegInput::new("list.txt")
+                    .format("concat")
+                    .option("-safe", "0"),
+            )
+            .input(Path::new("logo.png"))
+            .option("-crf", "23");
+        assert_eq!(
+            strings(&command),
+            [
+                "-f",
+                "concat",
+                "-safe",
+                "0",
+                "-i",
+                "list.txt",
+                "-i",
+                "logo.png",
+                "-filter_complex",
+                "[0:v]scale=1280:720[v]",
+                "-map",
+                "[v]",
+                "-c:v",
+                "libx264",
+                "-crf",
+                "23",
+                "-f",
+                "mp4",
+                "-y",
+                "out.mp4",
+            ]
+        );
+    }
+
+    #[test]
+    fn video_filters_are_chained() {
+        let command = FfmpegCommand::new()
+            .input(FfmpegInput::new("a.mp4").seek(1.5).duration(2.0))
+            .video_filter("transpose=1")
+            .video_filter("scale=640:-2")
+            .null_output();
+        assert_eq!(
+            strings(&command),
+            [
+                "-ss",
+                "1.500",
+                "-t",
+                "2.000",
+                "-i",
+                "a.mp4",
+                "-vf",
+                "transpose=1,scale=640:-2",
+                "-f",
+                "null",
+                "-y",
+                NULL_OUTPUT,
+            ]
+        );
+    }
+
+    #[test]
+    fn display_quotes_special_arguments() {
+        let command = FfmpegCommand::new()
+            .input(Path::new("my clip.mp4"))
+            .metadata("title", "say \"hi\"")
+            .output("out.mp4");
+        assert_eq!(
+            command.to_string(),
+            r#"ffmpeg -i "my clip.mp4" -metadata "title=say \"hi\"" -y out.mp4"#
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use crate::ffmpeg::args::FfmpegInput;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 外部音频的使用方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum AudioTrackMode {
+    /// 丢弃原声，只保留外部音频
+    #[default]
+    Replace,
+    /// 与原声混合（背景音乐、旁白）
+    Mix,
+}
+
+impl AudioTrackMode {
+    pub const ALL: [AudioTrackMode; 2] = [AudioTrackMode::Replace, AudioTrackMode::Mix];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AudioTrackMode::Replace => "替换原声",
+            AudioTrackMode::Mix => "与原声混合",
+        }
+    }
+}
+
+/// 合并结果使用的外部音频
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExternalAudio {
+    pub path: PathBuf,
+    pub mode: AudioTrackMode,
+    /// 外部音频相对视频开头的偏移（秒），负数表示跳过音频开头
+    pub offset: f64,
+    /// 外部音频音量倍数，1.0 为原始音量
+    pub volume: f32,
+    /// 混合时原声的音量倍数
+    pub original_volume: f32,
+}
+
+impl Default for ExternalAudio {
+    fn default() -> Self {
+        ExternalAudio {
+            path: PathBuf::new(),
+            mode: AudioTrackMode::default(),
+            offset: 0.0,
+            volume: 1.0,
+            original_volume: 1.0,
+        }
+    }
+}
+
+impl ExternalAudio {
+    /// 作为额外输入传给 FFmpeg，负偏移通过输入端跳转实现
+    pub fn input(&self) -> FfmpegInput {
+        let input = FfmpegInput::from(&self.path);
+        if self.offset < 0.0 {
+            input.seek(-self.offset)
+        } else {
+            input
+        }
+    }
+
+    /// 是否需要原声参与
+    pub fn uses_original(&self) -> bool {
+        self.mode == AudioTrackMode::Mix
+    }
+
+    /// 生成音频滤镜图，原声标签为 `original`，外部音频为第 `audio_input` 个输入，
+    /// 输出标签为 `[a]`。输出长度跟随视频，需要配合 `-shortest` 使用
+    pub fn filter(&self, original: &str, audio_input: usize) -> String {
+        let delay_ms = (self.offset.max(0.0) * 1000.0).round() as u64;
+        let volume = self.volume.max(0.0);
+        let external = if delay_ms > 0 {
+            format!("[{audio_input}:a]adelay={delay_ms}:all=1,volume={volume:.2}")
+        } else {
+            format!("[{audio_input}:a]volume={volume:.2}")
+        };
+        match self.mode {
+            // 外部音频比视频短时补静音，保证画面结尾仍有音轨
+            AudioTrackMode::Replace => format!("{external},apad[a]"),
+            AudioTrackMode::Mix => format!(
+                "[{original}]volume={original_volume:.2}[a0];{external}[a1];[a0][a1]amix=inputs=2:duration=first:dropout_transition=0:normalize=0[a]",
+                original_volume = self.original_volume.max(0.0),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_delays_or_skips_external_audio() {
+        let audio = ExternalAudio {
+            path: PathBuf::from("music.mp3"),
+            mode: AudioTrackMode::Mix,
+            offset: 1.5,
+            volume: 0.3,
+            ..Default::default()
+        };
+        assert_eq!(
+            audio.filter("0:a", 2),
+            "[0:a]volume=1.00[a0];[2:a]adelay=1500:all=1,volume=0.30[a1];[a0][a1]amix=inputs=2:duration=first:dropout_transition=0:normalize=0[a]"
+        );
+        assert_eq!(audio.input(), FfmpegInput::from(&audio.path));
+
+        let audio = ExternalAudio {
+            offset: -2.0,
+            ..audio
+        };
+        assert_eq!(audio.input(), FfmpegInput::from(&audio.path).seek(2.0));
+        let audio = ExternalAudio {
+            mode: AudioTrackMode::Replace,
+            ..audio
+        };
+        assert_eq!(audio.filter("0:a", 1), "[1:a]volume=0.30,apad[a]");
+    }
+}
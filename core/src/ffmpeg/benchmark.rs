@@ -0,0 +1,113 @@
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::encoder::EncoderPreset;
+use crate::ffmpeg::probe::probe_duration_cached;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// 测速时截取的片段长度（秒）
+const SAMPLE_SECONDS: f64 = 5.0;
+/// 输入总大小超过该值时建议先测速
+pub const LARGE_JOB_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// 参与测速的处理方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BenchmarkTarget {
+    Copy,
+    ReEncode(EncoderPreset),
+}
+
+impl BenchmarkTarget {
+    /// 直接复制加上每个编码速度预设
+    pub fn all() -> Vec<BenchmarkTarget> {
+        let mut targets = vec![BenchmarkTarget::Copy];
+        targets.extend(EncoderPreset::ALL.map(BenchmarkTarget::ReEncode));
+        targets
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            BenchmarkTarget::Copy => "直接合并".to_string(),
+            BenchmarkTarget::ReEncode(preset) => format!("重新编码（{}）", preset.label()),
+        }
+    }
+}
+
+/// 用于测速的样本：取第一个文件中间的一小段
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkSample {
+    pub file: PathBuf,
+    pub start: f64,
+    pub length: f64,
+    /// 所有输入的总时长，用于按比例估算
+    pub total_duration: f64,
+}
+
+/// 输入总大小是否大到值得先测速
+pub fn is_large_job(files: &[PathBuf]) -> bool {
+    let total: u64 = files
+        .iter()
+        .filter_map(|f| std::fs::metadata(f).ok())
+        .map(|m| m.len())
+        .sum();
+    total >= LARGE_JOB_BYTES
+}
+
+/// 探测输入时长并选出测速样本
+pub async fn prepare_sample(files: &[PathBuf]) -> Result<BenchmarkSample, AppError> {
+    let Some(first) = files.first() else {
+        return Err(AppError::Other("没有可测速的文件".to_string()));
+    };
+    let mut total_duration = 0.0;
+    for file in files {
+        total_duration += probe_duration_cached(file).await?;
+    }
+    let first_duration = probe_duration_cached(first).await?;
+    let length = SAMPLE_SECONDS.min(first_duration);
+    if length <= 0.0 {
+        return Err(AppError::Other("视频时长为 0，无法测速".to_string()));
+    }
+    Ok(BenchmarkSample {
+        file: first.clone(),
+        start: ((first_duration - length) / 2.0).max(0.0),
+        length,
+        total_duration,
+    })
+}
+
+/// 处理样本并按时长比例估算全部输入所需的秒数
+pub async fn estimate_total_secs(
+    sample: &BenchmarkSample,
+    target: BenchmarkTarget,
+) -> Result<f64, AppError> {
+    let elapsed = time_sample(&sample.file, sample.start, sample.length, target).await?;
+    Ok(elapsed * sample.total_duration / sample.length)
+}
+
+async fn time_sample(
+    file: &Path,
+    start: f64,
+    length: f64,
+    target: BenchmarkTarget,
+) -> Result<f64, AppError> {
+    let command = FfmpegCommand::new().input(FfmpegInput::new(file).seek(start).duration(length));
+    let command = match target {
+        BenchmarkTarget::Copy => command.copy_codecs(),
+        BenchmarkTarget::ReEncode(preset) => command
+            .video_codec("libx264")
+            .option("-preset", preset.as_str())
+            .audio_codec("aac")
+            .option("-b:a", "192k"),
+    };
+    let args = command.null_output().build();
+
+    let started = Instant::now();
+    let output = SystemRunner.output("ffmpeg", &args).await?;
+    if !output.exit.success {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last_line = stderr.lines().last().unwrap_or_default();
+        return Err(AppError::Ffmpeg(format!("测速失败: {}", last_line)));
+    }
+    Ok(started.elapsed().as_secs_f64())
+}
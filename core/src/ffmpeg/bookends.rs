@@ -0,0 +1,105 @@
+//! 片头/片尾：复制模式下参数与正片不一致时先转码匹配，避免拼接后画面花屏或无法播放
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::normalize::NormalizePlan;
+use crate::ffmpeg::probe::VideoStreamInfo;
+use std::path::Path;
+
+/// 片头/片尾与正片的编码、分辨率或帧率是否不同
+pub fn needs_conform(info: &VideoStreamInfo, reference: &VideoStreamInfo) -> bool {
+    info.codec != reference.codec
+        || info.width != reference.width
+        || info.height != reference.height
+        || (info.fps - reference.fps).abs() > 0.01
+}
+
+/// 把片头/片尾转码为与正片相同的编码、分辨率和帧率，音频统一为 AAC 48kHz 立体声
+pub fn conform_command(
+    input: &Path,
+    reference: &VideoStreamInfo,
+    output: &Path,
+) -> Result<FfmpegCommand, AppError> {
+    let encoder = match reference.codec.as_str() {
+        "h264" => "libx264",
+        "hevc" => "libx265",
+        other => {
+            return Err(AppError::Other(format!(
+                "无法自动转换片头/片尾以匹配 {} 编码的正片，请改用“重新编码”模式",
+                other
+            )));
+        }
+    };
+    if reference.width == 0 || reference.height == 0 || reference.fps <= 0.0 {
+        return Err(AppError::Other(
+            "无法读取正片的分辨率或帧率，请改用“重新编码”模式".to_string(),
+        ));
+    }
+    let target = NormalizePlan {
+        width: reference.width,
+        height: reference.height,
+        fps: reference.fps,
+        inputs: Vec::new(),
+    };
+    let mut command = FfmpegCommand::new()
+        .input(input)
+        .video_filter(target.filter())
+        .video_codec(encoder)
+        .option("-preset", "veryfast")
+        .option("-crf", "18")
+        .option("-pix_fmt", "yuv420p");
+    if encoder == "libx265" {
+        command = command.option("-tag:v", "hvc1");
+    }
+    Ok(command
+        .audio_codec("aac")
+        .option("-ar", "48000")
+        .option("-ac", "2")
+        .format("mp4")
+        .output(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(codec: &str, width: u32, height: u32, fps: f64) -> VideoStreamInfo {
+        VideoStreamInfo {
+            codec: codec.to_string(),
+            width,
+            height,
+            fps,
+        }
+    }
+
+    #[test]
+    fn intro_is_conformed_to_main_clips() {
+        let reference = stream("h264", 1920, 1080, 29.97);
+        assert!(!needs_conform(
+            &stream("h264", 1920, 1080, 29.97),
+            &reference
+        ));
+        assert!(needs_conform(&stream("h264", 1280, 720, 29.97), &reference));
+        assert!(needs_conform(
+            &stream("hevc", 1920, 1080, 29.97),
+            &reference
+        ));
+
+        let args: Vec<String> =
+            conform_command(Path::new("intro.mov"), &reference, Path::new("out.mp4"))
+                .unwrap()
+                .build()
+                .iter()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect();
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx264"]));
+        assert!(args.iter().any(|a| a.contains("scale=1920:1080")));
+        assert!(
+            conform_command(
+                Path::new("a"),
+                &stream("vp9", 1920, 1080, 30.0),
+                Path::new("b")
+            )
+            .is_err()
+        );
+    }
+}
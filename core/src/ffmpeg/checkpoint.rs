@@ -0,0 +1,186 @@
+//! 重新编码的断点续传：逐段编码到断点目录，每段完成后记录其 SHA-256，
+//! 程序崩溃或合并失败后再次合并相同的文件时，哈希一致的分段直接复用
+use crate::ffmpeg::options::MergeOptions;
+use crate::utils::{app_data_dir, sha256_file};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// 总时长达到该值（秒）的重新编码合并才分段编码
+pub const CHECKPOINT_MIN_SECS: f64 = 600.0;
+
+/// 超过该时长未再使用的断点目录在启动时清理
+const STALE_AFTER: Duration = Duration::from_secs(7 * 24 * 3600);
+
+const MANIFEST: &str = "manifest.json";
+
+/// 一次合并的断点目录，合并成功后调用 `remove` 删除
+pub struct Checkpoint {
+    dir: PathBuf,
+    /// 已完成分段的序号与 SHA-256
+    completed: BTreeMap<usize, String>,
+}
+
+impl Checkpoint {
+    /// 打开 `key` 对应的断点目录，不存在时创建
+    pub fn open(key: &str) -> Result<Self, io::Error> {
+        let dir = checkpoints_root()?.join(key);
+        fs::create_dir_all(&dir)?;
+        let completed = fs::read_to_string(dir.join(MANIFEST))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Ok(Self { dir, completed })
+    }
+
+    /// 断点目录下的文件路径
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// 第 `index` 段的输出路径
+    pub fn segment_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("segment-{:04}.mp4", index))
+    }
+
+    /// 第 `index` 段已完成且文件内容与记录的哈希一致
+    pub async fn is_complete(&self, index: usize) -> bool {
+        let Some(expected) = self.completed.get(&index).cloned() else {
+            return false;
+        };
+        let path = self.segment_path(index);
+        tokio::task::spawn_blocking(move || sha256_file(&path))
+            .await
+            .is_ok_and(|hash| hash.is_ok_and(|hash| hash == expected))
+    }
+
+    /// 记录第 `index` 段已完成，立即写入清单以便崩溃后恢复
+    pub async fn mark_complete(&mut self, index: usize) -> Result<(), io::Error> {
+        let path = self.segment_path(index);
+        let hash = tokio::task::spawn_blocking(move || sha256_file(&path))
+            .await
+            .map_err(io::Error::other)??;
+        self.completed.insert(index, hash);
+        let text = serde_json::to_string_pretty(&self.completed).map_err(io::Error::other)?;
+        fs::write(self.dir.join(MANIFEST), text)
+    }
+
+    /// 删除断点目录
+    pub fn remove(self) {
+        if let Err(e) = fs::remove_dir_all(&self.dir) {
+            eprintln!("清理断点目录失败 {}: {}", self.dir.display(), e);
+        }
+    }
+}
+
+/// 断点目录的名称：由输入文件（路径、大小、修改时间）、输出路径和合并选项决定，
+/// 任何一项变化都会开始新的断点
+pub fn checkpoint_key(files: &[PathBuf], output_path: &Path, options: &MergeOptions) -> String {
+    let mut hasher = Sha256::new();
+    for file in files {
+        hasher.update(file.as_os_str().as_encoded_bytes());
+        if let Ok(meta) = fs::metadata(file) {
+            hasher.update(meta.len().to_le_bytes());
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .unwrap_or_default();
+            hasher.update(modified.as_nanos().to_le_bytes());
+        }
+        hasher.update([0]);
+    }
+    hasher.update(output_path.as_os_str().as_encoded_bytes());
+    hasher.update(serde_json::to_vec(options).unwrap_or_default());
+    hasher
+        .finalize()
+        .iter()
+        .take(16)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 启动时清理长时间未使用的断点目录
+pub fn clean_stale_checkpoints() {
+    let Ok(entries) = checkpoints_root().and_then(fs::read_dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age > STALE_AFTER);
+        if path.is_dir()
+            && stale
+            && let Err(e) = fs::remove_dir_all(&path)
+        {
+            eprintln!("清理过期断点目录失败 {}: {}", path.display(), e);
+        }
+    }
+}
+
+fn checkpoints_root() -> Result<PathBuf, io::Error> {
+    Ok(app_data_dir()?.join("checkpoints"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_changes_with_options() {
+        let files = vec![PathBuf::from("a.mp4"), PathBuf::from("b.mp4")];
+        let output = Path::new("out.mp4");
+        let options = MergeOptions::default();
+        let key = checkpoint_key(&files, output, &options);
+        assert_eq!(key, checkpoint_key(&files, output, &options));
+        assert_eq!(key.len(), 32);
+
+        let reordered = vec![PathBuf::from("b.mp4"), PathBuf::from("a.mp4")];
+        assert_ne!(key, checkpoint_key(&reordered, output, &options));
+        let changed = MergeOptions {
+            precise_cuts: !options.precise_cuts,
+            ..options.clone()
+        };
+        assert_ne!(key, checkpoint_key(&files, output, &changed));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_utf8_clip_settings_are_kept() {
+        use crate::ffmpeg::dead_air::CutRange;
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let clip = PathBuf::from(OsString::from_vec(b"/videos/clip\xff.mp4".to_vec()));
+        let mut options = MergeOptions::default();
+        options.set_speed(clip.clone(), 2.0);
+        options.set_cuts(
+            clip.clone(),
+            vec![CutRange {
+                start: 1.0,
+                end: 2.0,
+            }],
+        );
+
+        let json = serde_json::to_string(&options).unwrap();
+        let saved: MergeOptions = serde_json::from_str(&json).unwrap();
+        assert_eq!(saved, options);
+        assert_eq!(saved.speed_of(&clip), 2.0);
+
+        let files = vec![clip.clone()];
+        let output = Path::new("out.mp4");
+        let mut faster = options.clone();
+        faster.set_speed(clip, 4.0);
+        assert_ne!(
+            checkpoint_key(&files, output, &options),
+            checkpoint_key(&files, output, &faster)
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::MergeEvent;
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::command::SystemRunner;
+use crate::ffmpeg::merge_mp4::MergeEventSender;
+use crate::ffmpeg::phase::MergePhase;
+use crate::ffmpeg::runner::run_ffmpeg_with_progress;
+use crate::ffmpeg::workspace::part_path;
+use std::path::{Path, PathBuf};
+
+/// 片段导出格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipFormat {
+    Gif,
+    Mp4,
+}
+
+impl ClipFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ClipFormat::Gif => "gif",
+            ClipFormat::Mp4 => "mp4",
+        }
+    }
+}
+
+/// GIF/短片段导出参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipExportOptions {
+    pub start: f64,
+    pub duration: f64,
+    /// 输出宽度，高度按比例缩放；None 保持原尺寸
+    pub width: Option<u32>,
+    /// 输出帧率；None 保持原帧率（GIF 默认 15）
+    pub fps: Option<u32>,
+    pub format: ClipFormat,
+}
+
+impl ClipExportOptions {
+    /// 根据源文件大小/尺寸粗略估算输出大小（字节）
+    pub fn estimate_size(
+        &self,
+        source_size: u64,
+        source_duration: f64,
+        source_width: u16,
+        source_height: u16,
+    ) -> u64 {
+        let width = self.width.unwrap_or(source_width as u32).max(1) as f64;
+        let scale = width / (source_width.max(1) as f64);
+        let height = source_height as f64 * scale;
+        match self.format {
+            ClipFormat::Gif => {
+                // 调色板 GIF 大约每像素每帧 0.15 字节
+                let fps = self.fps.unwrap_or(15) as f64;
+                (width * height * fps * self.duration * 0.15) as u64
+            }
+            ClipFormat::Mp4 => {
+                if source_duration <= 0.0 {
+                    return 0;
+                }
+                let bytes_per_sec = source_size as f64 / source_duration;
+                (bytes_per_sec * self.duration * scale * scale) as u64
+            }
+        }
+    }
+
+    fn filter(&self) -> Option<String> {
+        let mut filters = Vec::new();
+        match self.format {
+            ClipFormat::Gif => {
+                filters.push(format!("fps={}", self.fps.unwrap_or(15)));
+                if let Some(width) = self.width {
+                    filters.push(format!("scale={}:-1:flags=lanczos", width));
+                }
+                let chain = filters.join(",");
+                // 先生成调色板再应用，画质比默认 256 色好得多
+                Some(format!(
+                    "{},split[s0][s1];[s0]palettegen[p];[s1][p]paletteuse",
+                    chain
+                ))
+            }
+            ClipFormat::Mp4 => {
+                if let Some(fps) = self.fps {
+                    filters.push(format!("fps={}", fps));
+                }
+                if let Some(width) = self.width {
+                    filters.push(format!("scale={}:-2", width));
+                }
+                (!filters.is_empty()).then(|| filters.join(","))
+            }
+        }
+    }
+
+    fn command(&self, input: &Path, output: &Path) -> FfmpegCommand {
+        let mut command = FfmpegCommand::new().input(
+            FfmpegInput::new(input)
+                .seek(self.start)
+                .duration(self.duration),
+        );
+        if let Some(filter) = self.filter() {
+            command = command.filter_complex(filter);
+        }
+        let command = match self.format {
+            ClipFormat::Gif => command.option("-loop", "0").format("gif"),
+            ClipFormat::Mp4 => command
+                .video_codec("libx264")
+                .option("-preset", "veryfast")
+                .option("-crf", "23")
+                .audio_codec("aac")
+                .option("-movflags", "+faststart")
+                .format("mp4"),
+        };
+        command.output(output)
+    }
+}
+
+/// 从单个文件中截取一段导出为 GIF 或 MP4
+pub async fn run_clip_export(
+    input: PathBuf,
+    output_path: PathBuf,
+    options: ClipExportOptions,
+    tx: impl Into<MergeEventSender>,
+) {
+    let tx: MergeEventSender = tx.into();
+    let part_output_path = part_path(&output_path);
+
+    tx.send(MergeEvent::Status("正在导出片段...".to_string()));
+    let command = options.command(&input, &part_output_path);
+    if let Err(e) = run_ffmpeg_with_progress(
+        &SystemRunner,
+        command,
+        options.duration,
+        MergePhase::Concatenating,
+        0.0,
+        100.0,
+        &tx,
+    )
+    .await
+    {
+        let _ = std::fs::remove_file(&part_output_path);
+        tx.send(MergeEvent::Error(e));
+        return;
+    }
+    if let Err(e) = std::fs::rename(&part_output_path, &output_path) {
+        let _ = std::fs::remove_file(&part_output_path);
+        tx.send(MergeEvent::Error(AppError::io("重命名输出文件失败", e)));
+        return;
+    }
+    tx.send(MergeEvent::Success(output_path.display().to_string()));
+}
@@ -0,0 +1,217 @@
+//! 外部进程执行层：FFmpeg 相关逻辑通过 [`CommandRunner`] 启动进程，
+//! 测试时可替换为回放预录 stderr 的实现，不需要真实的 FFmpeg
+use std::ffi::OsString;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// 进程的退出状态
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitInfo {
+    pub success: bool,
+    /// 形如 "exit code: 1"，用于错误信息
+    pub description: String,
+}
+
+/// 执行结束后收集到的输出
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandOutput {
+    pub exit: ExitInfo,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// 进程执行失败的阶段
+#[derive(Debug)]
+pub enum CommandError {
+    Spawn(io::Error),
+    Wait(io::Error),
+}
+
+/// 启动外部程序的方式
+pub trait CommandRunner: Send + Sync {
+    /// 程序能否在 PATH 中找到
+    fn is_available(&self, program: &str) -> bool;
+
+    /// 执行并等待结束，收集 stdout 与 stderr
+    fn output<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [OsString],
+    ) -> BoxFuture<'a, Result<CommandOutput, CommandError>>;
+
+    /// 执行并把 stderr 逐行交给 `on_line`，stdout 丢弃
+    fn stream_stderr<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [OsString],
+        on_line: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<ExitInfo, CommandError>>;
+}
+
+/// 通过 `tokio::process` 启动真实进程
+pub struct SystemRunner;
+
+// 返回的 future 被丢弃（取消任务、超时）时结束进程，不留下孤儿 FFmpeg
+fn command(program: &str, args: &[OsString]) -> Command {
+    let mut command = Command::new(program);
+    command
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .kill_on_drop(true)
+        .args(args);
+    command
+}
+
+fn exit_info(status: std::process::ExitStatus) -> ExitInfo {
+    ExitInfo {
+        success: status.success(),
+        description: status.to_string(),
+    }
+}
+
+impl CommandRunner for SystemRunner {
+    fn is_available(&self, program: &str) -> bool {
+        which::which(program).is_ok()
+    }
+
+    fn output<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [OsString],
+    ) -> BoxFuture<'a, Result<CommandOutput, CommandError>> {
+        Box::pin(async move {
+            let output = command(program, args)
+                .output()
+                .await
+                .map_err(CommandError::Spawn)?;
+            Ok(CommandOutput {
+                exit: exit_info(output.status),
+                stdout: output.stdout,
+                stderr: output.stderr,
+            })
+        })
+    }
+
+    fn stream_stderr<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [OsString],
+        on_line: &'a mut (dyn FnMut(String) + Send),
+    ) -> BoxFuture<'a, Result<ExitInfo, CommandError>> {
+        Box::pin(async move {
+            let mut child = command(program, args)
+                .stderr(Stdio::piped())
+                .stdout(Stdio::null())
+                .spawn()
+                .map_err(CommandError::Spawn)?;
+            let stderr = child.stderr.take().unwrap();
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                on_line(line);
+            }
+            child
+                .wait()
+                .await
+                .map(exit_info)
+                .map_err(CommandError::Wait)
+        })
+    }
+}
+
+/// 回放预录输出的执行器，供单元测试使用
+#[cfg(test)]
+pub(crate) mod fake {
+    use super::*;
+    use crate::ffmpeg::encoder::NULL_OUTPUT;
+    use std::sync::Mutex;
+
+    pub(crate) struct FakeRunner {
+        /// `ffmpeg -i` 探测时返回的 stderr
+        pub probe_stderr: String,
+        /// 转码时逐行输出的 stderr
+        pub transcript: Vec<String>,
+        pub success: bool,
+        /// 探测时永不返回，模拟卡住的损坏文件
+        pub hang: bool,
+        /// 每次转码的参数
+        pub calls: Mutex<Vec<Vec<OsString>>>,
+    }
+
+    impl FakeRunner {
+        pub(crate) fn new(probe_stderr: &str, transcript: &str, success: bool) -> Self {
+            Self {
+                probe_stderr: probe_stderr.to_string(),
+                transcript: transcript.lines().map(str::to_string).collect(),
+                success,
+                hang: false,
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn exit(&self) -> ExitInfo {
+            ExitInfo {
+                success: self.success,
+                description: if self.success {
+                    "exit status: 0"
+                } else {
+                    "exit status: 1"
+                }
+                .to_string(),
+            }
+        }
+    }
+
+    impl CommandRunner for FakeRunner {
+        fn is_available(&self, _program: &str) -> bool {
+            true
+        }
+
+        fn output<'a>(
+            &'a self,
+            _program: &'a str,
+            _args: &'a [OsString],
+        ) -> BoxFuture<'a, Result<CommandOutput, CommandError>> {
+            Box::pin(async move {
+                if self.hang {
+                    std::future::pending::<()>().await;
+                }
+                Ok(CommandOutput {
+                    // 只给 -i 不给输出时 FFmpeg 总是以失败退出
+                    exit: ExitInfo {
+                        success: false,
+                        description: "exit status: 1".to_string(),
+                    },
+                    stdout: Vec::new(),
+                    stderr: self.probe_stderr.clone().into_bytes(),
+                })
+            })
+        }
+
+        fn stream_stderr<'a>(
+            &'a self,
+            _program: &'a str,
+            args: &'a [OsString],
+            on_line: &'a mut (dyn FnMut(String) + Send),
+        ) -> BoxFuture<'a, Result<ExitInfo, CommandError>> {
+            Box::pin(async move {
+                self.calls.lock().unwrap().push(args.to_vec());
+                for line in &self.transcript {
+                    on_line(line.clone());
+                }
+                // 成功时像 FFmpeg 一样写出最后一个参数指定的输出文件
+                if self.success
+                    && let Some(output) = args.last()
+                    && output != NULL_OUTPUT
+                {
+                    std::fs::write(output, b"fake mp4").map_err(CommandError::Wait)?;
+                }
+                Ok(self.exit())
+            })
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::MergeEvent;
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::command::SystemRunner;
+use crate::ffmpeg::encoder::{EncodePass, EncodeQuality, EncoderPreset};
+use crate::ffmpeg::merge_mp4::MergeEventSender;
+use crate::ffmpeg::phase::MergePhase;
+use crate::ffmpeg::probe::{get_video_duration, probe_video_stream};
+use crate::ffmpeg::runner::run_ffmpeg_with_progress;
+use crate::ffmpeg::workspace::{JobWorkspace, part_path};
+use crate::utils::format_size;
+use std::path::{Path, PathBuf};
+
+/// 压缩时音频使用的码率（kbps）
+const AUDIO_BITRATE: u32 = 128;
+/// 为封装开销预留的比例
+const CONTAINER_OVERHEAD: f64 = 0.03;
+/// 视频码率低于该值时画质通常难以接受（kbps）
+const MIN_VIDEO_BITRATE: u32 = 150;
+/// 每像素每帧的比特数低于该值时画面会明显模糊
+const MIN_BITS_PER_PIXEL: f64 = 0.03;
+
+/// 按目标大小压缩的计算结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressPlan {
+    pub duration: f64,
+    /// 视频码率（kbps）
+    pub video_bitrate: u32,
+    /// 画质可能无法接受时的提示
+    pub warning: Option<String>,
+}
+
+/// 根据目标大小与时长计算视频码率（kbps），扣除音频与封装开销
+pub fn target_video_bitrate(target_bytes: u64, duration: f64) -> Result<u32, AppError> {
+    if duration <= 0.0 {
+        return Err(AppError::Other("无法获取视频时长".to_string()));
+    }
+    let total_kbps = target_bytes as f64 * 8.0 * (1.0 - CONTAINER_OVERHEAD) / duration / 1000.0;
+    let video_kbps = total_kbps - AUDIO_BITRATE as f64;
+    if video_kbps < 1.0 {
+        return Err(AppError::Other(format!(
+            "目标大小过小：{:.0} 秒的视频至少需要 {}",
+            duration,
+            format_size(Some(
+                ((AUDIO_BITRATE + MIN_VIDEO_BITRATE) as f64 * 1000.0 * duration / 8.0) as u64
+            ))
+        )));
+    }
+    Ok(video_kbps as u32)
+}
+
+/// 探测输入并计算压缩方案
+pub async fn plan_compression(input: &Path, target_bytes: u64) -> Result<CompressPlan, AppError> {
+    let duration = get_video_duration(input).await?;
+    let video_bitrate = target_video_bitrate(target_bytes, duration)?;
+    let stream = probe_video_stream(input).await?;
+
+    let pixels_per_sec = stream.width as f64 * stream.height as f64 * stream.fps.max(1.0);
+    let bits_per_pixel = video_bitrate as f64 * 1000.0 / pixels_per_sec;
+    let warning = if video_bitrate < MIN_VIDEO_BITRATE || bits_per_pixel < MIN_BITS_PER_PIXEL {
+        Some(format!(
+            "视频码率仅 {} kbps（{}x{} @ {:.0} fps），画质可能严重下降，建议先降低分辨率或缩短时长",
+            video_bitrate, stream.width, stream.height, stream.fps
+        ))
+    } else {
+        None
+    };
+
+    Ok(CompressPlan {
+        duration,
+        video_bitrate,
+        warning,
+    })
+}
+
+/// 以两遍编码把文件压缩到目标大小，成功后报告实际大小
+pub async fn run_compress(
+    input: PathBuf,
+    output_path: PathBuf,
+    plan: CompressPlan,
+    tx: impl Into<MergeEventSender>,
+) {
+    let tx: MergeEventSender = tx.into();
+    let workspace = match JobWorkspace::create() {
+        Ok(w) => w,
+        Err(e) => {
+            tx.send(MergeEvent::Error(AppError::io("创建工作目录失败", e)));
+            return;
+        }
+    };
+    let quality = EncodeQuality {
+        preset: EncoderPreset::Slow,
+        two_pass_bitrate: Some(plan.video_bitrate),
+        ..Default::default()
+    };
+    let log_prefix = workspace.file("ffmpeg2pass");
+    let part_output_path = part_path(&output_path);
+
+    for index in 1..=2u8 {
+        tx.send(MergeEvent::Status(format!("第 {}/2 遍编码...", index)));
+        let pass = EncodePass {
+            index,
+            log_prefix: &log_prefix,
+        };
+        let command = quality.apply(FfmpegCommand::new().input(&input), Some(pass));
+        let command = if index == 1 {
+            command.no_audio().null_output()
+        } else {
+            command
+                .audio_codec("aac")
+                .option("-b:a", format!("{}k", AUDIO_BITRATE))
+                .format("mp4")
+                .output(&part_output_path)
+        };
+        let start = 50.0 * (index - 1) as f64;
+        if let Err(e) = run_ffmpeg_with_progress(
+            &SystemRunner,
+            command,
+            plan.duration,
+            MergePhase::Concatenating,
+            start,
+            50.0,
+            &tx,
+        )
+        .await
+        {
+            let _ = std::fs::remove_file(&part_output_path);
+            tx.send(MergeEvent::Error(e));
+            return;
+        }
+    }
+
+    if let Err(e) = std::fs::rename(&part_output_path, &output_path) {
+        let _ = std::fs::remove_file(&part_output_path);
+        tx.send(MergeEvent::Error(AppError::io("重命名输出文件失败", e)));
+        return;
+    }
+    let size = std::fs::metadata(&output_path).ok().map(|m| m.len());
+    tx.send(MergeEvent::Success(format!(
+        "{}（实际大小 {}）",
+        output_path.display(),
+        format_size(size)
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitrate_leaves_room_for_audio() {
+        // 25 MB / 60 秒 ≈ 3495 kbps，扣除 3% 开销与 128k 音频
+        let bitrate = target_video_bitrate(25 * 1024 * 1024, 60.0).unwrap();
+        assert_eq!(bitrate, 3262);
+    }
+
+    #[test]
+    fn rejects_target_too_small() {
+        assert!(target_video_bitrate(100 * 1024, 60.0).is_err());
+        assert!(target_video_bitrate(25 * 1024 * 1024, 0.0).is_err());
+    }
+}
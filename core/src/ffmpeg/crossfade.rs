@@ -0,0 +1,134 @@
+//! 片段之间的交叉淡化转场：每个片段作为独立输入，统一格式后用 `xfade`/`acrossfade` 依次衔接
+use crate::ffmpeg::normalize::NormalizePlan;
+use serde::{Deserialize, Serialize};
+
+/// 交叉淡化设置，仅在重新编码模式下生效
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct CrossfadeOptions {
+    /// 每个转场的时长（秒）
+    pub duration: f64,
+}
+
+impl Default for CrossfadeOptions {
+    fn default() -> Self {
+        CrossfadeOptions { duration: 1.0 }
+    }
+}
+
+/// 根据各片段实际时长得出的转场方案
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossfadePlan {
+    durations: Vec<f64>,
+    fade: f64,
+    /// xfade 要求所有输入分辨率、帧率一致，因此总是需要统一方案
+    normalize: NormalizePlan,
+}
+
+impl CrossfadePlan {
+    /// 转场时长不超过最短片段的一半，避免相邻转场重叠
+    pub fn new(options: &CrossfadeOptions, durations: Vec<f64>, normalize: NormalizePlan) -> Self {
+        let shortest = durations.iter().copied().fold(f64::INFINITY, f64::min);
+        let fade = options.duration.min(shortest / 2.0).max(0.0);
+        CrossfadePlan {
+            durations,
+            fade,
+            normalize,
+        }
+    }
+
+    /// 片段输入数量，额外输入（水印、外部音频）排在其后
+    pub fn input_count(&self) -> usize {
+        self.durations.len()
+    }
+
+    /// 实际使用的转场时长
+    pub fn fade(&self) -> f64 {
+        self.fade
+    }
+
+    /// 输出总时长，每个转场让相邻片段重叠一次
+    pub fn output_duration(&self) -> f64 {
+        let total: f64 = self.durations.iter().sum();
+        total - self.fade * self.durations.len().saturating_sub(1) as f64
+    }
+
+    /// 生成滤镜图，视频输出标签为 `[xv]`；`with_audio` 时同时输出 `[xa]`（要求每个片段都有音轨）
+    pub fn filter(&self, with_audio: bool) -> String {
+        let count = self.input_count();
+        let mut parts = Vec::new();
+        for index in 0..count {
+            parts.push(format!(
+                "[{index}:v]{},format=yuv420p,settb=AVTB[cv{index}]",
+                self.normalize.filter()
+            ));
+            if with_audio {
+                parts.push(format!(
+                    "[{index}:a]aformat=sample_fmts=fltp:sample_rates=48000:channel_layouts=stereo[ca{index}]"
+                ));
+            }
+        }
+
+        let fade = format!("{:.3}", self.fade);
+        let mut video = "cv0".to_string();
+        let mut audio = "ca0".to_string();
+        let mut offset = 0.0;
+        for index in 1..count {
+            // 第 index 个转场在已拼接部分结束前 fade 秒开始
+            offset += self.durations[index - 1] - self.fade;
+            parts.push(format!(
+                "[{video}][cv{index}]xfade=transition=fade:duration={fade}:offset={offset:.3}[cvx{index}]"
+            ));
+            video = format!("cvx{index}");
+            if with_audio {
+                parts.push(format!(
+                    "[{audio}][ca{index}]acrossfade=d={fade}[cax{index}]"
+                ));
+                audio = format!("cax{index}");
+            }
+        }
+        parts.push(format!("[{video}]null[xv]"));
+        if with_audio {
+            parts.push(format!("[{audio}]anull[xa]"));
+        }
+        parts.join(";")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_account_for_previous_overlaps() {
+        let normalize = NormalizePlan {
+            width: 1280,
+            height: 720,
+            fps: 30.0,
+            inputs: Vec::new(),
+        };
+        let plan = CrossfadePlan::new(
+            &CrossfadeOptions { duration: 1.0 },
+            vec![10.0, 5.0, 8.0],
+            normalize,
+        );
+        assert_eq!(plan.output_duration(), 21.0);
+        let graph = plan.filter(true);
+        assert!(
+            graph.contains("[cv0][cv1]xfade=transition=fade:duration=1.000:offset=9.000[cvx1]")
+        );
+        assert!(
+            graph.contains("[cvx1][cv2]xfade=transition=fade:duration=1.000:offset=13.000[cvx2]")
+        );
+        assert!(graph.contains("[cax1][ca2]acrossfade=d=1.000[cax2]"));
+        assert!(graph.ends_with("[cvx2]null[xv];[cax2]anull[xa]"));
+
+        // 转场不能长于最短片段的一半
+        let short = CrossfadePlan::new(
+            &CrossfadeOptions { duration: 3.0 },
+            vec![10.0, 2.0],
+            plan.normalize.clone(),
+        );
+        assert_eq!(short.fade(), 1.0);
+        assert!(!short.filter(false).contains(":a]"));
+    }
+}
@@ -0,0 +1,174 @@
+//! 静音与黑场检测（`silencedetect`/`blackdetect`），为片段建议剪切点，以及剪切后保留区间的计算
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::probe::get_video_duration;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 检测时默认的最短时长（秒），更短的静音或黑场视为正常停顿
+pub const DEFAULT_MIN_DEAD_AIR_SECS: f64 = 2.0;
+
+/// 要从片段中剪掉的一段时间（秒）
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct CutRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl CutRange {
+    pub fn duration(&self) -> f64 {
+        (self.end - self.start).max(0.0)
+    }
+}
+
+/// 剪掉 `cuts` 后保留的区间，`end` 为 None 表示到文件结尾；`duration` 未知时传 0
+pub fn keep_ranges(duration: f64, cuts: &[CutRange]) -> Vec<(f64, Option<f64>)> {
+    let mut cuts: Vec<CutRange> = cuts.iter().copied().filter(|c| c.end > c.start).collect();
+    cuts.sort_by(|a, b| a.start.total_cmp(&b.start));
+    let mut keep = Vec::new();
+    let mut cursor = 0.0_f64;
+    for cut in cuts {
+        if cut.start > cursor {
+            keep.push((cursor, Some(cut.start)));
+        }
+        cursor = cursor.max(cut.end);
+    }
+    // 剪切延伸到结尾（留下不足 0.1 秒）时不再保留尾巴
+    if duration <= 0.0 || cursor < duration - 0.1 {
+        keep.push((cursor, None));
+    }
+    keep
+}
+
+/// 剪切后的总时长
+pub fn kept_duration(duration: f64, cuts: &[CutRange]) -> f64 {
+    keep_ranges(duration, cuts)
+        .iter()
+        .map(|(start, end)| end.unwrap_or(duration) - start)
+        .sum()
+}
+
+/// 检测到的空白类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeadAirKind {
+    Silence,
+    Black,
+}
+
+impl DeadAirKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeadAirKind::Silence => "静音",
+            DeadAirKind::Black => "黑场",
+        }
+    }
+}
+
+/// 一段建议剪掉的静音或黑场
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeadAir {
+    pub kind: DeadAirKind,
+    pub range: CutRange,
+}
+
+/// 解码整个文件，找出不短于 `min_secs` 的静音和黑场，按开始时间排序
+pub async fn detect_dead_air(path: &Path, min_secs: f64) -> Result<Vec<DeadAir>, AppError> {
+    if !path.is_file() {
+        return Err(AppError::FileNotFound(path.to_path_buf()));
+    }
+    let duration = get_video_duration(path).await.unwrap_or(0.0);
+    let args = FfmpegCommand::new()
+        .input(path)
+        .option(
+            "-af",
+            format!("silencedetect=noise=-50dB:d={:.2}", min_secs),
+        )
+        .video_filter(format!("blackdetect=d={:.2}:pix_th=0.10", min_secs))
+        .null_output()
+        .build();
+    let output = SystemRunner.output("ffmpeg", &args).await?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.exit.success {
+        let last = stderr.lines().last().unwrap_or("FFmpeg 异常退出");
+        return Err(AppError::Ffmpeg(last.to_string()));
+    }
+    Ok(parse_dead_air(&stderr, duration))
+}
+
+// 解析检测滤镜的日志；文件以静音结尾时没有 silence_end，用总时长补齐
+fn parse_dead_air(stderr: &str, duration: f64) -> Vec<DeadAir> {
+    let silence_start = Regex::new(r"silence_start: (-?[\d.]+)").unwrap();
+    let silence_end = Regex::new(r"silence_end: ([\d.]+)").unwrap();
+    let black = Regex::new(r"black_start:([\d.]+) black_end:([\d.]+)").unwrap();
+
+    let mut found = Vec::new();
+    let mut open_silence: Option<f64> = None;
+    for line in stderr.lines() {
+        if let Some(caps) = silence_start.captures(line) {
+            open_silence = caps[1].parse::<f64>().ok().map(|s| s.max(0.0));
+        } else if let Some(caps) = silence_end.captures(line)
+            && let (Some(start), Ok(end)) = (open_silence.take(), caps[1].parse::<f64>())
+        {
+            found.push(DeadAir {
+                kind: DeadAirKind::Silence,
+                range: CutRange { start, end },
+            });
+        } else if let Some(caps) = black.captures(line)
+            && let (Ok(start), Ok(end)) = (caps[1].parse::<f64>(), caps[2].parse::<f64>())
+        {
+            found.push(DeadAir {
+                kind: DeadAirKind::Black,
+                range: CutRange { start, end },
+            });
+        }
+    }
+    if let Some(start) = open_silence
+        && duration > start
+    {
+        found.push(DeadAir {
+            kind: DeadAirKind::Silence,
+            range: CutRange {
+                start,
+                end: duration,
+            },
+        });
+    }
+    found.sort_by(|a, b| a.range.start.total_cmp(&b.range.start));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detection_log_is_parsed_into_cut_suggestions() {
+        let stderr = "[blackdetect @ 0x1] black_start:0 black_end:2.5 black_duration:2.5
+[silencedetect @ 0x2] silence_start: 10.25
+[silencedetect @ 0x2] silence_end: 14.75 | silence_duration: 4.5
+[silencedetect @ 0x2] silence_start: 55
+";
+        let found = parse_dead_air(stderr, 60.0);
+        let ranges: Vec<(DeadAirKind, f64, f64)> = found
+            .iter()
+            .map(|d| (d.kind, d.range.start, d.range.end))
+            .collect();
+        assert_eq!(
+            ranges,
+            [
+                (DeadAirKind::Black, 0.0, 2.5),
+                (DeadAirKind::Silence, 10.25, 14.75),
+                (DeadAirKind::Silence, 55.0, 60.0),
+            ]
+        );
+
+        let cuts: Vec<CutRange> = found.iter().map(|d| d.range).collect();
+        assert_eq!(
+            keep_ranges(60.0, &cuts),
+            [(2.5, Some(10.25)), (14.75, Some(55.0))]
+        );
+        assert_eq!(kept_duration(60.0, &cuts), 48.0);
+    }
+}
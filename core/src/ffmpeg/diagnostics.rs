@@ -0,0 +1,226 @@
+use crate::ffmpeg::encoder::HardwareEncoder;
+use std::env;
+use std::path::PathBuf;
+use tokio::process::Command;
+use which::which;
+
+/// 编解码器列表中的一项
+#[derive(Debug, Clone, PartialEq)]
+pub struct Codec {
+    /// V 视频 / A 音频 / S 字幕
+    pub kind: char,
+    pub name: String,
+    pub description: String,
+}
+
+/// PATH 中的一个目录及其中是否有 ffmpeg/ffprobe
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathEntry {
+    pub dir: PathBuf,
+    pub has_ffmpeg: bool,
+    pub has_ffprobe: bool,
+}
+
+/// FFmpeg 运行环境的诊断信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostics {
+    pub ffmpeg_path: Option<PathBuf>,
+    pub ffprobe_path: Option<PathBuf>,
+    pub ffmpeg_version: Result<String, String>,
+    pub ffprobe_version: Result<String, String>,
+    pub encoders: Vec<Codec>,
+    pub decoders: Vec<Codec>,
+    pub hwaccels: Vec<String>,
+    pub path_entries: Vec<PathEntry>,
+}
+
+impl Diagnostics {
+    /// 生成可附在问题反馈中的纯文本
+    pub fn report(&self) -> String {
+        let show = |path: &Option<PathBuf>| {
+            path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "未找到".to_string())
+        };
+        let version = |v: &Result<String, String>| match v {
+            Ok(v) => v.clone(),
+            Err(e) => format!("错误: {}", e),
+        };
+        let codecs = |list: &[Codec]| {
+            list.iter()
+                .map(|c| format!("{}:{}", c.kind, c.name))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let paths = self
+            .path_entries
+            .iter()
+            .map(|entry| {
+                let mut found = Vec::new();
+                if entry.has_ffmpeg {
+                    found.push("ffmpeg");
+                }
+                if entry.has_ffprobe {
+                    found.push("ffprobe");
+                }
+                format!("  {} {}", entry.dir.display(), found.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "mp4文件合并 {} 环境诊断\n\
+             系统: {} {}\n\
+             ffmpeg: {}\n  {}\n\
+             ffprobe: {}\n  {}\n\
+             硬件加速: {}\n\
+             编码器({}): {}\n\
+             解码器({}): {}\n\
+             PATH:\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            env::consts::OS,
+            env::consts::ARCH,
+            show(&self.ffmpeg_path),
+            version(&self.ffmpeg_version),
+            show(&self.ffprobe_path),
+            version(&self.ffprobe_version),
+            self.hwaccels.join(", "),
+            self.encoders.len(),
+            codecs(&self.encoders),
+            self.decoders.len(),
+            codecs(&self.decoders),
+            paths,
+        )
+    }
+}
+
+/// 收集 FFmpeg 环境诊断信息，单项失败不影响其他项
+pub async fn collect_diagnostics() -> Diagnostics {
+    let ffmpeg_available = which("ffmpeg").is_ok();
+    let (encoders, decoders, hwaccels) = if ffmpeg_available {
+        (
+            run_tool("ffmpeg", &["-hide_banner", "-encoders"])
+                .await
+                .map(|out| parse_codecs(&out))
+                .unwrap_or_default(),
+            run_tool("ffmpeg", &["-hide_banner", "-decoders"])
+                .await
+                .map(|out| parse_codecs(&out))
+                .unwrap_or_default(),
+            run_tool("ffmpeg", &["-hide_banner", "-hwaccels"])
+                .await
+                .map(|out| parse_hwaccels(&out))
+                .unwrap_or_default(),
+        )
+    } else {
+        Default::default()
+    };
+
+    Diagnostics {
+        ffmpeg_path: which("ffmpeg").ok(),
+        ffprobe_path: which("ffprobe").ok(),
+        ffmpeg_version: tool_version("ffmpeg").await,
+        ffprobe_version: tool_version("ffprobe").await,
+        encoders,
+        decoders,
+        hwaccels,
+        path_entries: path_entries(),
+    }
+}
+
+/// 本机 FFmpeg 支持的硬件编码器，FFmpeg 不可用时为空
+pub async fn detect_hardware_encoders() -> Vec<HardwareEncoder> {
+    let codecs = run_tool("ffmpeg", &["-hide_banner", "-encoders"])
+        .await
+        .map(|out| parse_codecs(&out))
+        .unwrap_or_default();
+    let names: Vec<&str> = codecs.iter().map(|c| c.name.as_str()).collect();
+    HardwareEncoder::detect(&names)
+}
+
+async fn tool_version(tool: &str) -> Result<String, String> {
+    let output = run_tool(tool, &["-version"]).await?;
+    output
+        .lines()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| "没有输出版本信息".to_string())
+}
+
+async fn run_tool(tool: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(tool)
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("无法运行 {}: {}", tool, e))?;
+    if !output.status.success() {
+        return Err(format!("{} 异常退出: {}", tool, output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn path_entries() -> Vec<PathEntry> {
+    let exe = |name: &str| format!("{}{}", name, env::consts::EXE_SUFFIX);
+    env::var_os("PATH")
+        .map(|path| {
+            env::split_paths(&path)
+                .map(|dir| PathEntry {
+                    has_ffmpeg: dir.join(exe("ffmpeg")).is_file(),
+                    has_ffprobe: dir.join(exe("ffprobe")).is_file(),
+                    dir,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// `-encoders`/`-decoders` 的输出：说明部分以 " ------" 结束，之后每行形如
+// " V....D libx264              libx264 H.264 / AVC ..."
+fn parse_codecs(output: &str) -> Vec<Codec> {
+    output
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let flags = parts.next()?;
+            let name = parts.next()?;
+            Some(Codec {
+                kind: flags.chars().next()?,
+                name: name.to_string(),
+                description: parts.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+// `-hwaccels` 的输出：第一行是标题 "Hardware acceleration methods:"
+fn parse_hwaccels(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip(1)
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_list_is_parsed() {
+        let output = "Encoders:\n V..... = Video\n A..... = Audio\n ------\n V....D libx264              libx264 H.264 / AVC\n A....D aac                  AAC (Advanced Audio Coding)\n";
+        let codecs = parse_codecs(output);
+        assert_eq!(codecs.len(), 2);
+        assert_eq!(codecs[0].kind, 'V');
+        assert_eq!(codecs[0].name, "libx264");
+        assert_eq!(codecs[1].description, "AAC (Advanced Audio Coding)");
+        assert_eq!(
+            parse_hwaccels("Hardware acceleration methods:\ncuda\nqsv\n\n"),
+            vec!["cuda", "qsv"]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::ffmpeg::args::FfmpegCommand;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 两遍编码第一遍的输出位置（只生成统计文件，不保留视频）
+#[cfg(windows)]
+pub const NULL_OUTPUT: &str = "NUL";
+#[cfg(not(windows))]
+pub const NULL_OUTPUT: &str = "/dev/null";
+
+/// x264 编码速度预设，越慢压缩率越高
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum EncoderPreset {
+    Ultrafast,
+    Veryfast,
+    Fast,
+    #[default]
+    Medium,
+    Slow,
+    Slower,
+}
+
+impl EncoderPreset {
+    pub const ALL: [EncoderPreset; 6] = [
+        EncoderPreset::Ultrafast,
+        EncoderPreset::Veryfast,
+        EncoderPreset::Fast,
+        EncoderPreset::Medium,
+        EncoderPreset::Slow,
+        EncoderPreset::Slower,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EncoderPreset::Ultrafast => "ultrafast",
+            EncoderPreset::Veryfast => "veryfast",
+            EncoderPreset::Fast => "fast",
+            EncoderPreset::Medium => "medium",
+            EncoderPreset::Slow => "slow",
+            EncoderPreset::Slower => "slower",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EncoderPreset::Ultrafast => "最快",
+            EncoderPreset::Veryfast => "很快",
+            EncoderPreset::Fast => "较快",
+            EncoderPreset::Medium => "均衡",
+            EncoderPreset::Slow => "较慢",
+            EncoderPreset::Slower => "最慢",
+        }
+    }
+}
+
+/// 显卡提供的 H.264 硬件编码器，速度快、CPU 占用低，同等画质下文件略大
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum HardwareEncoder {
+    Nvenc,
+    Qsv,
+    Amf,
+    VideoToolbox,
+}
+
+impl HardwareEncoder {
+    pub const ALL: [HardwareEncoder; 4] = [
+        HardwareEncoder::Nvenc,
+        HardwareEncoder::Qsv,
+        HardwareEncoder::Amf,
+        HardwareEncoder::VideoToolbox,
+    ];
+
+    /// FFmpeg 中的编码器名称
+    pub fn codec(&self) -> &'static str {
+        match self {
+            HardwareEncoder::Nvenc => "h264_nvenc",
+            HardwareEncoder::Qsv => "h264_qsv",
+            HardwareEncoder::Amf => "h264_amf",
+            HardwareEncoder::VideoToolbox => "h264_videotoolbox",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HardwareEncoder::Nvenc => "NVIDIA NVENC",
+            HardwareEncoder::Qsv => "Intel Quick Sync",
+            HardwareEncoder::Amf => "AMD AMF",
+            HardwareEncoder::VideoToolbox => "Apple VideoToolbox",
+        }
+    }
+
+    /// 按 CRF 设置近似的恒定画质参数，各编码器的参数名和取值范围不同
+    fn apply_quality(&self, command: FfmpegCommand, crf: u8) -> FfmpegCommand {
+        match self {
+            HardwareEncoder::Nvenc => command.option("-rc", "vbr").option("-cq", crf.to_string()),
+            HardwareEncoder::Qsv => command.option("-global_quality", crf.to_string()),
+            HardwareEncoder::Amf => command
+                .option("-rc", "cqp")
+                .option("-qp_i", crf.to_string())
+                .option("-qp_p", crf.to_string()),
+            // VideoToolbox 的 q:v 为 1~100，越大画质越高
+            HardwareEncoder::VideoToolbox => {
+                let q = (100 - u32::from(crf.min(51)) * 100 / 51).max(1);
+                command.option("-q:v", q.to_string())
+            }
+        }
+    }
+
+    /// 从 `ffmpeg -encoders` 的编码器列表中找出本机 FFmpeg 支持的硬件编码器。
+    /// 编译时支持不代表有对应显卡，实际能否使用要运行时才知道
+    pub fn detect(encoder_names: &[&str]) -> Vec<HardwareEncoder> {
+        HardwareEncoder::ALL
+            .into_iter()
+            .filter(|e| encoder_names.contains(&e.codec()))
+            .collect()
+    }
+}
+
+/// 两遍编码中的某一遍
+#[derive(Debug, Clone, Copy)]
+pub struct EncodePass<'a> {
+    /// 1 或 2
+    pub index: u8,
+    /// FFmpeg 统计文件前缀，两遍必须相同
+    pub log_prefix: &'a Path,
+}
+
+/// 重新编码时的画质设置
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct EncodeQuality {
+    /// 0~51，越小画质越高
+    pub crf: u8,
+    pub preset: EncoderPreset,
+    /// 设置后改用两遍编码并以该码率（kbps）为目标，忽略 CRF
+    pub two_pass_bitrate: Option<u32>,
+    /// 设置后改用显卡编码，不支持两遍编码，设置码率时按该码率单遍编码
+    #[serde(default)]
+    pub hardware: Option<HardwareEncoder>,
+}
+
+impl Default for EncodeQuality {
+    fn default() -> Self {
+        EncodeQuality {
+            crf: 23,
+            preset: EncoderPreset::Medium,
+            two_pass_bitrate: None,
+            hardware: None,
+        }
+    }
+}
+
+impl EncodeQuality {
+    pub fn is_two_pass(&self) -> bool {
+        self.two_pass_bitrate.is_some() && self.hardware.is_none()
+    }
+
+    /// 实际使用的视频编码器名称
+    pub fn encoder_name(&self) -> &'static str {
+        self.hardware.map_or("libx264", |h| h.codec())
+    }
+
+    /// 追加视频编码参数；两遍编码时 `pass` 指定当前是第几遍
+    pub fn apply(&self, command: FfmpegCommand, pass: Option<EncodePass>) -> FfmpegCommand {
+        if let Some(hardware) = self.hardware {
+            let command = command.video_codec(hardware.codec());
+            return match self.two_pass_bitrate {
+                Some(bitrate) => command.option("-b:v", format!("{}k", bitrate)),
+                None => hardware.apply_quality(command, self.crf),
+            };
+        }
+        let command = command
+            .video_codec("libx264")
+            .option("-preset", self.preset.as_str());
+        match (self.two_pass_bitrate, pass) {
+            (Some(bitrate), Some(pass)) => command
+                .option("-b:v", format!("{}k", bitrate))
+                .option("-pass", pass.index.to_string())
+                .option("-passlogfile", pass.log_prefix),
+            _ => command.option("-crf", self.crf.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hardware_encoder_replaces_libx264() {
+        let quality = EncodeQuality {
+            hardware: Some(HardwareEncoder::Nvenc),
+            two_pass_bitrate: Some(4000),
+            ..Default::default()
+        };
+        assert!(!quality.is_two_pass());
+        let args: Vec<String> = quality
+            .apply(FfmpegCommand::new(), None)
+            .build()
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert!(args.windows(2).any(|w| w == ["-c:v", "h264_nvenc"]));
+        assert!(args.windows(2).any(|w| w == ["-b:v", "4000k"]));
+        assert_eq!(
+            HardwareEncoder::detect(&["libx264", "h264_qsv", "hevc_qsv"]),
+            vec![HardwareEncoder::Qsv]
+        );
+    }
+}
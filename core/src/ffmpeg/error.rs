@@ -0,0 +1,184 @@
+/// 可一键执行的补救操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Remedy {
+    OpenSettings,
+    DownloadFfmpeg,
+    SwitchToReEncode,
+    ChooseOutputDirectory,
+    /// 以管理员身份重新打开程序，仅 Windows 提供
+    RunAsAdministrator,
+}
+
+impl Remedy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Remedy::OpenSettings => "打开设置",
+            Remedy::DownloadFfmpeg => "下载 FFmpeg",
+            Remedy::SwitchToReEncode => "改用重新编码",
+            Remedy::ChooseOutputDirectory => "更换输出目录",
+            Remedy::RunAsAdministrator => "以管理员身份重新打开",
+        }
+    }
+}
+
+/// 常见失败的分类
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorCode {
+    FfmpegMissing,
+    CodecMismatch,
+    PermissionDenied,
+    DiskFull,
+    ProbeTimeout,
+}
+
+impl ErrorCode {
+    /// 反馈问题时引用的错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCode::FfmpegMissing => "E001",
+            ErrorCode::CodecMismatch => "E002",
+            ErrorCode::PermissionDenied => "E003",
+            ErrorCode::DiskFull => "E004",
+            ErrorCode::ProbeTimeout => "E005",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ErrorCode::FfmpegMissing => "找不到 FFmpeg",
+            ErrorCode::CodecMismatch => "文件编码不一致",
+            ErrorCode::PermissionDenied => "没有写入权限",
+            ErrorCode::DiskFull => "磁盘空间不足",
+            ErrorCode::ProbeTimeout => "文件读取超时",
+        }
+    }
+
+    /// 一句话的解决建议
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            ErrorCode::FfmpegMissing => "请安装 FFmpeg，或在设置中指定其所在文件夹。",
+            ErrorCode::CodecMismatch => "直接复制要求各文件编码参数一致，可改用重新编码后再合并。",
+            ErrorCode::PermissionDenied => "请换一个有写入权限的输出目录，或以管理员身份运行。",
+            ErrorCode::DiskFull => "请清理输出目录所在磁盘，或换到空间更大的磁盘。",
+            ErrorCode::ProbeTimeout => "该文件可能已损坏，请从列表中移除后重试。",
+        }
+    }
+
+    /// "了解更多" 中展开的详细说明
+    pub fn details(&self) -> &'static str {
+        match self {
+            ErrorCode::FfmpegMissing => {
+                "合并依赖 ffmpeg 与 ffprobe 两个程序。程序会在 PATH 环境变量列出的目录中查找它们；\
+                 下载后可以把所在文件夹加入 PATH，也可以在首次启动向导中直接选择该文件夹。\
+                 设置页底部的“FFmpeg 环境诊断”会列出实际的查找位置。"
+            }
+            ErrorCode::CodecMismatch => {
+                "“直接复制”只是把各文件的音视频流首尾相接，速度快且不损失画质，\
+                 但要求所有文件的编码格式、分辨率、帧率和音频参数完全一致。\
+                 来自不同设备或经过不同软件导出的文件通常不满足这个条件，\
+                 此时需要“重新编码”把它们转换成统一的格式。"
+            }
+            ErrorCode::PermissionDenied => {
+                "系统拒绝了对输出目录或文件的写入。常见原因是目录位于 Program Files 等受保护位置、\
+                 文件被设为只读，或被杀毒软件拦截。\
+                 以管理员身份重新打开后，需要在新窗口中重新开始本次操作。"
+            }
+            ErrorCode::DiskFull => {
+                "合并时会先写入临时文件，完成后再重命名，所需空间约等于所有输入文件大小之和；\
+                 重新编码的输出大小取决于画质设置。"
+            }
+            ErrorCode::ProbeTimeout => {
+                "合并前会逐个读取文件时长，损坏或未写完的文件可能让 FFmpeg 一直无法返回。\
+                 超时后已中止本次合并并结束所有读取进程，可以先用播放器确认该文件能否正常打开。"
+            }
+        }
+    }
+
+    pub fn remedies(&self) -> &'static [Remedy] {
+        match self {
+            ErrorCode::FfmpegMissing => &[Remedy::DownloadFfmpeg, Remedy::OpenSettings],
+            ErrorCode::CodecMismatch => &[Remedy::SwitchToReEncode],
+            ErrorCode::PermissionDenied => {
+                &[Remedy::ChooseOutputDirectory, Remedy::RunAsAdministrator]
+            }
+            ErrorCode::DiskFull => &[Remedy::ChooseOutputDirectory],
+            ErrorCode::ProbeTimeout => &[],
+        }
+    }
+
+    // 错误文本中的特征，按小写匹配
+    fn patterns(&self) -> &'static [&'static str] {
+        match self {
+            ErrorCode::FfmpegMissing => &["未找到ffmpeg", "启动ffmpeg失败", "program not found"],
+            ErrorCode::CodecMismatch => &[
+                "non-monotonous dts",
+                "non monotonically increasing dts",
+                "codec parameters",
+                "could not find tag for codec",
+            ],
+            ErrorCode::PermissionDenied => &[
+                "permission denied",
+                "access is denied",
+                "拒绝访问",
+                "(os error 5)",
+                "(os error 13)",
+            ],
+            ErrorCode::DiskFull => &[
+                "no space left on device",
+                "not enough space on the disk",
+                "磁盘空间不足",
+                "(os error 28)",
+                "(os error 112)",
+            ],
+            ErrorCode::ProbeTimeout => &["秒未响应"],
+        }
+    }
+
+    /// 从错误信息识别常见失败，无法识别时返回 None
+    pub fn classify(message: &str) -> Option<Self> {
+        let lower = message.to_lowercase();
+        [
+            ErrorCode::DiskFull,
+            ErrorCode::PermissionDenied,
+            ErrorCode::FfmpegMissing,
+            ErrorCode::CodecMismatch,
+            ErrorCode::ProbeTimeout,
+        ]
+        .into_iter()
+        .find(|code| code.patterns().iter().any(|p| lower.contains(p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_failures_are_classified() {
+        let code = ErrorCode::classify;
+        assert_eq!(
+            code("未找到FFmpeg，请确保已安装并添加到系统PATH中"),
+            Some(ErrorCode::FfmpegMissing)
+        );
+        assert_eq!(
+            code(
+                "FFmpeg进程异常退出，退出码: exit code: 1：[mp4 @ 0x1] Non-monotonous DTS in output stream 0:1"
+            ),
+            Some(ErrorCode::CodecMismatch)
+        );
+        assert_eq!(
+            code("重命名输出文件失败: Access is denied. (os error 5)"),
+            Some(ErrorCode::PermissionDenied)
+        );
+        assert_eq!(
+            code("av_interleaved_write_frame(): No space left on device"),
+            Some(ErrorCode::DiskFull)
+        );
+        assert_eq!(
+            code("无法读取视频时长 a.mp4: FFmpeg 超过 30 秒未响应，文件可能已损坏"),
+            Some(ErrorCode::ProbeTimeout)
+        );
+        assert_eq!(code("文件不存在: a.mp4"), None);
+        assert_eq!(code("(os error 53)"), None);
+    }
+}
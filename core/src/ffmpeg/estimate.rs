@@ -0,0 +1,72 @@
+use crate::error::AppError;
+use crate::ffmpeg::benchmark::{BenchmarkTarget, estimate_total_secs, prepare_sample};
+use crate::ffmpeg::options::{MergeMode, MergeOptions};
+use crate::utils::{format_duration, format_size};
+use std::path::PathBuf;
+
+/// 重新编码时输入总时长超过该值才测速估算耗时，短任务不值得等待测速
+const TIMED_ESTIMATE_MIN_SECS: f64 = 600.0;
+
+/// 估算时假定的音频码率（kbps）
+const AUDIO_KBPS: f64 = 192.0;
+
+/// 合并前对输出大小与耗时的估算
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobEstimate {
+    pub output_bytes: u64,
+    /// 处理耗时（秒），直接复制或输入较短时不估算
+    pub secs: Option<f64>,
+}
+
+impl JobEstimate {
+    /// 形如 "预计输出 25.3 GB，耗时约 03:10:00"
+    pub fn describe(&self) -> String {
+        let mut text = format!("预计输出 {}", format_size(Some(self.output_bytes)));
+        if let Some(secs) = self.secs {
+            text.push_str(&format!("，耗时约 {}", format_duration(secs)));
+        }
+        text
+    }
+}
+
+/// 按合并方式估算输出大小；重新编码且输入较长时截取样本测速估算耗时
+pub async fn estimate_job(
+    files: &[PathBuf],
+    options: &MergeOptions,
+) -> Result<JobEstimate, AppError> {
+    let input_bytes: u64 = files
+        .iter()
+        .filter_map(|f| std::fs::metadata(f).ok())
+        .map(|m| m.len())
+        .sum();
+    if options.mode == MergeMode::Copy {
+        return Ok(JobEstimate {
+            output_bytes: input_bytes,
+            secs: None,
+        });
+    }
+
+    let sample = prepare_sample(files).await?;
+    let bytes_at = |kbps: f64| (kbps * 1000.0 / 8.0 * sample.total_duration) as u64;
+    let quality = options.quality;
+    let output_bytes = match options.mode {
+        MergeMode::AudioOnly(_) => bytes_at(AUDIO_KBPS),
+        _ => match quality.two_pass_bitrate {
+            Some(kbps) => bytes_at(kbps as f64 + AUDIO_KBPS),
+            // CRF 每增减 6 输出大小约减半/翻倍，以 CRF 23 约等于输入大小粗略估算
+            None => (input_bytes as f64 * 2f64.powf((23.0 - quality.crf as f64) / 6.0)) as u64,
+        },
+    };
+
+    let secs = if options.mode == MergeMode::ReEncode
+        && sample.total_duration >= TIMED_ESTIMATE_MIN_SECS
+    {
+        let passes = if quality.is_two_pass() { 2.0 } else { 1.0 };
+        let secs = estimate_total_secs(&sample, BenchmarkTarget::ReEncode(quality.preset)).await?;
+        Some(secs * passes)
+    } else {
+        None
+    };
+
+    Ok(JobEstimate { output_bytes, secs })
+}
@@ -0,0 +1,175 @@
+use crate::MergeEvent;
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::command::SystemRunner;
+use crate::ffmpeg::merge_mp4::MergeEventSender;
+use crate::ffmpeg::phase::MergePhase;
+use crate::ffmpeg::probe::get_video_duration;
+use crate::ffmpeg::runner::run_ffmpeg_with_progress;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// 截图图片格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+        }
+    }
+}
+
+/// 截图方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameMode {
+    /// 指定时间点的单帧（秒）
+    Single(f64),
+    /// 每隔 N 秒截取一帧
+    Interval(f64),
+    /// 场景切换处截取，参数为 0~1 的变化阈值
+    Scene(f64),
+}
+
+/// 截图参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameExtractOptions {
+    pub mode: FrameMode,
+    pub format: ImageFormat,
+}
+
+impl FrameExtractOptions {
+    // 单帧输出为固定文件名，其余按序号输出；序号模式下原名中的 `%` 需转义为 `%%`
+    fn output_pattern(&self, dir: &Path, stem: &OsStr) -> PathBuf {
+        let ext = self.format.extension();
+        let name = match self.mode {
+            FrameMode::Single(at) => {
+                let mut name = stem.to_os_string();
+                name.push(format!("_{:.0}s.{}", at, ext));
+                name
+            }
+            FrameMode::Interval(_) | FrameMode::Scene(_) => {
+                let mut name = match stem.to_str() {
+                    Some(stem) => OsString::from(stem.replace('%', "%%")),
+                    None => stem.to_os_string(),
+                };
+                name.push(format!("_%04d.{}", ext));
+                name
+            }
+        };
+        dir.join(name)
+    }
+
+    fn command(&self, input: &Path, output: &Path) -> FfmpegCommand {
+        let command = match self.mode {
+            FrameMode::Single(at) => FfmpegCommand::new()
+                .input(FfmpegInput::new(input).seek(at))
+                .option("-frames:v", "1")
+                .option("-update", "1"),
+            FrameMode::Interval(seconds) => FfmpegCommand::new()
+                .input(input)
+                .video_filter(format!("fps=1/{}", seconds)),
+            FrameMode::Scene(threshold) => FfmpegCommand::new()
+                .input(input)
+                .video_filter(format!("select='gt(scene,{:.2})'", threshold))
+                .option("-fps_mode", "vfr"),
+        };
+        let command = if self.format == ImageFormat::Jpeg {
+            command.option("-q:v", "2")
+        } else {
+            command
+        };
+        command.no_audio().output(output)
+    }
+}
+
+/// 从单个文件中截取图片保存到 `output_dir`，成功时报告导出数量
+pub async fn run_frame_extract(
+    input: PathBuf,
+    output_dir: PathBuf,
+    options: FrameExtractOptions,
+    tx: impl Into<MergeEventSender>,
+) {
+    let tx: MergeEventSender = tx.into();
+    let stem = input
+        .file_stem()
+        .map(OsStr::to_os_string)
+        .unwrap_or_else(|| OsString::from("frame"));
+    let duration = match options.mode {
+        FrameMode::Single(_) => 0.0,
+        _ => get_video_duration(&input).await.unwrap_or(0.0),
+    };
+
+    let output = options.output_pattern(&output_dir, &stem);
+    tx.send(MergeEvent::Status("正在截图...".to_string()));
+    let command = options.command(&input, &output);
+    if let Err(e) = run_ffmpeg_with_progress(
+        &SystemRunner,
+        command,
+        duration,
+        MergePhase::Concatenating,
+        0.0,
+        100.0,
+        &tx,
+    )
+    .await
+    {
+        tx.send(MergeEvent::Error(e));
+        return;
+    }
+
+    let prefix = format!("{}_", stem.to_string_lossy());
+    let ext = options.format.extension();
+    let count = std::fs::read_dir(&output_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.starts_with(&prefix) && name.ends_with(ext)
+                })
+                .count()
+        })
+        .unwrap_or(0);
+    if count == 0 {
+        tx.send(MergeEvent::Error(AppError::Other(
+            "没有截取到任何画面".to_string(),
+        )));
+        return;
+    }
+    tx.send(MergeEvent::Success(format!(
+        "已导出 {} 张图片到 {}",
+        count,
+        output_dir.display()
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_pattern_escapes_percent() {
+        let options = FrameExtractOptions {
+            mode: FrameMode::Interval(5.0),
+            format: ImageFormat::Png,
+        };
+        assert_eq!(
+            options.output_pattern(Path::new("out"), OsStr::new("100% 完成 🎉")),
+            Path::new("out").join("100%% 完成 🎉_%04d.png")
+        );
+        let single = FrameExtractOptions {
+            mode: FrameMode::Single(3.0),
+            ..options
+        };
+        assert_eq!(
+            single.output_pattern(Path::new("out"), OsStr::new("100% 完成 🎉")),
+            Path::new("out").join("100% 完成 🎉_3s.png")
+        );
+    }
+}
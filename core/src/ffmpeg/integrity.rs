@@ -0,0 +1,79 @@
+//! 完整解码一遍文件以发现损坏，相当于 `ffmpeg -v error -i file -f null -`
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use std::path::{Path, PathBuf};
+
+/// 报告中最多保留的错误行数，严重损坏的文件可能输出上万行
+pub const MAX_REPORTED_ERRORS: usize = 50;
+
+/// 单个文件的检查结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegrityReport {
+    pub path: PathBuf,
+    /// 解码错误（最多 [`MAX_REPORTED_ERRORS`] 行）
+    pub errors: Vec<String>,
+    /// 错误总行数
+    pub error_count: usize,
+}
+
+impl IntegrityReport {
+    pub fn is_ok(&self) -> bool {
+        self.error_count == 0
+    }
+}
+
+/// 解码整个文件并收集错误输出，文件较大时耗时与一次转码相当
+pub async fn check_integrity(path: &Path) -> Result<IntegrityReport, AppError> {
+    if !path.is_file() {
+        return Err(AppError::FileNotFound(path.to_path_buf()));
+    }
+    let args = FfmpegCommand::new()
+        .input(path)
+        .option("-v", "error")
+        .null_output()
+        .build();
+    let output = SystemRunner.output("ffmpeg", &args).await?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut errors = decode_errors(&stderr);
+    // 无法打开文件时没有任何解码输出，也按错误报告
+    if !output.exit.success && errors.is_empty() {
+        errors.push("FFmpeg 异常退出".to_string());
+    }
+    let error_count = errors.len();
+    errors.truncate(MAX_REPORTED_ERRORS);
+    Ok(IntegrityReport {
+        path: path.to_path_buf(),
+        errors,
+        error_count,
+    })
+}
+
+// -v error 时 stderr 只包含错误，逐行去掉空白
+fn decode_errors(stderr: &str) -> Vec<String> {
+    stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_stderr_means_no_errors() {
+        assert!(decode_errors("\n  \n").is_empty());
+        assert_eq!(
+            decode_errors(
+                "[h264 @ 0x1] error while decoding MB 3 5\r\n\n[aac @ 0x2] Invalid data\n"
+            ),
+            [
+                "[h264 @ 0x1] error while decoding MB 3 5",
+                "[aac @ 0x2] Invalid data"
+            ]
+        );
+    }
+}
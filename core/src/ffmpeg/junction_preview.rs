@@ -0,0 +1,186 @@
+//! 衔接预览：把前一片段最后几秒和后一片段开头几秒拼成一个小尺寸文件，合并前确认衔接处是否连贯
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::dead_air::{CutRange, keep_ranges};
+use crate::ffmpeg::options::MergeOptions;
+use crate::ffmpeg::probe::{probe_duration_cached, probe_has_audio};
+use crate::ffmpeg::proxy::preview_source;
+use crate::utils::app_data_dir;
+use chrono::Local;
+use std::path::{Path, PathBuf};
+
+/// 衔接处两侧各截取的时长（秒）
+pub const JUNCTION_PREVIEW_SECS: f64 = 2.0;
+
+// 预览统一缩放到的画面尺寸和帧率，两侧参数不同也能拼接
+const PREVIEW_SIZE: (u32, u32) = (640, 360);
+const PREVIEW_FPS: u32 = 30;
+
+/// 片段结尾处的截取区间（开始，时长），已剪掉的部分不计入
+pub fn tail_window(duration: f64, cuts: &[CutRange]) -> (f64, f64) {
+    let (start, end) = keep_ranges(duration, cuts)
+        .last()
+        .map(|&(start, end)| (start, end.unwrap_or(duration)))
+        .unwrap_or((0.0, duration));
+    let from = (end - JUNCTION_PREVIEW_SECS).max(start);
+    (from, end - from)
+}
+
+/// 片段开头处的截取区间（开始，时长），已剪掉的部分不计入
+pub fn head_window(duration: f64, cuts: &[CutRange]) -> (f64, f64) {
+    let (start, end) = keep_ranges(duration, cuts)
+        .first()
+        .map(|&(start, end)| (start, end.unwrap_or(duration)))
+        .unwrap_or((0.0, duration));
+    (start, JUNCTION_PREVIEW_SECS.min(end - start))
+}
+
+/// 两段截取统一尺寸后拼接；`with_audio` 为 false 时只拼接画面
+pub fn preview_command(
+    before: (&Path, (f64, f64)),
+    after: (&Path, (f64, f64)),
+    with_audio: bool,
+    output: &Path,
+) -> FfmpegCommand {
+    let (width, height) = PREVIEW_SIZE;
+    let video = |input: usize| {
+        format!(
+            "[{input}:v]scale={width}:{height}:force_original_aspect_ratio=decrease,\
+             pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={PREVIEW_FPS},format=yuv420p[v{input}]"
+        )
+    };
+    let audio = |input: usize| {
+        format!("[{input}:a]aresample=48000,aformat=channel_layouts=stereo[a{input}]")
+    };
+    let graph = if with_audio {
+        format!(
+            "{};{};{};{};[v0][a0][v1][a1]concat=n=2:v=1:a=1[v][a]",
+            video(0),
+            audio(0),
+            video(1),
+            audio(1)
+        )
+    } else {
+        format!("{};{};[v0][v1]concat=n=2:v=1:a=0[v]", video(0), video(1))
+    };
+
+    let input = |(path, (start, duration)): (&Path, (f64, f64))| {
+        FfmpegInput::new(path).seek(start).duration(duration)
+    };
+    let command = FfmpegCommand::new()
+        .input(input(before))
+        .input(input(after))
+        .filter_complex(graph)
+        .map("[v]");
+    let command = if with_audio {
+        command.map("[a]").audio_codec("aac")
+    } else {
+        command.no_audio()
+    };
+    command
+        .video_codec("libx264")
+        .option("-preset", "ultrafast")
+        .option("-crf", "28")
+        .option("-movflags", "+faststart")
+        .format("mp4")
+        .output(output)
+}
+
+/// 生成 `before` 与 `after` 衔接处的预览文件，按合并选项中的剪切设置截取，返回文件路径
+pub async fn junction_preview(
+    before: &Path,
+    after: &Path,
+    options: &MergeOptions,
+) -> Result<PathBuf, AppError> {
+    for path in [before, after] {
+        if !path.is_file() {
+            return Err(AppError::FileNotFound(path.to_path_buf()));
+        }
+    }
+    let before_window = tail_window(
+        probe_duration_cached(before).await?,
+        options.cuts_of(before),
+    );
+    let after_window = head_window(probe_duration_cached(after).await?, options.cuts_of(after));
+    let with_audio = probe_has_audio(before).await? && probe_has_audio(after).await?;
+
+    let dir = previews_dir()?;
+    let output = dir.join(format!(
+        "junction-{}.mp4",
+        Local::now().format("%Y%m%d%H%M%S%3f")
+    ));
+    // 有代理时从代理截取，时间轴与原文件一致
+    let args = preview_command(
+        (&preview_source(before), before_window),
+        (&preview_source(after), after_window),
+        with_audio,
+        &output,
+    )
+    .build();
+    let result = SystemRunner.output("ffmpeg", &args).await?;
+    if !result.exit.success || !output.is_file() {
+        let _ = std::fs::remove_file(&output);
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        let last_line = stderr.lines().last().unwrap_or_default();
+        return Err(AppError::Ffmpeg(format!("生成衔接预览失败: {}", last_line)));
+    }
+    Ok(output)
+}
+
+// 预览文件所在目录，每次生成前清掉旧的预览（正在播放而被占用的文件留到下次）
+fn previews_dir() -> Result<PathBuf, AppError> {
+    let dir = app_data_dir()
+        .map_err(|e| AppError::io("无法获取数据目录", e))?
+        .join("previews");
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| AppError::io("创建预览目录失败", e))?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_skip_trimmed_parts() {
+        assert_eq!(tail_window(60.0, &[]), (58.0, 2.0));
+        assert_eq!(head_window(60.0, &[]), (0.0, 2.0));
+
+        let cuts = [
+            CutRange {
+                start: 0.0,
+                end: 3.0,
+            },
+            CutRange {
+                start: 50.0,
+                end: 60.0,
+            },
+        ];
+        assert_eq!(tail_window(60.0, &cuts), (48.0, 2.0));
+        assert_eq!(head_window(60.0, &cuts), (3.0, 2.0));
+        // 片段短于截取时长时取整段
+        assert_eq!(tail_window(1.5, &[]), (0.0, 1.5));
+
+        let args: Vec<String> = preview_command(
+            (Path::new("a.mp4"), (58.0, 2.0)),
+            (Path::new("b.mp4"), (0.0, 2.0)),
+            false,
+            Path::new("out.mp4"),
+        )
+        .build()
+        .iter()
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+        assert!(args.windows(2).any(|w| w == ["-ss", "58.000"]));
+        assert!(
+            args.iter()
+                .any(|a| a.ends_with("[v0][v1]concat=n=2:v=1:a=0[v]"))
+        );
+        assert!(args.contains(&"-an".to_string()));
+    }
+}
@@ -0,0 +1,48 @@
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use which::which;
+
+/// FFmpeg 官方下载页面
+pub const FFMPEG_DOWNLOAD_URL: &str = "https://ffmpeg.org/download.html";
+
+#[cfg(windows)]
+const EXE_SUFFIX: &str = ".exe";
+#[cfg(not(windows))]
+const EXE_SUFFIX: &str = "";
+
+/// 在 PATH 中查找 ffmpeg，返回可执行文件路径
+pub fn find_ffmpeg() -> Option<PathBuf> {
+    which("ffmpeg").ok()
+}
+
+/// 检查目录中是否同时有 ffmpeg 与 ffprobe
+pub fn check_ffmpeg_dir(dir: &Path) -> Result<(), String> {
+    for name in ["ffmpeg", "ffprobe"] {
+        let exe = dir.join(format!("{}{}", name, EXE_SUFFIX));
+        if !exe.is_file() {
+            return Err(format!("{} 中没有找到 {}", dir.display(), name));
+        }
+    }
+    Ok(())
+}
+
+/// 把目录加到 PATH 最前面，之后启动的 ffmpeg/ffprobe 都从这里查找
+pub fn add_ffmpeg_dir(dir: &Path) {
+    let current = env::var_os("PATH").unwrap_or_default();
+    let mut paths: Vec<PathBuf> = env::split_paths(&current).collect();
+    if paths.first().is_some_and(|first| first == dir) {
+        return;
+    }
+    paths.retain(|p| p != dir);
+    paths.insert(0, dir.to_path_buf());
+    let Ok(joined) = env::join_paths(paths) else {
+        return;
+    };
+    set_path(joined);
+}
+
+fn set_path(value: OsString) {
+    // SAFETY: 只在启动时和用户在向导中选择目录时调用，此时没有其他线程在读取环境变量
+    unsafe { env::set_var("PATH", value) };
+}
@@ -0,0 +1,1032 @@
+use crate::MergeEvent;
+use crate::config::AppConfig;
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::bookends::{conform_command, needs_conform};
+use crate::ffmpeg::checkpoint::{CHECKPOINT_MIN_SECS, Checkpoint, checkpoint_key};
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::crossfade::CrossfadePlan;
+use crate::ffmpeg::dead_air::{CutRange, keep_ranges, kept_duration};
+use crate::ffmpeg::encoder::EncodePass;
+use crate::ffmpeg::normalize::{NormalizeOptions, NormalizePlan, plan_normalization_with};
+use crate::ffmpeg::options::{MergeMode, MergeOptions, SourceAction};
+use crate::ffmpeg::phase::{MergePhase, MergeProgress, segment_boundaries};
+use crate::ffmpeg::probe::{
+    get_video_duration, get_video_duration_with, probe_durations_with, probe_rotation_with,
+    probe_video_stream_with,
+};
+use crate::ffmpeg::runner::run_ffmpeg_with_progress;
+use crate::ffmpeg::smart_cut::{encoder_for, keyframe_times_with, plan_pieces};
+use crate::ffmpeg::speed::{speed_command, speed_label};
+use crate::ffmpeg::title_card::TitleCardOptions;
+use crate::ffmpeg::workspace::{JobWorkspace, part_path};
+use crate::history::{HistoryEntry, compute_checksums, record_history};
+use crate::post_process::{PostStep, run_post_processors};
+use crate::utils::{SleepInhibitor, find_locked_files, record_log};
+use futures::channel::mpsc::UnboundedSender;
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use tokio::sync::Notify;
+
+/// 正在进行的合并数量（合并页与队列共用），托盘据此显示状态
+pub static RUNNING_MERGES: AtomicUsize = AtomicUsize::new(0);
+
+// 退出程序前请求中止全部合并
+static CANCEL_MERGES: Notify = Notify::const_new();
+
+/// 中止正在进行的全部合并：FFmpeg 进程随之结束，未完成的输出被删除
+pub fn cancel_all_merges() {
+    CANCEL_MERGES.notify_waiters();
+}
+
+// 合并期间计数加一，函数返回时自动减一
+struct RunningGuard;
+
+impl RunningGuard {
+    fn new() -> Self {
+        RUNNING_MERGES.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING_MERGES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// 合并事件的发送端，可来自界面协程或队列任务自建的通道
+#[derive(Clone)]
+pub struct MergeEventSender(UnboundedSender<MergeEvent>);
+
+impl MergeEventSender {
+    pub fn send(&self, event: MergeEvent) {
+        // 保留最近的状态和结果，崩溃时写入报告
+        match &event {
+            MergeEvent::Progress(_) | MergeEvent::Segments(_) | MergeEvent::Probing { .. } => {}
+            MergeEvent::Status(s) | MergeEvent::Success(s) => record_log(s),
+            MergeEvent::Error(e) => record_log(&e.to_string()),
+        }
+        let _ = self.0.unbounded_send(event);
+    }
+}
+
+impl From<UnboundedSender<MergeEvent>> for MergeEventSender {
+    fn from(tx: UnboundedSender<MergeEvent>) -> Self {
+        Self(tx)
+    }
+}
+
+/// 合并成功后的收尾操作，取自设置
+#[derive(Debug, Clone, PartialEq)]
+pub struct AfterMerge {
+    /// 计算输出与输入的 SHA-256 一并记录
+    pub checksums: bool,
+    /// 归档原文件时使用的子文件夹名
+    pub archive_folder: String,
+    /// 合并后执行的命令
+    pub post_command: Option<String>,
+}
+
+impl AfterMerge {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            checksums: config.compute_checksums,
+            archive_folder: config.archive_folder.clone(),
+            post_command: Some(config.post_command.trim().to_string()).filter(|c| !c.is_empty()),
+        }
+    }
+
+    /// 本次合并依次执行的后处理：先按选项处理原文件，再执行设置中的合并后命令，
+    /// 最后是预设中的步骤
+    pub fn post_steps(&self, options: &MergeOptions) -> Vec<PostStep> {
+        let sources = match options.source_action {
+            SourceAction::Keep => None,
+            SourceAction::Archive => Some(PostStep::ArchiveSources {
+                folder: self.archive_folder.clone(),
+            }),
+            SourceAction::Recycle => Some(PostStep::RecycleSources),
+        };
+        let command = self
+            .post_command
+            .clone()
+            .map(|command| PostStep::RunCommand { command });
+        sources
+            .into_iter()
+            .chain(command)
+            .chain(options.post_steps.iter().cloned())
+            .collect()
+    }
+}
+
+/// 合并文件并通过 `tx` 报告进度与结果，成功后按选项处理原文件，结束后写入历史记录。
+/// 成功时返回输出文件的最终位置（后处理可能已移动它）
+pub async fn run_ffmpeg_merge(
+    files: Vec<PathBuf>,
+    output_path: PathBuf,
+    options: MergeOptions,
+    after: AfterMerge,
+    tx: impl Into<MergeEventSender>,
+) -> Option<PathBuf> {
+    let tx: MergeEventSender = tx.into();
+
+    // 合并期间阻止系统休眠，函数返回时自动释放
+    let _sleep_guard = SleepInhibitor::acquire("正在合并MP4文件");
+    let _running_guard = RunningGuard::new();
+
+    let started = Instant::now();
+    // 中止时丢弃合并的 future，正在运行的 FFmpeg 随之结束
+    let mut result = tokio::select! {
+        result = merge(
+            &SystemRunner,
+            files.clone(),
+            output_path.clone(),
+            options.clone(),
+            &tx,
+        ) => result,
+        _ = CANCEL_MERGES.notified() => {
+            let _ = std::fs::remove_file(part_path(&output_path));
+            Err(AppError::Cancelled)
+        }
+    };
+    let steps = after.post_steps(&options);
+    let mut entry = HistoryEntry::new(
+        files.clone(),
+        output_path.clone(),
+        options,
+        result.as_ref().err().map(ToString::to_string),
+    );
+    entry.elapsed_secs = started.elapsed().as_secs_f64();
+    if result.is_ok() {
+        entry.duration_secs = get_video_duration(&entry.output_path).await.unwrap_or(0.0);
+    }
+    // 在后处理移动或回收文件之前计算，NFO 等来源记录也会用到
+    if after.checksums && result.is_ok() {
+        tx.send(MergeEvent::Status("计算 SHA-256 校验值...".to_string()));
+        let hashed =
+            tokio::task::spawn_blocking(move || compute_checksums(&output_path, &files)).await;
+        match hashed {
+            Ok(Ok(checksums)) => entry.checksums = checksums,
+            Ok(Err(e)) => eprintln!("{}", e),
+            Err(e) => eprintln!("计算校验值的线程异常: {}", e),
+        }
+    }
+    // 后处理失败只作为提示附在成功信息后，不影响合并结果
+    if let Ok(message) = &mut result
+        && !steps.is_empty()
+    {
+        let notes = run_post_processors(&steps, &mut entry, |status| {
+            tx.send(MergeEvent::Status(status))
+        })
+        .await;
+        for note in notes {
+            message.push_str(&format!("（{}）", note));
+        }
+    }
+    let final_path = entry.output_path.clone();
+    record_history(entry);
+    match result {
+        Ok(message) => {
+            tx.send(MergeEvent::Success(message));
+            Some(final_path)
+        }
+        Err(e) => {
+            tx.send(MergeEvent::Error(e));
+            None
+        }
+    }
+}
+
+async fn merge(
+    runner: &dyn CommandRunner,
+    files: Vec<PathBuf>,
+    output_path: PathBuf,
+    options: MergeOptions,
+    tx: &MergeEventSender,
+) -> Result<String, AppError> {
+    // Validate FFmpeg installation
+    if !runner.is_available("ffmpeg") {
+        return Err(AppError::FfmpegNotFound);
+    }
+
+    // 片头/片尾与正片一起校验和探测，正片位于 main 范围内
+    let intro = usize::from(options.intro.is_some());
+    let main = intro..intro + files.len();
+    let files = options.with_intro_outro(files);
+    // 剪切与变速按原文件设置，方向矫正等步骤之后文件可能被替换为临时文件
+    let cuts: Vec<Vec<CutRange>> = files
+        .iter()
+        .enumerate()
+        .map(|(index, file)| {
+            if main.contains(&index) {
+                options.cuts_of(file).to_vec()
+            } else {
+                Vec::new()
+            }
+        })
+        .collect();
+    let speeds: Vec<f64> = files
+        .iter()
+        .enumerate()
+        .map(|(index, file)| {
+            if main.contains(&index) {
+                options.speed_of(file)
+            } else {
+                1.0
+            }
+        })
+        .collect();
+
+    // Validate input files
+    for file in &files {
+        if !file.exists() {
+            return Err(AppError::FileNotFound(file.clone()));
+        }
+        if !file.is_file() {
+            return Err(AppError::Other(format!("不是文件: {}", file.display())));
+        }
+    }
+
+    if let Some(audio) = options.active_external_audio()
+        && !audio.path.is_file()
+    {
+        return Err(AppError::FileNotFound(audio.path.clone()));
+    }
+
+    // 断点按原始输入计算，中间步骤生成的临时文件每次路径都不同
+    let resume_key = checkpoint_key(&files, &output_path, &options);
+
+    // 输出文件被其他程序占用时，最后的重命名会失败
+    if let Some(locked) = find_locked_files(std::slice::from_ref(&output_path)).first() {
+        return Err(AppError::Other(format!(
+            "无法覆盖输出文件：{}，请关闭相关程序后重试",
+            locked.describe()
+        )));
+    }
+
+    // Validate output directory
+    if let Some(parent) = output_path.parent()
+        && !parent.exists()
+    {
+        return Err(AppError::Other(format!(
+            "输出目录不存在: {}",
+            parent.display()
+        )));
+    }
+
+    tx.send(MergeEvent::Status("计算视频总时长...".to_string()));
+    // 并发探测时长（限制同时运行的进程数，单个文件超时即中止），结果按路径+修改时间缓存
+    let durations = probe_durations_with(runner, &files, |probed, file| {
+        tx.send(MergeEvent::Probing {
+            index: probed,
+            total: files.len(),
+            name: file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        });
+        let progress_pct = probed as f64 / files.len() as f64 * 100.0;
+        send_progress(tx, MergePhase::Probing, progress_pct);
+    })
+    .await?;
+
+    tx.send(MergeEvent::Segments(segment_boundaries(&durations)));
+
+    send_progress(tx, MergePhase::PreparingList, 0.0);
+
+    // 任务工作目录，函数返回时（无论成功失败）自动清理
+    let workspace = JobWorkspace::create().map_err(|e| AppError::io("创建工作目录失败", e))?;
+    // 各输入的旋转方向不一致时，复制模式会让部分片段方向错误
+    let files = normalize_orientation(runner, files, options.mode, &workspace, tx).await?;
+    let files = conform_intro_outro(runner, files, &main, options.mode, &workspace, tx).await?;
+    let (files, durations) =
+        apply_clip_cuts(runner, files, durations, &cuts, &options, &workspace, tx).await?;
+    let (files, durations) = if options.mode == MergeMode::ReEncode {
+        apply_clip_speeds(runner, files, durations, &speeds, &workspace, tx).await?
+    } else {
+        (files, durations)
+    };
+
+    // 重新编码时按设置统一分辨率/帧率，目标参数只取决于正片；
+    // 交叉淡化、片头/片尾和标题卡要求各片段格式一致，未设置时使用默认方案
+    let has_intro_outro = main.len() < files.len();
+    let normalize = options.normalize.or_else(|| {
+        (options.active_crossfade().is_some()
+            || options.active_title_cards().is_some()
+            || has_intro_outro)
+            .then(NormalizeOptions::default)
+    });
+    let plan = match (options.mode, normalize.as_ref()) {
+        (MergeMode::ReEncode, Some(normalize)) => {
+            tx.send(MergeEvent::Status("计算分辨率/帧率统一方案...".to_string()));
+            Some(plan_normalization_with(runner, &files[main.clone()], normalize).await?)
+        }
+        _ => None,
+    };
+    let (files, durations) = match (options.active_title_cards(), plan.as_ref()) {
+        (Some(cards), Some(plan)) => {
+            let titles =
+                generate_title_cards(runner, &files[main.clone()], cards, plan, &workspace, tx)
+                    .await?;
+            // 标题卡插在对应正片之前
+            let mut titles = titles.into_iter();
+            files
+                .into_iter()
+                .zip(durations)
+                .enumerate()
+                .flat_map(|(index, clip)| {
+                    let title = main.contains(&index).then(|| titles.next()).flatten();
+                    title.map(|t| (t, cards.duration)).into_iter().chain([clip])
+                })
+                .unzip()
+        }
+        _ => (files, durations),
+    };
+    // 标题卡会增加总时长
+    let total_duration: f64 = durations.iter().sum();
+    let segments: Vec<(PathBuf, f64)> = files.iter().cloned().zip(durations.clone()).collect();
+    let (plan, crossfade) = match (options.active_crossfade(), plan) {
+        (Some(crossfade), Some(plan)) => {
+            (None, Some(CrossfadePlan::new(crossfade, durations, plan)))
+        }
+        (_, plan) => (plan, None),
+    };
+    // 每个转场让相邻片段重叠，输出比各片段之和短
+    let total_duration = crossfade
+        .as_ref()
+        .map_or(total_duration, CrossfadePlan::output_duration);
+
+    let input = match &crossfade {
+        // 交叉淡化时每个片段作为独立输入
+        Some(_) => files.iter().fold(FfmpegCommand::new(), |c, f| c.input(f)),
+        None => {
+            let temp_path = workspace.file("concat.txt");
+            write_concat_list(&temp_path, &files)?;
+            FfmpegCommand::new().input(
+                FfmpegInput::new(temp_path)
+                    .format("concat")
+                    .option("-safe", "0"),
+            )
+        }
+    };
+    send_progress(tx, MergePhase::PreparingList, 100.0);
+
+    // 先写入 .part 文件，成功后再重命名，避免残留不完整的输出
+    let part_output_path = part_path(&output_path);
+
+    tx.send(MergeEvent::Status("启动FFmpeg合并...".to_string()));
+
+    let input = options.add_extra_inputs(input);
+
+    // 较长的重新编码逐段进行并记录断点；交叉淡化、两遍编码和外部音轨跨越片段边界，只能整体编码
+    let segmented = options.mode == MergeMode::ReEncode
+        && crossfade.is_none()
+        && !options.quality.is_two_pass()
+        && options.active_external_audio().is_none()
+        && segments.len() > 1
+        && total_duration >= CHECKPOINT_MIN_SECS;
+    let mut checkpoint = None;
+    let result = if segmented {
+        encode_segments(
+            runner,
+            &segments,
+            &options,
+            plan.as_ref(),
+            &resume_key,
+            &part_output_path,
+            tx,
+        )
+        .await
+        .map(|c| checkpoint = Some(c))
+    } else if options.mode == MergeMode::ReEncode && options.quality.is_two_pass() {
+        // 两遍编码：第一遍占拼接阶段的 0~50%，第二遍占 50~100%
+        let log_prefix = workspace.file("ffmpeg2pass");
+        let mut result = Ok(());
+        for index in 1..=2u8 {
+            tx.send(MergeEvent::Status(format!("第 {}/2 遍编码...", index)));
+            let pass = EncodePass {
+                index,
+                log_prefix: &log_prefix,
+            };
+            let command =
+                options.apply_output(input.clone(), plan.as_ref(), crossfade.as_ref(), Some(pass));
+            let command = if index == 1 {
+                command.null_output()
+            } else {
+                command.output(&part_output_path)
+            };
+            let start = 50.0 * (index - 1) as f64;
+            result = run_ffmpeg_with_progress(
+                runner,
+                command,
+                total_duration,
+                MergePhase::Concatenating,
+                start,
+                50.0,
+                tx,
+            )
+            .await;
+            if result.is_err() {
+                break;
+            }
+        }
+        result
+    } else {
+        let command = options
+            .apply_output(input, plan.as_ref(), crossfade.as_ref(), None)
+            .output(&part_output_path);
+        run_ffmpeg_with_progress(
+            runner,
+            command,
+            total_duration,
+            MergePhase::Concatenating,
+            0.0,
+            100.0,
+            tx,
+        )
+        .await
+    };
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&part_output_path);
+        return Err(e);
+    }
+
+    tx.send(MergeEvent::Status("校验输出文件...".to_string()));
+    send_progress(tx, MergePhase::Verifying, 0.0);
+    if let Err(e) = verify_output(runner, &part_output_path, total_duration).await {
+        let _ = std::fs::remove_file(&part_output_path);
+        return Err(AppError::Other(format!("输出文件校验失败: {}", e)));
+    }
+    send_progress(tx, MergePhase::Finalizing, 0.0);
+    if let Err(e) = std::fs::rename(&part_output_path, &output_path) {
+        let _ = std::fs::remove_file(&part_output_path);
+        return Err(AppError::io("重命名输出文件失败", e));
+    }
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.remove();
+    }
+    send_progress(tx, MergePhase::Finalizing, 100.0);
+    Ok(format!("文件已保存到: {}", output_path.display()))
+}
+
+// 逐段重新编码到断点目录后无损拼接，哈希与记录一致的分段直接复用。
+// 分段编码占拼接阶段的 0~90%，拼接占其余部分
+async fn encode_segments(
+    runner: &dyn CommandRunner,
+    segments: &[(PathBuf, f64)],
+    options: &MergeOptions,
+    plan: Option<&NormalizePlan>,
+    resume_key: &str,
+    part_output_path: &Path,
+    tx: &MergeEventSender,
+) -> Result<Checkpoint, AppError> {
+    let mut checkpoint =
+        Checkpoint::open(resume_key).map_err(|e| AppError::io("创建断点目录失败", e))?;
+    let total: f64 = segments.iter().map(|(_, duration)| duration).sum();
+    let mut encoded = 0.0;
+    let mut outputs = Vec::with_capacity(segments.len());
+    for (index, (file, duration)) in segments.iter().enumerate() {
+        let segment = checkpoint.segment_path(index);
+        let start = 90.0 * encoded / total;
+        let span = 90.0 * duration / total;
+        encoded += duration;
+        outputs.push(segment.clone());
+        if checkpoint.is_complete(index).await {
+            tx.send(MergeEvent::Status(format!(
+                "第 {}/{} 段已在上次编码完成，跳过",
+                index + 1,
+                segments.len()
+            )));
+            send_progress(tx, MergePhase::Concatenating, start + span);
+            continue;
+        }
+        tx.send(MergeEvent::Status(format!(
+            "编码第 {}/{} 段...",
+            index + 1,
+            segments.len()
+        )));
+        let segment_part = part_path(&segment);
+        let input = options.add_extra_inputs(FfmpegCommand::new().input(file));
+        let command = options
+            .apply_output(input, plan, None, None)
+            .output(&segment_part);
+        let result = run_ffmpeg_with_progress(
+            runner,
+            command,
+            *duration,
+            MergePhase::Concatenating,
+            start,
+            span,
+            tx,
+        )
+        .await;
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&segment_part);
+            return Err(e);
+        }
+        std::fs::rename(&segment_part, &segment)
+            .map_err(|e| AppError::io("保存编码分段失败", e))?;
+        checkpoint
+            .mark_complete(index)
+            .await
+            .map_err(|e| AppError::io("记录断点失败", e))?;
+    }
+
+    tx.send(MergeEvent::Status("拼接已编码的分段...".to_string()));
+    let list_path = checkpoint.file("segments.txt");
+    write_concat_list(&list_path, &outputs)?;
+    let command = FfmpegCommand::new()
+        .input(
+            FfmpegInput::new(list_path)
+                .format("concat")
+                .option("-safe", "0"),
+        )
+        .copy_codecs()
+        .format("mp4")
+        .output(part_output_path);
+    run_ffmpeg_with_progress(
+        runner,
+        command,
+        total,
+        MergePhase::Concatenating,
+        90.0,
+        10.0,
+        tx,
+    )
+    .await?;
+    Ok(checkpoint)
+}
+
+// 复制模式下片头/片尾的编码、分辨率或帧率与正片不同时，先转码为与第一个正片相同的参数
+async fn conform_intro_outro(
+    runner: &dyn CommandRunner,
+    mut files: Vec<PathBuf>,
+    main: &Range<usize>,
+    mode: MergeMode,
+    workspace: &JobWorkspace,
+    tx: &MergeEventSender,
+) -> Result<Vec<PathBuf>, AppError> {
+    if mode != MergeMode::Copy || main.is_empty() || main.len() == files.len() {
+        return Ok(files);
+    }
+
+    tx.send(MergeEvent::Status("检查片头/片尾参数...".to_string()));
+    let reference = probe_video_stream_with(runner, &files[main.start]).await?;
+    let extra: Vec<usize> = (0..files.len()).filter(|i| !main.contains(i)).collect();
+    for index in extra {
+        let info = probe_video_stream_with(runner, &files[index]).await?;
+        if !needs_conform(&info, &reference) {
+            continue;
+        }
+        let file = files[index].clone();
+        tx.send(MergeEvent::Status(format!(
+            "转换片头/片尾以匹配正片: {}",
+            file.file_name().unwrap_or_default().to_string_lossy()
+        )));
+        let conformed = workspace.file(&format!("bookend_{}.mp4", index));
+        let command = conform_command(&file, &reference, &conformed)?;
+        run_ffmpeg_with_progress(
+            runner,
+            command,
+            0.0,
+            MergePhase::PreparingList,
+            0.0,
+            0.0,
+            tx,
+        )
+        .await
+        .map_err(|e| match e {
+            AppError::Ffmpeg(message) => {
+                AppError::Ffmpeg(format!("转换片头/片尾失败 {}: {}", file.display(), message))
+            }
+            other => other,
+        })?;
+        files[index] = conformed;
+    }
+    Ok(files)
+}
+
+// 预先剪掉设置的区间，`cuts` 与 `files` 一一对应，返回新的文件列表和对应时长。
+// 用 concat 分离器的 inpoint/outpoint 截取保留部分，复制模式下切点会对齐到关键帧；
+// 开启精确剪切时改为拼接 smart render 生成的分段
+async fn apply_clip_cuts(
+    runner: &dyn CommandRunner,
+    mut files: Vec<PathBuf>,
+    mut durations: Vec<f64>,
+    cuts: &[Vec<CutRange>],
+    options: &MergeOptions,
+    workspace: &JobWorkspace,
+    tx: &MergeEventSender,
+) -> Result<(Vec<PathBuf>, Vec<f64>), AppError> {
+    let precise = options.precise_cuts && options.mode == MergeMode::Copy;
+    for (index, cuts) in cuts.iter().enumerate() {
+        if cuts.is_empty() {
+            continue;
+        }
+        let file = files[index].clone();
+        let keep = keep_ranges(durations[index], cuts);
+        if keep.is_empty() {
+            return Err(AppError::Other(format!(
+                "片段已被全部剪掉: {}",
+                file.display()
+            )));
+        }
+        tx.send(MergeEvent::Status(format!(
+            "剪切片段: {}",
+            file.file_name().unwrap_or_default().to_string_lossy()
+        )));
+        let cut_error = |e: AppError| match e {
+            AppError::Ffmpeg(message) => {
+                AppError::Ffmpeg(format!("剪切失败 {}: {}", file.display(), message))
+            }
+            other => other,
+        };
+        let list = workspace.file(&format!("cut_{}.txt", index));
+        if precise {
+            let pieces = render_cut_pieces(runner, &file, &keep, index, workspace, tx)
+                .await
+                .map_err(cut_error)?;
+            write_concat_list(&list, &pieces)?;
+        } else {
+            write_cut_list(&list, &file, &keep)?;
+        }
+        let cut = workspace.file(&format!("cut_{}.mp4", index));
+        let command = FfmpegCommand::new().input(
+            FfmpegInput::new(&list)
+                .format("concat")
+                .option("-safe", "0"),
+        );
+        let command = if options.mode == MergeMode::ReEncode {
+            command
+                .video_codec("libx264")
+                .option("-preset", "veryfast")
+                .option("-crf", "18")
+                .audio_codec("aac")
+        } else {
+            command.copy_codecs()
+        };
+        run_ffmpeg_with_progress(
+            runner,
+            command.format("mp4").output(&cut),
+            0.0,
+            MergePhase::PreparingList,
+            0.0,
+            0.0,
+            tx,
+        )
+        .await
+        .map_err(cut_error)?;
+        durations[index] = kept_duration(durations[index], cuts);
+        files[index] = cut;
+    }
+    Ok((files, durations))
+}
+
+// 精确剪切：按关键帧把每个保留区间拆成重新编码和直接复制的分段，返回按顺序排列的分段文件
+async fn render_cut_pieces(
+    runner: &dyn CommandRunner,
+    file: &Path,
+    keep: &[(f64, Option<f64>)],
+    index: usize,
+    workspace: &JobWorkspace,
+    tx: &MergeEventSender,
+) -> Result<Vec<PathBuf>, AppError> {
+    let info = probe_video_stream_with(runner, file).await?;
+    let encoder = encoder_for(&info.codec).ok_or_else(|| {
+        AppError::Other(format!(
+            "精确剪切不支持 {} 编码的视频，请关闭精确剪切或改用“重新编码”模式",
+            info.codec
+        ))
+    })?;
+    let keyframes = keyframe_times_with(runner, file).await?;
+    let mut pieces = Vec::new();
+    for (start, end) in keep {
+        for piece in plan_pieces(&keyframes, *start, *end) {
+            let output = workspace.file(&format!("cut_{}_{}.mp4", index, pieces.len()));
+            run_ffmpeg_with_progress(
+                runner,
+                piece.command(file, encoder, &output),
+                0.0,
+                MergePhase::PreparingList,
+                0.0,
+                0.0,
+                tx,
+            )
+            .await?;
+            pieces.push(output);
+        }
+    }
+    Ok(pieces)
+}
+
+// 预先生成变速后的片段，`speeds` 与 `files` 一一对应，返回新的文件列表和对应时长
+async fn apply_clip_speeds(
+    runner: &dyn CommandRunner,
+    mut files: Vec<PathBuf>,
+    mut durations: Vec<f64>,
+    speeds: &[f64],
+    workspace: &JobWorkspace,
+    tx: &MergeEventSender,
+) -> Result<(Vec<PathBuf>, Vec<f64>), AppError> {
+    for (index, &speed) in speeds.iter().enumerate() {
+        if (speed - 1.0).abs() < f64::EPSILON {
+            continue;
+        }
+        let file = files[index].clone();
+        tx.send(MergeEvent::Status(format!(
+            "处理变速 {}: {}",
+            speed_label(speed),
+            file.file_name().unwrap_or_default().to_string_lossy()
+        )));
+        let sped = workspace.file(&format!("speed_{}.mp4", index));
+        run_ffmpeg_with_progress(
+            runner,
+            speed_command(&file, speed, &sped),
+            0.0,
+            MergePhase::PreparingList,
+            0.0,
+            0.0,
+            tx,
+        )
+        .await
+        .map_err(|e| match e {
+            AppError::Ffmpeg(message) => {
+                AppError::Ffmpeg(format!("变速处理失败 {}: {}", file.display(), message))
+            }
+            other => other,
+        })?;
+        files[index] = sped;
+        durations[index] /= speed;
+    }
+    Ok((files, durations))
+}
+
+// 为每个正片生成标题卡，顺序与 `clips` 一致
+async fn generate_title_cards(
+    runner: &dyn CommandRunner,
+    clips: &[PathBuf],
+    cards: &TitleCardOptions,
+    plan: &NormalizePlan,
+    workspace: &JobWorkspace,
+    tx: &MergeEventSender,
+) -> Result<Vec<PathBuf>, AppError> {
+    let mut titles = Vec::with_capacity(clips.len());
+    for (index, clip) in clips.iter().enumerate() {
+        tx.send(MergeEvent::Status(format!(
+            "生成标题卡 {}/{}...",
+            index + 1,
+            clips.len()
+        )));
+        let title = workspace.file(&format!("title_{}.mp4", index));
+        let command = cards.command(&cards.text_for(index, clip), plan, &title);
+        run_ffmpeg_with_progress(
+            runner,
+            command,
+            0.0,
+            MergePhase::PreparingList,
+            0.0,
+            0.0,
+            tx,
+        )
+        .await
+        .map_err(|e| match e {
+            AppError::Ffmpeg(message) => AppError::Ffmpeg(format!("生成标题卡失败: {}", message)),
+            other => other,
+        })?;
+        titles.push(title);
+    }
+    Ok(titles)
+}
+
+/// 写入 concat 分离器使用的文件列表，路径统一为绝对路径，单引号按 concat 语法转义
+pub fn write_concat_list(list_path: &Path, files: &[PathBuf]) -> Result<(), AppError> {
+    let mut list =
+        std::fs::File::create(list_path).map_err(|e| AppError::io("创建临时文件失败", e))?;
+    for file_path in files {
+        let mut entry = concat_file_entry(file_path)?;
+        entry.push(b'\n');
+        list.write_all(&entry)
+            .map_err(|e| AppError::io("写入临时文件失败", e))?;
+    }
+    Ok(())
+}
+
+// 只保留 `keep` 区间的文件列表，同一文件按区间重复列出
+fn write_cut_list(
+    list_path: &Path,
+    file_path: &Path,
+    keep: &[(f64, Option<f64>)],
+) -> Result<(), AppError> {
+    let mut list =
+        std::fs::File::create(list_path).map_err(|e| AppError::io("创建临时文件失败", e))?;
+    let entry = concat_file_entry(file_path)?;
+    for (start, end) in keep {
+        let mut lines = format!("\ninpoint {:.3}", start);
+        if let Some(end) = end {
+            lines.push_str(&format!("\noutpoint {:.3}", end));
+        }
+        list.write_all(&entry)
+            .and_then(|_| writeln!(list, "{}", lines))
+            .map_err(|e| AppError::io("写入临时文件失败", e))?;
+    }
+    Ok(())
+}
+
+// concat 列表中的 `file '...'` 行（不含换行），路径统一为绝对路径，单引号按 concat 语法转义
+fn concat_file_entry(file_path: &Path) -> Result<Vec<u8>, AppError> {
+    let abs_path = std::fs::canonicalize(file_path)
+        .map_err(|e| AppError::io(format!("无法解析文件路径 {}", file_path.display()), e))?;
+    let mut entry = b"file '".to_vec();
+    for (i, part) in concat_path_bytes(&abs_path)?
+        .split(|b| *b == b'\'')
+        .enumerate()
+    {
+        if i > 0 {
+            entry.extend_from_slice(b"'\\''");
+        }
+        entry.extend_from_slice(part);
+    }
+    entry.push(b'\'');
+    Ok(entry)
+}
+
+// FFmpeg 在 Unix 上按原始字节读取列表中的路径，不经过 UTF-8 转换，任何文件名都能原样写入
+#[cfg(unix)]
+fn concat_path_bytes(path: &Path) -> Result<&[u8], AppError> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(path.as_os_str().as_bytes())
+}
+
+// Windows 上 FFmpeg 把列表中的路径当作 UTF-8 读取，无法表示的文件名只能提示重命名
+#[cfg(not(unix))]
+fn concat_path_bytes(path: &Path) -> Result<&[u8], AppError> {
+    path.to_str().map(str::as_bytes).ok_or_else(|| {
+        AppError::Other(format!(
+            "文件名包含无法识别的字符，FFmpeg 无法读取，请重命名后重试: {}",
+            path.display()
+        ))
+    })
+}
+
+fn send_progress(tx: &MergeEventSender, phase: MergePhase, percent: f64) {
+    tx.send(MergeEvent::Progress(MergeProgress::new(phase, percent)));
+}
+
+/// 统一输入文件的旋转方向：重新编码模式下把带旋转的文件预先转正，复制模式下报错提示
+async fn normalize_orientation(
+    runner: &dyn CommandRunner,
+    files: Vec<PathBuf>,
+    mode: MergeMode,
+    workspace: &JobWorkspace,
+    tx: &MergeEventSender,
+) -> Result<Vec<PathBuf>, AppError> {
+    if matches!(mode, MergeMode::AudioOnly(_)) {
+        return Ok(files);
+    }
+
+    tx.send(MergeEvent::Status("检查视频方向...".to_string()));
+    let mut rotations = Vec::with_capacity(files.len());
+    for file in &files {
+        rotations.push(probe_rotation_with(runner, file).await?);
+    }
+    if rotations.windows(2).all(|w| w[0] == w[1]) {
+        return Ok(files);
+    }
+
+    if mode == MergeMode::Copy {
+        return Err(AppError::Other(
+            "输入文件的旋转方向不一致，直接复制会导致部分片段方向错误。请切换到“重新编码”模式，或先使用“旋转/矫正”统一方向"
+                .to_string(),
+        ));
+    }
+
+    let mut normalized = Vec::with_capacity(files.len());
+    for (index, (file, rotation)) in files.into_iter().zip(rotations).enumerate() {
+        if rotation == 0 {
+            normalized.push(file);
+            continue;
+        }
+        tx.send(MergeEvent::Status(format!(
+            "矫正方向: {}",
+            file.file_name().unwrap_or_default().to_string_lossy()
+        )));
+        let upright = workspace.file(&format!("upright_{}.mp4", index));
+        // 重新编码时 FFmpeg 会按元数据自动旋转画面，输出不再带旋转信息
+        let command = FfmpegCommand::new()
+            .input(&file)
+            .video_codec("libx264")
+            .option("-preset", "veryfast")
+            .option("-crf", "18")
+            .audio_codec("copy")
+            .format("mp4")
+            .output(&upright);
+        run_ffmpeg_with_progress(
+            runner,
+            command,
+            0.0,
+            MergePhase::PreparingList,
+            0.0,
+            0.0,
+            tx,
+        )
+        .await
+        .map_err(|e| match e {
+            AppError::Ffmpeg(message) => {
+                AppError::Ffmpeg(format!("矫正方向失败 {}: {}", file.display(), message))
+            }
+            e => e,
+        })?;
+        normalized.push(upright);
+    }
+    Ok(normalized)
+}
+
+/// 校验合并结果：文件非空且时长与输入总时长基本一致
+async fn verify_output(
+    runner: &dyn CommandRunner,
+    path: &Path,
+    expected_duration: f64,
+) -> Result<(), AppError> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| AppError::io("无法读取输出文件", e))?
+        .len();
+    if size == 0 {
+        return Err(AppError::Other("输出文件为空".to_string()));
+    }
+
+    let actual_duration = get_video_duration_with(runner, path).await?;
+    // 允许 1 秒或 1% 的误差（取较大者）
+    let tolerance = (expected_duration * 0.01).max(1.0);
+    if (actual_duration - expected_duration).abs() > tolerance {
+        return Err(AppError::Other(format!(
+            "输出时长 {:.2} 秒与预期 {:.2} 秒不符",
+            actual_duration, expected_duration
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 在临时目录中创建文件并返回其 concat 条目
+    fn entry_for(name: impl AsRef<std::ffi::OsStr>) -> Vec<u8> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name.as_ref());
+        std::fs::write(&path, b"").unwrap();
+        concat_file_entry(&path).unwrap()
+    }
+
+    #[test]
+    fn global_settings_become_post_steps() {
+        let after = AfterMerge {
+            checksums: false,
+            archive_folder: "已合并".to_string(),
+            post_command: Some("echo {output}".to_string()),
+        };
+        let mut options = MergeOptions {
+            source_action: SourceAction::Archive,
+            post_steps: vec![PostStep::Thumbnail],
+            ..Default::default()
+        };
+        assert_eq!(
+            after.post_steps(&options),
+            [
+                PostStep::ArchiveSources {
+                    folder: "已合并".to_string()
+                },
+                PostStep::RunCommand {
+                    command: "echo {output}".to_string()
+                },
+                PostStep::Thumbnail,
+            ]
+        );
+
+        options.source_action = SourceAction::Keep;
+        let after = AfterMerge {
+            post_command: None,
+            ..after
+        };
+        assert_eq!(after.post_steps(&options), [PostStep::Thumbnail]);
+    }
+
+    #[test]
+    fn concat_entry_keeps_unicode_names() {
+        let entry = String::from_utf8(entry_for("旅行 🎬 it's.mp4")).unwrap();
+        assert!(entry.starts_with("file '"));
+        assert!(entry.ends_with("旅行 🎬 it'\\''s.mp4'"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn concat_entry_keeps_invalid_utf8_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let entry = entry_for(std::ffi::OsStr::from_bytes(b"clip\xff.mp4"));
+        assert!(entry.ends_with(b"/clip\xff.mp4'"));
+    }
+}
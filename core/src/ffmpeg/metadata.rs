@@ -0,0 +1,69 @@
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::probe::probe_format_tags;
+use crate::ffmpeg::workspace::part_path;
+use std::path::Path;
+
+/// 可编辑的 MP4 元数据标签
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ClipMetadata {
+    pub title: String,
+    pub artist: String,
+    pub comment: String,
+    /// ISO 8601 格式，如 2024-01-01T12:00:00Z
+    pub creation_time: String,
+}
+
+impl ClipMetadata {
+    const KEYS: [&'static str; 4] = ["title", "artist", "comment", "creation_time"];
+
+    fn values(&self) -> [&str; 4] {
+        [
+            &self.title,
+            &self.artist,
+            &self.comment,
+            &self.creation_time,
+        ]
+    }
+}
+
+/// 读取文件当前的元数据
+pub async fn read_metadata(path: &Path) -> Result<ClipMetadata, AppError> {
+    let mut tags = probe_format_tags(path).await?;
+    let mut take = |key: &str| tags.remove(key).unwrap_or_default();
+    Ok(ClipMetadata {
+        title: take("title"),
+        artist: take("artist"),
+        comment: take("comment"),
+        creation_time: take("creation_time"),
+    })
+}
+
+/// 以流复制方式写入元数据：先输出到临时文件，成功后原子替换原文件。
+/// 值为空的标签会被删除。
+pub async fn write_metadata(path: &Path, metadata: &ClipMetadata) -> Result<(), AppError> {
+    let part_output_path = part_path(path);
+    let mut command = FfmpegCommand::new()
+        .input(path)
+        .map("0")
+        .option("-map_metadata", "0")
+        .copy_codecs();
+    for (key, value) in ClipMetadata::KEYS.iter().zip(metadata.values()) {
+        command = command.metadata(key, value.trim());
+    }
+    let args = command.format("mp4").output(&part_output_path).build();
+
+    let output = SystemRunner.output("ffmpeg", &args).await?;
+    if !output.exit.success {
+        let _ = std::fs::remove_file(&part_output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last_line = stderr.lines().last().unwrap_or_default();
+        return Err(AppError::Ffmpeg(format!("写入元数据失败: {}", last_line)));
+    }
+
+    std::fs::rename(&part_output_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&part_output_path);
+        AppError::io("替换原文件失败", e)
+    })
+}
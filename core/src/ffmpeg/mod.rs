@@ -0,0 +1,35 @@
+pub mod args;
+pub mod audio_track;
+pub mod benchmark;
+pub mod bookends;
+pub mod checkpoint;
+pub mod clip_export;
+pub mod command;
+pub mod compress;
+pub mod crossfade;
+pub mod dead_air;
+pub mod diagnostics;
+pub mod encoder;
+pub mod error;
+pub mod estimate;
+pub mod frames;
+pub mod integrity;
+pub mod junction_preview;
+pub mod locate;
+pub mod merge_mp4;
+pub mod metadata;
+pub mod normalize;
+pub mod options;
+pub mod phase;
+pub mod probe;
+pub mod proxy;
+pub mod rotate;
+pub mod runner;
+pub mod smart_cut;
+pub mod speed;
+pub mod thumbnail;
+pub mod title_card;
+pub mod transcode;
+pub mod watermark;
+pub mod waveform;
+pub mod workspace;
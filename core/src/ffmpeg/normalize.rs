@@ -0,0 +1,122 @@
+use crate::error::AppError;
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::probe::{VideoStreamInfo, probe_video_stream_with};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 统一分辨率的目标
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ResolutionTarget {
+    /// 使用输入中最大的分辨率
+    #[default]
+    Largest,
+    Fixed {
+        width: u32,
+        height: u32,
+    },
+}
+
+impl ResolutionTarget {
+    pub fn label(&self) -> String {
+        match self {
+            ResolutionTarget::Largest => "最大分辨率".to_string(),
+            ResolutionTarget::Fixed { width, height } => format!("{}x{}", width, height),
+        }
+    }
+}
+
+/// 统一帧率的目标
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum FpsTarget {
+    /// 使用输入中最高的帧率
+    #[default]
+    Highest,
+    Fixed(u32),
+}
+
+impl FpsTarget {
+    pub fn label(&self) -> String {
+        match self {
+            FpsTarget::Highest => "最高帧率".to_string(),
+            FpsTarget::Fixed(fps) => format!("{} fps", fps),
+        }
+    }
+}
+
+/// 重新编码时统一分辨率/帧率的设置
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct NormalizeOptions {
+    pub resolution: ResolutionTarget,
+    pub fps: FpsTarget,
+}
+
+/// 根据实际输入得出的统一方案，开始合并前展示给用户确认
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizePlan {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    pub inputs: Vec<(PathBuf, VideoStreamInfo)>,
+}
+
+impl NormalizePlan {
+    /// 缩放并补黑边到目标分辨率，再转换帧率
+    pub fn filter(&self) -> String {
+        let (w, h) = (self.width, self.height);
+        format!(
+            "scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={:.3}",
+            self.fps
+        )
+    }
+
+    /// 该输入是否需要缩放或转换帧率
+    pub fn changes(&self, info: &VideoStreamInfo) -> bool {
+        info.width != self.width || info.height != self.height || (info.fps - self.fps).abs() > 0.01
+    }
+}
+
+/// 探测所有输入并计算统一方案
+pub async fn plan_normalization(
+    files: &[PathBuf],
+    options: &NormalizeOptions,
+) -> Result<NormalizePlan, AppError> {
+    plan_normalization_with(&SystemRunner, files, options).await
+}
+
+/// 同 [`plan_normalization`]，通过 `runner` 启动 FFmpeg
+pub async fn plan_normalization_with(
+    runner: &dyn CommandRunner,
+    files: &[PathBuf],
+    options: &NormalizeOptions,
+) -> Result<NormalizePlan, AppError> {
+    let mut inputs = Vec::with_capacity(files.len());
+    for file in files {
+        let info = probe_video_stream_with(runner, file).await?;
+        inputs.push((file.clone(), info));
+    }
+
+    let (width, height) = match options.resolution {
+        ResolutionTarget::Fixed { width, height } => (width, height),
+        ResolutionTarget::Largest => inputs
+            .iter()
+            .map(|(_, i)| (i.width, i.height))
+            .max_by_key(|(w, h)| w * h)
+            .unwrap_or((1920, 1080)),
+    };
+    let fps = match options.fps {
+        FpsTarget::Fixed(fps) => fps as f64,
+        FpsTarget::Highest => inputs
+            .iter()
+            .map(|(_, i)| i.fps)
+            .fold(0.0, f64::max)
+            .max(1.0),
+    };
+
+    Ok(NormalizePlan {
+        // libx264 要求宽高为偶数
+        width: width / 2 * 2,
+        height: height / 2 * 2,
+        fps,
+        inputs,
+    })
+}
@@ -0,0 +1,472 @@
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::audio_track::ExternalAudio;
+use crate::ffmpeg::crossfade::{CrossfadeOptions, CrossfadePlan};
+use crate::ffmpeg::dead_air::{CutRange, kept_duration};
+use crate::ffmpeg::encoder::{EncodePass, EncodeQuality};
+use crate::ffmpeg::normalize::{NormalizeOptions, NormalizePlan};
+use crate::ffmpeg::title_card::TitleCardOptions;
+use crate::ffmpeg::watermark::{WatermarkKind, WatermarkOptions};
+use crate::post_process::{PostStep, deserialize_saved_steps};
+use crate::utils::{deserialize_path_pairs, serialize_path_pairs};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 音频输出格式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum AudioFormat {
+    M4a,
+    Mp3,
+}
+
+impl AudioFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::M4a => "m4a",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+
+    /// FFmpeg 封装格式名（输出写入 .part 文件时无法从扩展名推断）
+    fn muxer(&self) -> &'static str {
+        match self {
+            AudioFormat::M4a => "ipod",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+
+    fn apply(&self, command: FfmpegCommand) -> FfmpegCommand {
+        match self {
+            AudioFormat::M4a => command.audio_codec("aac").option("-b:a", "192k"),
+            AudioFormat::Mp3 => command.audio_codec("libmp3lame").option("-q:a", "2"),
+        }
+        .format(self.muxer())
+    }
+}
+
+/// 合并模式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum MergeMode {
+    /// 直接复制音视频流（要求各文件编码参数一致）
+    #[default]
+    Copy,
+    /// 重新编码为 H.264/AAC，可使用水印等滤镜
+    ReEncode,
+    /// 只提取并拼接音频
+    AudioOnly(AudioFormat),
+}
+
+impl MergeMode {
+    pub const ALL: [MergeMode; 4] = [
+        MergeMode::Copy,
+        MergeMode::ReEncode,
+        MergeMode::AudioOnly(AudioFormat::M4a),
+        MergeMode::AudioOnly(AudioFormat::Mp3),
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeMode::Copy => "视频 - 直接复制",
+            MergeMode::ReEncode => "视频 - 重新编码",
+            MergeMode::AudioOnly(AudioFormat::M4a) => "仅音频 (M4A)",
+            MergeMode::AudioOnly(AudioFormat::Mp3) => "仅音频 (MP3)",
+        }
+    }
+
+    /// 输出文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MergeMode::Copy | MergeMode::ReEncode => "mp4",
+            MergeMode::AudioOnly(format) => format.extension(),
+        }
+    }
+}
+
+/// 合并成功后如何处理原文件
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SourceAction {
+    #[default]
+    Keep,
+    /// 移动到原文件所在目录下的归档子文件夹
+    Archive,
+    /// 移到回收站
+    Recycle,
+}
+
+impl SourceAction {
+    pub const ALL: [SourceAction; 3] = [
+        SourceAction::Keep,
+        SourceAction::Archive,
+        SourceAction::Recycle,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SourceAction::Keep => "保留",
+            SourceAction::Archive => "移到归档文件夹",
+            SourceAction::Recycle => "移到回收站",
+        }
+    }
+}
+
+/// 一次合并使用的选项
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct MergeOptions {
+    pub mode: MergeMode,
+    /// 水印，仅在重新编码模式下生效
+    #[serde(default)]
+    pub watermark: Option<WatermarkOptions>,
+    /// 统一分辨率/帧率，仅在重新编码模式下生效
+    #[serde(default)]
+    pub normalize: Option<NormalizeOptions>,
+    /// 画质设置，仅在重新编码模式下生效
+    #[serde(default)]
+    pub quality: EncodeQuality,
+    /// 合并成功后对原文件的处理
+    #[serde(default)]
+    pub source_action: SourceAction,
+    /// 替换或混入外部音频，仅在视频模式下生效
+    #[serde(default)]
+    pub external_audio: Option<ExternalAudio>,
+    /// 片段之间的交叉淡化，仅在重新编码模式下生效
+    #[serde(default)]
+    pub crossfade: Option<CrossfadeOptions>,
+    /// 自动加在最前面的片头
+    #[serde(default)]
+    pub intro: Option<PathBuf>,
+    /// 自动加在最后面的片尾
+    #[serde(default)]
+    pub outro: Option<PathBuf>,
+    /// 每个片段前插入的标题卡，仅在重新编码模式下生效
+    #[serde(default)]
+    pub title_cards: Option<TitleCardOptions>,
+    /// 按文件设置的播放速度，未列出的为 1 倍速，仅在重新编码模式下生效
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_path_pairs",
+        deserialize_with = "deserialize_path_pairs"
+    )]
+    pub clip_speeds: Vec<(PathBuf, f64)>,
+    /// 按文件设置的剪切区间（剪掉的部分）
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_path_pairs",
+        deserialize_with = "deserialize_path_pairs"
+    )]
+    pub clip_cuts: Vec<(PathBuf, Vec<CutRange>)>,
+    /// 复制模式下只重新编码切点附近的画面，剪切位置精确到帧
+    #[serde(default)]
+    pub precise_cuts: bool,
+    /// 合并成功后按顺序执行的后处理步骤
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_saved_steps"
+    )]
+    pub post_steps: Vec<PostStep>,
+}
+
+impl MergeOptions {
+    /// 在片段前后加上片头、片尾，返回实际拼接的文件列表
+    pub fn with_intro_outro(&self, files: Vec<PathBuf>) -> Vec<PathBuf> {
+        self.intro
+            .iter()
+            .cloned()
+            .chain(files)
+            .chain(self.outro.iter().cloned())
+            .collect()
+    }
+
+    /// 在主输入之后追加额外输入（如图片水印、外部音频）
+    pub fn add_extra_inputs(&self, command: FfmpegCommand) -> FfmpegCommand {
+        let command = match self.watermark_image() {
+            Some(path) => command.input(path),
+            None => command,
+        };
+        match self.active_external_audio() {
+            Some(audio) => command.input(audio.input()),
+            None => command,
+        }
+    }
+
+    /// 实际生效的交叉淡化设置，仅在重新编码模式下生效
+    pub fn active_crossfade(&self) -> Option<&CrossfadeOptions> {
+        self.crossfade
+            .as_ref()
+            .filter(|_| self.mode == MergeMode::ReEncode)
+    }
+
+    /// 实际生效的标题卡设置，仅在重新编码模式下生效
+    pub fn active_title_cards(&self) -> Option<&TitleCardOptions> {
+        self.title_cards
+            .as_ref()
+            .filter(|_| self.mode == MergeMode::ReEncode)
+    }
+
+    /// 片段的播放速度，未设置时为 1.0
+    pub fn speed_of(&self, file: &Path) -> f64 {
+        clip_value(&self.clip_speeds, file).copied().unwrap_or(1.0)
+    }
+
+    /// 设置片段的播放速度，1 倍速时移除记录
+    pub fn set_speed(&mut self, file: PathBuf, speed: f64) {
+        let speed = Some(speed).filter(|speed| (speed - 1.0).abs() >= f64::EPSILON);
+        set_clip_value(&mut self.clip_speeds, file, speed);
+    }
+
+    /// 片段要剪掉的区间
+    pub fn cuts_of(&self, file: &Path) -> &[CutRange] {
+        clip_value(&self.clip_cuts, file)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// 设置片段的剪切区间，为空时移除记录
+    pub fn set_cuts(&mut self, file: PathBuf, cuts: Vec<CutRange>) {
+        let cuts = Some(cuts).filter(|cuts| !cuts.is_empty());
+        set_clip_value(&mut self.clip_cuts, file, cuts);
+    }
+
+    /// 片段经剪切、变速后在成片中的时长，`duration` 为原始时长
+    pub fn effective_duration(&self, file: &Path, duration: f64) -> f64 {
+        let kept = kept_duration(duration, self.cuts_of(file)).max(0.0);
+        if self.mode == MergeMode::ReEncode {
+            kept / self.speed_of(file)
+        } else {
+            kept
+        }
+    }
+
+    /// 实际生效的外部音频，仅音频模式下忽略
+    pub fn active_external_audio(&self) -> Option<&ExternalAudio> {
+        self.external_audio
+            .as_ref()
+            .filter(|_| matches!(self.mode, MergeMode::Copy | MergeMode::ReEncode))
+    }
+
+    /// 追加滤镜、映射、编码与输出格式，`plan` 为已确定的分辨率/帧率统一方案，
+    /// `crossfade` 为交叉淡化方案（此时每个片段是独立输入，统一方案由它负责），
+    /// `pass` 为两遍编码时的当前遍（第一遍只输出统计信息，应写入 [`NULL_OUTPUT`]）
+    ///
+    /// [`NULL_OUTPUT`]: crate::ffmpeg::encoder::NULL_OUTPUT
+    pub fn apply_output(
+        &self,
+        command: FfmpegCommand,
+        plan: Option<&NormalizePlan>,
+        crossfade: Option<&CrossfadePlan>,
+        pass: Option<EncodePass>,
+    ) -> FfmpegCommand {
+        match (self.mode, self.active_external_audio()) {
+            (MergeMode::Copy, None) => command.copy_codecs().format("mp4"),
+            (MergeMode::Copy, Some(audio)) => command
+                .filter_complex(audio.filter("0:a", self.audio_input_index(1)))
+                .map("0:v")
+                .map("[a]")
+                .video_codec("copy")
+                .audio_codec("aac")
+                .option("-b:a", "192k")
+                .flag("-shortest")
+                .format("mp4"),
+            (MergeMode::ReEncode, audio) => {
+                let first_pass = pass.is_some_and(|p| p.index == 1);
+                // 第一遍不输出音频，不需要音频滤镜
+                let audio = audio.filter(|_| !first_pass);
+                let inputs = crossfade.map_or(1, CrossfadePlan::input_count);
+                // 交叉淡化时原视频、原声来自转场滤镜的输出
+                let (video_source, audio_source) = match crossfade {
+                    Some(_) => ("xv", "xa"),
+                    None => ("0:v", "0:a"),
+                };
+                let mut graphs = Vec::new();
+                if let Some(crossfade) = crossfade {
+                    let with_audio = !first_pass && audio.is_none_or(|a| a.uses_original());
+                    graphs.push(crossfade.filter(with_audio));
+                }
+                let video_graph = self.video_filter_graph(
+                    plan.filter(|_| crossfade.is_none()),
+                    video_source,
+                    inputs,
+                );
+                let video_map = match (&video_graph, crossfade) {
+                    (Some(_), _) => "[v]",
+                    (None, Some(_)) => "[xv]",
+                    (None, None) => "0:v",
+                };
+                graphs.extend(video_graph);
+                let audio_graph =
+                    audio.map(|a| a.filter(audio_source, self.audio_input_index(inputs)));
+                let audio_map = match (&audio_graph, crossfade) {
+                    (Some(_), _) => "[a]",
+                    (None, Some(_)) => "[xa]",
+                    (None, None) => "0:a?",
+                };
+                graphs.extend(audio_graph);
+                let command = if graphs.is_empty() {
+                    command
+                } else {
+                    let command = command.filter_complex(graphs.join(";")).map(video_map);
+                    if first_pass {
+                        command
+                    } else {
+                        command.map(audio_map)
+                    }
+                };
+                let command = self.quality.apply(command, pass);
+                if first_pass {
+                    command.no_audio()
+                } else {
+                    let command = command.audio_codec("aac").option("-b:a", "192k");
+                    match audio {
+                        Some(_) => command.flag("-shortest"),
+                        None => command,
+                    }
+                    .format("mp4")
+                }
+            }
+            MergeMode::AudioOnly(format) => format.apply(command.no_video()),
+        }
+    }
+
+    /// 各项设置的可读描述，用于展示和对比两组选项
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        let watermark = match &self.watermark {
+            None => "无".to_string(),
+            Some(w) => match &w.kind {
+                WatermarkKind::Text { text, .. } => {
+                    format!("文字“{}”，{}", text, w.position.label())
+                }
+                WatermarkKind::Image(path) => {
+                    format!("图片 {}，{}", path.display(), w.position.label())
+                }
+            },
+        };
+        let normalize = match self.normalize {
+            None => "不统一".to_string(),
+            Some(n) => format!("{} / {}", n.resolution.label(), n.fps.label()),
+        };
+        let quality = match self.quality.two_pass_bitrate {
+            Some(bitrate) => format!("两遍编码 {} kbps，{}", bitrate, self.quality.preset.label()),
+            None => format!("CRF {}，{}", self.quality.crf, self.quality.preset.label()),
+        };
+        let external_audio = match &self.external_audio {
+            None => "无".to_string(),
+            Some(a) => format!(
+                "{} {}，偏移 {:.1} 秒，音量 {:.0}%",
+                a.mode.label(),
+                a.path.display(),
+                a.offset,
+                a.volume * 100.0
+            ),
+        };
+        let crossfade = match self.crossfade {
+            None => "无".to_string(),
+            Some(c) => format!("交叉淡化 {:.1} 秒", c.duration),
+        };
+        let title_cards = match &self.title_cards {
+            None => "无".to_string(),
+            Some(t) => format!("“{}”，{:.1} 秒", t.text, t.duration),
+        };
+        let clip_speeds = match self.clip_speeds.len() {
+            0 => "无".to_string(),
+            n => format!("{} 个片段", n),
+        };
+        let clip_cuts = match self.clip_cuts.len() {
+            0 => "无".to_string(),
+            n if self.precise_cuts && self.mode == MergeMode::Copy => {
+                format!("{} 个片段（精确剪切）", n)
+            }
+            n => format!("{} 个片段", n),
+        };
+        let post_steps = if self.post_steps.is_empty() {
+            "无".to_string()
+        } else {
+            self.post_steps
+                .iter()
+                .map(PostStep::kind_label)
+                .collect::<Vec<_>>()
+                .join(" → ")
+        };
+        let file_name = |path: &Option<PathBuf>| match path {
+            Some(path) => path.display().to_string(),
+            None => "无".to_string(),
+        };
+        vec![
+            ("合并方式", self.mode.label().to_string()),
+            ("片头", file_name(&self.intro)),
+            ("片尾", file_name(&self.outro)),
+            ("转场", crossfade),
+            ("标题卡", title_cards),
+            ("片段变速", clip_speeds),
+            ("片段剪切", clip_cuts),
+            ("水印", watermark),
+            ("外部音频", external_audio),
+            ("统一分辨率/帧率", normalize),
+            ("画质", quality),
+            ("合并后原文件", self.source_action.label().to_string()),
+            ("后处理", post_steps),
+        ]
+    }
+
+    // 图片水印只在重新编码时作为额外输入
+    fn watermark_image(&self) -> Option<&PathBuf> {
+        self.watermark
+            .as_ref()
+            .and_then(|w| w.extra_input())
+            .filter(|_| self.mode == MergeMode::ReEncode)
+    }
+
+    // 外部音频排在 `inputs` 个主输入和图片水印之后
+    fn audio_input_index(&self, inputs: usize) -> usize {
+        inputs + usize::from(self.watermark_image().is_some())
+    }
+
+    // 从 `source` 开始依次拼接统一分辨率与水印滤镜，最终输出标签为 [v]；
+    // 图片水印紧跟在 `inputs` 个主输入之后
+    fn video_filter_graph(
+        &self,
+        plan: Option<&NormalizePlan>,
+        source: &str,
+        inputs: usize,
+    ) -> Option<String> {
+        match (plan, &self.watermark) {
+            (None, None) => None,
+            (Some(plan), None) => Some(format!("[{}]{}[v]", source, plan.filter())),
+            (None, Some(watermark)) => Some(watermark.filter(source, "v", inputs)),
+            (Some(plan), Some(watermark)) => Some(format!(
+                "[{}]{}[n];{}",
+                source,
+                plan.filter(),
+                watermark.filter("n", "v", inputs)
+            )),
+        }
+    }
+}
+
+// 以文件为键的设置中查找某个文件的值
+fn clip_value<'a, T>(values: &'a [(PathBuf, T)], file: &Path) -> Option<&'a T> {
+    values
+        .iter()
+        .find(|(path, _)| path == file)
+        .map(|(_, value)| value)
+}
+
+// 更新以文件为键的设置，已有记录原位替换，`None` 表示移除
+fn set_clip_value<T>(values: &mut Vec<(PathBuf, T)>, file: PathBuf, value: Option<T>) {
+    let index = values.iter().position(|(path, _)| *path == file);
+    match (index, value) {
+        (Some(index), Some(value)) => values[index].1 = value,
+        (Some(index), None) => {
+            values.remove(index);
+        }
+        (None, Some(value)) => values.push((file, value)),
+        (None, None) => {}
+    }
+}
+
+/// 保存在配置中的命名预设
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MergePreset {
+    pub name: String,
+    pub options: MergeOptions,
+}
@@ -0,0 +1,134 @@
+use std::cmp::Ordering;
+
+/// 合并的各个阶段，按执行顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePhase {
+    Probing,
+    PreparingList,
+    Concatenating,
+    Verifying,
+    Finalizing,
+}
+
+impl MergePhase {
+    pub const ALL: [MergePhase; 5] = [
+        MergePhase::Probing,
+        MergePhase::PreparingList,
+        MergePhase::Concatenating,
+        MergePhase::Verifying,
+        MergePhase::Finalizing,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergePhase::Probing => "读取时长",
+            MergePhase::PreparingList => "准备列表",
+            MergePhase::Concatenating => "拼接视频",
+            MergePhase::Verifying => "校验输出",
+            MergePhase::Finalizing => "保存文件",
+        }
+    }
+
+    // 在整体进度中所占的区间 (起点, 跨度)，拼接耗时最长
+    fn range(&self) -> (f64, f64) {
+        match self {
+            MergePhase::Probing => (0.0, 10.0),
+            MergePhase::PreparingList => (10.0, 5.0),
+            MergePhase::Concatenating => (15.0, 80.0),
+            MergePhase::Verifying => (95.0, 4.0),
+            MergePhase::Finalizing => (99.0, 1.0),
+        }
+    }
+}
+
+/// 进度事件：当前阶段及其阶段内进度（0~100）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MergeProgress {
+    pub phase: MergePhase,
+    pub percent: f64,
+    /// 本次 FFmpeg 运行已处理的时长占总时长的比例（0~1），用于定位当前片段
+    pub position: Option<f64>,
+}
+
+impl MergeProgress {
+    pub fn new(phase: MergePhase, percent: f64) -> Self {
+        Self {
+            phase,
+            percent: percent.clamp(0.0, 100.0),
+            position: None,
+        }
+    }
+
+    pub fn with_position(mut self, position: f64) -> Self {
+        self.position = Some(position.clamp(0.0, 1.0));
+        self
+    }
+
+    /// 换算成整体进度（0~100）
+    pub fn overall(&self) -> f64 {
+        let (start, span) = self.phase.range();
+        start + span * self.percent / 100.0
+    }
+
+    /// 某个阶段的进度：之前的阶段已完成，之后的阶段尚未开始
+    pub fn phase_percent(&self, phase: MergePhase) -> f64 {
+        match (phase as usize).cmp(&(self.phase as usize)) {
+            Ordering::Less => 100.0,
+            Ordering::Equal => self.percent,
+            Ordering::Greater => 0.0,
+        }
+    }
+}
+
+/// 由各片段时长计算累计边界（占总时长的比例，最后一项为 1）
+pub fn segment_boundaries(durations: &[f64]) -> Vec<f64> {
+    let total: f64 = durations.iter().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    let mut elapsed = 0.0;
+    durations
+        .iter()
+        .map(|d| {
+            elapsed += d;
+            elapsed / total
+        })
+        .collect()
+}
+
+/// 处理位置所在的片段序号（从 0 开始）
+pub fn current_segment(boundaries: &[f64], position: f64) -> usize {
+    boundaries
+        .iter()
+        .position(|end| position < *end)
+        .unwrap_or(boundaries.len().saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overall_progress_follows_phase_order() {
+        let progress = MergeProgress::new(MergePhase::Concatenating, 50.0);
+        assert_eq!(progress.overall(), 55.0);
+        assert_eq!(progress.phase_percent(MergePhase::Probing), 100.0);
+        assert_eq!(progress.phase_percent(MergePhase::Concatenating), 50.0);
+        assert_eq!(progress.phase_percent(MergePhase::Verifying), 0.0);
+        assert_eq!(
+            MergeProgress::new(MergePhase::Finalizing, 100.0).overall(),
+            100.0
+        );
+    }
+
+    #[test]
+    fn segment_is_located_by_position() {
+        let boundaries = segment_boundaries(&[30.0, 10.0, 60.0]);
+        assert_eq!(boundaries, vec![0.3, 0.4, 1.0]);
+        assert_eq!(current_segment(&boundaries, 0.0), 0);
+        assert_eq!(current_segment(&boundaries, 0.35), 1);
+        assert_eq!(current_segment(&boundaries, 0.4), 2);
+        assert_eq!(current_segment(&boundaries, 1.0), 2);
+        assert!(segment_boundaries(&[]).is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::error::AppError;
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use futures_util::{StreamExt, stream};
+use regex::Regex;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+/// 同时运行的探测进程数量上限
+pub const PROBE_CONCURRENCY: usize = 8;
+/// 单个文件探测的最长等待时间
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 时长缓存，按 路径 + 修改时间 区分，文件被修改后自动失效
+static DURATION_CACHE: OnceLock<Mutex<HashMap<(PathBuf, SystemTime), f64>>> = OnceLock::new();
+
+/// 获取视频时长（秒），命中缓存时不再启动 FFmpeg
+pub async fn probe_duration_cached(path: &Path) -> Result<f64, AppError> {
+    probe_duration_cached_with(&SystemRunner, path).await
+}
+
+/// 同 [`probe_duration_cached`]，通过 `runner` 启动 FFmpeg
+pub async fn probe_duration_cached_with(
+    runner: &dyn CommandRunner,
+    path: &Path,
+) -> Result<f64, AppError> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let cache = DURATION_CACHE.get_or_init(Default::default);
+
+    if let Some(modified) = modified
+        && let Some(duration) = cache.lock().unwrap().get(&(path.to_path_buf(), modified))
+    {
+        return Ok(*duration);
+    }
+
+    let duration = get_video_duration_with(runner, path).await?;
+    if let Some(modified) = modified {
+        cache
+            .lock()
+            .unwrap()
+            .insert((path.to_path_buf(), modified), duration);
+    }
+    Ok(duration)
+}
+
+/// 并发探测多个文件的时长，结果保持输入顺序，每完成一个以（已完成数, 文件）调用 `on_probed`。
+/// 任一文件失败或超时立即返回错误，其余仍在进行的探测随之结束
+pub async fn probe_durations_with(
+    runner: &dyn CommandRunner,
+    files: &[PathBuf],
+    mut on_probed: impl FnMut(usize, &Path),
+) -> Result<Vec<f64>, AppError> {
+    let mut probes = stream::iter(files)
+        .map(|file| async move { (file, probe_duration_cached_with(runner, file).await) })
+        .buffered(PROBE_CONCURRENCY);
+    let mut durations = Vec::with_capacity(files.len());
+    while let Some((file, result)) = probes.next().await {
+        durations.push(result?);
+        on_probed(durations.len(), file);
+    }
+    Ok(durations)
+}
+
+/// 调用 FFmpeg 读取视频时长（秒）
+pub async fn get_video_duration(path: &Path) -> Result<f64, AppError> {
+    get_video_duration_with(&SystemRunner, path).await
+}
+
+/// 同 [`get_video_duration`]，通过 `runner` 启动 FFmpeg
+pub async fn get_video_duration_with(
+    runner: &dyn CommandRunner,
+    path: &Path,
+) -> Result<f64, AppError> {
+    let stderr = read_stream_info(runner, path).await?;
+    let re = Regex::new(r"Duration: (\d{2}):(\d{2}):(\d{2}\.\d{2})").unwrap();
+
+    if let Some(caps) = re.captures(&stderr) {
+        let hours: f64 = caps[1].parse().unwrap_or(0.0);
+        let minutes: f64 = caps[2].parse().unwrap_or(0.0);
+        let seconds: f64 = caps[3].parse().unwrap_or(0.0);
+        Ok(hours * 3600.0 + minutes * 60.0 + seconds)
+    } else {
+        Err(AppError::Probe {
+            path: path.to_path_buf(),
+            message: "无法解析视频时长信息".to_string(),
+        })
+    }
+}
+
+/// 读取视频的显示旋转角度（逆时针，0/90/180/270），没有旋转信息时返回 0
+pub async fn probe_rotation(path: &Path) -> Result<i32, AppError> {
+    probe_rotation_with(&SystemRunner, path).await
+}
+
+/// 同 [`probe_rotation`]，通过 `runner` 启动 FFmpeg
+pub async fn probe_rotation_with(runner: &dyn CommandRunner, path: &Path) -> Result<i32, AppError> {
+    let stderr = read_stream_info(runner, path).await?;
+    // 新版 FFmpeg 输出 displaymatrix（逆时针角度），旧版输出 rotate 标签（顺时针角度）
+    let display_matrix =
+        Regex::new(r"displaymatrix: rotation of (-?\d+(?:\.\d+)?) degrees").unwrap();
+    let rotate_tag = Regex::new(r"rotate\s*:\s*(-?\d+)").unwrap();
+
+    let ccw = if let Some(caps) = display_matrix.captures(&stderr) {
+        caps[1].parse::<f64>().unwrap_or(0.0).round() as i32
+    } else if let Some(caps) = rotate_tag.captures(&stderr) {
+        -caps[1].parse::<i32>().unwrap_or(0)
+    } else {
+        0
+    };
+    Ok(ccw.rem_euclid(360))
+}
+
+/// 文件是否包含音频流
+pub async fn probe_has_audio(path: &Path) -> Result<bool, AppError> {
+    let stderr = read_stream_info(&SystemRunner, path).await?;
+    Ok(stderr
+        .lines()
+        .any(|l| l.trim_start().starts_with("Stream #") && l.contains(": Audio:")))
+}
+
+/// 读取容器级别的元数据标签（title、artist 等），键统一为小写
+pub async fn probe_format_tags(path: &Path) -> Result<HashMap<String, String>, AppError> {
+    let stderr = read_stream_info(&SystemRunner, path).await?;
+    let mut tags = HashMap::new();
+    let mut in_metadata = false;
+    for line in stderr.lines() {
+        let indent = line.len() - line.trim_start().len();
+        if indent == 2 && line.trim() == "Metadata:" {
+            in_metadata = true;
+            continue;
+        }
+        // 容器标签缩进 4 格，遇到 Duration/Stream 等同级行即结束
+        if in_metadata && indent <= 2 {
+            break;
+        }
+        if in_metadata && let Some((key, value)) = line.split_once(':') {
+            let key = key.trim();
+            if !key.is_empty() {
+                tags.insert(key.to_lowercase(), value.trim().to_string());
+            }
+        }
+    }
+    Ok(tags)
+}
+
+// `ffmpeg -i` 不指定输出时会把流信息打印到 stderr；损坏的文件可能让 FFmpeg 一直不退出，
+// 超时后丢弃 future 即结束进程
+async fn read_stream_info(runner: &dyn CommandRunner, path: &Path) -> Result<String, AppError> {
+    let args = [OsString::from("-i"), path.as_os_str().to_os_string()];
+    let output = tokio::time::timeout(PROBE_TIMEOUT, runner.output("ffmpeg", &args))
+        .await
+        .map_err(|_| AppError::ProbeTimeout {
+            path: path.to_path_buf(),
+            secs: PROBE_TIMEOUT.as_secs(),
+        })??;
+
+    Ok(String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+/// 视频流的基本参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoStreamInfo {
+    pub codec: String,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+}
+
+/// 读取第一个视频流的编码、分辨率和帧率
+pub async fn probe_video_stream(path: &Path) -> Result<VideoStreamInfo, AppError> {
+    probe_video_stream_with(&SystemRunner, path).await
+}
+
+/// 同 [`probe_video_stream`]，通过 `runner` 启动 FFmpeg
+pub async fn probe_video_stream_with(
+    runner: &dyn CommandRunner,
+    path: &Path,
+) -> Result<VideoStreamInfo, AppError> {
+    let stderr = read_stream_info(runner, path).await?;
+    let stream_re =
+        Regex::new(r"Stream #\d+:\d+.*?: Video: (\w+).*?, (\d{2,5})x(\d{2,5})").unwrap();
+    let fps_re = Regex::new(r"(\d+(?:\.\d+)?) fps").unwrap();
+
+    let line = stderr
+        .lines()
+        .find(|l| stream_re.is_match(l))
+        .ok_or_else(|| AppError::Probe {
+            path: path.to_path_buf(),
+            message: "未找到视频流".to_string(),
+        })?;
+    let caps = stream_re.captures(line).unwrap();
+    let fps = fps_re
+        .captures(line)
+        .and_then(|c| c[1].parse::<f64>().ok())
+        .unwrap_or(0.0);
+    Ok(VideoStreamInfo {
+        codec: caps[1].to_string(),
+        width: caps[2].parse().unwrap_or(0),
+        height: caps[3].parse().unwrap_or(0),
+        fps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffmpeg::command::fake::FakeRunner;
+
+    const PROBE: &str = "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'a.mp4':
+  Metadata:
+    major_brand     : isom
+  Duration: 00:01:04.50, start: 0.000000, bitrate: 1205 kb/s
+  Stream #0:0[0x1](und): Video: h264 (High) (avc1 / 0x31637661), yuv420p(progressive), 1920x1080 [SAR 1:1 DAR 16:9], 1000 kb/s, 29.97 fps, 29.97 tbr, 30k tbn (default)
+      Side data:
+        displaymatrix: rotation of -90.00 degrees
+  Stream #0:1[0x2](und): Audio: aac (LC) (mp4a / 0x6134706D), 48000 Hz, stereo, fltp, 192 kb/s (default)
+At least one output file must be specified";
+
+    #[tokio::test]
+    async fn stream_info_is_parsed_from_stderr() {
+        let runner = FakeRunner::new(PROBE, "", true);
+        let path = Path::new("a.mp4");
+        assert_eq!(get_video_duration_with(&runner, path).await.unwrap(), 64.5);
+        assert_eq!(probe_rotation_with(&runner, path).await.unwrap(), 270);
+        assert_eq!(
+            probe_video_stream_with(&runner, path).await.unwrap(),
+            VideoStreamInfo {
+                codec: "h264".to_string(),
+                width: 1920,
+                height: 1080,
+                fps: 29.97,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_stream_info_is_an_error() {
+        let runner = FakeRunner::new("a.mp4: No such file or directory", "", true);
+        let path = Path::new("a.mp4");
+        assert!(matches!(
+            get_video_duration_with(&runner, path).await,
+            Err(AppError::Probe { .. })
+        ));
+        assert_eq!(probe_rotation_with(&runner, path).await.unwrap(), 0);
+        assert_eq!(
+            probe_video_stream_with(&runner, path)
+                .await
+                .unwrap_err()
+                .to_string(),
+            "无法读取视频信息 a.mp4: 未找到视频流"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn hanging_probe_times_out_and_aborts() {
+        let mut runner = FakeRunner::new(PROBE, "", true);
+        runner.hang = true;
+        let files = vec![PathBuf::from("a.mp4"), PathBuf::from("b.mp4")];
+        let mut probed = Vec::new();
+        let error = probe_durations_with(&runner, &files, |n, _| probed.push(n))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, AppError::ProbeTimeout { path, secs: 30 } if path == &files[0]),
+            "{}",
+            error
+        );
+        assert!(probed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn durations_keep_input_order() {
+        let runner = FakeRunner::new(PROBE, "", true);
+        let files = vec![PathBuf::from("a.mp4"), PathBuf::from("b.mp4")];
+        let mut probed = Vec::new();
+        let durations = probe_durations_with(&runner, &files, |n, file| {
+            probed.push((n, file.to_path_buf()))
+        })
+        .await
+        .unwrap();
+        assert_eq!(durations, vec![64.5, 64.5]);
+        assert_eq!(probed, vec![(1, files[0].clone()), (2, files[1].clone())]);
+    }
+}
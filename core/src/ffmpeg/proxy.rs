@@ -0,0 +1,126 @@
+//! 高分辨率素材的低分辨率代理：剪切分析和预览改用代理，解码快得多；合并时仍使用原文件。
+//! 代理与原文件的时间轴一致，剪切时间不需要换算
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::probe::{VideoStreamInfo, probe_video_stream};
+use crate::ffmpeg::thumbnail::file_cache_key;
+use crate::ffmpeg::workspace::part_path;
+use crate::utils::app_data_dir;
+use std::path::{Path, PathBuf};
+use tokio::sync::Semaphore;
+
+/// 代理画面的高度（像素），宽度按比例缩放
+pub const PROXY_HEIGHT: u32 = 540;
+
+// 同时生成代理的 FFmpeg 进程数，高分辨率解码很占资源，一次只做一个
+static PROXY_SLOTS: Semaphore = Semaphore::const_new(1);
+
+/// 分辨率高于 2560x1440 的视频才生成代理
+pub fn needs_proxy(info: &VideoStreamInfo) -> bool {
+    info.width > 2560 || info.height > 1440
+}
+
+/// 已生成的代理文件
+pub fn cached_proxy(path: &Path) -> Option<PathBuf> {
+    cache_path(path).ok().filter(|p| p.is_file())
+}
+
+/// 预览与分析使用的文件：有代理时用代理，否则用原文件
+pub fn preview_source(path: &Path) -> PathBuf {
+    cached_proxy(path).unwrap_or_else(|| path.to_path_buf())
+}
+
+/// 为高分辨率视频生成代理，已有代理时直接返回；不需要代理时返回 None
+pub async fn ensure_proxy(path: &Path) -> Result<Option<PathBuf>, AppError> {
+    if let Some(cached) = cached_proxy(path) {
+        return Ok(Some(cached));
+    }
+    if !needs_proxy(&probe_video_stream(path).await?) {
+        return Ok(None);
+    }
+    let _slot = PROXY_SLOTS
+        .acquire()
+        .await
+        .map_err(|_| AppError::Cancelled)?;
+    // 排队期间可能已由其他工作区生成
+    if let Some(cached) = cached_proxy(path) {
+        return Ok(Some(cached));
+    }
+    let cached = cache_path(path)?;
+    if let Some(dir) = cached.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| AppError::io("创建代理目录失败", e))?;
+    }
+
+    let part = part_path(&cached);
+    let args = command(path, &part).build();
+    let output = SystemRunner.output("ffmpeg", &args).await?;
+    if !output.exit.success || !part.is_file() {
+        let _ = std::fs::remove_file(&part);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last_line = stderr.lines().last().unwrap_or_default();
+        return Err(AppError::Ffmpeg(format!("生成代理失败: {}", last_line)));
+    }
+    std::fs::rename(&part, &cached).map_err(|e| AppError::io("保存代理文件失败", e))?;
+    Ok(Some(cached))
+}
+
+/// 删除全部代理文件，返回释放的字节数
+pub fn clear_proxies() -> Result<u64, AppError> {
+    let dir = proxies_dir()?;
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(0);
+    };
+    let mut freed = 0;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if std::fs::remove_file(entry.path()).is_ok() {
+            freed += size;
+        }
+    }
+    Ok(freed)
+}
+
+fn command(input: &Path, output: &Path) -> FfmpegCommand {
+    FfmpegCommand::new()
+        .input(input)
+        .video_filter(format!("scale=-2:{}", PROXY_HEIGHT))
+        .video_codec("libx264")
+        .option("-preset", "veryfast")
+        .option("-crf", "28")
+        // 关键帧间隔短，预览时定位更快
+        .option("-g", "15")
+        .option("-pix_fmt", "yuv420p")
+        .audio_codec("aac")
+        // 输出为 .part 文件，无法从扩展名推断格式
+        .format("mp4")
+        .output(output)
+}
+
+fn proxies_dir() -> Result<PathBuf, AppError> {
+    let dir = app_data_dir().map_err(|e| AppError::io("无法定位数据目录", e))?;
+    Ok(dir.join("proxies"))
+}
+
+fn cache_path(path: &Path) -> Result<PathBuf, AppError> {
+    Ok(proxies_dir()?.join(format!("{}.mp4", file_cache_key(path)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_high_resolution_sources_get_proxies() {
+        let info = |width, height| VideoStreamInfo {
+            codec: "h264".to_string(),
+            width,
+            height,
+            fps: 30.0,
+        };
+        assert!(needs_proxy(&info(3840, 2160)));
+        assert!(needs_proxy(&info(2160, 3840)));
+        assert!(!needs_proxy(&info(1920, 1080)));
+        assert!(!needs_proxy(&info(2560, 1440)));
+    }
+}
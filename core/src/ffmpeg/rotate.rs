@@ -0,0 +1,111 @@
+use crate::MergeEvent;
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::command::SystemRunner;
+use crate::ffmpeg::merge_mp4::MergeEventSender;
+use crate::ffmpeg::phase::MergePhase;
+use crate::ffmpeg::probe::{get_video_duration, probe_rotation};
+use crate::ffmpeg::runner::run_ffmpeg_with_progress;
+use crate::ffmpeg::workspace::part_path;
+use std::path::{Path, PathBuf};
+
+/// 旋转方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RotateMethod {
+    /// 只改写显示旋转元数据，不重新编码（无损、速度快）
+    Metadata,
+    /// 使用 transpose 滤镜把旋转写入画面并重新编码
+    ReEncode,
+}
+
+/// 旋转/矫正参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotateOptions {
+    /// 在当前方向基础上顺时针旋转的角度（0/90/180/270）
+    pub clockwise: i32,
+    pub method: RotateMethod,
+}
+
+impl RotateOptions {
+    fn command(&self, input: &Path, output: &Path, current_ccw: i32) -> FfmpegCommand {
+        let command = match self.method {
+            RotateMethod::Metadata => {
+                // -display_rotation 为输入选项，角度按逆时针计算
+                let new_ccw = (current_ccw - self.clockwise).rem_euclid(360);
+                FfmpegCommand::new()
+                    .input(
+                        FfmpegInput::new(input)
+                            .option("-display_rotation:v:0", new_ccw.to_string()),
+                    )
+                    .map("0")
+                    .copy_codecs()
+            }
+            RotateMethod::ReEncode => {
+                let mut command = FfmpegCommand::new().input(input);
+                // FFmpeg 默认会先按原有元数据自动旋转，再应用这里的 transpose
+                if let Some(filter) = transpose_filter(self.clockwise) {
+                    command = command.video_filter(filter);
+                }
+                command
+                    .video_codec("libx264")
+                    .option("-preset", "medium")
+                    .option("-crf", "20")
+                    .audio_codec("copy")
+            }
+        };
+        command.format("mp4").output(output)
+    }
+}
+
+/// 顺时针旋转角度对应的滤镜
+pub fn transpose_filter(clockwise: i32) -> Option<&'static str> {
+    match clockwise.rem_euclid(360) {
+        90 => Some("transpose=1"),
+        180 => Some("transpose=1,transpose=1"),
+        270 => Some("transpose=2"),
+        _ => None,
+    }
+}
+
+/// 旋转单个文件，结果保存到 `output_path`
+pub async fn run_rotate(
+    input: PathBuf,
+    output_path: PathBuf,
+    options: RotateOptions,
+    tx: impl Into<MergeEventSender>,
+) {
+    let tx: MergeEventSender = tx.into();
+    let current_ccw = match probe_rotation(&input).await {
+        Ok(r) => r,
+        Err(e) => {
+            tx.send(MergeEvent::Error(e));
+            return;
+        }
+    };
+    let duration = get_video_duration(&input).await.unwrap_or(0.0);
+
+    let part_output_path = part_path(&output_path);
+    tx.send(MergeEvent::Status("正在旋转...".to_string()));
+    let command = options.command(&input, &part_output_path, current_ccw);
+    if let Err(e) = run_ffmpeg_with_progress(
+        &SystemRunner,
+        command,
+        duration,
+        MergePhase::Concatenating,
+        0.0,
+        100.0,
+        &tx,
+    )
+    .await
+    {
+        let _ = std::fs::remove_file(&part_output_path);
+        tx.send(MergeEvent::Error(e));
+        return;
+    }
+    if let Err(e) = std::fs::rename(&part_output_path, &output_path) {
+        let _ = std::fs::remove_file(&part_output_path);
+        tx.send(MergeEvent::Error(AppError::io("重命名输出文件失败", e)));
+        return;
+    }
+    tx.send(MergeEvent::Success(output_path.display().to_string()));
+}
@@ -0,0 +1,137 @@
+use crate::MergeEvent;
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::command::CommandRunner;
+use crate::ffmpeg::merge_mp4::MergeEventSender;
+use crate::ffmpeg::phase::{MergePhase, MergeProgress};
+use crate::utils::record_log;
+use regex::Regex;
+
+/// 运行 FFmpeg，并把 stderr 中的 `time=` 换算成进度发送出去。
+///
+/// `progress_start` 与 `progress_span` 指定本次运行在 `phase` 阶段内所占的区间，
+/// 例如两遍编码时第一遍占拼接阶段的 0~50%，第二遍占 50~100%。
+pub async fn run_ffmpeg_with_progress(
+    runner: &dyn CommandRunner,
+    command: FfmpegCommand,
+    total_duration: f64,
+    phase: MergePhase,
+    progress_start: f64,
+    progress_span: f64,
+    tx: &MergeEventSender,
+) -> Result<(), AppError> {
+    record_log(&command.to_string());
+    let args = command.build();
+    let time_regex = Regex::new(r"time=(\d{2}):(\d{2}):(\d{2}\.\d{2})").unwrap();
+
+    // 最后一行非进度输出，通常是失败原因
+    let mut last_message = String::new();
+    let mut on_line = |line: String| {
+        tx.send(MergeEvent::Status(line.clone()));
+        if !line.contains("time=") && !line.trim().is_empty() {
+            last_message = line.trim().to_string();
+        }
+
+        if let Some(caps) = time_regex.captures(&line)
+            && let (Ok(hours), Ok(minutes), Ok(seconds)) = (
+                caps[1].parse::<f64>(),
+                caps[2].parse::<f64>(),
+                caps[3].parse::<f64>(),
+            )
+        {
+            let current_time = hours * 3600.0 + minutes * 60.0 + seconds;
+            if total_duration > 0.0 {
+                let progress_pct =
+                    (current_time / total_duration).min(0.99) * progress_span + progress_start;
+                tx.send(MergeEvent::Progress(
+                    MergeProgress::new(phase, progress_pct)
+                        .with_position(current_time / total_duration),
+                ));
+            }
+        }
+    };
+    let exit = runner.stream_stderr("ffmpeg", &args, &mut on_line).await?;
+
+    if exit.success {
+        Ok(())
+    } else if last_message.is_empty() {
+        Err(AppError::Ffmpeg(format!(
+            "FFmpeg进程异常退出，退出码: {}",
+            exit.description
+        )))
+    } else {
+        Err(AppError::Ffmpeg(format!(
+            "FFmpeg进程异常退出，退出码: {}：{}",
+            exit.description, last_message
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffmpeg::command::fake::FakeRunner;
+    use crate::ffmpeg::error::ErrorCode;
+    use futures::StreamExt;
+    use futures::channel::mpsc::unbounded;
+    use std::path::Path;
+
+    // 运行一次并收集全部事件
+    async fn run(runner: &FakeRunner) -> (Result<(), AppError>, Vec<MergeEvent>) {
+        let (tx, rx) = unbounded();
+        let result = run_ffmpeg_with_progress(
+            runner,
+            FfmpegCommand::new()
+                .input(Path::new("list.txt"))
+                .output("out.mp4"),
+            20.0,
+            MergePhase::Concatenating,
+            0.0,
+            100.0,
+            &MergeEventSender::from(tx),
+        )
+        .await;
+        (result, rx.collect().await)
+    }
+
+    #[tokio::test]
+    async fn progress_follows_time_lines() {
+        let runner = FakeRunner::new(
+            "",
+            "frame=  100 fps=0.0 q=-1.0 size=    1024kB time=00:00:05.00 bitrate=1677.7kbits/s speed=10x
+frame=  200 fps=0.0 q=-1.0 size=    2048kB time=00:00:10.00 bitrate=1677.7kbits/s speed=10x",
+            true,
+        );
+        let (result, events) = run(&runner).await;
+        assert!(result.is_ok());
+        let progress: Vec<f64> = events
+            .iter()
+            .filter_map(|e| match e {
+                MergeEvent::Progress(p) => Some(p.percent),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(progress, vec![25.0, 50.0]);
+        assert_eq!(runner.calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failure_reports_last_message() {
+        let runner = FakeRunner::new(
+            "",
+            "frame=   50 fps=0.0 q=-1.0 size=     512kB time=00:00:02.00 bitrate=1677.7kbits/s speed=10x
+[mp4 @ 0x1] Non-monotonous DTS in output stream 0:1",
+            false,
+        );
+        let (result, _) = run(&runner).await;
+        let error = result.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .ends_with("Non-monotonous DTS in output stream 0:1"),
+            "{}",
+            error
+        );
+        assert_eq!(error.code(), Some(ErrorCode::CodecMismatch));
+    }
+}
@@ -0,0 +1,202 @@
+//! 精确剪切（smart render）：复制模式下只重新编码切点到相邻关键帧之间的画面，其余部分直接复制
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::command::CommandRunner;
+use regex::Regex;
+use std::path::Path;
+
+/// 切点与关键帧相差不超过该值（秒）时视为已对齐，不再单独编码
+const KEYFRAME_TOLERANCE: f64 = 0.05;
+
+/// 保留区间拆分出的一段，`end` 为 None 表示到文件结尾
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CutPiece {
+    /// 重新编码：切点与关键帧之间的部分
+    Encode { start: f64, end: Option<f64> },
+    /// 直接复制：从关键帧开始
+    Copy { start: f64, end: Option<f64> },
+}
+
+impl CutPiece {
+    /// 按片段类型生成命令，重新编码部分使用 `encoder`，音频始终复制
+    pub fn command(&self, input: &Path, encoder: &str, output: &Path) -> FfmpegCommand {
+        let (start, end) = match *self {
+            CutPiece::Encode { start, end } | CutPiece::Copy { start, end } => (start, end),
+        };
+        let mut source = FfmpegInput::new(input).seek(start);
+        if let Some(end) = end {
+            source = source.duration(end - start);
+        }
+        let command = FfmpegCommand::new().input(source);
+        let command = match self {
+            CutPiece::Copy { .. } => command.copy_codecs(),
+            CutPiece::Encode { .. } => {
+                let command = command
+                    .video_codec(encoder)
+                    .option("-preset", "veryfast")
+                    .option("-crf", "18")
+                    .option("-pix_fmt", "yuv420p");
+                let command = if encoder == "libx265" {
+                    command.option("-tag:v", "hvc1")
+                } else {
+                    command
+                };
+                command.audio_codec("copy")
+            }
+        };
+        command
+            .option("-avoid_negative_ts", "make_zero")
+            .format("mp4")
+            .output(output)
+    }
+}
+
+/// 与源视频编码一致的编码器，只支持 H.264/H.265
+pub fn encoder_for(codec: &str) -> Option<&'static str> {
+    match codec {
+        "h264" => Some("libx264"),
+        "hevc" => Some("libx265"),
+        _ => None,
+    }
+}
+
+/// 把保留区间拆成“切点→关键帧”“关键帧→关键帧”“关键帧→切点”三段，`keyframes` 需升序
+pub fn plan_pieces(keyframes: &[f64], start: f64, end: Option<f64>) -> Vec<CutPiece> {
+    let limit = end.unwrap_or(f64::INFINITY);
+    let first = keyframes
+        .iter()
+        .copied()
+        .find(|&k| k >= start - KEYFRAME_TOLERANCE && k < limit - KEYFRAME_TOLERANCE);
+    // 区间内没有关键帧，整段重新编码
+    let Some(first) = first else {
+        return vec![CutPiece::Encode { start, end }];
+    };
+
+    let mut pieces = Vec::new();
+    if first > start + KEYFRAME_TOLERANCE {
+        pieces.push(CutPiece::Encode {
+            start,
+            end: Some(first),
+        });
+    }
+    let Some(end) = end else {
+        pieces.push(CutPiece::Copy {
+            start: first,
+            end: None,
+        });
+        return pieces;
+    };
+    let last = keyframes
+        .iter()
+        .copied()
+        .rev()
+        .find(|&k| k <= end + KEYFRAME_TOLERANCE)
+        .unwrap_or(first)
+        .max(first);
+    if last >= end - KEYFRAME_TOLERANCE {
+        pieces.push(CutPiece::Copy {
+            start: first,
+            end: Some(end),
+        });
+    } else {
+        if last > first {
+            pieces.push(CutPiece::Copy {
+                start: first,
+                end: Some(last),
+            });
+        }
+        pieces.push(CutPiece::Encode {
+            start: last,
+            end: Some(end),
+        });
+    }
+    pieces
+}
+
+/// 读取视频流所有关键帧的时间（秒），只解码关键帧，速度较快
+pub async fn keyframe_times_with(
+    runner: &dyn CommandRunner,
+    path: &Path,
+) -> Result<Vec<f64>, AppError> {
+    let args = FfmpegCommand::new()
+        .input(FfmpegInput::new(path).option("-skip_frame", "nokey"))
+        .map("0:v:0")
+        .video_filter("showinfo")
+        .null_output()
+        .build();
+    let output = runner.output("ffmpeg", &args).await?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.exit.success {
+        let last = stderr.lines().last().unwrap_or("FFmpeg 异常退出");
+        return Err(AppError::Ffmpeg(last.to_string()));
+    }
+    Ok(parse_keyframes(&stderr))
+}
+
+// showinfo 每帧一行，取其中的 pts_time
+fn parse_keyframes(stderr: &str) -> Vec<f64> {
+    let re = Regex::new(r"Parsed_showinfo.*\bpts_time:\s*(-?[\d.]+)").unwrap();
+    let mut times: Vec<f64> = stderr
+        .lines()
+        .filter_map(|line| re.captures(line))
+        .filter_map(|caps| caps[1].parse::<f64>().ok())
+        .collect();
+    times.sort_by(f64::total_cmp);
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cut_points_between_keyframes_are_reencoded() {
+        let stderr = "[Parsed_showinfo_0 @ 0x1] config in time_base: 1/15360, frame_rate: 30/1
+[Parsed_showinfo_0 @ 0x1] n:   0 pts:      0 pts_time:0       duration:    512
+[Parsed_showinfo_0 @ 0x1] n:   1 pts:  30720 pts_time:2       duration:    512
+[Parsed_showinfo_0 @ 0x1] n:   2 pts:  61440 pts_time:4       duration:    512
+[Parsed_showinfo_0 @ 0x1] n:   3 pts:  92160 pts_time:6       duration:    512
+";
+        let keyframes = parse_keyframes(stderr);
+        assert_eq!(keyframes, [0.0, 2.0, 4.0, 6.0]);
+
+        assert_eq!(
+            plan_pieces(&keyframes, 1.0, Some(5.5)),
+            [
+                CutPiece::Encode {
+                    start: 1.0,
+                    end: Some(2.0)
+                },
+                CutPiece::Copy {
+                    start: 2.0,
+                    end: Some(4.0)
+                },
+                CutPiece::Encode {
+                    start: 4.0,
+                    end: Some(5.5)
+                },
+            ]
+        );
+        assert_eq!(
+            plan_pieces(&keyframes, 2.0, None),
+            [CutPiece::Copy {
+                start: 2.0,
+                end: None
+            }]
+        );
+        assert_eq!(
+            plan_pieces(&keyframes, 0.0, Some(4.0)),
+            [CutPiece::Copy {
+                start: 0.0,
+                end: Some(4.0)
+            }]
+        );
+        assert_eq!(
+            plan_pieces(&keyframes, 4.5, Some(5.5)),
+            [CutPiece::Encode {
+                start: 4.5,
+                end: Some(5.5)
+            }]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! 单个片段的变速：画面用 `setpts`，声音用 `atempo`，在拼接前预先处理
+use crate::ffmpeg::args::FfmpegCommand;
+use std::path::Path;
+
+/// 合并列表中可选的播放速度
+pub const SPEED_CHOICES: [f64; 8] = [0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 2.0, 4.0];
+
+/// 显示用的速度文字，如 `2x`、`0.5x`
+pub fn speed_label(speed: f64) -> String {
+    format!("{}x", speed)
+}
+
+/// 单个 atempo 只接受 0.5~2.0，超出范围时拆成多级相乘
+pub fn atempo_chain(speed: f64) -> String {
+    let mut remaining = speed;
+    let mut stages = Vec::new();
+    while remaining > 2.0 {
+        stages.push(2.0);
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 {
+        stages.push(0.5);
+        remaining /= 0.5;
+    }
+    stages.push(remaining);
+    stages
+        .iter()
+        .map(|s| format!("atempo={:.4}", s))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 以 `speed` 倍速重新编码片段，输出时长为原来的 1/speed
+pub fn speed_command(input: &Path, speed: f64, output: &Path) -> FfmpegCommand {
+    FfmpegCommand::new()
+        .input(input)
+        .video_filter(format!("setpts=PTS/{:.4}", speed))
+        .option("-af", atempo_chain(speed))
+        .video_codec("libx264")
+        .option("-preset", "veryfast")
+        .option("-crf", "18")
+        .audio_codec("aac")
+        .format("mp4")
+        .output(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atempo_is_split_into_supported_stages() {
+        assert_eq!(atempo_chain(1.5), "atempo=1.5000");
+        assert_eq!(atempo_chain(4.0), "atempo=2.0000,atempo=2.0000");
+        assert_eq!(atempo_chain(0.25), "atempo=0.5000,atempo=0.5000");
+        assert_eq!(speed_label(0.5), "0.5x");
+        assert_eq!(speed_label(2.0), "2x");
+    }
+}
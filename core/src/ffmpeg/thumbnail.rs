@@ -0,0 +1,109 @@
+//! 扫描列表网格视图使用的缩略图，按文件路径、大小和修改时间缓存在数据目录中
+use crate::error::AppError;
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::command::{CommandRunner, SystemRunner};
+use crate::ffmpeg::workspace::part_path;
+use crate::utils::app_data_dir;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tokio::sync::Semaphore;
+
+/// 缩略图宽度（像素），高度按比例缩放
+pub const THUMBNAIL_WIDTH: u32 = 320;
+
+// 同时生成缩略图的 FFmpeg 进程数，避免一页几十张卡片同时启动进程
+static THUMBNAIL_SLOTS: Semaphore = Semaphore::const_new(4);
+
+/// 获取视频的缩略图（JPEG），已缓存时直接返回缓存路径。
+/// `duration` 为视频时长（秒），截取约 10% 处的画面，避开常见的黑屏片头
+pub async fn thumbnail(path: &Path, duration: f64) -> Result<PathBuf, AppError> {
+    let cached = cache_path(path)?;
+    if cached.is_file() {
+        return Ok(cached);
+    }
+    let _slot = THUMBNAIL_SLOTS
+        .acquire()
+        .await
+        .map_err(|_| AppError::Cancelled)?;
+    // 排队期间可能已由同一文件的其他卡片生成
+    if cached.is_file() {
+        return Ok(cached);
+    }
+    if let Some(dir) = cached.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| AppError::io("创建缩略图目录失败", e))?;
+    }
+
+    let part = part_path(&cached);
+    let args = command(path, &part, seek_position(duration)).build();
+    let output = SystemRunner.output("ffmpeg", &args).await?;
+    if !output.exit.success || !part.is_file() {
+        let _ = std::fs::remove_file(&part);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last_line = stderr.lines().last().unwrap_or_default();
+        return Err(AppError::Ffmpeg(format!("生成缩略图失败: {}", last_line)));
+    }
+    std::fs::rename(&part, &cached).map_err(|e| AppError::io("保存缩略图失败", e))?;
+    Ok(cached)
+}
+
+/// 截取 `at` 秒处的一帧，缩放后以 JPEG 写入 `output`
+pub(crate) fn command(input: &Path, output: &Path, at: f64) -> FfmpegCommand {
+    FfmpegCommand::new()
+        .input(FfmpegInput::new(input).seek(at))
+        .video_filter(format!("scale={}:-2", THUMBNAIL_WIDTH))
+        .option("-frames:v", "1")
+        .option("-q:v", "5")
+        .no_audio()
+        // 输出为 .part 文件，无法从扩展名推断格式
+        .format("mjpeg")
+        .output(output)
+}
+
+/// 截图位置：取 10% 处，最多第 10 秒；时长未知时取开头
+pub(crate) fn seek_position(duration: f64) -> f64 {
+    if duration.is_finite() && duration > 0.0 {
+        (duration * 0.1).min(10.0)
+    } else {
+        0.0
+    }
+}
+
+fn cache_path(path: &Path) -> Result<PathBuf, AppError> {
+    let dir = app_data_dir().map_err(|e| AppError::io("无法定位数据目录", e))?;
+    Ok(dir
+        .join("thumbnails")
+        .join(format!("{}.jpg", file_cache_key(path)?)))
+}
+
+/// 按路径、大小和修改时间计算的缓存键，文件被替换或修改后键随之变化，不会用到过期的缓存
+pub(crate) fn file_cache_key(path: &Path) -> Result<String, AppError> {
+    let metadata = std::fs::metadata(path).map_err(|e| AppError::io("读取文件信息失败", e))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(path.as_os_str().as_encoded_bytes());
+    hasher.update(metadata.len().to_le_bytes());
+    hasher.update(modified.to_le_bytes());
+    Ok(hasher.finalize()[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thumbnail_skips_intro() {
+        assert_eq!(seek_position(30.0), 3.0);
+        assert_eq!(seek_position(3600.0), 10.0);
+        assert_eq!(seek_position(0.0), 0.0);
+        assert_eq!(seek_position(f64::NAN), 0.0);
+    }
+}
@@ -0,0 +1,101 @@
+//! 片段之间的标题卡：用 lavfi 纯色画面加 drawtext 生成一段带静音音轨的短视频
+use crate::ffmpeg::args::{FfmpegCommand, FfmpegInput};
+use crate::ffmpeg::normalize::NormalizePlan;
+use crate::ffmpeg::watermark::{escape_drawtext, font_option};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 标题卡设置，仅在重新编码模式下生效
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TitleCardOptions {
+    /// 卡片文字，`{name}` 替换为下一个片段的文件名，`{index}` 替换为片段序号
+    pub text: String,
+    /// 每张卡片的时长（秒）
+    pub duration: f64,
+    /// 背景颜色，FFmpeg 颜色名或 #RRGGBB
+    pub background: String,
+    pub font_size: u32,
+}
+
+impl Default for TitleCardOptions {
+    fn default() -> Self {
+        TitleCardOptions {
+            text: "{name}".to_string(),
+            duration: 2.0,
+            background: "black".to_string(),
+            font_size: 64,
+        }
+    }
+}
+
+impl TitleCardOptions {
+    /// 第 `index` 个片段（从 0 开始）前的卡片文字
+    pub fn text_for(&self, index: usize, clip: &Path) -> String {
+        let name = clip
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.text
+            .replace("{name}", &name)
+            .replace("{index}", &(index + 1).to_string())
+    }
+
+    /// 按统一方案的分辨率和帧率生成一张卡片，音轨为 48kHz 立体声静音
+    pub fn command(&self, text: &str, plan: &NormalizePlan, output: &Path) -> FfmpegCommand {
+        let color = format!(
+            "color=c={}:s={}x{}:r={:.3}",
+            self.background, plan.width, plan.height, plan.fps
+        );
+        let drawtext = format!(
+            "drawtext={}expansion=none:text={}:fontsize={}:fontcolor=white:x=(w-tw)/2:y=(h-th)/2",
+            font_option(),
+            escape_drawtext(text),
+            self.font_size
+        );
+        FfmpegCommand::new()
+            .input(FfmpegInput::new(color).format("lavfi"))
+            .input(FfmpegInput::new("anullsrc=r=48000:cl=stereo").format("lavfi"))
+            .video_filter(drawtext)
+            .video_codec("libx264")
+            .option("-preset", "veryfast")
+            .option("-crf", "18")
+            .option("-pix_fmt", "yuv420p")
+            .audio_codec("aac")
+            .option("-t", format!("{:.3}", self.duration))
+            .format("mp4")
+            .output(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn card_text_uses_clip_name_and_index() {
+        let options = TitleCardOptions {
+            text: "第 {index} 段：{name}".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.text_for(1, Path::new("videos/旅行.mp4")),
+            "第 2 段：旅行"
+        );
+
+        let plan = NormalizePlan {
+            width: 1280,
+            height: 720,
+            fps: 30.0,
+            inputs: Vec::new(),
+        };
+        let args: Vec<String> = options
+            .command("a:b", &plan, Path::new("card.mp4"))
+            .build()
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert!(args.contains(&"color=c=black:s=1280x720:r=30.000".to_string()));
+        assert!(args.iter().any(|a| a.contains("text=a\\\\:b:")));
+        assert!(args.windows(2).any(|w| w == ["-t", "2.000"]));
+    }
+}
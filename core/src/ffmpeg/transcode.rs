@@ -0,0 +1,176 @@
+use crate::MergeEvent;
+use crate::error::AppError;
+use crate::ffmpeg::args::FfmpegCommand;
+use crate::ffmpeg::command::SystemRunner;
+use crate::ffmpeg::encoder::EncoderPreset;
+use crate::ffmpeg::merge_mp4::MergeEventSender;
+use crate::ffmpeg::phase::MergePhase;
+use crate::ffmpeg::probe::get_video_duration;
+use crate::ffmpeg::runner::run_ffmpeg_with_progress;
+use crate::ffmpeg::workspace::part_path;
+use crate::utils::with_stem_suffix;
+use std::path::{Path, PathBuf};
+
+/// 转码使用的视频编码
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TranscodeCodec {
+    #[default]
+    H264,
+    H265,
+}
+
+impl TranscodeCodec {
+    pub const ALL: [TranscodeCodec; 2] = [TranscodeCodec::H264, TranscodeCodec::H265];
+
+    pub fn encoder(&self) -> &'static str {
+        match self {
+            TranscodeCodec::H264 => "libx264",
+            TranscodeCodec::H265 => "libx265",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TranscodeCodec::H264 => "H.264（兼容性好）",
+            TranscodeCodec::H265 => "H.265（体积更小）",
+        }
+    }
+
+    /// 画质与 H.264 CRF 23 相近的默认值
+    pub fn default_crf(&self) -> u8 {
+        match self {
+            TranscodeCodec::H264 => 23,
+            TranscodeCodec::H265 => 28,
+        }
+    }
+
+    // 输出文件名后缀
+    fn suffix(&self) -> &'static str {
+        match self {
+            TranscodeCodec::H264 => "h264",
+            TranscodeCodec::H265 => "h265",
+        }
+    }
+}
+
+/// 批量转码时所有文件共用的设置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TranscodeOptions {
+    pub codec: TranscodeCodec,
+    /// 0~51，越小画质越高
+    pub crf: u8,
+    pub preset: EncoderPreset,
+    /// 最大高度（像素），只缩小不放大；None 保持原分辨率
+    pub max_height: Option<u32>,
+    /// 转码成功后把原文件移到回收站
+    pub recycle_originals: bool,
+}
+
+impl Default for TranscodeOptions {
+    fn default() -> Self {
+        TranscodeOptions {
+            codec: TranscodeCodec::default(),
+            crf: TranscodeCodec::default().default_crf(),
+            preset: EncoderPreset::Medium,
+            max_height: None,
+            recycle_originals: false,
+        }
+    }
+}
+
+impl TranscodeOptions {
+    fn command(&self, input: &Path, output: &Path) -> FfmpegCommand {
+        let mut command = FfmpegCommand::new().input(input);
+        if let Some(height) = self.max_height {
+            // 宽度取偶数以满足编码器要求
+            command = command.video_filter(format!("scale=-2:'min({},ih)'", height));
+        }
+        command = command
+            .video_codec(self.codec.encoder())
+            .option("-preset", self.preset.as_str())
+            .option("-crf", self.crf.to_string());
+        if self.codec == TranscodeCodec::H265 {
+            // 默认的 hev1 标记在 QuickTime 与 iOS 上无法播放
+            command = command.option("-tag:v", "hvc1");
+        }
+        command.audio_codec("copy").format("mp4").output(output)
+    }
+}
+
+/// 转码结果的默认保存位置：原目录下的 `<原名>_h264.mp4` / `<原名>_h265.mp4`
+pub fn transcode_output_path(input: &Path, codec: TranscodeCodec) -> PathBuf {
+    with_stem_suffix(input, &format!("_{}.mp4", codec.suffix()), "video")
+}
+
+/// 转码单个文件，结果保存到 `output_path`
+pub async fn run_transcode(
+    input: PathBuf,
+    output_path: PathBuf,
+    options: TranscodeOptions,
+    tx: impl Into<MergeEventSender>,
+) {
+    let tx: MergeEventSender = tx.into();
+    if output_path == input {
+        tx.send(MergeEvent::Error(AppError::Other(
+            "输出文件不能与原文件相同".to_string(),
+        )));
+        return;
+    }
+    let duration = get_video_duration(&input).await.unwrap_or(0.0);
+
+    let part_output_path = part_path(&output_path);
+    tx.send(MergeEvent::Status("正在转码...".to_string()));
+    let command = options.command(&input, &part_output_path);
+    if let Err(e) = run_ffmpeg_with_progress(
+        &SystemRunner,
+        command,
+        duration,
+        MergePhase::Concatenating,
+        0.0,
+        100.0,
+        &tx,
+    )
+    .await
+    {
+        let _ = std::fs::remove_file(&part_output_path);
+        tx.send(MergeEvent::Error(e));
+        return;
+    }
+    if let Err(e) = std::fs::rename(&part_output_path, &output_path) {
+        let _ = std::fs::remove_file(&part_output_path);
+        tx.send(MergeEvent::Error(AppError::io("重命名输出文件失败", e)));
+        return;
+    }
+    tx.send(MergeEvent::Success(output_path.display().to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn h265_output_is_tagged_and_downscaled() {
+        let options = TranscodeOptions {
+            codec: TranscodeCodec::H265,
+            crf: 28,
+            max_height: Some(720),
+            ..Default::default()
+        };
+        let args: Vec<String> = options
+            .command(Path::new("a.mp4"), Path::new("a_h265.mp4"))
+            .build()
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert!(
+            args.windows(2)
+                .any(|w| w == ["-vf", "scale=-2:'min(720,ih)'"])
+        );
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx265"]));
+        assert!(args.windows(2).any(|w| w == ["-tag:v", "hvc1"]));
+        assert_eq!(
+            transcode_output_path(Path::new("videos/a.mp4"), TranscodeCodec::H265),
+            Path::new("videos/a_h265.mp4")
+        );
+    }
+}
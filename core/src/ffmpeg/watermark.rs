@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 水印内容
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum WatermarkKind {
+    /// PNG 图片（支持透明通道）
+    Image(PathBuf),
+    /// 文字水印
+    Text { text: String, font_size: u32 },
+}
+
+/// 水印位置
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum WatermarkPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+    Center,
+}
+
+impl WatermarkPosition {
+    pub const ALL: [WatermarkPosition; 5] = [
+        WatermarkPosition::TopLeft,
+        WatermarkPosition::TopRight,
+        WatermarkPosition::BottomLeft,
+        WatermarkPosition::BottomRight,
+        WatermarkPosition::Center,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WatermarkPosition::TopLeft => "左上",
+            WatermarkPosition::TopRight => "右上",
+            WatermarkPosition::BottomLeft => "左下",
+            WatermarkPosition::BottomRight => "右下",
+            WatermarkPosition::Center => "居中",
+        }
+    }
+
+    /// 返回 (x, y) 表达式；`w`/`h` 为水印尺寸变量名，`W`/`H` 为画面尺寸变量名
+    fn coordinates(
+        &self,
+        w: &str,
+        h: &str,
+        big_w: &str,
+        big_h: &str,
+        margin: u32,
+    ) -> (String, String) {
+        match self {
+            WatermarkPosition::TopLeft => (margin.to_string(), margin.to_string()),
+            WatermarkPosition::TopRight => (format!("{big_w}-{w}-{margin}"), margin.to_string()),
+            WatermarkPosition::BottomLeft => (margin.to_string(), format!("{big_h}-{h}-{margin}")),
+            WatermarkPosition::BottomRight => (
+                format!("{big_w}-{w}-{margin}"),
+                format!("{big_h}-{h}-{margin}"),
+            ),
+            WatermarkPosition::Center => (format!("({big_w}-{w})/2"), format!("({big_h}-{h})/2")),
+        }
+    }
+}
+
+/// 重新编码合并时叠加的水印
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WatermarkOptions {
+    pub kind: WatermarkKind,
+    pub position: WatermarkPosition,
+    /// 不透明度 0.0 ~ 1.0
+    pub opacity: f32,
+    /// 与画面边缘的距离（像素）
+    pub margin: u32,
+}
+
+impl WatermarkOptions {
+    /// 图片水印需要作为额外输入传给 FFmpeg
+    pub fn extra_input(&self) -> Option<&PathBuf> {
+        match &self.kind {
+            WatermarkKind::Image(path) => Some(path),
+            WatermarkKind::Text { .. } => None,
+        }
+    }
+
+    /// 生成滤镜图片段：输入视频标签 `input`，输出标签 `output`，
+    /// 图片水印使用第 `image_input` 个输入
+    pub fn filter(&self, input: &str, output: &str, image_input: usize) -> String {
+        let opacity = self.opacity.clamp(0.0, 1.0);
+        match &self.kind {
+            WatermarkKind::Image(_) => {
+                let (x, y) = self.position.coordinates("w", "h", "W", "H", self.margin);
+                format!(
+                    "[{image_input}:v]format=rgba,colorchannelmixer=aa={opacity:.2}[wm];[{input}][wm]overlay={x}:{y}[{output}]"
+                )
+            }
+            WatermarkKind::Text { text, font_size } => {
+                let (x, y) = self.position.coordinates("tw", "th", "w", "h", self.margin);
+                format!(
+                    "[{input}]drawtext={font}expansion=none:text={text}:fontsize={font_size}:fontcolor=white@{opacity:.2}:borderw=1:bordercolor=black@{opacity:.2}:x={x}:y={y}[{output}]",
+                    font = font_option(),
+                    text = escape_drawtext(text),
+                )
+            }
+        }
+    }
+}
+
+// Windows 下 fontconfig 通常不可用，需要显式指定支持中文的字体
+pub(crate) fn font_option() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "fontfile='C\\:/Windows/Fonts/msyh.ttc':"
+    } else {
+        ""
+    }
+}
+
+// drawtext 的文字经过两层解析：滤镜图先按 `[],;` 切分并去掉一层转义，选项解析再按 `:`
+// 切分并去掉一层。单引号内的 `\` 不转义 `'`，因此不加引号，逐层用 `\` 转义；
+// 调用方需同时指定 `expansion=none`，`%` 便不会被当作 `%{...}` 展开
+pub(crate) fn escape_drawtext(text: &str) -> String {
+    let option = escape_with(text, &['\\', '\'', ':', ' ']);
+    escape_with(&option, &['\\', '\'', '[', ']', ',', ';'])
+}
+
+fn escape_with(text: &str, specials: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if specials.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按 FFmpeg av_get_token 的规则读取一个值：`\` 转义下一个字符，单引号内原样保留
+    fn unescape(value: &str, terminators: &[char]) -> String {
+        let mut out = String::new();
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => out.extend(chars.next()),
+                '\'' => out.extend(chars.by_ref().take_while(|&c| c != '\'')),
+                c if terminators.contains(&c) => break,
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn parse(escaped: &str) -> String {
+        unescape(&unescape(escaped, &['[', ']', ',', ';']), &[':'])
+    }
+
+    #[test]
+    fn special_characters_survive_both_levels() {
+        assert_eq!(escape_drawtext("it's"), "it\\\\\\'s");
+        assert_eq!(escape_drawtext("a:b"), "a\\\\:b");
+        assert_eq!(escape_drawtext("50%"), "50%");
+        assert_eq!(escape_drawtext("C:\\x"), "C\\\\:\\\\\\\\x");
+        for text in ["it's", "a:b", "50%", "C:\\x", "[a],b;c", "a b ", "'':\\'"] {
+            assert_eq!(parse(&escape_drawtext(text)), text);
+        }
+    }
+
+    #[test]
+    fn text_filter_disables_expansion() {
+        let watermark = WatermarkOptions {
+            kind: WatermarkKind::Text {
+                text: "it's 50%".to_string(),
+                font_size: 24,
+            },
+            position: WatermarkPosition::TopLeft,
+            opacity: 1.0,
+            margin: 10,
+        };
+        let filter = watermark.filter("0:v", "v", 1);
+        assert!(filter.contains("expansion=none:text=it\\\\\\'s\\\\ 50%:fontsize=24"));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::utils::app_data_dir;
+use chrono::Local;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 单个任务的临时工作目录，位于应用数据目录下，离开作用域时自动删除
+pub struct JobWorkspace {
+    dir: PathBuf,
+}
+
+impl JobWorkspace {
+    /// 为新任务创建工作目录
+    pub fn create() -> Result<Self, io::Error> {
+        let job_id = format!(
+            "{}-{}",
+            Local::now().format("%Y%m%d%H%M%S%3f"),
+            std::process::id()
+        );
+        let dir = workspaces_root()?.join(job_id);
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// 工作目录下的文件路径
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+}
+
+impl Drop for JobWorkspace {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.dir) {
+            eprintln!("清理工作目录失败 {}: {}", self.dir.display(), e);
+        }
+    }
+}
+
+/// 启动时清理上次崩溃遗留的工作目录
+pub fn clean_stale_workspaces() {
+    let Ok(root) = workspaces_root() else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&root) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir()
+            && let Err(e) = fs::remove_dir_all(&path)
+        {
+            eprintln!("清理遗留工作目录失败 {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// 合并输出的临时文件路径：`name.mp4.part`
+pub fn part_path(output_path: &Path) -> PathBuf {
+    let mut part = output_path.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+fn workspaces_root() -> Result<PathBuf, io::Error> {
+    Ok(app_data_dir()?.join("jobs"))
+}
@@ -0,0 +1,401 @@
+use crate::error::AppError;
+use crate::ffmpeg::options::MergeOptions;
+use crate::utils::{app_data_dir, display_name, format_size, sha256_file};
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+// 完成时间的保存格式，与界面语言无关
+const FINISHED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 最多保留的历史记录条数
+const HISTORY_LIMIT: usize = 200;
+
+// 多个合并可能同时结束，写文件时串行
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// 文件及其 SHA-256
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileChecksum {
+    pub path: PathBuf,
+    pub sha256: String,
+}
+
+/// 历史记录对应的任务类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum JobKind {
+    #[default]
+    Merge,
+    Compress,
+    Transcode,
+}
+
+impl JobKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobKind::Merge => "合并",
+            JobKind::Compress => "压缩",
+            JobKind::Transcode => "转码",
+        }
+    }
+}
+
+/// 压缩或转码前后的文件大小
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SizeComparison {
+    pub original: PathBuf,
+    pub output: PathBuf,
+    pub original_size: u64,
+    pub output_size: u64,
+}
+
+impl SizeComparison {
+    /// 读取输出文件的实际大小，原文件大小由调用方提供（原文件可能随后被删除）
+    pub fn new(original: PathBuf, original_size: u64, output: PathBuf) -> Result<Self, AppError> {
+        let output_size = fs::metadata(&output)
+            .map_err(|e| AppError::io("读取输出文件大小失败", e))?
+            .len();
+        Ok(Self {
+            original,
+            output,
+            original_size,
+            output_size,
+        })
+    }
+}
+
+/// 原文件与输出的总大小
+pub fn total_sizes(sizes: &[SizeComparison]) -> (u64, u64) {
+    sizes.iter().fold((0, 0), |(original, output), s| {
+        (original + s.original_size, output + s.output_size)
+    })
+}
+
+/// 描述节省的空间，如“节省 1.20 GiB（35%）”；输出更大时说明增大了多少
+pub fn describe_savings(original_size: u64, output_size: u64) -> String {
+    let percent = |diff: u64| {
+        if original_size == 0 {
+            0.0
+        } else {
+            diff as f64 / original_size as f64 * 100.0
+        }
+    };
+    if output_size <= original_size {
+        let saved = original_size - output_size;
+        format!(
+            "节省 {}（{:.0}%）",
+            format_size(Some(saved)),
+            percent(saved)
+        )
+    } else {
+        let grown = output_size - original_size;
+        format!(
+            "增大 {}（{:.0}%）",
+            format_size(Some(grown)),
+            percent(grown)
+        )
+    }
+}
+
+/// 一次合并的历史记录，保存当时使用的完整选项，重新运行时原样套用
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub finished_at: String,
+    pub files: Vec<PathBuf>,
+    pub output_path: PathBuf,
+    pub options: MergeOptions,
+    /// 失败原因，成功时为 None
+    pub error: Option<String>,
+    /// 开启校验值时记录的输出（第一项）与输入的 SHA-256
+    #[serde(default)]
+    pub checksums: Vec<FileChecksum>,
+    /// 合并后命令的输出
+    #[serde(default)]
+    pub post_command_log: Option<String>,
+    /// 旧记录没有该字段，均为合并
+    #[serde(default)]
+    pub kind: JobKind,
+    /// 压缩或转码任务中每个文件的前后大小
+    #[serde(default)]
+    pub sizes: Vec<SizeComparison>,
+    /// 输出的时长（秒），旧记录与失败的任务为 0
+    #[serde(default)]
+    pub duration_secs: f64,
+    /// 任务耗时（秒），旧记录为 0
+    #[serde(default)]
+    pub elapsed_secs: f64,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        files: Vec<PathBuf>,
+        output_path: PathBuf,
+        options: MergeOptions,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            finished_at: Local::now().format(FINISHED_AT_FORMAT).to_string(),
+            files,
+            output_path,
+            options,
+            error,
+            checksums: Vec::new(),
+            post_command_log: None,
+            kind: JobKind::Merge,
+            sizes: Vec::new(),
+            duration_secs: 0.0,
+            elapsed_secs: 0.0,
+        }
+    }
+
+    /// 压缩或转码任务的空间节省记录，`sizes` 只包含成功的文件
+    pub fn size_report(kind: JobKind, sizes: Vec<SizeComparison>) -> Self {
+        let files = sizes.iter().map(|s| s.original.clone()).collect();
+        let output_path = sizes.first().map(|s| s.output.clone()).unwrap_or_default();
+        Self {
+            kind,
+            sizes,
+            ..Self::new(files, output_path, MergeOptions::default(), None)
+        }
+    }
+
+    /// 完成时间，无法解析时为 None
+    pub fn finished_time(&self) -> Option<SystemTime> {
+        let naive = NaiveDateTime::parse_from_str(&self.finished_at, FINISHED_AT_FORMAT).ok()?;
+        Some(naive.and_local_timezone(Local).earliest()?.into())
+    }
+
+    /// 输出文件名，用作任务名称
+    pub fn name(&self) -> String {
+        let name = self
+            .output_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if self.sizes.len() > 1 {
+            format!("{} 等 {} 个文件", name, self.sizes.len())
+        } else {
+            name
+        }
+    }
+}
+
+/// 历史记录的汇总统计
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HistoryStats {
+    pub jobs: usize,
+    pub succeeded: usize,
+    /// 成功任务处理的视频文件数
+    pub videos: usize,
+    /// 成功任务的输出总时长（秒）
+    pub duration_secs: f64,
+    // 同时记录了时长和耗时的任务，用于计算平均速度
+    timed_duration: f64,
+    timed_elapsed: f64,
+}
+
+impl HistoryStats {
+    pub fn new(entries: &[HistoryEntry]) -> Self {
+        let mut stats = Self {
+            jobs: entries.len(),
+            ..Self::default()
+        };
+        for entry in entries.iter().filter(|e| e.error.is_none()) {
+            stats.succeeded += 1;
+            stats.videos += entry.files.len();
+            stats.duration_secs += entry.duration_secs;
+            if entry.duration_secs > 0.0 && entry.elapsed_secs > 0.0 {
+                stats.timed_duration += entry.duration_secs;
+                stats.timed_elapsed += entry.elapsed_secs;
+            }
+        }
+        stats
+    }
+
+    /// 平均处理速度，即输出时长与耗时之比（倍速）；没有计时数据时为 None
+    pub fn average_speed(&self) -> Option<f64> {
+        (self.timed_elapsed > 0.0).then(|| self.timed_duration / self.timed_elapsed)
+    }
+}
+
+/// 历史记录的导出格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryExportFormat {
+    Csv,
+    Json,
+}
+
+impl HistoryExportFormat {
+    pub const ALL: [HistoryExportFormat; 2] = [HistoryExportFormat::Csv, HistoryExportFormat::Json];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            HistoryExportFormat::Csv => "csv",
+            HistoryExportFormat::Json => "json",
+        }
+    }
+}
+
+/// 把历史记录导出为文本：JSON 为完整记录，CSV 每行一个任务，便于用表格软件统计
+pub fn export_history(
+    entries: &[HistoryEntry],
+    format: HistoryExportFormat,
+) -> Result<String, AppError> {
+    match format {
+        HistoryExportFormat::Json => serde_json::to_string_pretty(entries)
+            .map_err(|e| AppError::Other(format!("导出历史记录失败: {}", e))),
+        HistoryExportFormat::Csv => {
+            // 带 BOM，Excel 打开时才能正确识别中文
+            let mut csv =
+                String::from("\u{feff}完成时间,类型,状态,文件数,输出,输出时长(秒),耗时(秒),错误\n");
+            for entry in entries {
+                let fields = [
+                    entry.finished_at.clone(),
+                    entry.kind.label().to_string(),
+                    if entry.error.is_some() {
+                        "失败"
+                    } else {
+                        "成功"
+                    }
+                    .to_string(),
+                    entry.files.len().to_string(),
+                    entry.output_path.display().to_string(),
+                    format!("{:.1}", entry.duration_secs),
+                    format!("{:.1}", entry.elapsed_secs),
+                    entry.error.clone().unwrap_or_default(),
+                ];
+                let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                csv.push_str(&line.join(","));
+                csv.push('\n');
+            }
+            Ok(csv)
+        }
+    }
+}
+
+// 含逗号、引号或换行的字段用引号包起来，引号加倍
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 重新计算记录中各文件的 SHA-256：全部一致时返回文件数，否则返回各问题的说明
+pub fn verify_checksums(checksums: &[FileChecksum]) -> Result<usize, Vec<String>> {
+    let problems: Vec<String> = checksums
+        .iter()
+        .filter_map(|c| {
+            let name = display_name(&c.path);
+            match sha256_file(&c.path) {
+                Ok(sha256) if sha256 == c.sha256 => None,
+                Ok(_) => Some(format!("{} 内容已改变", name)),
+                Err(e) => Some(format!("{} 无法读取: {}", name, e)),
+            }
+        })
+        .collect();
+    if problems.is_empty() {
+        Ok(checksums.len())
+    } else {
+        Err(problems)
+    }
+}
+
+/// 计算输出与输入文件的 SHA-256，输出在前
+pub fn compute_checksums(output: &Path, inputs: &[PathBuf]) -> Result<Vec<FileChecksum>, AppError> {
+    std::iter::once(output)
+        .chain(inputs.iter().map(PathBuf::as_path))
+        .map(|path| {
+            sha256_file(path)
+                .map(|sha256| FileChecksum {
+                    path: path.to_path_buf(),
+                    sha256,
+                })
+                .map_err(|e| AppError::io(format!("无法计算 {} 的校验值", path.display()), e))
+        })
+        .collect()
+}
+
+/// 读取历史记录，最新的在前
+pub fn load_history() -> Vec<HistoryEntry> {
+    history_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 追加一条历史记录，写入失败只打印日志
+pub fn record_history(entry: HistoryEntry) {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut history = load_history();
+    history.insert(0, entry);
+    history.truncate(HISTORY_LIMIT);
+    if let Err(e) = save_history(&history) {
+        eprintln!("保存历史记录失败: {}", e);
+    }
+}
+
+/// 覆盖保存全部历史记录（用于删除/清空）
+pub fn save_history(history: &[HistoryEntry]) -> Result<(), io::Error> {
+    let path = history_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(history)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, content)
+}
+
+fn history_path() -> Result<PathBuf, io::Error> {
+    Ok(app_data_dir()?.join("history.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn savings_report_direction() {
+        assert_eq!(describe_savings(1000, 250), "节省 750 B（75%）");
+        assert_eq!(describe_savings(1000, 1500), "增大 500 B（50%）");
+        assert_eq!(describe_savings(0, 0), "节省 0 B（0%）");
+    }
+
+    #[test]
+    fn stats_and_csv_export() {
+        let mut ok = HistoryEntry::new(
+            vec![PathBuf::from("a.mp4"), PathBuf::from("b.mp4")],
+            PathBuf::from("out, final.mp4"),
+            MergeOptions::default(),
+            None,
+        );
+        ok.duration_secs = 120.0;
+        ok.elapsed_secs = 30.0;
+        let mut old = ok.clone();
+        old.elapsed_secs = 0.0;
+        let failed = HistoryEntry::new(
+            vec![PathBuf::from("c.mp4")],
+            PathBuf::from("x.mp4"),
+            MergeOptions::default(),
+            Some("失败 \"原因\"".to_string()),
+        );
+        let entries = [ok, old, failed];
+
+        let stats = HistoryStats::new(&entries);
+        assert_eq!((stats.jobs, stats.succeeded, stats.videos), (3, 2, 4));
+        assert_eq!(stats.duration_secs, 240.0);
+        assert_eq!(stats.average_speed(), Some(4.0));
+
+        let csv = export_history(&entries, HistoryExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].contains(",合并,成功,2,\"out, final.mp4\",120.0,30.0,"));
+        assert!(lines[3].ends_with(",\"失败 \"\"原因\"\"\""));
+    }
+}
@@ -0,0 +1,33 @@
+//! 合并 MP4 的核心逻辑：FFmpeg 流程、时长探测、配置、历史记录与任务队列数据，
+//! 不依赖界面框架，供桌面程序和其他前端使用
+pub mod config;
+pub mod error;
+pub mod ffmpeg;
+pub mod history;
+pub mod post_command;
+pub mod post_process;
+pub mod queue;
+pub mod shortcuts;
+pub mod sidecar;
+pub mod upload;
+pub mod utils;
+
+use error::AppError;
+use ffmpeg::phase::MergeProgress;
+
+/// 合并、压缩等长时间任务向界面报告的事件
+#[derive(Clone, Debug)]
+pub enum MergeEvent {
+    Progress(MergeProgress),
+    /// 各输入片段在总时长中的累计边界
+    Segments(Vec<f64>),
+    /// 探测时长阶段已完成的文件数（从 1 开始）、文件总数与刚完成的文件名
+    Probing {
+        index: usize,
+        total: usize,
+        name: String,
+    },
+    Status(String),
+    Error(AppError),
+    Success(String),
+}
@@ -0,0 +1,184 @@
+//! 合并成功后执行的自定义命令，例如上传脚本或 rclone 复制
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+/// 命令中可用的占位符说明
+pub const PLACEHOLDER_HELP: &str =
+    "{output} 输出文件完整路径，{dir} 所在目录，{name} 文件名（均自动加引号，勿再加引号）";
+
+/// 命令执行结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostCommandResult {
+    pub success: bool,
+    /// 实际执行的命令与 stdout/stderr 输出
+    pub log: String,
+}
+
+// 占位符与传递对应路径的环境变量
+const PLACEHOLDERS: [(&str, &str); 3] = [
+    ("{output}", "MERGE_MP4_OUTPUT"),
+    ("{dir}", "MERGE_MP4_DIR"),
+    ("{name}", "MERGE_MP4_NAME"),
+];
+
+/// 执行命令的 shell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    /// `sh -c`
+    Sh,
+    /// `cmd /V:OFF /C`
+    Cmd,
+}
+
+impl Shell {
+    /// 当前系统使用的 shell
+    pub const CURRENT: Shell = if cfg!(target_os = "windows") {
+        Shell::Cmd
+    } else {
+        Shell::Sh
+    };
+}
+
+/// 把占位符替换为带引号的环境变量引用。路径只经环境变量传入，其中的 `$`、反引号、
+/// `%` 等不会被 shell 再次解析
+pub fn expand_command(template: &str, shell: Shell) -> String {
+    PLACEHOLDERS
+        .iter()
+        .fold(template.to_string(), |command, (placeholder, var)| {
+            let reference = match shell {
+                Shell::Sh => format!("\"${}\"", var),
+                Shell::Cmd => format!("\"%{}%\"", var),
+            };
+            command.replace(placeholder, &reference)
+        })
+}
+
+/// 占位符对应的环境变量与值，路径按原样传递
+pub fn command_env(output: &Path) -> Vec<(&'static str, OsString)> {
+    let dir = output.parent().map(Path::as_os_str).unwrap_or_default();
+    let name = output.file_name().unwrap_or_default();
+    vec![
+        (PLACEHOLDERS[0].1, output.as_os_str().to_os_string()),
+        (PLACEHOLDERS[1].1, dir.to_os_string()),
+        (PLACEHOLDERS[2].1, name.to_os_string()),
+    ]
+}
+
+// 日志中显示的命令，占位符替换为实际路径，仅供阅读
+fn describe_command(template: &str, output: &Path) -> String {
+    command_env(output).into_iter().zip(PLACEHOLDERS).fold(
+        template.to_string(),
+        |command, ((_, value), (placeholder, _))| {
+            command.replace(placeholder, &format!("\"{}\"", value.to_string_lossy()))
+        },
+    )
+}
+
+/// 通过系统 shell 执行命令并收集输出
+pub async fn run_post_command(template: &str, output: &Path) -> PostCommandResult {
+    let command = expand_command(template, Shell::CURRENT);
+    // cmd 的双引号内无法转义双引号，路径含双引号时拒绝执行（Windows 文件名本身不允许双引号）
+    if Shell::CURRENT == Shell::Cmd && output.to_string_lossy().contains('"') {
+        return PostCommandResult {
+            success: false,
+            log: format!("路径中含有双引号，已拒绝执行命令: {}", output.display()),
+        };
+    }
+    #[cfg(target_os = "windows")]
+    let result = tokio::process::Command::new("cmd")
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .raw_arg("/V:OFF")
+        .raw_arg("/C")
+        .raw_arg(&command)
+        .envs(command_env(output))
+        .output()
+        .await;
+    #[cfg(not(target_os = "windows"))]
+    let result = tokio::process::Command::new("sh")
+        .args(["-c", &command])
+        .envs(command_env(output))
+        .output()
+        .await;
+
+    let mut log = format!("$ {}\n", describe_command(template, output));
+    match result {
+        Ok(out) => {
+            log.push_str(String::from_utf8_lossy(&out.stdout).trim_end());
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            if !stderr.trim().is_empty() {
+                log.push('\n');
+                log.push_str(stderr.trim_end());
+            }
+            if !out.status.success() {
+                log.push_str(&format!("\n命令退出码: {}", out.status));
+            }
+            PostCommandResult {
+                success: out.status.success(),
+                log: log.trim_end().to_string(),
+            }
+        }
+        Err(e) => {
+            log.push_str(&format!("无法执行命令: {}", e));
+            PostCommandResult {
+                success: false,
+                log,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_reference_environment() {
+        let template = "rclone copy {output} remote:{name} && ls {dir}";
+        assert_eq!(
+            expand_command(template, Shell::Sh),
+            "rclone copy \"$MERGE_MP4_OUTPUT\" remote:\"$MERGE_MP4_NAME\" && ls \"$MERGE_MP4_DIR\""
+        );
+        assert_eq!(
+            expand_command(template, Shell::Cmd),
+            "rclone copy \"%MERGE_MP4_OUTPUT%\" remote:\"%MERGE_MP4_NAME%\" && ls \"%MERGE_MP4_DIR%\""
+        );
+    }
+
+    #[test]
+    fn unusual_names_stay_out_of_the_command() {
+        for name in [
+            "a$(touch x).mp4",
+            "a`id`.mp4",
+            "a\"b.mp4",
+            "100%PATH%.mp4",
+            "my clip.mp4",
+        ] {
+            let output = Path::new("/videos").join(name);
+            let command = expand_command("echo {name}", Shell::CURRENT);
+            assert!(!command.contains(name));
+            let env = command_env(&output);
+            assert_eq!(env[0].1, output.as_os_str());
+            assert_eq!(env[1].1, OsStr::new("/videos"));
+            assert_eq!(env[2].1, OsStr::new(name));
+        }
+    }
+
+    // 实际交给 sh 执行，名称按原样输出且没有执行其中的命令
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn sh_does_not_evaluate_names() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        for name in [
+            "a$(touch x).mp4",
+            "a`touch y`.mp4",
+            "a\"b\\.mp4",
+            "100%PATH%.mp4",
+        ] {
+            let result = run_post_command("cd {dir} && printf %s {name}", &dir.join(name)).await;
+            assert!(result.success, "{}", result.log);
+            assert!(result.log.ends_with(name), "{}", result.log);
+        }
+        assert!(!dir.join("x").exists() && !dir.join("y").exists());
+    }
+}
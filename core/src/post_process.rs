@@ -0,0 +1,342 @@
+//! 合并成功后的后处理步骤。每一步实现 [`PostProcessor`]，按预设中启用的顺序依次执行，
+//! 新的收尾功能只需增加一个实现和对应的 [`PostStep`]。
+//! 执行命令与处理原文件的步骤只由本机设置生成，不随预设、项目或接口请求保存和读取
+use crate::error::AppError;
+use crate::ffmpeg::command::{BoxFuture, CommandRunner, SystemRunner};
+use crate::ffmpeg::thumbnail;
+use crate::history::HistoryEntry;
+use crate::post_command::run_post_command;
+use crate::sidecar::Provenance;
+use crate::utils::{archive_files, recycle_files, with_stem_suffix};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 一步后处理的结果
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PostOutcome {
+    /// 输出文件被移动后的新路径，之后的步骤使用新路径
+    pub moved_to: Option<PathBuf>,
+    /// 原文件被移动后的新路径，写入历史记录
+    pub sources_moved_to: Option<Vec<PathBuf>>,
+    /// 追加到历史记录中的日志
+    pub log: Option<String>,
+}
+
+/// 合并成功后对输出文件执行的一步处理
+pub trait PostProcessor: Send + Sync {
+    /// 进度提示与错误信息中显示的名称
+    fn label(&self) -> String;
+
+    /// 处理 `output`，`job` 为本次合并的记录（输入文件、选项、时长等）
+    fn run<'a>(
+        &'a self,
+        job: &'a HistoryEntry,
+        output: &'a Path,
+    ) -> BoxFuture<'a, Result<PostOutcome, AppError>>;
+}
+
+/// 预设中保存的后处理步骤
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum PostStep {
+    /// 把输出文件移到所在目录下的子文件夹
+    MoveToArchive { folder: String },
+    /// 在输出文件旁生成同名 JPEG 缩略图
+    Thumbnail,
+    /// 在输出文件旁写入媒体库使用的 NFO 信息文件，简介中列出来源
+    Nfo,
+    /// 在输出文件旁写入 JSON 来源记录
+    JsonSidecar,
+    /// 执行设置中的合并后命令，可用占位符见 `post_command::PLACEHOLDER_HELP`
+    #[serde(skip)]
+    RunCommand { command: String },
+    /// 把原文件移到各自目录下的子文件夹，由合并选项中的原文件处理生成
+    #[serde(skip)]
+    ArchiveSources { folder: String },
+    /// 把原文件移到回收站，由合并选项中的原文件处理生成
+    #[serde(skip)]
+    RecycleSources,
+}
+
+/// 读取保存的后处理步骤，跳过无法识别的步骤（包括旧版本保存的执行命令步骤）
+pub(crate) fn deserialize_saved_steps<'de, D>(deserializer: D) -> Result<Vec<PostStep>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SavedStep {
+        Known(PostStep),
+        Unknown(IgnoredAny),
+    }
+
+    let steps = Vec::<SavedStep>::deserialize(deserializer)?;
+    Ok(steps
+        .into_iter()
+        .filter_map(|step| match step {
+            SavedStep::Known(step) => Some(step),
+            SavedStep::Unknown(_) => None,
+        })
+        .collect())
+}
+
+impl PostStep {
+    /// 新增步骤时的默认设置。合并后命令与原文件处理在设置和合并选项中设置，不在此列出
+    pub fn defaults() -> [PostStep; 4] {
+        [
+            PostStep::MoveToArchive {
+                folder: "已完成".to_string(),
+            },
+            PostStep::Thumbnail,
+            PostStep::Nfo,
+            PostStep::JsonSidecar,
+        ]
+    }
+
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            PostStep::MoveToArchive { .. } => "移到归档文件夹",
+            PostStep::Thumbnail => "生成缩略图",
+            PostStep::Nfo => "写入 NFO 信息文件",
+            PostStep::JsonSidecar => "写入 JSON 来源记录",
+            PostStep::RunCommand { .. } => "执行命令",
+            PostStep::ArchiveSources { .. } => "原文件移到归档文件夹",
+            PostStep::RecycleSources => "原文件移到回收站",
+        }
+    }
+
+    /// 对应的处理器
+    pub fn processor(&self) -> Box<dyn PostProcessor> {
+        match self {
+            PostStep::MoveToArchive { folder } => Box::new(MoveToArchive {
+                folder: folder.clone(),
+            }),
+            PostStep::Thumbnail => Box::new(Thumbnail),
+            PostStep::Nfo => Box::new(Sidecar { json: false }),
+            PostStep::JsonSidecar => Box::new(Sidecar { json: true }),
+            PostStep::RunCommand { command } => Box::new(RunCommand {
+                command: command.clone(),
+            }),
+            PostStep::ArchiveSources { folder } => Box::new(ArchiveSources {
+                folder: folder.clone(),
+            }),
+            PostStep::RecycleSources => Box::new(RecycleSources),
+        }
+    }
+}
+
+/// 依次执行后处理步骤，某一步失败不影响后续步骤。
+/// 输出被移动时更新 `job.output_path`，返回需要附在成功信息后的提示
+pub async fn run_post_processors(
+    steps: &[PostStep],
+    job: &mut HistoryEntry,
+    on_status: impl Fn(String),
+) -> Vec<String> {
+    let mut notes = Vec::new();
+    for step in steps {
+        let processor = step.processor();
+        on_status(format!("后处理：{}...", processor.label()));
+        let output = job.output_path.clone();
+        match processor.run(job, &output).await {
+            Ok(outcome) => {
+                if let Some(moved) = outcome.moved_to {
+                    notes.push(format!("已移动到 {}", moved.display()));
+                    job.output_path = moved;
+                }
+                if let Some(moved) = outcome.sources_moved_to {
+                    job.files = moved;
+                }
+                if let Some(log) = outcome.log {
+                    let history = job.post_command_log.get_or_insert_with(String::new);
+                    if !history.is_empty() {
+                        history.push('\n');
+                    }
+                    history.push_str(&log);
+                }
+            }
+            Err(e) => notes.push(format!("警告：{}失败：{}", processor.label(), e)),
+        }
+    }
+    notes
+}
+
+struct MoveToArchive {
+    folder: String,
+}
+
+impl PostProcessor for MoveToArchive {
+    fn label(&self) -> String {
+        format!("移到“{}”", self.folder)
+    }
+
+    fn run<'a>(
+        &'a self,
+        _job: &'a HistoryEntry,
+        output: &'a Path,
+    ) -> BoxFuture<'a, Result<PostOutcome, AppError>> {
+        Box::pin(async move {
+            let moved = archive_files(&[output.to_path_buf()], &self.folder)?;
+            Ok(PostOutcome {
+                moved_to: moved.into_iter().next(),
+                ..Default::default()
+            })
+        })
+    }
+}
+
+struct Thumbnail;
+
+impl PostProcessor for Thumbnail {
+    fn label(&self) -> String {
+        "生成缩略图".to_string()
+    }
+
+    fn run<'a>(
+        &'a self,
+        job: &'a HistoryEntry,
+        output: &'a Path,
+    ) -> BoxFuture<'a, Result<PostOutcome, AppError>> {
+        Box::pin(async move {
+            let target = with_stem_suffix(output, ".jpg", "thumbnail");
+            let args =
+                thumbnail::command(output, &target, thumbnail::seek_position(job.duration_secs))
+                    .build();
+            let result = SystemRunner.output("ffmpeg", &args).await?;
+            if !result.exit.success {
+                let stderr = String::from_utf8_lossy(&result.stderr);
+                let last_line = stderr.lines().last().unwrap_or_default();
+                return Err(AppError::Ffmpeg(format!("生成缩略图失败: {}", last_line)));
+            }
+            Ok(PostOutcome::default())
+        })
+    }
+}
+
+// 输出文件旁的来源记录，`json` 为 false 时写 NFO
+struct Sidecar {
+    json: bool,
+}
+
+impl PostProcessor for Sidecar {
+    fn label(&self) -> String {
+        if self.json {
+            "写入 JSON 来源记录".to_string()
+        } else {
+            "写入 NFO".to_string()
+        }
+    }
+
+    fn run<'a>(
+        &'a self,
+        job: &'a HistoryEntry,
+        output: &'a Path,
+    ) -> BoxFuture<'a, Result<PostOutcome, AppError>> {
+        Box::pin(async move {
+            let provenance = Provenance::collect(job, output).await;
+            let (extension, content) = if self.json {
+                (".json", provenance.to_json())
+            } else {
+                (".nfo", provenance.to_nfo())
+            };
+            let target = with_stem_suffix(output, extension, "video");
+            std::fs::write(&target, content)
+                .map_err(|e| AppError::io(format!("写入 {} 失败", target.display()), e))?;
+            Ok(PostOutcome::default())
+        })
+    }
+}
+
+struct RunCommand {
+    command: String,
+}
+
+impl PostProcessor for RunCommand {
+    fn label(&self) -> String {
+        "执行命令".to_string()
+    }
+
+    fn run<'a>(
+        &'a self,
+        _job: &'a HistoryEntry,
+        output: &'a Path,
+    ) -> BoxFuture<'a, Result<PostOutcome, AppError>> {
+        Box::pin(async move {
+            if self.command.trim().is_empty() {
+                return Err(AppError::Other("未填写命令".to_string()));
+            }
+            let result = run_post_command(&self.command, output).await;
+            if result.success {
+                Ok(PostOutcome {
+                    log: Some(result.log),
+                    ..Default::default()
+                })
+            } else {
+                Err(AppError::Other(result.log))
+            }
+        })
+    }
+}
+
+struct ArchiveSources {
+    folder: String,
+}
+
+impl PostProcessor for ArchiveSources {
+    fn label(&self) -> String {
+        format!("原文件移到“{}”", self.folder)
+    }
+
+    fn run<'a>(
+        &'a self,
+        job: &'a HistoryEntry,
+        _output: &'a Path,
+    ) -> BoxFuture<'a, Result<PostOutcome, AppError>> {
+        Box::pin(async move {
+            let moved = archive_files(&job.files, &self.folder)?;
+            Ok(PostOutcome {
+                sources_moved_to: Some(moved),
+                ..Default::default()
+            })
+        })
+    }
+}
+
+struct RecycleSources;
+
+impl PostProcessor for RecycleSources {
+    fn label(&self) -> String {
+        "原文件移到回收站".to_string()
+    }
+
+    fn run<'a>(
+        &'a self,
+        job: &'a HistoryEntry,
+        _output: &'a Path,
+    ) -> BoxFuture<'a, Result<PostOutcome, AppError>> {
+        Box::pin(async move {
+            recycle_files(&job.files).await?;
+            Ok(PostOutcome::default())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ffmpeg::options::MergeOptions;
+    use crate::post_process::PostStep;
+    use serde_json::json;
+
+    #[test]
+    fn saved_commands_are_dropped() {
+        let options: MergeOptions = serde_json::from_value(json!({
+            "mode": "Copy",
+            "post_steps": [
+                { "RunCommand": { "command": "rm -rf ~" } },
+                "Thumbnail",
+                "RecycleSources",
+            ]
+        }))
+        .unwrap();
+        assert_eq!(options.post_steps, [PostStep::Thumbnail]);
+    }
+}
@@ -0,0 +1,65 @@
+//! 任务队列的数据类型，界面中的队列状态与执行逻辑见 GUI 的 `queue` 模块
+use crate::ffmpeg::options::MergeOptions;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 暂停后不再启动新的任务，正在执行的任务会继续完成
+pub static QUEUE_PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// 队列任务状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Done,
+    Failed(String),
+}
+
+/// 队列中的一个合并任务
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeJob {
+    pub id: u64,
+    pub name: String,
+    pub files: Vec<PathBuf>,
+    pub output_path: PathBuf,
+    pub options: MergeOptions,
+    pub status: JobStatus,
+    pub progress: f64,
+    pub status_message: String,
+    /// 因暂时性错误自动重试过的次数
+    pub retries: u32,
+}
+
+impl MergeJob {
+    pub fn new(
+        name: String,
+        files: Vec<PathBuf>,
+        output_path: PathBuf,
+        options: MergeOptions,
+    ) -> Self {
+        Self {
+            id: NEXT_JOB_ID.fetch_add(1, Ordering::SeqCst),
+            name,
+            files,
+            output_path,
+            options,
+            status: JobStatus::Pending,
+            progress: 0.0,
+            status_message: String::new(),
+            retries: 0,
+        }
+    }
+}
+
+/// 一次队列执行的结果汇总
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct QueueSummary {
+    pub done: usize,
+    pub failed: usize,
+    /// 因暂停或失败停止而未执行的任务
+    pub remaining: usize,
+    /// 按设置在任务失败后停止了队列
+    pub stopped_on_error: bool,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// 可绑定快捷键的操作
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShortcutAction {
+    AddFiles,
+    RemoveSelected,
+    StartMerge,
+    OpenSettings,
+    Rescan,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 5] = [
+        ShortcutAction::AddFiles,
+        ShortcutAction::RemoveSelected,
+        ShortcutAction::StartMerge,
+        ShortcutAction::OpenSettings,
+        ShortcutAction::Rescan,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::AddFiles => "添加文件",
+            ShortcutAction::RemoveSelected => "移除选中文件",
+            ShortcutAction::StartMerge => "开始合并",
+            ShortcutAction::OpenSettings => "打开设置",
+            ShortcutAction::Rescan => "重新扫描文件库",
+        }
+    }
+
+    pub fn default_binding(&self) -> &'static str {
+        match self {
+            ShortcutAction::AddFiles => "Ctrl+O",
+            ShortcutAction::RemoveSelected => "Delete",
+            ShortcutAction::StartMerge => "Ctrl+Enter",
+            ShortcutAction::OpenSettings => "Ctrl+,",
+            ShortcutAction::Rescan => "F5",
+        }
+    }
+
+    /// 触发时需要切换到的标签页
+    pub fn tab(&self) -> &'static str {
+        match self {
+            ShortcutAction::AddFiles
+            | ShortcutAction::RemoveSelected
+            | ShortcutAction::StartMerge => "tab1",
+            ShortcutAction::Rescan => "tab2",
+            ShortcutAction::OpenSettings => "tab4",
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! 合并输出旁的来源记录（NFO/JSON）：原文件及其时长、合并时间、所用选项与校验值
+use crate::ffmpeg::options::MergeOptions;
+use crate::ffmpeg::probe::probe_duration_cached;
+use crate::history::HistoryEntry;
+use crate::utils::format_duration;
+use serde::Serialize;
+use std::path::Path;
+
+/// 来源记录中的一个原文件
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SourceRecord {
+    pub path: String,
+    /// 原文件已被移到回收站等无法读取时为 None
+    pub duration_secs: Option<f64>,
+    /// 未开启校验值计算时为 None
+    pub sha256: Option<String>,
+}
+
+/// 一次合并的来源记录
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Provenance {
+    pub output: String,
+    pub output_sha256: Option<String>,
+    pub merged_at: String,
+    pub elapsed_secs: f64,
+    pub duration_secs: f64,
+    pub sources: Vec<SourceRecord>,
+    pub options: MergeOptions,
+}
+
+impl Provenance {
+    /// 汇总合并记录，`output` 为输出文件当前的位置。原文件时长优先使用探测缓存
+    pub async fn collect(job: &HistoryEntry, output: &Path) -> Self {
+        // 校验值按 `compute_checksums` 的顺序：第一个是输出，之后是各原文件
+        let output_sha256 = job.checksums.first().map(|c| c.sha256.clone());
+        let mut sources = Vec::with_capacity(job.files.len());
+        for file in &job.files {
+            sources.push(SourceRecord {
+                path: file.to_string_lossy().into_owned(),
+                duration_secs: probe_duration_cached(file).await.ok(),
+                sha256: job
+                    .checksums
+                    .iter()
+                    .skip(1)
+                    .find(|c| c.path == *file)
+                    .map(|c| c.sha256.clone()),
+            });
+        }
+        Self {
+            output: output.to_string_lossy().into_owned(),
+            output_sha256,
+            merged_at: job.finished_at.clone(),
+            elapsed_secs: job.elapsed_secs,
+            duration_secs: job.duration_secs,
+            sources,
+            options: job.options.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Kodi 等媒体库识别的 `<movie>` 格式：片名取文件名，片长按分钟取整，来源写在简介中
+    pub fn to_nfo(&self) -> String {
+        let title = Path::new(&self.output)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut plot = format!("由 {} 个文件合并：\n", self.sources.len());
+        for (index, source) in self.sources.iter().enumerate() {
+            let duration = source
+                .duration_secs
+                .map(format_duration)
+                .unwrap_or_else(|| "时长未知".to_string());
+            plot.push_str(&format!("{}. {}（{}）\n", index + 1, source.path, duration));
+            if let Some(sha256) = &source.sha256 {
+                plot.push_str(&format!("   SHA-256: {}\n", sha256));
+            }
+        }
+        for (name, value) in self.options.summary() {
+            plot.push_str(&format!("{}：{}\n", name, value));
+        }
+        if let Some(sha256) = &self.output_sha256 {
+            plot.push_str(&format!("输出 SHA-256: {}\n", sha256));
+        }
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<movie>\n  <title>{}</title>\n  <runtime>{}</runtime>\n  <dateadded>{}</dateadded>\n  <plot>{}</plot>\n</movie>\n",
+            escape_xml(&title),
+            (self.duration_secs / 60.0).round() as u64,
+            escape_xml(&self.merged_at),
+            escape_xml(plot.trim_end())
+        )
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Provenance {
+        Provenance {
+            output: "/videos/Tom & Jerry <1>.mp4".to_string(),
+            output_sha256: Some("ff00".to_string()),
+            merged_at: "2026-10-18 12:00:00".to_string(),
+            elapsed_secs: 12.5,
+            duration_secs: 1500.0,
+            sources: vec![
+                SourceRecord {
+                    path: "/videos/a.mp4".to_string(),
+                    duration_secs: Some(900.0),
+                    sha256: Some("aa11".to_string()),
+                },
+                SourceRecord {
+                    path: "/videos/b.mp4".to_string(),
+                    duration_secs: None,
+                    sha256: None,
+                },
+            ],
+            options: MergeOptions::default(),
+        }
+    }
+
+    #[test]
+    fn nfo_lists_sources_and_escapes_title() {
+        let nfo = sample().to_nfo();
+        assert!(nfo.contains("<title>Tom &amp; Jerry &lt;1&gt;</title>"));
+        assert!(nfo.contains("<runtime>25</runtime>"));
+        assert!(nfo.contains("1. /videos/a.mp4（15:00）"));
+        assert!(nfo.contains("SHA-256: aa11"));
+        assert!(nfo.contains("2. /videos/b.mp4（时长未知）"));
+    }
+
+    #[test]
+    fn json_round_trips_fields() {
+        let json: serde_json::Value = serde_json::from_str(&sample().to_json()).unwrap();
+        assert_eq!(json["sources"][0]["duration_secs"], 900.0);
+        assert_eq!(json["sources"][1]["sha256"], serde_json::Value::Null);
+        assert_eq!(json["output_sha256"], "ff00");
+        assert_eq!(json["options"]["mode"], "Copy");
+    }
+}
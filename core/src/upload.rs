@@ -0,0 +1,287 @@
+//! 合并成功后把输出文件上传到 WebDAV 或 S3 兼容存储，报告进度并按重试策略自动重试
+use crate::config::{RetryPolicy, UploadKind, UploadSettings};
+use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use reqwest::header::{AUTHORIZATION, CONTENT_LENGTH};
+use reqwest::{Body, Client, RequestBuilder, Url};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio_util::io::ReaderStream;
+
+/// 上传过程中报告给界面的事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadEvent {
+    /// 已发送的字节数与文件大小
+    Progress { sent: u64, total: u64 },
+    /// 上传失败，等待 `delay_secs` 秒后进行第 `attempt` 次重试
+    Retrying {
+        attempt: u32,
+        delay_secs: u64,
+        error: String,
+    },
+}
+
+/// 未填写区域时 S3 使用的默认区域
+const DEFAULT_REGION: &str = "us-east-1";
+/// 错误信息中保留的服务器返回内容长度
+const ERROR_BODY_CHARS: usize = 200;
+/// 每次读取并发送的字节数，也是进度事件的间隔
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// 上传 `path`，成功时返回文件的访问链接。网络错误、服务器 5xx 与 429 按 `retry` 重试，
+/// 其余错误（地址或账号有误等）直接返回
+pub async fn upload_file(
+    settings: &UploadSettings,
+    retry: &RetryPolicy,
+    path: &Path,
+    on_event: impl Fn(UploadEvent) + Send + Sync + 'static,
+) -> Result<String, AppError> {
+    let on_event: Arc<dyn Fn(UploadEvent) + Send + Sync> = Arc::new(on_event);
+    let client = Client::builder()
+        .connect_timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| AppError::Other(format!("初始化上传失败: {}", e)))?;
+    let mut attempt = 0;
+    loop {
+        match upload_once(&client, settings, path, on_event.clone()).await {
+            Ok(link) => return Ok(link),
+            Err(failure) if failure.retryable && attempt < retry.max_retries => {
+                attempt += 1;
+                let delay = retry.delay(attempt);
+                on_event(UploadEvent::Retrying {
+                    attempt,
+                    delay_secs: delay.as_secs(),
+                    error: failure.error.to_string(),
+                });
+                tokio::time::sleep(delay).await;
+            }
+            Err(failure) => return Err(failure.error),
+        }
+    }
+}
+
+// 一次上传失败的原因，`retryable` 表示值得稍后重试
+struct Failure {
+    error: AppError,
+    retryable: bool,
+}
+
+impl Failure {
+    fn fatal(error: AppError) -> Self {
+        Self {
+            error,
+            retryable: false,
+        }
+    }
+}
+
+async fn upload_once(
+    client: &Client,
+    settings: &UploadSettings,
+    path: &Path,
+    on_event: Arc<dyn Fn(UploadEvent) + Send + Sync>,
+) -> Result<String, Failure> {
+    let name = path
+        .file_name()
+        .map(|n| encode_segment(&n.to_string_lossy()))
+        .ok_or_else(|| Failure::fatal(AppError::Other("输出文件名无效".to_string())))?;
+    let base = settings.url.trim().trim_end_matches('/');
+    if base.is_empty() {
+        return Err(Failure::fatal(AppError::Other(
+            "未填写上传地址，请在设置中配置".to_string(),
+        )));
+    }
+
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| Failure::fatal(AppError::io(format!("读取 {} 失败", path.display()), e)))?;
+    let total = file
+        .metadata()
+        .await
+        .map_err(|e| Failure::fatal(AppError::io(format!("读取 {} 失败", path.display()), e)))?
+        .len();
+    let sent = Arc::new(AtomicU64::new(0));
+    let progress = on_event.clone();
+    let stream = ReaderStream::with_capacity(file, CHUNK_SIZE).inspect(move |chunk| {
+        if let Ok(bytes) = chunk {
+            let len = bytes.len() as u64;
+            let sent = sent.fetch_add(len, Ordering::Relaxed) + len;
+            progress(UploadEvent::Progress { sent, total });
+        }
+    });
+
+    let (request, link) = match settings.kind {
+        UploadKind::WebDav => {
+            let url = format!("{}/{}", base, name);
+            let mut request = client.put(&url);
+            if !settings.username.is_empty() {
+                request = request.basic_auth(&settings.username, Some(&settings.password));
+            }
+            (request, url)
+        }
+        UploadKind::S3 => {
+            let bucket = settings.bucket.trim();
+            if bucket.is_empty() {
+                return Err(Failure::fatal(AppError::Other(
+                    "未填写存储桶，请在设置中配置".to_string(),
+                )));
+            }
+            let url = format!("{}/{}/{}", base, encode_segment(bucket), name);
+            let request = sign_s3_put(client.put(&url), settings, &url, Utc::now())
+                .map_err(Failure::fatal)?;
+            (request, url)
+        }
+    };
+
+    on_event(UploadEvent::Progress { sent: 0, total });
+    let response = request
+        .header(CONTENT_LENGTH, total)
+        .body(Body::wrap_stream(stream))
+        .send()
+        .await
+        .map_err(|e| Failure {
+            error: AppError::Other(format!("上传失败: {}", e)),
+            retryable: true,
+        })?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let body: String = body.trim().chars().take(ERROR_BODY_CHARS).collect();
+        let hint = match status.as_u16() {
+            401 | 403 => "，请检查账号与密码",
+            404 => "，请检查上传地址或存储桶是否存在",
+            _ => "",
+        };
+        return Err(Failure {
+            error: AppError::Other(format!("上传失败: 服务器返回 {}{} {}", status, hint, body)),
+            retryable: status.is_server_error() || status.as_u16() == 429,
+        });
+    }
+
+    let public = settings.public_url.trim().trim_end_matches('/');
+    Ok(if public.is_empty() {
+        link
+    } else {
+        format!("{}/{}", public, name)
+    })
+}
+
+// 按 AWS Signature V4 为路径形式的 PUT 请求签名，正文不参与签名
+fn sign_s3_put(
+    request: RequestBuilder,
+    settings: &UploadSettings,
+    url: &str,
+    now: DateTime<Utc>,
+) -> Result<RequestBuilder, AppError> {
+    let parsed = Url::parse(url).map_err(|e| AppError::Other(format!("上传地址无效: {}", e)))?;
+    let host = match (parsed.host_str(), parsed.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return Err(AppError::Other(format!("上传地址无效: {}", url))),
+    };
+    let region = Some(settings.region.trim())
+        .filter(|r| !r.is_empty())
+        .unwrap_or(DEFAULT_REGION);
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload = "UNSIGNED-PAYLOAD";
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        parsed.path(),
+        host,
+        payload,
+        amz_date,
+        signed_headers,
+        payload
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+    let key = signing_key(&settings.password, &date, region, "s3");
+    let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+    Ok(request
+        .header("x-amz-content-sha256", payload)
+        .header("x-amz-date", amz_date)
+        .header(
+            AUTHORIZATION,
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                settings.username, scope, signed_headers, signature
+            ),
+        ))
+}
+
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+    let key = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(data);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// 对路径中的一段做百分号编码，只保留 RFC 3986 的非保留字符（与 S3 签名要求一致）
+fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_key_matches_aws_example() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn segments_are_percent_encoded() {
+        assert_eq!(encode_segment("a b+c.mp4"), "a%20b%2Bc.mp4");
+        assert_eq!(encode_segment("合并.mp4"), "%E5%90%88%E5%B9%B6.mp4");
+    }
+}
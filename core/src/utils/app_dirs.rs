@@ -0,0 +1,40 @@
+use std::io;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const APP_DIR_NAME: &str = "merge-mp4";
+
+static PORTABLE_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// 便携模式：可执行文件旁存在 `portable.flag` 或 `config.json` 时，
+/// 所有配置与数据都保存在可执行文件所在目录，返回该目录
+pub fn portable_dir() -> Option<PathBuf> {
+    PORTABLE_DIR
+        .get_or_init(|| {
+            let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+            (exe_dir.join("portable.flag").exists() || exe_dir.join("config.json").exists())
+                .then_some(exe_dir)
+        })
+        .clone()
+}
+
+/// 配置文件所在目录
+pub fn app_config_dir() -> Result<PathBuf, io::Error> {
+    if let Some(dir) = portable_dir() {
+        return Ok(dir);
+    }
+    let config_dir = dirs::config_dir().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "Could not find config directory")
+    })?;
+    Ok(config_dir.join(APP_DIR_NAME))
+}
+
+/// 缓存、任务工作目录和崩溃报告所在目录
+pub fn app_data_dir() -> Result<PathBuf, io::Error> {
+    if let Some(dir) = portable_dir() {
+        return Ok(dir.join("data"));
+    }
+    let data_dir = dirs::data_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Could not find data directory"))?;
+    Ok(data_dir.join(APP_DIR_NAME))
+}
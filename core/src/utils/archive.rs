@@ -0,0 +1,127 @@
+#[cfg(any(windows, test))]
+use super::powershell;
+use crate::error::AppError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 把 `PATH_VAR` 指向的文件移到回收站
+#[cfg(any(windows, test))]
+const RECYCLE_SCRIPT: &str = "Add-Type -AssemblyName Microsoft.VisualBasic; [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile($env:MERGE_MP4_PATH, 'OnlyErrorDialogs', 'SendToRecycleBin')";
+
+/// 把文件移到各自所在目录下的 `folder` 子文件夹，重名时追加序号，返回移动后的路径
+pub fn archive_files(files: &[PathBuf], folder: &str) -> Result<Vec<PathBuf>, AppError> {
+    let mut moved = Vec::with_capacity(files.len());
+    for file in files {
+        let parent = file.parent().unwrap_or(Path::new(""));
+        let dir = parent.join(folder);
+        fs::create_dir_all(&dir).map_err(|e| AppError::io("无法创建归档文件夹", e))?;
+        let target = unique_path(&dir, file);
+        fs::rename(file, &target)
+            .map_err(|e| AppError::io(format!("无法归档 {}", file.display()), e))?;
+        moved.push(target);
+    }
+    Ok(moved)
+}
+
+/// 把文件移到系统回收站
+pub async fn recycle_files(files: &[PathBuf]) -> Result<(), AppError> {
+    for file in files {
+        // 路径经环境变量或单独的参数原样传入，不经过脚本文本
+        #[cfg(target_os = "windows")]
+        let output = tokio::process::Command::new("powershell")
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .args(powershell::args(RECYCLE_SCRIPT))
+            .env(powershell::PATH_VAR, file)
+            .output()
+            .await;
+        #[cfg(target_os = "macos")]
+        let output = tokio::process::Command::new("osascript")
+            .args([
+                "-e",
+                "on run argv\ntell application \"Finder\" to delete (POSIX file (item 1 of argv) as alias)\nend run",
+            ])
+            .arg(file)
+            .output()
+            .await;
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let output = tokio::process::Command::new("gio")
+            .arg("trash")
+            .arg(file)
+            .output()
+            .await;
+
+        match output {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => {
+                return Err(AppError::Other(format!(
+                    "无法移到回收站 {}: {}",
+                    file.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+            Err(e) => {
+                return Err(AppError::io(
+                    format!("无法移到回收站 {}", file.display()),
+                    e,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 目标文件夹中已有同名文件时改为 "name (2).mp4" 形式
+fn unique_path(dir: &Path, file: &Path) -> PathBuf {
+    let name = file.file_name().unwrap_or_default();
+    let target = dir.join(name);
+    if !target.exists() {
+        return target;
+    }
+    let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = file
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|i| dir.join(format!("{} ({}){}", stem, i, ext)))
+        .find(|p| !p.exists())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archived_files_do_not_overwrite_existing() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join("已合并")).unwrap();
+        fs::write(dir.join("已合并").join("a.mp4"), b"old").unwrap();
+        fs::write(dir.join("a.mp4"), b"new").unwrap();
+        fs::write(dir.join("b.mp4"), b"").unwrap();
+
+        let moved = archive_files(&[dir.join("a.mp4"), dir.join("b.mp4")], "已合并").unwrap();
+        assert_eq!(
+            moved,
+            vec![
+                dir.join("已合并").join("a (2).mp4"),
+                dir.join("已合并").join("b.mp4")
+            ]
+        );
+        assert!(!dir.join("a.mp4").exists());
+        assert_eq!(fs::read(dir.join("已合并").join("a.mp4")).unwrap(), b"old");
+    }
+
+    #[test]
+    fn recycle_argv_carries_no_path() {
+        let [profile, flag, encoded] = powershell::args(RECYCLE_SCRIPT);
+        assert_eq!(
+            (profile.as_str(), flag.as_str()),
+            ("-NoProfile", "-EncodedCommand")
+        );
+        let script = powershell::decode(&encoded);
+        assert!(script.contains(&format!("DeleteFile($env:{},", powershell::PATH_VAR)));
+        assert!(!script.contains("$args"));
+    }
+}
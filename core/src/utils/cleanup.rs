@@ -0,0 +1,198 @@
+//! 扫描结果的清理建议：空文件、误录的极短片段、无法解析的文件、重复文件与过期片段
+use crate::config::CleanupRules;
+use crate::utils::{Mp4FileInfo, natural_cmp_path};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 建议删除的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum CleanupReason {
+    /// 0 字节
+    Empty,
+    /// 时长短于规则下限（整秒）
+    TooShort(u32),
+    /// 扫描时无法解析，附带错误信息
+    Unreadable(String),
+    /// 与另一个文件内容完全相同，保留较早的那个
+    Duplicate { original: PathBuf },
+    /// 修改时间早于规则天数
+    Stale { days: u64 },
+}
+
+impl CleanupReason {
+    pub fn label(&self) -> String {
+        match self {
+            CleanupReason::Empty => "空文件".to_string(),
+            CleanupReason::TooShort(0) => "时长不足 1 秒".to_string(),
+            CleanupReason::TooShort(secs) => format!("时长仅 {} 秒", secs),
+            CleanupReason::Unreadable(e) => format!("无法解析: {}", e),
+            CleanupReason::Duplicate { original } => format!(
+                "与 {} 重复",
+                original.file_name().unwrap_or_default().to_string_lossy()
+            ),
+            CleanupReason::Stale { days } => format!("{} 天前的片段", days),
+        }
+    }
+}
+
+/// 一条清理建议
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanupCandidate {
+    pub path: PathBuf,
+    pub size: u64,
+    pub reason: CleanupReason,
+}
+
+/// 分析扫描结果并给出清理建议，每个文件只按最先命中的原因列出一次。
+/// `unreadable` 为扫描时解析失败的文件及错误；`hash` 计算文件内容摘要，
+/// 只对大小相同的文件调用，大文件较慢，应在阻塞线程中执行
+pub fn cleanup_candidates(
+    files: &[Mp4FileInfo],
+    unreadable: &[(PathBuf, String)],
+    rules: &CleanupRules,
+    now: SystemTime,
+    hash: impl Fn(&Path) -> Option<String>,
+) -> Vec<CleanupCandidate> {
+    let mut candidates: Vec<CleanupCandidate> = unreadable
+        .iter()
+        .map(|(path, error)| {
+            let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            CleanupCandidate {
+                path: path.clone(),
+                size,
+                reason: if size == 0 {
+                    CleanupReason::Empty
+                } else {
+                    CleanupReason::Unreadable(error.clone())
+                },
+            }
+        })
+        .collect();
+
+    let duplicates = find_duplicates(files, hash);
+    for info in files {
+        let age_days = info
+            .modified
+            .and_then(|m| now.duration_since(m).ok())
+            .map(|d| d.as_secs() / 86_400);
+        let reason = if info.size == 0 {
+            CleanupReason::Empty
+        } else if info.duration.as_secs_f64() < rules.min_duration_secs {
+            CleanupReason::TooShort(info.duration.as_secs() as u32)
+        } else if let Some(original) = duplicates.get(&info.file_path) {
+            CleanupReason::Duplicate {
+                original: original.clone(),
+            }
+        } else if let Some(days) = age_days
+            && rules.stale_days > 0
+            && days >= rules.stale_days as u64
+            && rules.stale_matches(&info.file_name)
+        {
+            CleanupReason::Stale { days }
+        } else {
+            continue;
+        };
+        candidates.push(CleanupCandidate {
+            path: info.file_path.clone(),
+            size: info.size,
+            reason,
+        });
+    }
+    candidates
+}
+
+// 重复文件 -> 保留的原文件。先按大小分组，只有大小相同时才计算摘要
+fn find_duplicates(
+    files: &[Mp4FileInfo],
+    hash: impl Fn(&Path) -> Option<String>,
+) -> HashMap<PathBuf, PathBuf> {
+    let mut by_size: HashMap<u64, Vec<&Mp4FileInfo>> = HashMap::new();
+    for info in files.iter().filter(|f| f.size > 0) {
+        by_size.entry(info.size).or_default().push(info);
+    }
+
+    let mut duplicates = HashMap::new();
+    for mut group in by_size.into_values().filter(|g| g.len() > 1) {
+        // 最早的文件视为原件
+        group.sort_by(|a, b| {
+            a.modified
+                .cmp(&b.modified)
+                .then_with(|| natural_cmp_path(&a.file_path, &b.file_path))
+        });
+        let mut seen: HashMap<String, PathBuf> = HashMap::new();
+        for info in group {
+            let Some(digest) = hash(&info.file_path) else {
+                continue;
+            };
+            match seen.get(&digest) {
+                Some(original) => {
+                    duplicates.insert(info.file_path.clone(), original.clone());
+                }
+                None => {
+                    seen.insert(digest, info.file_path.clone());
+                }
+            }
+        }
+    }
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn file(name: &str, size: u64, secs: f64, age_days: u64, now: SystemTime) -> Mp4FileInfo {
+        Mp4FileInfo {
+            file_name: name.to_string(),
+            size,
+            modified: Some(now - Duration::from_secs(age_days * 86_400)),
+            width: 1920,
+            height: 1080,
+            codec: "H.264".to_string(),
+            duration: Duration::from_secs_f64(secs),
+            file_path: PathBuf::from(name),
+        }
+    }
+
+    #[test]
+    fn suggests_each_file_once() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100 * 86_400);
+        let files = [
+            file("empty.mp4", 0, 0.0, 1, now),
+            file("short.mp4", 10, 0.6, 1, now),
+            file("a.mp4", 100, 60.0, 5, now),
+            file("a copy.mp4", 100, 60.0, 2, now),
+            file("b.mp4", 100, 60.0, 1, now),
+            file("old_rec.mp4", 200, 60.0, 40, now),
+            file("old_keep.mp4", 300, 60.0, 40, now),
+        ];
+        let rules = CleanupRules {
+            stale_days: 30,
+            stale_pattern: "*rec*".to_string(),
+            ..Default::default()
+        };
+        // a 与 a copy 内容相同，b 大小相同但内容不同
+        let hash = |p: &Path| Some(if p == Path::new("b.mp4") { "2" } else { "1" }.to_string());
+        let candidates = cleanup_candidates(&files, &[], &rules, now, hash);
+        let reasons: Vec<(&str, &CleanupReason)> = candidates
+            .iter()
+            .map(|c| (c.path.to_str().unwrap(), &c.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                ("empty.mp4", &CleanupReason::Empty),
+                ("short.mp4", &CleanupReason::TooShort(0)),
+                (
+                    "a copy.mp4",
+                    &CleanupReason::Duplicate {
+                        original: PathBuf::from("a.mp4")
+                    }
+                ),
+                ("old_rec.mp4", &CleanupReason::Stale { days: 40 }),
+            ]
+        );
+    }
+}
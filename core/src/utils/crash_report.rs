@@ -0,0 +1,126 @@
+use super::app_dirs::app_data_dir;
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// 崩溃报告中保留的最近日志行数
+const LOG_CAPACITY: usize = 200;
+
+static RECENT_LOG: OnceLock<Mutex<VecDeque<String>>> = OnceLock::new();
+
+/// 记录一行日志，崩溃时写入报告
+pub fn record_log(line: &str) {
+    let log = RECENT_LOG.get_or_init(Default::default);
+    // 崩溃时锁可能已被污染，仍然继续记录
+    let mut log = log.lock().unwrap_or_else(|e| e.into_inner());
+    if log.len() == LOG_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(format!("[{}] {}", Local::now().format("%H:%M:%S"), line));
+}
+
+/// 把最近的日志写入数据目录下的 `recent.log`，返回文件路径，用于“查看日志”
+pub fn write_recent_log() -> Result<PathBuf, io::Error> {
+    let dir = app_data_dir()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("recent.log");
+    fs::write(&path, recent_log())?;
+    Ok(path)
+}
+
+/// 安装 panic 钩子：写入崩溃报告并标记，下次启动时提示用户
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_crash_report(&info.to_string()) {
+            Ok(path) => eprintln!("崩溃报告已保存到 {}", path.display()),
+            Err(e) => eprintln!("写入崩溃报告失败: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+/// 取出上次运行留下的崩溃报告路径（只返回一次）
+pub fn take_pending_crash_report() -> Option<PathBuf> {
+    let marker = crashes_dir().ok()?.join("pending");
+    let path = PathBuf::from(fs::read_to_string(&marker).ok()?.trim());
+    let _ = fs::remove_file(&marker);
+    path.exists().then_some(path)
+}
+
+/// 用系统默认程序打开文件
+pub fn open_path(path: &Path) -> Result<(), io::Error> {
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let program = "xdg-open";
+    std::process::Command::new(program).arg(path).spawn()?;
+    Ok(())
+}
+
+/// 在文件管理器中显示文件并选中它；Linux 上没有统一的选中方式，只打开所在目录
+pub fn reveal_path(path: &Path) -> Result<(), io::Error> {
+    #[cfg(target_os = "windows")]
+    {
+        let mut select = std::ffi::OsString::from("/select,");
+        select.push(path);
+        std::process::Command::new("explorer").arg(select).spawn()?;
+    }
+    #[cfg(target_os = "macos")]
+    std::process::Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .spawn()?;
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    open_path(path.parent().unwrap_or(path))?;
+    Ok(())
+}
+
+fn write_crash_report(panic_message: &str) -> Result<PathBuf, io::Error> {
+    let dir = crashes_dir()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "crash-{}.txt",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    let report = format!(
+        "mp4文件合并 崩溃报告\n\
+         时间: {}\n\
+         版本: {}\n\
+         系统: {} {}\n\
+         \n{}\n\
+         \n== 调用栈 ==\n{}\n\
+         \n== 最近日志 ==\n{}\n",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        panic_message,
+        Backtrace::force_capture(),
+        recent_log()
+    );
+    fs::write(&path, report)?;
+    fs::write(dir.join("pending"), path.to_string_lossy().as_bytes())?;
+    Ok(path)
+}
+
+fn recent_log() -> String {
+    RECENT_LOG
+        .get()
+        .map(|log| {
+            let log = log.lock().unwrap_or_else(|e| e.into_inner());
+            log.iter().cloned().collect::<Vec<_>>().join("\n")
+        })
+        .unwrap_or_default()
+}
+
+fn crashes_dir() -> Result<PathBuf, io::Error> {
+    Ok(app_data_dir()?.join("crashes"))
+}
@@ -0,0 +1,11 @@
+use crate::error::AppError;
+use std::path::Path;
+
+/// 目录所在磁盘的可用空间（字节）。目录尚未创建时查询最近一个已存在的上级目录
+pub fn free_space(dir: &Path) -> Result<u64, AppError> {
+    let existing = dir
+        .ancestors()
+        .find(|p| p.is_dir())
+        .ok_or_else(|| AppError::FileNotFound(dir.to_path_buf()))?;
+    fs4::available_space(existing).map_err(|e| AppError::io("无法读取磁盘可用空间", e))
+}
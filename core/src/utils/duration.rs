@@ -0,0 +1,86 @@
+use crate::config::Language;
+use chrono::{DateTime, Local};
+use std::time::SystemTime;
+
+/// 将秒数格式化为时间字符串
+pub fn format_duration(seconds: f64) -> String {
+    let total_seconds = seconds.round() as u32;
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+/// 按界面语言格式化完整时间
+pub fn format_date(modified: Option<SystemTime>, language: Language) -> String {
+    match modified {
+        Some(time) => {
+            let datetime: DateTime<Local> = time.into();
+            datetime.format(language.datetime_format()).to_string()
+        }
+        _ => "未知".to_string(),
+    }
+}
+
+/// 相对现在的时间，如“刚刚”“5分钟前”“3天前”，超过 30 天或在未来时显示日期；
+/// 完整时间用 [`format_date`] 放在悬停提示中
+pub fn format_relative_date(modified: Option<SystemTime>, language: Language) -> String {
+    match modified {
+        Some(time) => describe_relative(time.into(), Local::now(), language),
+        _ => "未知".to_string(),
+    }
+}
+
+fn describe_relative(time: DateTime<Local>, now: DateTime<Local>, language: Language) -> String {
+    let secs = (now - time).num_seconds();
+    match language {
+        Language::ZhCn => match secs {
+            0..60 => "刚刚".to_string(),
+            60..3600 => format!("{}分钟前", secs / 60),
+            3600..86400 => format!("{}小时前", secs / 3600),
+            86400..2592000 => format!("{}天前", secs / 86400),
+            _ => time.format(language.date_format()).to_string(),
+        },
+    }
+}
+
+/// 解析用户输入的时间点，支持 `90`、`1:30`、`00:01:30.5` 等格式，返回秒数
+pub fn parse_timestamp(input: &str) -> Option<f64> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    let mut seconds = 0.0;
+    for part in input.split(':') {
+        let value: f64 = part.trim().parse().ok()?;
+        if value < 0.0 {
+            return None;
+        }
+        seconds = seconds * 60.0 + value;
+    }
+    Some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn relative_dates_fall_back_to_the_date() {
+        let now = Local.with_ymd_and_hms(2024, 5, 20, 12, 0, 0).unwrap();
+        let ago = |secs: i64| {
+            describe_relative(now - chrono::TimeDelta::seconds(secs), now, Language::ZhCn)
+        };
+        assert_eq!(ago(30), "刚刚");
+        assert_eq!(ago(5 * 60), "5分钟前");
+        assert_eq!(ago(3 * 86400 + 100), "3天前");
+        assert_eq!(ago(60 * 86400), "2024-03-21");
+        assert_eq!(ago(-3600), "2024-05-20");
+    }
+}
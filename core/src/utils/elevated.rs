@@ -0,0 +1,178 @@
+//! 没有权限时以管理员身份重做单个操作：Windows 弹出 UAC，macOS 与 Linux 请求管理员密码
+#[cfg(any(windows, test))]
+use super::powershell;
+use crate::error::AppError;
+use std::path::PathBuf;
+
+// 以管理员身份启动 `PATH_VAR` 指向的程序
+#[cfg(any(windows, test))]
+const RELAUNCH_SCRIPT: &str = "Start-Process -FilePath $env:MERGE_MP4_PATH -Verb RunAs";
+
+/// 以管理员身份删除文件，返回实际删除成功的文件。用户拒绝授权时返回错误
+pub async fn delete_files_elevated(files: &[PathBuf]) -> Result<Vec<PathBuf>, AppError> {
+    // 路径以字面量写入提权后执行的脚本，不经过其他进程可以改写的临时文件
+    #[cfg(target_os = "windows")]
+    let output = {
+        use std::os::windows::ffi::OsStrExt;
+
+        let paths: Vec<Vec<u16>> = files
+            .iter()
+            .map(|f| f.as_os_str().encode_wide().collect())
+            .collect();
+        let mut output = None;
+        for script in delete_scripts(&paths) {
+            let result = tokio::process::Command::new("powershell")
+                .creation_flags(0x08000000) // CREATE_NO_WINDOW
+                .args(powershell::args(ELEVATE_SCRIPT))
+                .env(SCRIPT_VAR, script)
+                .output()
+                .await;
+            let failed = !matches!(&result, Ok(o) if o.status.success());
+            output = Some(result);
+            if failed {
+                break;
+            }
+        }
+        match output {
+            Some(output) => output,
+            None => return Ok(Vec::new()),
+        }
+    };
+    #[cfg(target_os = "macos")]
+    let output = tokio::process::Command::new("osascript")
+        .args([
+            "-e",
+            "on run argv\nset cmd to \"rm -f --\"\nrepeat with p in argv\nset cmd to cmd & \" \" & quoted form of p\nend repeat\ndo shell script cmd with administrator privileges\nend run",
+        ])
+        .args(files)
+        .output()
+        .await;
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let output = tokio::process::Command::new("pkexec")
+        .args(["rm", "-f", "--"])
+        .args(files)
+        .output()
+        .await;
+
+    match output {
+        Ok(output) if output.status.success() => {
+            Ok(files.iter().filter(|f| !f.exists()).cloned().collect())
+        }
+        Ok(output) => Err(AppError::Other(format!(
+            "未能以管理员身份删除: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+        Err(e) => Err(AppError::io("无法请求管理员权限", e)),
+    }
+}
+
+/// 以管理员身份启动一个新的程序实例，仅 Windows 支持
+pub async fn relaunch_elevated() -> Result<(), AppError> {
+    #[cfg(target_os = "windows")]
+    {
+        let exe = std::env::current_exe().map_err(|e| AppError::io("无法获取程序路径", e))?;
+        let output = tokio::process::Command::new("powershell")
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .args(powershell::args(RELAUNCH_SCRIPT))
+            .env(powershell::PATH_VAR, &exe)
+            .output()
+            .await
+            .map_err(|e| AppError::io("无法请求管理员权限", e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(AppError::Other(format!(
+                "未能以管理员身份启动: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err(AppError::Other(
+            "当前系统不支持以管理员身份重新启动，请更换输出目录".to_string(),
+        ))
+    }
+}
+
+// 传递提权后执行的脚本（Base64）的环境变量
+#[cfg(any(windows, test))]
+const SCRIPT_VAR: &str = "MERGE_MP4_SCRIPT";
+
+// 经 UAC 启动 `SCRIPT_VAR` 中的脚本。提权后的进程不继承环境变量，脚本作为启动参数传入
+#[cfg(any(windows, test))]
+const ELEVATE_SCRIPT: &str = "Start-Process powershell -Verb RunAs -Wait -WindowStyle Hidden -ArgumentList ('-NoProfile -EncodedCommand ' + $env:MERGE_MP4_SCRIPT)";
+
+// 单个脚本的最大长度（UTF-16 代码单元）。编码后约为 2.7 倍，需低于命令行的 32767 字符上限
+#[cfg(any(windows, test))]
+const MAX_SCRIPT_UNITS: usize = 10_000;
+
+// 删除 `paths` 的脚本（已编码），路径较多时分成几批，每批需要一次授权
+#[cfg(any(windows, test))]
+fn delete_scripts(paths: &[Vec<u16>]) -> Vec<String> {
+    const PREFIX: &str = "foreach ($p in @(";
+    const SUFFIX: &str = ")) { Remove-Item -LiteralPath $p -Force }";
+    let mut scripts = Vec::new();
+    let mut script: Vec<u16> = Vec::new();
+    for path in paths {
+        let quoted = powershell::quote(path);
+        if !script.is_empty() && script.len() + quoted.len() + SUFFIX.len() + 1 > MAX_SCRIPT_UNITS {
+            script.extend(SUFFIX.encode_utf16());
+            scripts.push(powershell::encode_wide(&script));
+            script.clear();
+        }
+        if script.is_empty() {
+            script.extend(PREFIX.encode_utf16());
+        } else {
+            script.push(u16::from(b','));
+        }
+        script.extend(quoted);
+    }
+    if !script.is_empty() {
+        script.extend(SUFFIX.encode_utf16());
+        scripts.push(powershell::encode_wide(&script));
+    }
+    scripts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    #[test]
+    fn delete_script_embeds_escaped_paths() {
+        let paths = [wide("C:\\Temp\\it's $(x).mp4"), vec![0x61, 0xD800]];
+        let scripts = delete_scripts(&paths);
+        assert_eq!(scripts.len(), 1);
+        let mut expected = wide("foreach ($p in @('C:\\Temp\\it''s $(x).mp4','a");
+        expected.push(0xD800);
+        expected.extend(wide("')) { Remove-Item -LiteralPath $p -Force }"));
+        assert_eq!(powershell::decode_wide(&scripts[0]), expected);
+        assert!(ELEVATE_SCRIPT.contains(&format!("$env:{}", SCRIPT_VAR)));
+    }
+
+    #[test]
+    fn long_delete_lists_are_split() {
+        let paths = vec![wide(&"x".repeat(3000)); 10];
+        let scripts = delete_scripts(&paths);
+        assert!(scripts.len() > 1);
+        let mut count = 0;
+        for script in &scripts {
+            let script = powershell::decode(script);
+            assert!(script.len() <= MAX_SCRIPT_UNITS);
+            assert!(script.ends_with(" -Force }"));
+            count += script.matches(&"x".repeat(3000)).count();
+        }
+        assert_eq!(count, 10);
+    }
+
+    #[test]
+    fn relaunch_reads_path_from_environment() {
+        assert!(RELAUNCH_SCRIPT.contains(&format!("$env:{}", powershell::PATH_VAR)));
+        assert!(!RELAUNCH_SCRIPT.contains("$args"));
+    }
+}
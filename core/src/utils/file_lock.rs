@@ -0,0 +1,167 @@
+//! 检测文件是否被其他程序占用：Windows 上被播放器等打开的文件无法删除或覆盖
+use crate::utils::display_name;
+use std::path::{Path, PathBuf};
+
+/// 被占用的文件及可能占用它的程序
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockedFile {
+    pub path: PathBuf,
+    /// 占用该文件的程序名称，无法获取时为空
+    pub holders: Vec<String>,
+}
+
+impl LockedFile {
+    /// 形如 "a.mp4（被 vlc.exe 占用）"
+    pub fn describe(&self) -> String {
+        let name = display_name(&self.path);
+        if self.holders.is_empty() {
+            format!("{}（被其他程序占用）", name)
+        } else {
+            format!("{}（被 {} 占用）", name, self.holders.join("、"))
+        }
+    }
+}
+
+/// 找出被占用的文件，并尽量查出占用它们的程序
+pub fn find_locked_files(paths: &[PathBuf]) -> Vec<LockedFile> {
+    paths
+        .iter()
+        .filter(|path| is_file_locked(path))
+        .map(|path| LockedFile {
+            path: path.clone(),
+            holders: lock_holders(path),
+        })
+        .collect()
+}
+
+/// 文件存在且正被其他程序打开（无法独占打开）
+fn is_file_locked(path: &Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        const ERROR_SHARING_VIOLATION: i32 = 32;
+        const ERROR_LOCK_VIOLATION: i32 = 33;
+        match std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .share_mode(0)
+            .open(path)
+        {
+            Ok(_) => false,
+            Err(e) => matches!(
+                e.raw_os_error(),
+                Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+            ),
+        }
+    }
+    // 其他系统上打开中的文件可以直接删除或覆盖
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// 通过 Restart Manager 查询占用文件的程序
+#[cfg(target_os = "windows")]
+fn lock_holders(path: &Path) -> Vec<String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_rm::*;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain([0]).collect();
+    let mut session = 0u32;
+    let mut key = [0u16; CCH_RM_SESSION_KEY + 1];
+    // SAFETY: 参数均为本函数内的有效缓冲区，会话在返回前结束
+    unsafe {
+        if RmStartSession(&mut session, 0, key.as_mut_ptr()) != 0 {
+            return Vec::new();
+        }
+        let files = [wide.as_ptr()];
+        let mut holders = Vec::new();
+        if RmRegisterResources(
+            session,
+            1,
+            files.as_ptr(),
+            0,
+            std::ptr::null(),
+            0,
+            std::ptr::null(),
+        ) == 0
+        {
+            let mut needed = 0u32;
+            let mut count = MAX_PROCESSES as u32;
+            let mut infos: [RmProcessInfo; MAX_PROCESSES] = std::mem::zeroed();
+            let mut reasons = 0u32;
+            if RmGetList(
+                session,
+                &mut needed,
+                &mut count,
+                infos.as_mut_ptr(),
+                &mut reasons,
+            ) == 0
+            {
+                for info in infos.iter().take(count as usize) {
+                    let len = info
+                        .app_name
+                        .iter()
+                        .position(|c| *c == 0)
+                        .unwrap_or(info.app_name.len());
+                    holders.push(String::from_utf16_lossy(&info.app_name[..len]));
+                }
+            }
+        }
+        RmEndSession(session);
+        holders
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn lock_holders(_path: &Path) -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(target_os = "windows")]
+mod windows_rm {
+    pub const CCH_RM_SESSION_KEY: usize = 32;
+    pub const MAX_PROCESSES: usize = 8;
+
+    #[repr(C)]
+    pub struct RmUniqueProcess {
+        pub process_id: u32,
+        pub start_time_low: u32,
+        pub start_time_high: u32,
+    }
+
+    #[repr(C)]
+    pub struct RmProcessInfo {
+        pub process: RmUniqueProcess,
+        pub app_name: [u16; 256],
+        pub service_short_name: [u16; 64],
+        pub application_type: i32,
+        pub app_status: u32,
+        pub ts_session_id: u32,
+        pub restartable: i32,
+    }
+
+    #[link(name = "rstrtmgr")]
+    unsafe extern "system" {
+        pub fn RmStartSession(session: *mut u32, flags: u32, key: *mut u16) -> u32;
+        pub fn RmRegisterResources(
+            session: u32,
+            n_files: u32,
+            files: *const *const u16,
+            n_applications: u32,
+            applications: *const RmUniqueProcess,
+            n_services: u32,
+            services: *const *const u16,
+        ) -> u32;
+        pub fn RmGetList(
+            session: u32,
+            needed: *mut u32,
+            count: *mut u32,
+            infos: *mut RmProcessInfo,
+            reboot_reasons: *mut u32,
+        ) -> u32;
+        pub fn RmEndSession(session: u32) -> u32;
+    }
+}
@@ -0,0 +1,118 @@
+//! 文件名处理：路径始终以 `OsStr` 传递，只在界面显示时转换为字符串
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// 界面显示用的文件名，无法解码的字符显示为 �；没有文件名（如磁盘根目录）时显示完整路径
+pub fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// 同目录下在原文件名（不含扩展名）后追加 `suffix` 的路径，如 `a.mp4` → `a_rotated.mp4`。
+/// 直接拼接 `OsStr`，文件名含无法解码的字符时也不会改变原名
+pub fn with_stem_suffix(path: &Path, suffix: &str, fallback_stem: &str) -> PathBuf {
+    let mut name = path
+        .file_stem()
+        .map(OsStr::to_os_string)
+        .unwrap_or_else(|| OsString::from(fallback_stem));
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+// 能解码为 UTF-8 的路径保存为字符串，其余按平台原始编码保存
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SavedPath {
+    Text(String),
+    Raw(OsString),
+}
+
+/// 把以文件为键的设置保存为 `[路径, 值]` 列表，文件名含无法解码的字符时也不会丢失
+pub(crate) fn serialize_path_pairs<T, S>(
+    pairs: &[(PathBuf, T)],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(pairs.iter().map(|(path, value)| {
+        let path = match path.to_str() {
+            Some(text) => SavedPath::Text(text.to_string()),
+            None => SavedPath::Raw(path.as_os_str().to_os_string()),
+        };
+        (path, value)
+    }))
+}
+
+/// 读取 [`serialize_path_pairs`] 保存的列表
+pub(crate) fn deserialize_path_pairs<'de, T, D>(
+    deserializer: D,
+) -> Result<Vec<(PathBuf, T)>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let pairs = Vec::<(SavedPath, T)>::deserialize(deserializer)?;
+    Ok(pairs
+        .into_iter()
+        .map(|(path, value)| {
+            let path = match path {
+                SavedPath::Text(text) => PathBuf::from(text),
+                SavedPath::Raw(raw) => PathBuf::from(raw),
+            };
+            (path, value)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unicode_names_are_kept() {
+        let path = Path::new("/videos/旅行 🎬 vlog.mp4");
+        assert_eq!(display_name(path), "旅行 🎬 vlog.mp4");
+        assert_eq!(
+            with_stem_suffix(path, "_rotated.mp4", "video"),
+            Path::new("/videos/旅行 🎬 vlog_rotated.mp4")
+        );
+        assert_eq!(
+            with_stem_suffix(Path::new("/"), "_merged.mp4", "video"),
+            Path::new("/video_merged.mp4")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_utf8_names_round_trip() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+        let path = PathBuf::from(OsString::from_vec(b"/videos/clip\xff.mp4".to_vec()));
+        assert_eq!(display_name(&path), "clip\u{fffd}.mp4");
+        let output = with_stem_suffix(&path, "_h264.mp4", "video");
+        assert_eq!(output.file_name().unwrap().as_bytes(), b"clip\xff_h264.mp4");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn unpaired_surrogate_names_round_trip() {
+        use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+        // "clip" + 单独的高代理项 + ".mp4"，不是合法的 UTF-16
+        let mut wide: Vec<u16> = "C:\\videos\\clip".encode_utf16().collect();
+        wide.push(0xD800);
+        wide.extend(".mp4".encode_utf16());
+        let path = PathBuf::from(OsString::from_wide(&wide));
+        assert_eq!(display_name(&path), "clip\u{fffd}.mp4");
+        let output = with_stem_suffix(&path, "_h264.mp4", "video");
+        let name: Vec<u16> = output.file_name().unwrap().encode_wide().collect();
+        let mut expected: Vec<u16> = "clip".encode_utf16().collect();
+        expected.push(0xD800);
+        expected.extend("_h264.mp4".encode_utf16());
+        assert_eq!(name, expected);
+    }
+}
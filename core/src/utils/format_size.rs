@@ -0,0 +1,80 @@
+use crate::config::SizeUnits;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 全局的大小单位，启动时和修改设置时由界面更新；true 为 KB/MB（1000 进制）
+static DECIMAL_UNITS: AtomicBool = AtomicBool::new(false);
+
+/// 设置 [`format_size`] 使用的单位制
+pub fn set_size_units(units: SizeUnits) {
+    DECIMAL_UNITS.store(units == SizeUnits::Decimal, Ordering::Relaxed);
+}
+
+/// 按设置中的单位制格式化文件大小
+pub fn format_size(size: Option<u64>) -> String {
+    let units = if DECIMAL_UNITS.load(Ordering::Relaxed) {
+        SizeUnits::Decimal
+    } else {
+        SizeUnits::Binary
+    };
+    format_size_in(size, units)
+}
+
+/// 按指定单位制格式化文件大小
+pub fn format_size_in(size: Option<u64>, units: SizeUnits) -> String {
+    let Some(size) = size else {
+        return "未知".to_string();
+    };
+    let (base, names) = match units {
+        SizeUnits::Binary => (1024.0, ["KiB", "MiB", "GiB", "TiB"]),
+        SizeUnits::Decimal => (1000.0, ["KB", "MB", "GB", "TB"]),
+    };
+    if (size as f64) < base {
+        return format!("{} B", size);
+    }
+    let mut value = size as f64 / base;
+    let mut unit = names[0];
+    for name in &names[1..] {
+        if value < base {
+            break;
+        }
+        value /= base;
+        unit = name;
+    }
+    format!("{:.2} {}", value, unit)
+}
+
+/// 精确字节数（千位分隔），用于悬停提示
+pub fn format_bytes(size: u64) -> String {
+    let digits = size.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    format!("{} 字节", grouped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_use_the_selected_units() {
+        assert_eq!(format_size_in(Some(512), SizeUnits::Binary), "512 B");
+        assert_eq!(format_size_in(Some(1536), SizeUnits::Binary), "1.50 KiB");
+        assert_eq!(format_size_in(Some(1536), SizeUnits::Decimal), "1.54 KB");
+        assert_eq!(
+            format_size_in(Some(3 * 1024 * 1024 * 1024), SizeUnits::Binary),
+            "3.00 GiB"
+        );
+        assert_eq!(
+            format_size_in(Some(2_500_000_000), SizeUnits::Decimal),
+            "2.50 GB"
+        );
+        assert_eq!(format_size_in(None, SizeUnits::Decimal), "未知");
+        assert_eq!(format_bytes(1234567), "1,234,567 字节");
+        assert_eq!(format_bytes(999), "999 字节");
+    }
+}
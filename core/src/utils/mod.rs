@@ -0,0 +1,42 @@
+mod app_dirs;
+mod archive;
+mod checksum;
+mod cleanup;
+mod crash_report;
+mod disk_space;
+mod duration;
+mod elevated;
+mod file_lock;
+mod file_name;
+mod format_size;
+mod mp4;
+mod natural_sort;
+mod power_action;
+#[cfg(any(windows, test))]
+mod powershell;
+mod rename;
+mod scan;
+mod session_group;
+mod sleep_inhibitor;
+pub use app_dirs::{app_config_dir, app_data_dir, portable_dir};
+pub use archive::{archive_files, recycle_files};
+pub use checksum::sha256_file;
+pub use cleanup::{CleanupCandidate, CleanupReason, cleanup_candidates};
+pub use crash_report::{
+    install_panic_hook, open_path, record_log, reveal_path, take_pending_crash_report,
+    write_recent_log,
+};
+pub use disk_space::free_space;
+pub use duration::{format_date, format_duration, format_relative_date, parse_timestamp};
+pub use elevated::{delete_files_elevated, relaunch_elevated};
+pub use file_lock::{LockedFile, find_locked_files};
+pub(crate) use file_name::{deserialize_path_pairs, serialize_path_pairs};
+pub use file_name::{display_name, with_stem_suffix};
+pub use format_size::{format_bytes, format_size, format_size_in, set_size_units};
+pub use mp4::{Mp4FileInfo, parse_mp4_info};
+pub use natural_sort::natural_cmp_path;
+pub use power_action::PowerAction;
+pub use rename::rename_in_place;
+pub use scan::{list_mp4_files, wildcard_match};
+pub use session_group::detect_sessions;
+pub use sleep_inhibitor::SleepInhibitor;
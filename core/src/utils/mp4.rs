@@ -0,0 +1,240 @@
+use crate::error::AppError;
+use crate::utils::display_name;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// MP4 文件信息结构
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mp4FileInfo {
+    /// 显示用的文件名，读写文件时使用 `file_path`
+    pub file_name: String,
+    pub size: u64,
+    pub modified: Option<std::time::SystemTime>,
+    pub width: u16,
+    pub height: u16,
+    pub codec: String,      // H.264 / H.265 / HEVC / AV1 等
+    pub duration: Duration, // 保留毫秒，显示时再取整
+    pub file_path: PathBuf, // 添加文件路径
+}
+
+/// 解析单个 MP4 文件信息
+pub fn parse_mp4_info(path: PathBuf) -> Result<Mp4FileInfo, AppError> {
+    let file_name = display_name(&path);
+
+    let metadata = std::fs::metadata(&path).map_err(|e| AppError::io("读取文件信息失败", e))?;
+    let modified = metadata.modified().ok();
+    let size = metadata.len();
+
+    let file = File::open(&path).map_err(|e| AppError::io("打开文件失败", e))?;
+    let parse_error = |e: mp4::Error| AppError::Parse(format!("无法解析 {}: {}", file_name, e));
+    // 只读 ftyp 与 moov 在内存中解析；结构不常见（如分段 MP4）时退回完整解析
+    let video = read_header_boxes(&path, &file, size)
+        .ok()
+        .flatten()
+        .and_then(|header| {
+            let len = header.len() as u64;
+            let mp4 = mp4::Mp4Reader::read_header(Cursor::new(header), len).ok()?;
+            video_info(&mp4).ok()
+        });
+    let (duration, width, height, codec) = match video {
+        Some(video) => video,
+        None => {
+            let mut reader = BufReader::new(&file);
+            reader
+                .rewind()
+                .map_err(|e| AppError::io("读取文件失败", e))?;
+            let mp4 = mp4::Mp4Reader::read_header(reader, size).map_err(parse_error)?;
+            video_info(&mp4).map_err(parse_error)?
+        }
+    };
+
+    Ok(Mp4FileInfo {
+        file_name,
+        size,
+        modified,
+        width,
+        height,
+        codec,
+        duration,
+        file_path: path, // 保存完整路径
+    })
+}
+
+// 时长（秒）、第一个视频轨道的宽高与编码
+fn video_info<R>(mp4: &mp4::Mp4Reader<R>) -> Result<(Duration, u16, u16, String), mp4::Error> {
+    // 获取视频轨道信息
+    let mut width = 0u16;
+    let mut height = 0u16;
+    let mut codec = "未知".to_string();
+    let duration = mp4.duration();
+
+    for track in mp4.tracks().values() {
+        if let mp4::TrackType::Video = track.track_type()? {
+            width = track.width();
+            height = track.height();
+            // 编解码器类型
+            codec = match track.media_type() {
+                Ok(mp4::MediaType::H264) => "H.264 / AVC".to_string(),
+                Ok(mp4::MediaType::H265) => "H.265 / HEVC".to_string(),
+                Ok(mp4::MediaType::VP9) => "VP9".to_string(),
+                Ok(other) => format!("{:?}", other),
+                Err(_) => "未知".to_string(),
+            };
+            break; // 只取第一个视频轨道
+        }
+    }
+
+    Ok((duration, width, height, codec))
+}
+
+// moov 超过该大小时不整块读入内存，交给 mp4 库按需读取
+const MAX_HEADER_BYTES: u64 = 64 * 1024 * 1024;
+
+// 找出顶层的 ftyp 与 moov 并拼接返回，只为跳过 mdat 等数据块读取各盒子的头部。
+// Windows 上本地文件通过内存映射访问；网络共享上映射的页面缺失时每次都要走网络，改为按偏移读取。
+// 其他平台上映射期间文件可能被其他进程截断，访问截断部分会收到 SIGBUS，一律按偏移读取
+fn read_header_boxes(path: &Path, file: &File, size: u64) -> io::Result<Option<Vec<u8>>> {
+    #[cfg(windows)]
+    if !is_network_path(path) {
+        // SAFETY: 只读映射且只在本函数内使用；Windows 上存在映射视图时文件无法被截断，
+        // 其他进程的写入只会改变读到的内容，由 find_header_boxes 的边界检查兜底
+        if let Ok(map) = unsafe { memmap2::Mmap::map(file) } {
+            return find_header_boxes(&mut Cursor::new(&map[..]), size);
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = path;
+    find_header_boxes(&mut &*file, size)
+}
+
+fn find_header_boxes<R: Read + Seek>(reader: &mut R, size: u64) -> io::Result<Option<Vec<u8>>> {
+    let mut ftyp = None;
+    let mut moov = None;
+    let mut offset = 0;
+    while offset + 8 <= size && (ftyp.is_none() || moov.is_none()) {
+        reader.seek(SeekFrom::Start(offset))?;
+        let mut header = [0u8; 16];
+        reader.read_exact(&mut header[..8])?;
+        let box_size = match u32::from_be_bytes(header[..4].try_into().unwrap()) {
+            // 大小为 0 表示延伸到文件末尾
+            0 => size - offset,
+            // 大小为 1 表示后面跟 64 位大小
+            1 => {
+                reader.read_exact(&mut header[8..])?;
+                u64::from_be_bytes(header[8..].try_into().unwrap())
+            }
+            n => n as u64,
+        };
+        if box_size < 8 || offset.checked_add(box_size).is_none_or(|end| end > size) {
+            return Ok(None);
+        }
+        match &header[4..8] {
+            b"ftyp" | b"moov" if box_size <= MAX_HEADER_BYTES => {
+                let mut data = vec![0u8; box_size as usize];
+                reader.seek(SeekFrom::Start(offset))?;
+                reader.read_exact(&mut data)?;
+                if &header[4..8] == b"ftyp" {
+                    ftyp = Some(data);
+                } else {
+                    moov = Some(data);
+                }
+            }
+            // 分段 MP4 的时长在各 moof 中，需要完整解析
+            b"moof" => return Ok(None),
+            _ => {}
+        }
+        offset += box_size;
+    }
+    Ok(ftyp.zip(moov).map(|(mut ftyp, moov)| {
+        ftyp.extend(moov);
+        ftyp
+    }))
+}
+
+#[cfg(windows)]
+fn is_network_path(path: &Path) -> bool {
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::{Component, Prefix};
+
+    let Some(Component::Prefix(prefix)) = path.components().next() else {
+        return false;
+    };
+    match prefix.kind() {
+        Prefix::UNC(..) | Prefix::VerbatimUNC(..) => true,
+        Prefix::Disk(_) | Prefix::VerbatimDisk(_) => {
+            // 映射的网络驱动器（如 Z:）
+            let root: Vec<u16> = prefix
+                .as_os_str()
+                .encode_wide()
+                .chain("\\\0".encode_utf16())
+                .collect();
+            // SAFETY: root 是以 NUL 结尾的宽字符串
+            unsafe { drive_type::GetDriveTypeW(root.as_ptr()) == drive_type::DRIVE_REMOTE }
+        }
+        _ => false,
+    }
+}
+
+#[cfg(windows)]
+mod drive_type {
+    pub const DRIVE_REMOTE: u32 = 4;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        pub fn GetDriveTypeW(root_path_name: *const u16) -> u32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &[u8; 4], payload_len: usize) -> Vec<u8> {
+        let mut data = ((payload_len + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.resize(payload_len + 8, kind[0]);
+        data
+    }
+
+    #[test]
+    fn header_boxes_skip_media_data() {
+        let ftyp = mp4_box(b"ftyp", 16);
+        let moov = mp4_box(b"moov", 100);
+        let mut file = ftyp.clone();
+        file.extend(mp4_box(b"free", 4));
+        file.extend(mp4_box(b"mdat", 5000));
+        file.extend(&moov);
+        let size = file.len() as u64;
+        let header = find_header_boxes(&mut Cursor::new(file), size).unwrap();
+        assert_eq!(header, Some([ftyp, moov].concat()));
+    }
+
+    #[test]
+    fn unusual_layouts_fall_back() {
+        let mut fragmented = mp4_box(b"ftyp", 16);
+        fragmented.extend(mp4_box(b"moof", 32));
+        fragmented.extend(mp4_box(b"moov", 100));
+        let size = fragmented.len() as u64;
+        let header = find_header_boxes(&mut Cursor::new(fragmented), size).unwrap();
+        assert_eq!(header, None);
+
+        // 盒子大小超出文件（文件被截断）
+        let mut truncated = mp4_box(b"ftyp", 16);
+        truncated.extend(&mp4_box(b"mdat", 5000)[..100]);
+        let size = truncated.len() as u64;
+        let header = find_header_boxes(&mut Cursor::new(truncated), size).unwrap();
+        assert_eq!(header, None);
+
+        // 64 位大小字段损坏，与偏移相加会溢出
+        let mut malformed = mp4_box(b"ftyp", 16);
+        malformed.extend(1u32.to_be_bytes());
+        malformed.extend(b"free");
+        malformed.extend(u64::MAX.to_be_bytes());
+        malformed.extend(mp4_box(b"moov", 100));
+        let size = malformed.len() as u64;
+        let header = find_header_boxes(&mut Cursor::new(malformed), size).unwrap();
+        assert_eq!(header, None);
+    }
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+use std::path::Path;
+
+/// 自然排序比较：数字部分按数值比较，`clip_2` 排在 `clip_10` 之前
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            // 数值相同（如 01 与 1）时按原始字符串决定先后，保证排序稳定
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let mut x_num = String::new();
+                while let Some(c) = a_chars.next_if(|c| c.is_ascii_digit()) {
+                    x_num.push(c);
+                }
+                let mut y_num = String::new();
+                while let Some(c) = b_chars.next_if(|c| c.is_ascii_digit()) {
+                    y_num.push(c);
+                }
+                let x_trim = x_num.trim_start_matches('0');
+                let y_trim = y_num.trim_start_matches('0');
+                let ord = x_trim
+                    .len()
+                    .cmp(&y_trim.len())
+                    .then_with(|| x_trim.cmp(y_trim));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ord = x.to_lowercase().cmp(y.to_lowercase());
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// 按文件名自然排序比较两个路径，文件名含无法解码的字符而显示相同时按原始路径区分
+pub fn natural_cmp_path(a: &Path, b: &Path) -> Ordering {
+    natural_cmp(
+        &a.file_name().unwrap_or_default().to_string_lossy(),
+        &b.file_name().unwrap_or_default().to_string_lossy(),
+    )
+    .then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut v: Vec<String> = names.iter().map(|s| s.to_string()).collect();
+        v.sort_by(|a, b| natural_cmp(a, b));
+        v
+    }
+
+    #[test]
+    fn orders_numbers_by_value() {
+        assert_eq!(
+            sorted(&["clip_10.mp4", "clip_2.mp4", "clip_1.mp4"]),
+            ["clip_1.mp4", "clip_2.mp4", "clip_10.mp4"]
+        );
+    }
+
+    #[test]
+    fn handles_mixed_digit_groups_and_leading_zeros() {
+        assert_eq!(
+            sorted(&["s2_part10", "s10_part1", "s2_part9", "s02_part1"]),
+            ["s02_part1", "s2_part9", "s2_part10", "s10_part1"]
+        );
+        assert_eq!(natural_cmp("a01", "a1"), "a01".cmp("a1"));
+    }
+
+    #[test]
+    fn handles_cjk_names() {
+        assert_eq!(
+            sorted(&["视频10.mp4", "视频2.mp4", "录像1.mp4", "视频1.mp4"]),
+            ["录像1.mp4", "视频1.mp4", "视频2.mp4", "视频10.mp4"]
+        );
+        assert_eq!(natural_cmp("第2段", "第12段"), Ordering::Less);
+    }
+
+    #[test]
+    fn is_case_insensitive() {
+        assert_eq!(natural_cmp("Clip2", "clip10"), Ordering::Less);
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::process::Command;
+
+/// 任务全部完成后执行的电源操作
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum PowerAction {
+    #[default]
+    Shutdown,
+    Sleep,
+}
+
+impl PowerAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerAction::Shutdown => "关机",
+            PowerAction::Sleep => "睡眠",
+        }
+    }
+
+    /// 立即执行电源操作
+    pub fn execute(&self) -> Result<(), io::Error> {
+        let (program, args): (&str, &[&str]) = match self {
+            #[cfg(target_os = "windows")]
+            PowerAction::Shutdown => ("shutdown", &["/s", "/t", "0"]),
+            #[cfg(target_os = "windows")]
+            PowerAction::Sleep => ("rundll32.exe", &["powrprof.dll,SetSuspendState", "0,1,0"]),
+            #[cfg(target_os = "macos")]
+            PowerAction::Shutdown => (
+                "osascript",
+                &["-e", "tell application \"System Events\" to shut down"],
+            ),
+            #[cfg(target_os = "macos")]
+            PowerAction::Sleep => ("pmset", &["sleepnow"]),
+            #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+            PowerAction::Shutdown => ("systemctl", &["poweroff"]),
+            #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+            PowerAction::Sleep => ("systemctl", &["suspend"]),
+        };
+        Command::new(program).args(args).spawn()?;
+        Ok(())
+    }
+}
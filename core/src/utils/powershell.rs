@@ -0,0 +1,89 @@
+//! 调用 powershell.exe 的参数。脚本一律经 `-EncodedCommand` 传入：`-Command` 会把其后的
+//! 参数拼接进脚本文本而不是绑定到 `$args`，路径中的引号、`$` 等也会被再次解析
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// 传递单个路径的环境变量，脚本中以 `$env:MERGE_MP4_PATH` 读取。
+/// 环境变量按 UTF-16 原样传递，不会丢失无法转换为 UTF-8 的文件名
+pub(crate) const PATH_VAR: &str = "MERGE_MP4_PATH";
+
+/// 把脚本编码为 `-EncodedCommand` 接受的 Base64（UTF-16LE）
+pub(crate) fn encode(script: &str) -> String {
+    encode_wide(&script.encode_utf16().collect::<Vec<_>>())
+}
+
+/// 同 [`encode`]，脚本为 UTF-16 代码单元。Windows 文件名中不成对的代理项也能原样传递
+pub(crate) fn encode_wide(script: &[u16]) -> String {
+    let bytes: Vec<u8> = script.iter().copied().flat_map(u16::to_le_bytes).collect();
+    STANDARD.encode(bytes)
+}
+
+/// 执行 `script` 的完整参数
+pub(crate) fn args(script: &str) -> [String; 3] {
+    [
+        "-NoProfile".to_string(),
+        "-EncodedCommand".to_string(),
+        encode(script),
+    ]
+}
+
+/// 单引号字符串字面量（UTF-16），其中不展开变量与子表达式。
+/// PowerShell 把弯单引号也当作单引号，一并双写转义
+pub(crate) fn quote(s: &[u16]) -> Vec<u16> {
+    const QUOTE: u16 = b'\'' as u16;
+    let mut quoted = Vec::with_capacity(s.len() + 2);
+    quoted.push(QUOTE);
+    for &unit in s {
+        if matches!(unit, QUOTE | 0x2018..=0x201B) {
+            quoted.push(unit);
+        }
+        quoted.push(unit);
+    }
+    quoted.push(QUOTE);
+    quoted
+}
+
+/// 解码 `-EncodedCommand` 的参数，供测试检查脚本原文
+#[cfg(test)]
+pub(crate) fn decode(encoded: &str) -> String {
+    String::from_utf16(&decode_wide(encoded)).unwrap()
+}
+
+#[cfg(test)]
+pub(crate) fn decode_wide(encoded: &str) -> Vec<u16> {
+    STANDARD
+        .decode(encoded)
+        .unwrap()
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    #[test]
+    fn quotes_are_doubled() {
+        assert_eq!(quote(&wide("C:\\it's $x.mp4")), wide("'C:\\it''s $x.mp4'"));
+        assert_eq!(quote(&wide("a\u{2019}b")), wide("'a\u{2019}\u{2019}b'"));
+        // 不成对的代理项原样保留
+        let lone = [0x61, 0xD800, 0x62];
+        assert_eq!(quote(&lone), [0x27, 0x61, 0xD800, 0x62, 0x27]);
+        assert_eq!(decode_wide(&encode_wide(&lone)), lone);
+    }
+
+    #[test]
+    fn script_round_trips_through_encoded_command() {
+        let [profile, flag, encoded] = args("Write-Output \"$env:MERGE_MP4_PATH\"");
+        assert_eq!(profile, "-NoProfile");
+        assert_eq!(flag, "-EncodedCommand");
+        assert_eq!(decode(&encoded), "Write-Output \"$env:MERGE_MP4_PATH\"");
+        // UTF-16LE 的 "A" 为 41 00
+        assert_eq!(encode("A"), "QQA=");
+    }
+}
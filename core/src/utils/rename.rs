@@ -0,0 +1,70 @@
+//! 在原目录中重命名文件
+use crate::error::AppError;
+use std::path::{Path, PathBuf};
+
+/// 把文件重命名为 `new_name`（同一目录），返回新路径。
+/// 新名称不以原扩展名结尾时自动补上；目标已存在时拒绝，不会覆盖其他文件
+pub fn rename_in_place(path: &Path, new_name: &str) -> Result<PathBuf, AppError> {
+    let target = rename_target(path, new_name)?;
+    if target == path {
+        return Ok(target);
+    }
+    // 只改大小写时，不区分大小写的文件系统会把目标当成已存在
+    let case_only =
+        target.to_string_lossy().to_lowercase() == path.to_string_lossy().to_lowercase();
+    if target.exists() && !case_only {
+        return Err(AppError::Other(format!(
+            "{} 已存在",
+            target.file_name().unwrap_or_default().to_string_lossy()
+        )));
+    }
+    std::fs::rename(path, &target).map_err(|e| AppError::io("重命名失败", e))?;
+    Ok(target)
+}
+
+// 校验新名称并得到目标路径
+fn rename_target(path: &Path, new_name: &str) -> Result<PathBuf, AppError> {
+    let name = new_name.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        return Err(AppError::Other("文件名不能为空".to_string()));
+    }
+    if name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
+        return Err(AppError::Other(
+            "文件名不能包含 / \\ : * ? \" < > | 等字符".to_string(),
+        ));
+    }
+    let mut file_name = name.to_string();
+    // 名称中的点可能只是日期分隔（如 2024.01.01），不能当作扩展名
+    if let Some(ext) = path.extension() {
+        let ext = format!(".{}", ext.to_string_lossy());
+        if !name.to_lowercase().ends_with(&ext.to_lowercase()) {
+            file_name.push_str(&ext);
+        }
+    }
+    Ok(path.with_file_name(file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_target_keeps_directory_and_extension() {
+        let path = Path::new("videos/clip.mp4");
+        assert_eq!(
+            rename_target(path, " trip ").unwrap(),
+            Path::new("videos/trip.mp4")
+        );
+        assert_eq!(
+            rename_target(path, "trip.MP4").unwrap(),
+            Path::new("videos/trip.MP4")
+        );
+        assert_eq!(
+            rename_target(path, "2024.01.01").unwrap(),
+            Path::new("videos/2024.01.01.mp4")
+        );
+        assert!(rename_target(path, "").is_err());
+        assert!(rename_target(path, "../trip.mp4").is_err());
+        assert!(rename_target(path, "a:b").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! 按扫描规则列出目录中的 MP4 文件
+use crate::config::ScanRules;
+use std::fs::{self, DirEntry};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 列出目录中符合规则的 MP4 文件，开启子文件夹时逐层向下查找。
+/// 只有根目录无法读取时返回错误，子文件夹读取失败时跳过
+pub fn list_mp4_files(dir: &Path, rules: &ScanRules) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    let mut is_root = true;
+    while let Some(current) = pending.pop() {
+        let entries = match fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(e) if is_root => return Err(e),
+            Err(_) => continue,
+        };
+        is_root = false;
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if rules.excludes(&name) || (rules.ignore_hidden && is_hidden(&name, &entry)) {
+                continue;
+            }
+            let path = entry.path();
+            // 跟随符号链接，与之前 `Path::is_file` 的判断一致
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                // 不进入链接到的文件夹，避免链接成环时无限查找
+                if rules.include_subfolders && entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    pending.push(path);
+                }
+            } else if metadata.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("mp4"))
+                && !rules.too_small(metadata.len())
+            {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// 通配符匹配：`*` 匹配任意多个字符，`?` 匹配一个字符，不区分大小写
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // 最近一个 `*` 的位置及其当时对应的名称位置，失配时让它多吞一个字符再试
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(windows)]
+fn is_hidden(name: &str, entry: &DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    name.starts_with('.')
+        || entry
+            .metadata()
+            .is_ok_and(|m| m.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+}
+
+#[cfg(not(windows))]
+fn is_hidden(name: &str, _entry: &DirEntry) -> bool {
+    name.starts_with('.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_match_whole_names() {
+        assert!(wildcard_match("*.tmp.mp4", "clip.tmp.mp4"));
+        assert!(!wildcard_match("*.tmp.mp4", "clip.tmp.mp4.bak"));
+        assert!(wildcard_match("clip_??.mp4", "CLIP_01.MP4"));
+        assert!(!wildcard_match("clip_??.mp4", "clip_1.mp4"));
+        assert!(wildcard_match("*cache*", "thumbcache"));
+        assert!(wildcard_match("node_modules", "node_modules"));
+        assert!(!wildcard_match("node_modules", "node_modules2"));
+        assert!(wildcard_match("*", ""));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::utils::{Mp4FileInfo, natural_cmp_path};
+use std::time::{Duration, SystemTime};
+
+/// 相邻片段之间允许的最大时间间隔（秒）
+const MAX_GAP_SECS: u64 = 10;
+
+/// 根据文件名模式、时间连续性和流参数，把扫描结果分成录制会话。
+/// 只返回包含两个及以上片段的分组，组内按时间顺序排列。
+pub fn detect_sessions(files: &[Mp4FileInfo]) -> Vec<Vec<Mp4FileInfo>> {
+    let mut sorted: Vec<&Mp4FileInfo> = files.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.modified
+            .cmp(&b.modified)
+            .then_with(|| natural_cmp_path(&a.file_path, &b.file_path))
+    });
+
+    let mut groups: Vec<Vec<Mp4FileInfo>> = Vec::new();
+    let mut current: Vec<Mp4FileInfo> = Vec::new();
+    for info in sorted {
+        let continues = current
+            .last()
+            .map(|prev| belongs_to_session(prev, info))
+            .unwrap_or(false);
+        if !continues && !current.is_empty() {
+            groups.push(std::mem::take(&mut current));
+        }
+        current.push(info.clone());
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+
+    groups.retain(|g| g.len() > 1);
+    groups
+}
+
+fn belongs_to_session(prev: &Mp4FileInfo, next: &Mp4FileInfo) -> bool {
+    name_pattern(&prev.file_name) == name_pattern(&next.file_name)
+        && prev.width == next.width
+        && prev.height == next.height
+        && prev.codec == next.codec
+        && is_contiguous(prev, next)
+}
+
+// 修改时间视为录制结束时间，下一段开始时间 = 修改时间 - 时长
+fn is_contiguous(prev: &Mp4FileInfo, next: &Mp4FileInfo) -> bool {
+    let (Some(prev_end), Some(next_end)) = (prev.modified, next.modified) else {
+        return false;
+    };
+    let next_start = next_end
+        .checked_sub(next.duration)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let gap = match next_start.duration_since(prev_end) {
+        Ok(d) => d,
+        Err(e) => e.duration(),
+    };
+    gap <= Duration::from_secs(MAX_GAP_SECS)
+}
+
+// 把数字替换成 `#`，如 `2024-01-01_10-00-00.mp4` -> `#-#-#_#-#-#.mp#`
+fn name_pattern(name: &str) -> String {
+    let mut pattern = String::with_capacity(name.len());
+    let mut in_digits = false;
+    for c in name.chars() {
+        if c.is_ascii_digit() {
+            if !in_digits {
+                pattern.push('#');
+            }
+            in_digits = true;
+        } else {
+            pattern.push(c.to_ascii_lowercase());
+            in_digits = false;
+        }
+    }
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // 一分钟的行车记录仪片段，`end_secs` 为录制结束（修改时间）相对基准时间的秒数
+    fn clip(name: &str, end_secs: u64) -> Mp4FileInfo {
+        Mp4FileInfo {
+            file_name: name.to_string(),
+            size: 1024,
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000 + end_secs)),
+            width: 1920,
+            height: 1080,
+            codec: "H.264".to_string(),
+            duration: Duration::from_secs(60),
+            file_path: PathBuf::from(name),
+        }
+    }
+
+    fn names(groups: &[Vec<Mp4FileInfo>]) -> Vec<Vec<&str>> {
+        groups
+            .iter()
+            .map(|g| g.iter().map(|f| f.file_name.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn name_pattern_collapses_digits() {
+        assert_eq!(name_pattern("2024-01-01_10-00-00.MP4"), "#-#-#_#-#-#.mp#");
+        assert_eq!(name_pattern("NORM0012.mp4"), name_pattern("norm0013.mp4"));
+        assert_ne!(name_pattern("NORM0012.mp4"), name_pattern("EVNT0012.mp4"));
+    }
+
+    #[test]
+    fn contiguous_dashcam_clips_form_one_session() {
+        let files = [
+            clip("NORM0003.mp4", 180),
+            clip("NORM0001.mp4", 60),
+            clip("NORM0002.mp4", 121),
+        ];
+        assert_eq!(
+            names(&detect_sessions(&files)),
+            [["NORM0001.mp4", "NORM0002.mp4", "NORM0003.mp4"]]
+        );
+    }
+
+    #[test]
+    fn long_gap_splits_sessions() {
+        let gap = 60 + MAX_GAP_SECS + 1;
+        let files = [
+            clip("NORM0001.mp4", 60),
+            clip("NORM0002.mp4", 120),
+            clip("NORM0003.mp4", 120 + gap),
+            clip("NORM0004.mp4", 180 + gap),
+        ];
+        assert_eq!(
+            names(&detect_sessions(&files)),
+            [
+                ["NORM0001.mp4", "NORM0002.mp4"],
+                ["NORM0003.mp4", "NORM0004.mp4"]
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_changes_split_sessions() {
+        let mut resized = clip("NORM0002.mp4", 120);
+        resized.width = 1280;
+        resized.height = 720;
+        assert!(!belongs_to_session(&clip("NORM0001.mp4", 60), &resized));
+
+        let mut recoded = clip("NORM0002.mp4", 120);
+        recoded.codec = "HEVC".to_string();
+        assert!(!belongs_to_session(&clip("NORM0001.mp4", 60), &recoded));
+
+        let files = [
+            clip("NORM0001.mp4", 60),
+            clip("NORM0002.mp4", 120),
+            recoded,
+            clip("NORM0004.mp4", 240),
+        ];
+        assert_eq!(
+            names(&detect_sessions(&files)),
+            [["NORM0001.mp4", "NORM0002.mp4"]]
+        );
+    }
+
+    #[test]
+    fn single_clips_are_dropped() {
+        let files = [
+            clip("NORM0001.mp4", 60),
+            clip("EVNT0001.mp4", 120),
+            clip("NORM0002.mp4", 600),
+        ];
+        assert!(detect_sessions(&files).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+//! 长时间任务期间阻止系统休眠
+use std::process::Child;
+
+/// 持有期间阻止系统进入睡眠，离开作用域时自动释放。多个任务同时持有时互不影响
+pub struct SleepInhibitor {
+    child: Option<Child>,
+    /// Windows 电源请求句柄，每个实例单独创建，与线程无关
+    #[cfg(target_os = "windows")]
+    request: Option<isize>,
+}
+
+impl SleepInhibitor {
+    /// 申请阻止休眠，`reason` 会显示在支持的系统中（如 Linux 的 systemd-inhibit）
+    pub fn acquire(reason: &str) -> Self {
+        #[cfg(target_os = "windows")]
+        {
+            // SetThreadExecutionState 是按线程保存且不计数的状态，并发任务或在其他线程上
+            // 释放时会互相覆盖；电源请求按句柄计数，各实例独立设置和清除
+            Self {
+                child: None,
+                request: windows_power::create_request(reason),
+            }
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let _ = reason;
+            let child = std::process::Command::new("caffeinate")
+                .args(["-i", "-w", &std::process::id().to_string()])
+                .spawn()
+                .map_err(|e| eprintln!("无法启动 caffeinate: {}", e))
+                .ok();
+            Self { child }
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let child = std::process::Command::new("systemd-inhibit")
+                .args([
+                    "--what=sleep:idle",
+                    "--who=merge-mp4",
+                    &format!("--why={}", reason),
+                    "--mode=block",
+                    "sleep",
+                    "infinity",
+                ])
+                .spawn()
+                .map_err(|e| eprintln!("无法启动 systemd-inhibit: {}", e))
+                .ok();
+            Self { child }
+        }
+        #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+        {
+            let _ = reason;
+            Self { child: None }
+        }
+    }
+}
+
+impl Drop for SleepInhibitor {
+    fn drop(&mut self) {
+        #[cfg(target_os = "windows")]
+        if let Some(request) = self.request.take() {
+            windows_power::clear_request(request);
+        }
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows_power {
+    use std::ffi::c_void;
+
+    const POWER_REQUEST_CONTEXT_VERSION: u32 = 0;
+    const POWER_REQUEST_CONTEXT_SIMPLE_STRING: u32 = 0x1;
+    /// POWER_REQUEST_TYPE::PowerRequestSystemRequired
+    const POWER_REQUEST_SYSTEM_REQUIRED: i32 = 1;
+    const INVALID_HANDLE_VALUE: isize = -1;
+
+    // REASON_CONTEXT，原因联合体只使用简单字符串，其余字段按最大变体补齐
+    #[repr(C)]
+    struct ReasonContext {
+        version: u32,
+        flags: u32,
+        simple_reason_string: *const u16,
+        _detailed_rest: [u64; 2],
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn PowerCreateRequest(context: *const ReasonContext) -> *mut c_void;
+        fn PowerSetRequest(request: *mut c_void, request_type: i32) -> i32;
+        fn PowerClearRequest(request: *mut c_void, request_type: i32) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    /// 创建并设置“需要系统保持运行”的电源请求，失败时返回 None
+    pub fn create_request(reason: &str) -> Option<isize> {
+        let reason: Vec<u16> = reason.encode_utf16().chain(Some(0)).collect();
+        let context = ReasonContext {
+            version: POWER_REQUEST_CONTEXT_VERSION,
+            flags: POWER_REQUEST_CONTEXT_SIMPLE_STRING,
+            simple_reason_string: reason.as_ptr(),
+            _detailed_rest: [0; 2],
+        };
+        // SAFETY: context 与其中以 0 结尾的字符串在调用期间有效，系统会复制原因字符串；
+        // 返回的句柄只在本模块中使用，并由 clear_request 关闭
+        unsafe {
+            let handle = PowerCreateRequest(&context);
+            if handle.is_null() || handle as isize == INVALID_HANDLE_VALUE {
+                eprintln!("无法创建电源请求");
+                return None;
+            }
+            if PowerSetRequest(handle, POWER_REQUEST_SYSTEM_REQUIRED) == 0 {
+                eprintln!("无法设置电源请求");
+                CloseHandle(handle);
+                return None;
+            }
+            Some(handle as isize)
+        }
+    }
+
+    /// 清除并关闭 create_request 返回的电源请求
+    pub fn clear_request(request: isize) {
+        let handle = request as *mut c_void;
+        // SAFETY: handle 来自 create_request 且只关闭一次（调用方已 take）
+        unsafe {
+            PowerClearRequest(handle, POWER_REQUEST_SYSTEM_REQUIRED);
+            CloseHandle(handle);
+        }
+    }
+}
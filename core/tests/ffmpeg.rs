@@ -0,0 +1,183 @@
+//! 需要本机安装 FFmpeg 的端到端测试，用 `cargo test -p merge-mp4-core --features ffmpeg-tests` 运行。
+//! 夹具是现场生成的彩条视频，编码与分辨率各不相同
+#![cfg(feature = "ffmpeg-tests")]
+
+use futures::StreamExt;
+use futures::channel::mpsc::unbounded;
+use merge_mp4_core::MergeEvent;
+use merge_mp4_core::ffmpeg::merge_mp4::{AfterMerge, run_ffmpeg_merge, write_concat_list};
+use merge_mp4_core::ffmpeg::normalize::{NormalizeOptions, plan_normalization};
+use merge_mp4_core::ffmpeg::options::{MergeMode, MergeOptions};
+use merge_mp4_core::ffmpeg::probe::{get_video_duration, probe_video_stream};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// 生成带音轨的彩条视频
+fn color_bars(path: &Path, codec: &str, size: &str, secs: u32) {
+    let status = Command::new("ffmpeg")
+        .args(["-v", "error", "-y", "-f", "lavfi", "-i"])
+        .arg(format!("smptebars=size={}:rate=25:duration={}", size, secs))
+        .args(["-f", "lavfi", "-i"])
+        .arg(format!("sine=frequency=440:duration={}", secs))
+        .args([
+            "-c:v",
+            codec,
+            "-pix_fmt",
+            "yuv420p",
+            "-c:a",
+            "aac",
+            "-shortest",
+        ])
+        .arg(path)
+        .status()
+        .expect("无法启动 ffmpeg");
+    assert!(status.success(), "生成夹具失败: {}", path.display());
+}
+
+// 历史记录等数据写到测试程序旁的便携目录，不影响本机的真实数据
+fn fixture_dir() -> tempfile::TempDir {
+    let exe_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    fs::write(exe_dir.join("portable.flag"), b"").unwrap();
+    tempfile::tempdir().unwrap()
+}
+
+fn no_after_merge() -> AfterMerge {
+    AfterMerge {
+        checksums: false,
+        archive_folder: "已合并".to_string(),
+        post_command: None,
+    }
+}
+
+// 执行合并并返回全部事件
+async fn merge(files: Vec<PathBuf>, output: PathBuf, options: MergeOptions) -> Vec<MergeEvent> {
+    let (tx, rx) = unbounded();
+    run_ffmpeg_merge(files, output, options, no_after_merge(), tx).await;
+    rx.collect().await
+}
+
+fn outcome(events: &[MergeEvent]) -> Result<String, String> {
+    events
+        .iter()
+        .rev()
+        .find_map(|e| match e {
+            MergeEvent::Success(msg) => Some(Ok(msg.clone())),
+            MergeEvent::Error(e) => Some(Err(e.to_string())),
+            _ => None,
+        })
+        .expect("合并没有报告结果")
+}
+
+#[tokio::test]
+async fn probing_reads_duration_and_stream() {
+    let temp = fixture_dir();
+    let dir = temp.path();
+    let path = dir.join("bars.mp4");
+    color_bars(&path, "libx264", "320x240", 2);
+
+    let duration = get_video_duration(&path).await.unwrap();
+    assert!((duration - 2.0).abs() < 0.1, "时长 {}", duration);
+    let info = probe_video_stream(&path).await.unwrap();
+    assert_eq!(info.codec, "h264");
+    assert_eq!((info.width, info.height), (320, 240));
+    assert!((info.fps - 25.0).abs() < 0.01);
+
+    let mpeg4 = dir.join("bars-mpeg4.mp4");
+    color_bars(&mpeg4, "mpeg4", "640x480", 1);
+    let info = probe_video_stream(&mpeg4).await.unwrap();
+    assert_eq!(info.codec, "mpeg4");
+    assert_eq!((info.width, info.height), (640, 480));
+}
+
+#[test]
+fn concat_list_escapes_quotes() {
+    let temp = fixture_dir();
+    let dir = temp.path();
+    let files = vec![dir.join("a.mp4"), dir.join("it's.mp4")];
+    for file in &files {
+        fs::write(file, b"").unwrap();
+    }
+    let list = dir.join("concat.txt");
+    write_concat_list(&list, &files).unwrap();
+
+    let content = fs::read_to_string(&list).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    let a = fs::canonicalize(&files[0]).unwrap();
+    assert_eq!(lines[0], format!("file '{}'", a.display()));
+    assert!(lines[1].ends_with("it'\\''s.mp4'"), "{}", lines[1]);
+
+    assert!(write_concat_list(&list, &[dir.join("missing.mp4")]).is_err());
+}
+
+#[tokio::test]
+async fn copy_merge_joins_matching_clips() {
+    let temp = fixture_dir();
+    let dir = temp.path();
+    let files: Vec<PathBuf> = (1..=2).map(|i| dir.join(format!("{}.mp4", i))).collect();
+    for file in &files {
+        color_bars(file, "libx264", "320x240", 2);
+    }
+    let output = dir.join("merged.mp4");
+
+    let events = merge(files, output.clone(), MergeOptions::default()).await;
+    outcome(&events).unwrap();
+    assert!(events.iter().any(|e| matches!(
+        e,
+        MergeEvent::Probing {
+            index: 2,
+            total: 2,
+            ..
+        }
+    )));
+    let duration = get_video_duration(&output).await.unwrap();
+    assert!((duration - 4.0).abs() < 0.2, "时长 {}", duration);
+}
+
+#[tokio::test]
+async fn mismatched_clips_are_normalized_when_reencoding() {
+    let temp = fixture_dir();
+    let dir = temp.path();
+    let small = dir.join("small.mp4");
+    let large = dir.join("large.mp4");
+    color_bars(&small, "libx264", "320x240", 1);
+    color_bars(&large, "libx264", "640x480", 1);
+    let files = vec![small.clone(), large];
+
+    let normalize = NormalizeOptions::default();
+    let plan = plan_normalization(&files, &normalize).await.unwrap();
+    assert_eq!((plan.width, plan.height), (640, 480));
+    let needs_change: Vec<bool> = plan.inputs.iter().map(|(_, i)| plan.changes(i)).collect();
+    assert_eq!(needs_change, vec![true, false]);
+
+    let output = dir.join("merged.mp4");
+    let options = MergeOptions {
+        mode: MergeMode::ReEncode,
+        normalize: Some(normalize),
+        ..MergeOptions::default()
+    };
+    outcome(&merge(files, output.clone(), options).await).unwrap();
+    let info = probe_video_stream(&output).await.unwrap();
+    assert_eq!(info.codec, "h264");
+    assert_eq!((info.width, info.height), (640, 480));
+}
+
+#[tokio::test]
+async fn missing_input_is_reported() {
+    let temp = fixture_dir();
+    let dir = temp.path();
+    let output = dir.join("merged.mp4");
+    let events = merge(
+        vec![dir.join("missing.mp4")],
+        output.clone(),
+        MergeOptions::default(),
+    )
+    .await;
+    let error = outcome(&events).unwrap_err();
+    assert!(error.starts_with("文件不存在"), "{}", error);
+    assert!(!output.exists());
+}
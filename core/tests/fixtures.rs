@@ -0,0 +1,113 @@
+//! 使用运行时生成的小型 MP4 夹具测试不依赖 FFmpeg 的部分
+use bytes::Bytes;
+use merge_mp4_core::history::{compute_checksums, verify_checksums};
+use merge_mp4_core::utils::{archive_files, detect_sessions, parse_mp4_info};
+use mp4::{AvcConfig, MediaConfig, Mp4Config, Mp4Sample, Mp4Writer, TrackConfig, TrackType};
+use std::fs::File;
+use std::path::Path;
+
+// 写入只含一条 H.264 轨道的 MP4，每秒一个关键帧
+fn write_fixture(path: &Path, width: u16, height: u16, secs: u32) {
+    let config = Mp4Config {
+        major_brand: str::parse("isom").unwrap(),
+        minor_version: 512,
+        compatible_brands: vec![
+            str::parse("isom").unwrap(),
+            str::parse("avc1").unwrap(),
+            str::parse("mp41").unwrap(),
+        ],
+        timescale: 1000,
+    };
+    let mut writer = Mp4Writer::write_start(File::create(path).unwrap(), &config).unwrap();
+    writer
+        .add_track(&TrackConfig {
+            track_type: TrackType::Video,
+            timescale: 1000,
+            language: "und".to_string(),
+            media_conf: MediaConfig::AvcConfig(AvcConfig {
+                width,
+                height,
+                seq_param_set: vec![0x67, 0x64, 0x00, 0x1f, 0xac, 0xd9],
+                pic_param_set: vec![0x68, 0xeb, 0xe3, 0xcb],
+            }),
+        })
+        .unwrap();
+    for i in 0..secs {
+        writer
+            .write_sample(
+                1,
+                &Mp4Sample {
+                    start_time: i as u64 * 1000,
+                    duration: 1000,
+                    rendering_offset: 0,
+                    is_sync: true,
+                    bytes: Bytes::from(vec![i as u8; 64]),
+                },
+            )
+            .unwrap();
+    }
+    writer.write_end().unwrap();
+}
+
+#[test]
+fn fixture_info_is_parsed() {
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let path = dir.join("clip.mp4");
+    write_fixture(&path, 320, 240, 3);
+
+    let info = parse_mp4_info(path.clone()).unwrap();
+    assert_eq!((info.width, info.height), (320, 240));
+    assert_eq!(info.codec, "H.264 / AVC");
+    assert_eq!(info.duration.as_millis(), 3000);
+    assert_eq!(info.file_path, path);
+}
+
+#[test]
+fn numbered_fixtures_form_a_session() {
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let infos: Vec<_> = (1..=3)
+        .map(|i| {
+            let path = dir.join(format!("GX01000{}.mp4", i));
+            write_fixture(&path, 640, 360, 2);
+            parse_mp4_info(path).unwrap()
+        })
+        .collect();
+    let other = dir.join("other.mp4");
+    write_fixture(&other, 320, 240, 2);
+
+    let mut all = infos.clone();
+    all.push(parse_mp4_info(other).unwrap());
+    let sessions = detect_sessions(&all);
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].len(), 3);
+}
+
+#[test]
+fn checksums_detect_changes_and_archived_inputs() {
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let inputs = vec![dir.join("a.mp4"), dir.join("b.mp4")];
+    for input in &inputs {
+        write_fixture(input, 320, 240, 1);
+    }
+    let output = dir.join("merged.mp4");
+    write_fixture(&output, 320, 240, 2);
+
+    let checksums = compute_checksums(&output, &inputs).unwrap();
+    assert_eq!(checksums[0].path, output);
+    assert_eq!(verify_checksums(&checksums), Ok(3));
+
+    let moved = archive_files(&inputs, "已合并").unwrap();
+    let archive_dir = dir.join("已合并");
+    assert!(
+        moved
+            .iter()
+            .all(|p| p.parent() == Some(archive_dir.as_path()))
+    );
+    assert_eq!(verify_checksums(&checksums).map_err(|p| p.len()), Err(2));
+
+    write_fixture(&output, 320, 240, 3);
+    assert!(verify_checksums(&checksums[..1]).is_err());
+}
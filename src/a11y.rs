@@ -0,0 +1,37 @@
+use dioxus::html::Key;
+use dioxus::prelude::*;
+
+// 打开对话框前记下焦点所在的元素，可嵌套
+const SAVE_FOCUS_JS: &str = "(window.__focusStack ??= []).push(document.activeElement);";
+// 关闭后把焦点还回去，元素已被移除时不处理
+const RESTORE_FOCUS_JS: &str = r#"
+const el = (window.__focusStack ??= []).pop();
+if (el && el.isConnected && typeof el.focus === "function") el.focus();
+"#;
+
+/// 是否是激活可聚焦元素的按键（Enter 或空格），用于带 `onclick` 的非按钮元素
+pub fn is_activation_key(evt: &KeyboardEvent) -> bool {
+    match evt.key() {
+        Key::Enter => true,
+        Key::Character(s) => s == " ",
+        _ => false,
+    }
+}
+
+/// 对话框关闭后把焦点还给打开它的元素，键盘用户不会被带回页面开头。
+/// `is_open` 读取的信号变化时重新判断，应在组件提前返回之前调用
+pub fn use_restore_focus(is_open: impl Fn() -> bool + 'static) {
+    let mut was_open: Signal<bool> = use_signal(|| false);
+    use_effect(move || {
+        let open = is_open();
+        if open == *was_open.peek() {
+            return;
+        }
+        was_open.set(open);
+        document::eval(if open {
+            SAVE_FOCUS_JS
+        } else {
+            RESTORE_FOCUS_JS
+        });
+    });
+}
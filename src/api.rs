@@ -0,0 +1,413 @@
+//! 本机 HTTP 接口：提交合并任务、查询队列、通过 WebSocket 推送进度，
+//! 供 OBS 脚本、家庭自动化等外部程序使用。只监听 127.0.0.1，请求需携带令牌
+use crate::config::{ApiSettings, AppConfig};
+use crate::queue::{JobQueue, JobStatus, MergeJob, QUEUE_PAUSED, use_queue_runner};
+use crate::utils::record_log;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::AbortHandle;
+
+/// 接口返回的任务状态
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct JobInfo {
+    pub id: u64,
+    pub name: String,
+    /// pending / running / done / failed
+    pub status: &'static str,
+    pub error: Option<String>,
+    pub progress: f64,
+    pub message: String,
+    pub output_path: PathBuf,
+}
+
+impl From<&MergeJob> for JobInfo {
+    fn from(job: &MergeJob) -> Self {
+        let (status, error) = match &job.status {
+            JobStatus::Pending => ("pending", None),
+            JobStatus::Running => ("running", None),
+            JobStatus::Done => ("done", None),
+            JobStatus::Failed(e) => ("failed", Some(e.clone())),
+        };
+        Self {
+            id: job.id,
+            name: job.name.clone(),
+            status,
+            error,
+            progress: job.progress,
+            message: job.status_message.clone(),
+            output_path: job.output_path.clone(),
+        }
+    }
+}
+
+/// 提交任务的请求体。输出一律放在设置中的输出目录，请求只能指定文件名
+#[derive(Debug, Deserialize)]
+pub struct SubmitRequest {
+    pub files: Vec<PathBuf>,
+    /// 输出文件名，不能包含目录
+    #[serde(default)]
+    pub output_path: Option<PathBuf>,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 使用的预设名称，未指定时使用设置中的默认合并选项。
+    /// 不接受请求中的合并选项：其中的原文件处理与后处理步骤会移动或删除本机文件
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// 加入后立即开始执行队列
+    #[serde(default)]
+    pub start: bool,
+}
+
+// 需要在界面线程中处理的请求
+enum ApiCommand {
+    Submit(SubmitRequest, oneshot::Sender<Result<u64, String>>),
+    Start,
+}
+
+#[derive(Clone)]
+struct ApiState {
+    token: String,
+    jobs: Arc<Mutex<Vec<JobInfo>>>,
+    events: broadcast::Sender<JobInfo>,
+    commands: UnboundedSender<ApiCommand>,
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+/// 用系统安全随机数生成 16 字节令牌，返回十六进制字符串
+pub fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| format!("无法生成令牌: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// 按设置启动或停止接口服务，并把队列变化推送给已连接的客户端，应在根组件调用一次
+pub fn use_api_server(config: Signal<AppConfig>, mut queue: JobQueue) {
+    let runner = use_queue_runner();
+    let jobs = use_hook(|| Arc::new(Mutex::new(Vec::<JobInfo>::new())));
+    let events = use_hook(|| broadcast::channel::<JobInfo>(256).0);
+    let mut server: Signal<Option<(ApiSettings, AbortHandle)>> = use_signal(|| None);
+
+    let commands = use_coroutine(move |mut rx: UnboundedReceiver<ApiCommand>| async move {
+        while let Some(command) = rx.next().await {
+            match command {
+                ApiCommand::Submit(request, reply) => {
+                    let settings = config.peek();
+                    let options = match &request.preset {
+                        Some(name) => match settings.presets.iter().find(|p| &p.name == name) {
+                            Some(preset) => preset.options.clone(),
+                            None => {
+                                let _ = reply.send(Err(format!("预设不存在: {}", name)));
+                                continue;
+                            }
+                        },
+                        None => settings.default_options.clone(),
+                    };
+                    let file_name = request.output_path.unwrap_or_else(|| {
+                        let stem = request.files[0]
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_else(|| "api".to_string());
+                        PathBuf::from(format!("{}_merged.mp4", stem))
+                    });
+                    let output_path = settings
+                        .output_directory_for(&request.files)
+                        .join(file_name);
+                    let job = MergeJob::new(
+                        request.name.unwrap_or_else(|| {
+                            format!("接口提交的 {} 个文件", request.files.len())
+                        }),
+                        request.files,
+                        output_path,
+                        options,
+                    );
+                    drop(settings);
+                    let _ = reply.send(Ok(job.id));
+                    queue.write().push(job);
+                    if request.start {
+                        runner.start();
+                    }
+                }
+                ApiCommand::Start => runner.start(),
+            }
+        }
+    });
+
+    // 队列变化时更新快照并推送有变化的任务
+    let snapshot = jobs.clone();
+    let sender = events.clone();
+    use_effect(move || {
+        let current: Vec<JobInfo> = queue.read().iter().map(JobInfo::from).collect();
+        let mut previous = snapshot.lock().unwrap_or_else(|e| e.into_inner());
+        for job in &current {
+            if !previous.contains(job) {
+                // 没有客户端连接时发送失败，忽略即可
+                let _ = sender.send(job.clone());
+            }
+        }
+        *previous = current;
+    });
+
+    // 设置变化时重启服务
+    use_effect(move || {
+        let settings = config.read().api.clone();
+        if server.peek().as_ref().map(|(s, _)| s) == Some(&settings) {
+            return;
+        }
+        if let Some((_, handle)) = server.take() {
+            handle.abort();
+        }
+        if !settings.enabled || settings.token.is_empty() {
+            return;
+        }
+        let state = ApiState {
+            token: settings.token.clone(),
+            jobs: jobs.clone(),
+            events: events.clone(),
+            commands: commands.tx(),
+        };
+        let port = settings.port;
+        let handle = tokio::spawn(async move {
+            if let Err(e) = serve(state, port).await {
+                let message = format!("本机接口启动失败: {}", e);
+                eprintln!("{}", message);
+                record_log(&message);
+            }
+        })
+        .abort_handle();
+        server.set(Some((settings, handle)));
+    });
+
+    use_drop(move || {
+        if let Some((_, handle)) = server.take() {
+            handle.abort();
+        }
+    });
+}
+
+async fn serve(state: ApiState, port: u16) -> Result<(), std::io::Error> {
+    let app = Router::new()
+        .route("/api/queue", get(list_jobs))
+        .route("/api/queue/start", post(start_queue))
+        .route("/api/queue/pause", post(pause_queue))
+        .route("/api/jobs", post(submit_job))
+        .route("/api/events", get(stream_events))
+        .with_state(state);
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    axum::serve(listener, app).await
+}
+
+// 令牌可放在 Authorization: Bearer 请求头或 ?token= 查询参数中（WebSocket 客户端常用后者）
+fn request_token<'a>(headers: &'a HeaderMap, query: &'a TokenQuery) -> Option<&'a str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .or(query.token.as_deref())
+}
+
+fn check_token(state: &ApiState, headers: &HeaderMap, query: &TokenQuery) -> Result<(), Response> {
+    if request_token(headers, query).is_some_and(|token| tokens_match(token, &state.token)) {
+        Ok(())
+    } else {
+        Err(error_response(StatusCode::UNAUTHORIZED, "令牌无效"))
+    }
+}
+
+// 比较全部字节后才给出结果，耗时与第一个不同字节的位置无关
+fn tokens_match(given: &str, expected: &str) -> bool {
+    let (given, expected) = (given.as_bytes(), expected.as_bytes());
+    !expected.is_empty()
+        && given.len() == expected.len()
+        && given
+            .iter()
+            .zip(expected)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+async fn list_jobs(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+) -> Response {
+    if let Err(response) = check_token(&state, &headers, &query) {
+        return response;
+    }
+    let jobs = state.jobs.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    Json(jobs).into_response()
+}
+
+async fn start_queue(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+) -> Response {
+    if let Err(response) = check_token(&state, &headers, &query) {
+        return response;
+    }
+    let _ = state.commands.unbounded_send(ApiCommand::Start);
+    StatusCode::ACCEPTED.into_response()
+}
+
+async fn pause_queue(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+) -> Response {
+    if let Err(response) = check_token(&state, &headers, &query) {
+        return response;
+    }
+    QUEUE_PAUSED.store(true, Ordering::SeqCst);
+    StatusCode::ACCEPTED.into_response()
+}
+
+async fn submit_job(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+    Json(request): Json<SubmitRequest>,
+) -> Response {
+    if let Err(response) = check_token(&state, &headers, &query) {
+        return response;
+    }
+    if request.files.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "文件列表为空");
+    }
+    if let Some(missing) = request.files.iter().find(|f| !f.is_file()) {
+        let message = format!("文件不存在: {}", missing.display());
+        return error_response(StatusCode::BAD_REQUEST, &message);
+    }
+    if let Some(output) = &request.output_path
+        && !is_plain_file_name(output)
+    {
+        return error_response(StatusCode::BAD_REQUEST, "输出只能指定文件名，不能包含目录");
+    }
+    let (reply, id) = oneshot::channel();
+    if state
+        .commands
+        .unbounded_send(ApiCommand::Submit(request, reply))
+        .is_err()
+    {
+        return error_response(StatusCode::SERVICE_UNAVAILABLE, "程序正在退出");
+    }
+    match id.await {
+        Ok(Ok(id)) => (StatusCode::CREATED, Json(json!({ "id": id }))).into_response(),
+        Ok(Err(message)) => error_response(StatusCode::BAD_REQUEST, &message),
+        Err(_) => error_response(StatusCode::SERVICE_UNAVAILABLE, "程序正在退出"),
+    }
+}
+
+// 只由一个普通部分组成的路径，不含目录、`..` 或盘符
+fn is_plain_file_name(path: &Path) -> bool {
+    let mut components = path.components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
+
+// 连接后先发送当前所有任务，之后每个任务有变化时发送一条 JSON
+async fn stream_events(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<TokenQuery>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if let Err(response) = check_token(&state, &headers, &query) {
+        return response;
+    }
+    ws.on_upgrade(move |socket| push_events(socket, state))
+}
+
+async fn push_events(mut socket: WebSocket, state: ApiState) {
+    let mut events = state.events.subscribe();
+    let jobs = state.jobs.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    for job in jobs {
+        if send_job(&mut socket, &job).await.is_err() {
+            return;
+        }
+    }
+    loop {
+        match events.recv().await {
+            Ok(job) => {
+                if send_job(&mut socket, &job).await.is_err() {
+                    return;
+                }
+            }
+            // 客户端处理太慢时跳过积压的中间进度
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+async fn send_job(socket: &mut WebSocket, job: &JobInfo) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(job).unwrap_or_default();
+    socket.send(Message::Text(text.into())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn token_is_read_from_header_or_query() {
+        let mut headers = HeaderMap::new();
+        let query = TokenQuery {
+            token: Some("from-query".to_string()),
+        };
+        assert_eq!(request_token(&headers, &query), Some("from-query"));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer from-header"),
+        );
+        assert_eq!(request_token(&headers, &query), Some("from-header"));
+        assert_eq!(
+            request_token(&HeaderMap::new(), &TokenQuery { token: None }),
+            None
+        );
+    }
+
+    #[test]
+    fn output_must_be_a_plain_file_name() {
+        assert!(is_plain_file_name(Path::new("merged.mp4")));
+        assert!(!is_plain_file_name(Path::new("../merged.mp4")));
+        assert!(!is_plain_file_name(Path::new("sub/merged.mp4")));
+        assert!(!is_plain_file_name(Path::new("/tmp/merged.mp4")));
+        assert!(!is_plain_file_name(Path::new("")));
+    }
+
+    #[test]
+    fn tokens_are_random_and_compared_exactly() {
+        let token = generate_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, generate_token().unwrap());
+        assert!(tokens_match(&token, &token.clone()));
+        assert!(!tokens_match(&token[..31], &token));
+        let last = if token.ends_with('0') { '1' } else { '0' };
+        assert!(!tokens_match(&format!("{}{}", &token[..31], last), &token));
+        assert!(!tokens_match("", ""));
+    }
+}
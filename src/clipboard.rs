@@ -0,0 +1,174 @@
+use dioxus::prelude::*;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// 一次粘贴得到的路径，`seq` 用于区分连续两次相同内容的粘贴
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasteEvent {
+    pub paths: Vec<PathBuf>,
+    pub seq: u64,
+}
+
+pub type PasteEvents = Signal<Option<PasteEvent>>;
+
+// 页面脚本发来的粘贴内容
+#[derive(Debug, Deserialize)]
+struct PastePayload {
+    text: String,
+    /// 剪贴板中是否有从资源管理器/访达复制的文件
+    has_files: bool,
+}
+
+// 在 document 上监听粘贴：输入框内的粘贴留给输入框本身
+const LISTENER_JS: &str = r#"
+document.addEventListener('paste', (e) => {
+    const t = e.target;
+    if (t && (t.isContentEditable || ['INPUT', 'TEXTAREA'].includes(t.tagName))) return;
+    const data = e.clipboardData;
+    if (!data) return;
+    const text = data.getData('text/uri-list') || data.getData('text/plain') || '';
+    const hasFiles = data.files && data.files.length > 0;
+    if (!text && !hasFiles) return;
+    e.preventDefault();
+    dioxus.send({ text, has_files: hasFiles });
+});
+"#;
+
+/// 安装全局粘贴监听并提供 [`PasteEvents`] 上下文，应在根组件调用一次
+pub fn use_paste_listener() -> PasteEvents {
+    let mut events: PasteEvents = use_context_provider(|| Signal::new(None));
+    let listener = use_hook(|| document::eval(LISTENER_JS));
+    use_future(move || async move {
+        let mut listener = listener;
+        let mut seq = 0;
+        while let Ok(payload) = listener.recv::<PastePayload>().await {
+            let mut paths = parse_pasted_paths(&payload.text);
+            // 网页中拿不到复制文件的完整路径，改从系统剪贴板读取
+            if paths.is_empty() && payload.has_files {
+                paths = read_clipboard_files().await;
+            }
+            if !paths.is_empty() {
+                seq += 1;
+                events.set(Some(PasteEvent { paths, seq }));
+            }
+        }
+    });
+    events
+}
+
+/// 在组件中响应粘贴的路径
+pub fn use_paste(mut handler: impl FnMut(Vec<PathBuf>) + 'static) {
+    let events = use_context::<PasteEvents>();
+    // 挂载前已发生的粘贴不再处理
+    let mut handled = use_signal(|| events.peek().as_ref().map(|e| e.seq));
+    use_effect(move || {
+        if let Some(event) = events()
+            && Some(event.seq) != *handled.peek()
+        {
+            handled.set(Some(event.seq));
+            handler(event.paths);
+        }
+    });
+}
+
+/// 把文本写入系统剪贴板
+pub fn copy_text(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("无法写入剪贴板: {}", e))
+}
+
+/// 把粘贴的文本拆成路径：每行一个，去掉引号，支持 file:// 形式
+pub fn parse_pasted_paths(text: &str) -> Vec<PathBuf> {
+    text.lines().filter_map(parse_path_line).collect()
+}
+
+/// 解析一行路径文本，空行与 # 开头的注释返回 None
+pub fn parse_path_line(line: &str) -> Option<PathBuf> {
+    let line = line.trim().trim_matches('"').trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    Some(match line.strip_prefix("file://") {
+        Some(rest) => PathBuf::from(file_uri_path(rest)),
+        None => PathBuf::from(line),
+    })
+}
+
+// file:///C:/a%20b.mp4 → C:/a b.mp4，file:///home/a.mp4 → /home/a.mp4
+fn file_uri_path(rest: &str) -> String {
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    let bytes = rest.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = rest
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    let path = String::from_utf8_lossy(&decoded).into_owned();
+    // Windows 盘符前多出的斜杠
+    if path.len() > 2 && path.starts_with('/') && path.as_bytes()[2] == b':' {
+        path[1..].to_string()
+    } else {
+        path
+    }
+}
+
+/// 读取系统剪贴板中复制的文件列表
+async fn read_clipboard_files() -> Vec<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let output = tokio::process::Command::new("powershell")
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .args([
+            "-NoProfile",
+            "-Command",
+            "[Console]::OutputEncoding = [Text.Encoding]::UTF8; Get-Clipboard -Format FileDropList | ForEach-Object { $_.FullName }",
+        ])
+        .output()
+        .await;
+    #[cfg(target_os = "macos")]
+    let output = tokio::process::Command::new("osascript")
+        .args(["-e", "POSIX path of (the clipboard as «class furl»)"])
+        .output()
+        .await;
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let output = tokio::process::Command::new("xclip")
+        .args(["-selection", "clipboard", "-o", "-t", "text/uri-list"])
+        .output()
+        .await;
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_pasted_paths(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pasted_text_is_split_into_paths() {
+        let text =
+            "\"C:\\videos\\a.mp4\"\r\n\r\nfile:///C:/videos/b%20c.mp4\nfile:///home/u/d.mp4\n";
+        assert_eq!(
+            parse_pasted_paths(text),
+            vec![
+                PathBuf::from("C:\\videos\\a.mp4"),
+                PathBuf::from("C:/videos/b c.mp4"),
+                PathBuf::from("/home/u/d.mp4"),
+            ]
+        );
+    }
+}
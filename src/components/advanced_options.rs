@@ -0,0 +1,808 @@
+use super::button::{Button, ButtonVariant};
+use super::input::Input;
+use super::post_steps::PostStepsEditor;
+use crate::config::AppConfig;
+use crate::ffmpeg::audio_track::{AudioTrackMode, ExternalAudio};
+use crate::ffmpeg::crossfade::CrossfadeOptions;
+use crate::ffmpeg::encoder::{EncoderPreset, HardwareEncoder};
+use crate::ffmpeg::normalize::{FpsTarget, ResolutionTarget};
+use crate::ffmpeg::options::{MergeMode, MergeOptions, MergePreset};
+use crate::ffmpeg::title_card::TitleCardOptions;
+use crate::ffmpeg::watermark::{WatermarkKind, WatermarkOptions, WatermarkPosition};
+use dioxus::prelude::*;
+
+const FIXED_RESOLUTIONS: [(u32, u32); 3] = [(3840, 2160), (1920, 1080), (1280, 720)];
+const FIXED_FPS: [u32; 4] = [24, 25, 30, 60];
+
+// 高级选项：预设管理、片头/片尾、外部音频与重新编码时的转场、标题卡、水印、分辨率/帧率、画质设置和合并后处理
+#[component]
+pub fn AdvancedOptions(
+    merge_options: Signal<MergeOptions>,
+    config: Signal<AppConfig>,
+    error_message: Signal<Option<String>>,
+) -> Element {
+    let mut expanded: Signal<bool> = use_signal(|| false);
+    let mut preset_name: Signal<String> = use_signal(String::new);
+
+    let save_preset = move |_| {
+        let name = preset_name().trim().to_string();
+        if name.is_empty() {
+            error_message.set(Some("请输入预设名称".to_string()));
+            return;
+        }
+        let mut options = merge_options();
+        // 片段速度与剪切与具体文件相关，不随预设保存
+        options.clip_speeds.clear();
+        options.clip_cuts.clear();
+        {
+            let mut config_guard = config.write();
+            config_guard.presets.retain(|p| p.name != name);
+            config_guard.presets.push(MergePreset { name, options });
+        }
+        if let Err(e) = config.read().save() {
+            error_message.set(Some(format!("无法保存预设: {}", e)));
+        }
+        preset_name.set(String::new());
+    };
+
+    let apply_preset = move |evt: FormEvent| {
+        let name = evt.value();
+        let preset = config
+            .read()
+            .presets
+            .iter()
+            .find(|p| p.name == name)
+            .cloned();
+        if let Some(preset) = preset {
+            let mut options = preset.options;
+            options.clip_speeds = merge_options.peek().clip_speeds.clone();
+            options.clip_cuts = merge_options.peek().clip_cuts.clone();
+            merge_options.set(options);
+        }
+    };
+
+    let delete_preset = move |name: String| {
+        config.write().presets.retain(|p| p.name != name);
+        if let Err(e) = config.read().save() {
+            error_message.set(Some(format!("无法删除预设: {}", e)));
+        }
+    };
+
+    let pick_watermark_image = move |_| async move {
+        if let Some(file) = rfd::AsyncFileDialog::new()
+            .add_filter("PNG", &["png"])
+            .set_title("选择水印图片")
+            .pick_file()
+            .await
+        {
+            let mut options = merge_options.write();
+            let watermark = options.watermark.get_or_insert_with(default_watermark);
+            watermark.kind = WatermarkKind::Image(file.path().to_path_buf());
+        }
+    };
+
+    let pick_external_audio = move |_| async move {
+        if let Some(file) = rfd::AsyncFileDialog::new()
+            .add_filter("音频", &["mp3", "m4a", "aac", "wav", "flac", "ogg"])
+            .set_title("选择外部音频")
+            .pick_file()
+            .await
+        {
+            let mut options = merge_options.write();
+            let audio = options
+                .external_audio
+                .get_or_insert_with(ExternalAudio::default);
+            audio.path = file.path().to_path_buf();
+        }
+    };
+
+    // 选择片头（outro 为 false）或片尾
+    let pick_intro_outro = move |outro: bool| {
+        spawn(async move {
+            let title = if outro {
+                "选择片尾"
+            } else {
+                "选择片头"
+            };
+            if let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("视频", &["mp4", "mov", "mkv"])
+                .set_title(title)
+                .pick_file()
+                .await
+            {
+                let path = Some(file.path().to_path_buf());
+                let mut options = merge_options.write();
+                if outro {
+                    options.outro = path;
+                } else {
+                    options.intro = path;
+                }
+            }
+        });
+    };
+
+    let is_reencode = merge_options.read().mode == MergeMode::ReEncode;
+    let is_copy = merge_options.read().mode == MergeMode::Copy;
+    let is_video = matches!(
+        merge_options.read().mode,
+        MergeMode::Copy | MergeMode::ReEncode
+    );
+    let watermark = merge_options.read().watermark.clone();
+    let external_audio = merge_options.read().external_audio.clone();
+    let normalize = merge_options.read().normalize;
+    let quality = merge_options.read().quality;
+    let crossfade = merge_options.read().crossfade;
+    let title_cards = merge_options.read().title_cards.clone();
+    let precise_cuts = merge_options.read().precise_cuts;
+    let intro_outro = [
+        ("片头", merge_options.read().intro.clone(), false),
+        ("片尾", merge_options.read().outro.clone(), true),
+    ];
+
+    rsx! {
+        div { class: "mt-3",
+            button {
+                class: "text-sm text-gray-400 hover:text-gray-200",
+                onclick: move |_| expanded.toggle(),
+                if expanded() {
+                    "▾ 高级选项"
+                } else {
+                    "▸ 高级选项"
+                }
+            }
+            if expanded() {
+                div { class: "mt-2 space-y-3 text-sm",
+                    // 预设
+                    div { class: "flex items-center gap-2",
+                        span { class: "text-gray-400", "预设:" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            onchange: apply_preset,
+                            option { value: "", "选择预设..." }
+                            for preset in config.read().presets.iter() {
+                                option { value: "{preset.name}", "{preset.name}" }
+                            }
+                        }
+                        Input {
+                            placeholder: "预设名称",
+                            value: "{preset_name}",
+                            oninput: move |e: FormEvent| preset_name.set(e.value()),
+                        }
+                        Button { variant: ButtonVariant::Secondary, onclick: save_preset, "保存为预设" }
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            title: "新建工作区和历史记录对比时使用",
+                            onclick: move |_| {
+                                let mut options = merge_options();
+                                options.clip_speeds.clear();
+                                options.clip_cuts.clear();
+                                config.write().default_options = options;
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存默认选项: {}", e)));
+                                }
+                            },
+                            "设为默认"
+                        }
+                    }
+                    if !config.read().presets.is_empty() {
+                        div { class: "flex flex-wrap gap-2",
+                            for preset in config.read().presets.iter().cloned() {
+                                span { class: "px-2 py-0.5 rounded border border-gray-600 text-xs flex items-center gap-1",
+                                    "{preset.name}"
+                                    button {
+                                        class: "text-red-400",
+                                        title: "删除预设",
+                                        onclick: move |_| delete_preset(preset.name.clone()),
+                                        "✕"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // 水印
+                    div { class: "space-y-2",
+                        div { class: "flex items-center gap-2",
+                            span { class: "text-gray-400", "水印:" }
+                            select {
+                                class: "border rounded px-2 py-1 text-sm",
+                                disabled: !is_reencode,
+                                onchange: move |evt| {
+                                    let mut options = merge_options.write();
+                                    match evt.value().as_str() {
+                                        "text" => {
+                                            let watermark = options.watermark.get_or_insert_with(default_watermark);
+                                            if !matches!(watermark.kind, WatermarkKind::Text { .. }) {
+                                                watermark.kind = WatermarkKind::Text {
+                                                    text: String::new(),
+                                                    font_size: 24,
+                                                };
+                                            }
+                                        }
+                                        "image" => {
+                                            let watermark = options.watermark.get_or_insert_with(default_watermark);
+                                            if !matches!(watermark.kind, WatermarkKind::Image(_)) {
+                                                watermark.kind = WatermarkKind::Image(Default::default());
+                                            }
+                                        }
+                                        _ => options.watermark = None,
+                                    }
+                                },
+                                option { value: "none", selected: watermark.is_none(), "无" }
+                                option {
+                                    value: "text",
+                                    selected: matches!(watermark.as_ref().map(|w| &w.kind), Some(WatermarkKind::Text { .. })),
+                                    "文字"
+                                }
+                                option {
+                                    value: "image",
+                                    selected: matches!(watermark.as_ref().map(|w| &w.kind), Some(WatermarkKind::Image(_))),
+                                    "PNG 图片"
+                                }
+                            }
+                            if !is_reencode {
+                                span { class: "text-xs text-gray-500", "仅在“重新编码”模式下可用" }
+                            }
+                        }
+                        if let Some(watermark) = watermark.filter(|_| is_reencode) {
+                            {
+                                match watermark.kind.clone() {
+                                    WatermarkKind::Text { text, font_size } => rsx! {
+                                        div { class: "flex items-center gap-2",
+                                            Input {
+                                                placeholder: "水印文字",
+                                                value: "{text}",
+                                                oninput: move |e: FormEvent| {
+                                                    if let Some(w) = merge_options.write().watermark.as_mut() {
+                                                        w.kind = WatermarkKind::Text { text: e.value(), font_size };
+                                                    }
+                                                },
+                                            }
+                                            span { class: "text-gray-400", "字号" }
+                                            input {
+                                                r#type: "number",
+                                                class: "w-16 px-2 py-1 border rounded text-sm",
+                                                min: "8",
+                                                value: "{font_size}",
+                                                onchange: move |e| {
+                                                    if let Ok(size) = e.value().parse::<u32>()
+                                                        && let Some(w) = merge_options.write().watermark.as_mut()
+                                                        && let WatermarkKind::Text { font_size, .. } = &mut w.kind
+                                                    {
+                                                        *font_size = size;
+                                                    }
+                                                },
+                                            }
+                                        }
+                                    },
+                                    WatermarkKind::Image(path) => rsx! {
+                                        div { class: "flex items-center gap-2",
+                                            span { class: "flex-1 text-gray-300 break-all",
+                                                if path.as_os_str().is_empty() {
+                                                    "未选择图片"
+                                                } else {
+                                                    "{path.display()}"
+                                                }
+                                            }
+                                            Button { variant: ButtonVariant::Secondary, onclick: pick_watermark_image, "选择图片" }
+                                        }
+                                    },
+                                }
+                            }
+                            div { class: "flex items-center gap-2",
+                                span { class: "text-gray-400", "位置" }
+                                select {
+                                    class: "border rounded px-2 py-1 text-sm",
+                                    onchange: move |evt| {
+                                        if let Some(position) = evt
+                                            .value()
+                                            .parse::<usize>()
+                                            .ok()
+                                            .and_then(|i| WatermarkPosition::ALL.get(i))
+                                            && let Some(w) = merge_options.write().watermark.as_mut()
+                                        {
+                                            w.position = *position;
+                                        }
+                                    },
+                                    for (index , position) in WatermarkPosition::ALL.iter().enumerate() {
+                                        option {
+                                            value: "{index}",
+                                            selected: watermark.position == *position,
+                                            {position.label()}
+                                        }
+                                    }
+                                }
+                                span { class: "text-gray-400", "不透明度" }
+                                input {
+                                    r#type: "range",
+                                    min: "0",
+                                    max: "100",
+                                    value: "{(watermark.opacity * 100.0).round()}",
+                                    oninput: move |e| {
+                                        if let Ok(v) = e.value().parse::<f32>()
+                                            && let Some(w) = merge_options.write().watermark.as_mut()
+                                        {
+                                            w.opacity = v / 100.0;
+                                        }
+                                    },
+                                }
+                                span { class: "text-gray-400", "边距" }
+                                input {
+                                    r#type: "number",
+                                    class: "w-16 px-2 py-1 border rounded text-sm",
+                                    min: "0",
+                                    value: "{watermark.margin}",
+                                    onchange: move |e| {
+                                        if let Ok(v) = e.value().parse::<u32>()
+                                            && let Some(w) = merge_options.write().watermark.as_mut()
+                                        {
+                                            w.margin = v;
+                                        }
+                                    },
+                                }
+                            }
+                        }
+                    }
+
+                    // 片头/片尾
+                    div { class: "space-y-1",
+                        for (label , path , outro) in intro_outro {
+                            div { class: "flex items-center gap-2",
+                                span { class: "text-gray-400", "{label}:" }
+                                span { class: "flex-1 text-gray-300 break-all",
+                                    match &path {
+                                        Some(path) => path.display().to_string(),
+                                        None => "无".to_string(),
+                                    }
+                                }
+                                Button {
+                                    variant: ButtonVariant::Secondary,
+                                    onclick: move |_| pick_intro_outro(outro),
+                                    "选择"
+                                }
+                                if path.is_some() {
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: move |_| {
+                                            let mut options = merge_options.write();
+                                            if outro {
+                                                options.outro = None;
+                                            } else {
+                                                options.intro = None;
+                                            }
+                                        },
+                                        "清除"
+                                    }
+                                }
+                            }
+                        }
+                        p { class: "text-xs text-gray-500",
+                            "随预设保存，每次合并自动加在最前/最后；参数与正片不同时会先转换格式"
+                        }
+                    }
+
+                    // 转场
+                    div { class: "space-y-1",
+                        div { class: "flex items-center gap-2",
+                            label { class: "flex items-center gap-1",
+                                input {
+                                    r#type: "checkbox",
+                                    disabled: !is_reencode,
+                                    checked: crossfade.is_some(),
+                                    onchange: move |e| {
+                                        merge_options.write().crossfade = e.checked().then(CrossfadeOptions::default);
+                                    },
+                                }
+                                span { class: "text-gray-400", "片段间交叉淡化" }
+                            }
+                            if let Some(crossfade) = crossfade {
+                                input {
+                                    r#type: "number",
+                                    class: "w-16 px-2 py-1 border rounded text-sm",
+                                    min: "0.1",
+                                    step: "0.1",
+                                    disabled: !is_reencode,
+                                    value: "{crossfade.duration}",
+                                    onchange: move |e| {
+                                        if let Ok(v) = e.value().parse::<f64>()
+                                            && v > 0.0
+                                            && let Some(c) = merge_options.write().crossfade.as_mut()
+                                        {
+                                            c.duration = v;
+                                        }
+                                    },
+                                }
+                                span { class: "text-gray-400", "秒" }
+                            }
+                            if !is_reencode {
+                                span { class: "text-xs text-gray-500", "仅在“重新编码”模式下可用" }
+                            }
+                        }
+                        if crossfade.is_some() && is_reencode {
+                            p { class: "text-xs text-amber-600",
+                                "每个片段都会单独解码并统一分辨率/帧率，编码时间明显增加；所有片段都需要包含音轨"
+                            }
+                        }
+                    }
+
+                    // 标题卡
+                    div { class: "space-y-1",
+                        div { class: "flex items-center gap-2",
+                            label { class: "flex items-center gap-1",
+                                input {
+                                    r#type: "checkbox",
+                                    disabled: !is_reencode,
+                                    checked: title_cards.is_some(),
+                                    onchange: move |e| {
+                                        merge_options.write().title_cards = e.checked().then(TitleCardOptions::default);
+                                    },
+                                }
+                                span { class: "text-gray-400", "片段前插入标题卡" }
+                            }
+                            if !is_reencode {
+                                span { class: "text-xs text-gray-500", "仅在“重新编码”模式下可用" }
+                            }
+                        }
+                        if let Some(cards) = title_cards.filter(|_| is_reencode) {
+                            div { class: "flex items-center gap-2",
+                                Input {
+                                    placeholder: "{{name}} 为文件名，{{index}} 为序号",
+                                    value: "{cards.text}",
+                                    oninput: move |e: FormEvent| {
+                                        if let Some(c) = merge_options.write().title_cards.as_mut() {
+                                            c.text = e.value();
+                                        }
+                                    },
+                                }
+                                span { class: "text-gray-400", "时长" }
+                                input {
+                                    r#type: "number",
+                                    class: "w-16 px-2 py-1 border rounded text-sm",
+                                    min: "0.5",
+                                    step: "0.5",
+                                    value: "{cards.duration}",
+                                    onchange: move |e| {
+                                        if let Ok(v) = e.value().parse::<f64>()
+                                            && v > 0.0
+                                            && let Some(c) = merge_options.write().title_cards.as_mut()
+                                        {
+                                            c.duration = v;
+                                        }
+                                    },
+                                }
+                                span { class: "text-gray-400", "秒" }
+                                span { class: "text-gray-400", "字号" }
+                                input {
+                                    r#type: "number",
+                                    class: "w-16 px-2 py-1 border rounded text-sm",
+                                    min: "8",
+                                    value: "{cards.font_size}",
+                                    onchange: move |e| {
+                                        if let Ok(v) = e.value().parse::<u32>()
+                                            && let Some(c) = merge_options.write().title_cards.as_mut()
+                                        {
+                                            c.font_size = v;
+                                        }
+                                    },
+                                }
+                                span { class: "text-gray-400", "背景" }
+                                input {
+                                    r#type: "color",
+                                    value: if cards.background.starts_with('#') { cards.background.clone() } else { "#000000".to_string() },
+                                    onchange: move |e| {
+                                        if let Some(c) = merge_options.write().title_cards.as_mut() {
+                                            c.background = e.value();
+                                        }
+                                    },
+                                }
+                            }
+                        }
+                    }
+
+                    // 精确剪切
+                    div { class: "space-y-1",
+                        div { class: "flex items-center gap-2",
+                            label { class: "flex items-center gap-1",
+                                input {
+                                    r#type: "checkbox",
+                                    disabled: !is_copy,
+                                    checked: precise_cuts,
+                                    onchange: move |e| merge_options.write().precise_cuts = e.checked(),
+                                }
+                                span { class: "text-gray-400", "精确剪切" }
+                            }
+                            if !is_copy {
+                                span { class: "text-xs text-gray-500", "仅在“直接复制”模式下需要，重新编码本身就是精确的" }
+                            }
+                        }
+                        p { class: "text-xs text-gray-500",
+                            "剪切点不再对齐到关键帧：只重新编码切点附近的画面，其余部分直接复制"
+                        }
+                    }
+
+                    // 外部音频
+                    div { class: "space-y-2",
+                        div { class: "flex items-center gap-2",
+                            span { class: "text-gray-400", "外部音频:" }
+                            select {
+                                class: "border rounded px-2 py-1 text-sm",
+                                disabled: !is_video,
+                                onchange: move |evt| {
+                                    let mode = evt
+                                        .value()
+                                        .parse::<usize>()
+                                        .ok()
+                                        .and_then(|i| AudioTrackMode::ALL.get(i).copied());
+                                    let mut options = merge_options.write();
+                                    match mode {
+                                        Some(mode) => {
+                                            options.external_audio.get_or_insert_with(ExternalAudio::default).mode = mode;
+                                        }
+                                        None => options.external_audio = None,
+                                    }
+                                },
+                                option { value: "none", selected: external_audio.is_none(), "无" }
+                                for (index , mode) in AudioTrackMode::ALL.iter().enumerate() {
+                                    option {
+                                        value: "{index}",
+                                        selected: external_audio.as_ref().map(|a| a.mode) == Some(*mode),
+                                        {mode.label()}
+                                    }
+                                }
+                            }
+                            if !is_video {
+                                span { class: "text-xs text-gray-500", "仅在视频模式下可用" }
+                            }
+                        }
+                        if let Some(audio) = external_audio.filter(|_| is_video) {
+                            div { class: "flex items-center gap-2",
+                                span { class: "flex-1 text-gray-300 break-all",
+                                    if audio.path.as_os_str().is_empty() {
+                                        "未选择音频"
+                                    } else {
+                                        "{audio.path.display()}"
+                                    }
+                                }
+                                Button { variant: ButtonVariant::Secondary, onclick: pick_external_audio, "选择音频" }
+                            }
+                            div { class: "flex items-center gap-2",
+                                span { class: "text-gray-400", "偏移" }
+                                input {
+                                    r#type: "number",
+                                    class: "w-20 px-2 py-1 border rounded text-sm",
+                                    step: "0.1",
+                                    title: "正数延后开始播放，负数跳过音频开头",
+                                    value: "{audio.offset}",
+                                    onchange: move |e| {
+                                        if let Ok(v) = e.value().parse::<f64>()
+                                            && let Some(a) = merge_options.write().external_audio.as_mut()
+                                        {
+                                            a.offset = v;
+                                        }
+                                    },
+                                }
+                                span { class: "text-gray-400", "秒" }
+                                span { class: "text-gray-400", "音量" }
+                                input {
+                                    r#type: "range",
+                                    min: "0",
+                                    max: "200",
+                                    value: "{(audio.volume * 100.0).round()}",
+                                    oninput: move |e| {
+                                        if let Ok(v) = e.value().parse::<f32>()
+                                            && let Some(a) = merge_options.write().external_audio.as_mut()
+                                        {
+                                            a.volume = v / 100.0;
+                                        }
+                                    },
+                                }
+                                span { class: "text-gray-400 w-10", {format!("{:.0}%", audio.volume * 100.0)} }
+                                if audio.mode == AudioTrackMode::Mix {
+                                    span { class: "text-gray-400", "原声音量" }
+                                    input {
+                                        r#type: "range",
+                                        min: "0",
+                                        max: "200",
+                                        value: "{(audio.original_volume * 100.0).round()}",
+                                        oninput: move |e| {
+                                            if let Ok(v) = e.value().parse::<f32>()
+                                                && let Some(a) = merge_options.write().external_audio.as_mut()
+                                            {
+                                                a.original_volume = v / 100.0;
+                                            }
+                                        },
+                                    }
+                                    span { class: "text-gray-400 w-10", {format!("{:.0}%", audio.original_volume * 100.0)} }
+                                }
+                            }
+                            if merge_options.read().mode == MergeMode::Copy {
+                                p { class: "text-xs text-gray-500", "视频流仍直接复制，只重新编码音频" }
+                            }
+                        }
+                    }
+
+                    // 编码器
+                    div { class: "flex items-center gap-2",
+                        span { class: "text-gray-400", "编码器:" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            disabled: !is_reencode,
+                            onchange: move |evt| {
+                                merge_options.write().quality.hardware = evt
+                                    .value()
+                                    .parse::<usize>()
+                                    .ok()
+                                    .and_then(|i| HardwareEncoder::ALL.get(i).copied());
+                            },
+                            option { value: "", selected: quality.hardware.is_none(), "CPU (libx264)" }
+                            for (index , encoder) in HardwareEncoder::ALL.iter().enumerate() {
+                                option {
+                                    value: "{index}",
+                                    selected: quality.hardware == Some(*encoder),
+                                    "{encoder.label()} ({encoder.codec()})"
+                                }
+                            }
+                        }
+                        if quality.hardware.is_some() {
+                            span { class: "text-xs text-gray-500", "需要对应的显卡与驱动，不支持两遍编码" }
+                        }
+                    }
+
+                    // 画质
+                    div { class: "flex items-center gap-2",
+                        span { class: "text-gray-400", "编码速度:" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            disabled: !is_reencode || quality.hardware.is_some(),
+                            onchange: move |evt| {
+                                if let Some(preset) = evt
+                                    .value()
+                                    .parse::<usize>()
+                                    .ok()
+                                    .and_then(|i| EncoderPreset::ALL.get(i))
+                                {
+                                    merge_options.write().quality.preset = *preset;
+                                }
+                            },
+                            for (index , preset) in EncoderPreset::ALL.iter().enumerate() {
+                                option {
+                                    value: "{index}",
+                                    selected: quality.preset == *preset,
+                                    "{preset.label()} ({preset.as_str()})"
+                                }
+                            }
+                        }
+                        span { class: "text-gray-400", "CRF" }
+                        input {
+                            r#type: "number",
+                            class: "w-16 px-2 py-1 border rounded text-sm",
+                            min: "0",
+                            max: "51",
+                            disabled: !is_reencode || quality.two_pass_bitrate.is_some(),
+                            value: "{quality.crf}",
+                            onchange: move |e| {
+                                if let Ok(crf) = e.value().parse::<u8>() {
+                                    merge_options.write().quality.crf = crf.min(51);
+                                }
+                            },
+                        }
+                        label { class: "flex items-center gap-1",
+                            input {
+                                r#type: "checkbox",
+                                disabled: !is_reencode || quality.hardware.is_some(),
+                                checked: quality.is_two_pass(),
+                                onchange: move |e| {
+                                    merge_options.write().quality.two_pass_bitrate = if e.checked() {
+                                        Some(4000)
+                                    } else {
+                                        None
+                                    };
+                                },
+                            }
+                            span { class: "text-gray-400", "两遍编码" }
+                        }
+                        if let Some(bitrate) = quality.two_pass_bitrate {
+                            input {
+                                r#type: "number",
+                                class: "w-20 px-2 py-1 border rounded text-sm",
+                                min: "100",
+                                disabled: !is_reencode,
+                                value: "{bitrate}",
+                                onchange: move |e| {
+                                    if let Ok(v) = e.value().parse::<u32>()
+                                        && v > 0
+                                    {
+                                        merge_options.write().quality.two_pass_bitrate = Some(v);
+                                    }
+                                },
+                            }
+                            span { class: "text-gray-400", "kbps" }
+                        }
+                    }
+
+                    // 分辨率/帧率统一
+                    div { class: "flex items-center gap-2",
+                        span { class: "text-gray-400", "统一分辨率:" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            disabled: !is_reencode,
+                            onchange: move |evt| {
+                                let resolution = match evt.value().as_str() {
+                                    "largest" => Some(ResolutionTarget::Largest),
+                                    value => value.split_once('x').and_then(|(w, h)| {
+                                        Some(ResolutionTarget::Fixed {
+                                            width: w.parse().ok()?,
+                                            height: h.parse().ok()?,
+                                        })
+                                    }),
+                                };
+                                let mut options = merge_options.write();
+                                match resolution {
+                                    Some(resolution) => {
+                                        options.normalize.get_or_insert_with(Default::default).resolution = resolution;
+                                    }
+                                    None => options.normalize = None,
+                                }
+                            },
+                            option { value: "none", selected: normalize.is_none(), "不统一" }
+                            option {
+                                value: "largest",
+                                selected: normalize.map(|n| n.resolution) == Some(ResolutionTarget::Largest),
+                                "最大分辨率"
+                            }
+                            for (width , height) in FIXED_RESOLUTIONS {
+                                option {
+                                    value: "{width}x{height}",
+                                    selected: normalize.map(|n| n.resolution) == Some(ResolutionTarget::Fixed { width, height }),
+                                    "{width}x{height}"
+                                }
+                            }
+                        }
+                        span { class: "text-gray-400", "帧率:" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            disabled: !is_reencode || normalize.is_none(),
+                            onchange: move |evt| {
+                                let fps = evt
+                                    .value()
+                                    .parse::<u32>()
+                                    .map(FpsTarget::Fixed)
+                                    .unwrap_or(FpsTarget::Highest);
+                                if let Some(n) = merge_options.write().normalize.as_mut() {
+                                    n.fps = fps;
+                                }
+                            },
+                            option {
+                                value: "highest",
+                                selected: normalize.map(|n| n.fps) == Some(FpsTarget::Highest),
+                                "最高帧率"
+                            }
+                            for fps in FIXED_FPS {
+                                option {
+                                    value: "{fps}",
+                                    selected: normalize.map(|n| n.fps) == Some(FpsTarget::Fixed(fps)),
+                                    "{fps} fps"
+                                }
+                            }
+                        }
+                    }
+
+                    // 合并后处理
+                    PostStepsEditor { merge_options }
+                }
+            }
+        }
+    }
+}
+
+fn default_watermark() -> WatermarkOptions {
+    WatermarkOptions {
+        kind: WatermarkKind::Text {
+            text: String::new(),
+            font_size: 24,
+        },
+        position: WatermarkPosition::default(),
+        opacity: 0.8,
+        margin: 10,
+    }
+}
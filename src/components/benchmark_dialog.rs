@@ -0,0 +1,122 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::ffmpeg::benchmark::{BenchmarkTarget, estimate_total_secs, prepare_sample};
+use crate::ffmpeg::options::{MergeMode, MergeOptions};
+use crate::utils::format_duration;
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+// 耗时估算对话框：截取一小段分别测速，按总时长换算各处理方式的预计耗时
+#[component]
+pub fn BenchmarkDialog(
+    open: Signal<bool>,
+    files: Signal<Vec<PathBuf>>,
+    merge_options: Signal<MergeOptions>,
+) -> Element {
+    use_restore_focus(move || open());
+    let mut results: Signal<Vec<(BenchmarkTarget, Result<f64, String>)>> = use_signal(Vec::new);
+    let mut is_running: Signal<bool> = use_signal(|| false);
+    let mut error_message: Signal<Option<String>> = use_signal(|| None);
+
+    let start_benchmark = move |_| async move {
+        is_running.set(true);
+        results.set(Vec::new());
+        error_message.set(None);
+        match prepare_sample(&files()).await {
+            Ok(sample) => {
+                for target in BenchmarkTarget::all() {
+                    // 关闭对话框后不再继续测
+                    if !open() {
+                        break;
+                    }
+                    let estimate = estimate_total_secs(&sample, target)
+                        .await
+                        .map_err(|e| e.to_string());
+                    results.write().push((target, estimate));
+                }
+            }
+            Err(e) => error_message.set(Some(e.to_string())),
+        }
+        is_running.set(false);
+    };
+
+    let mut apply = move |target: BenchmarkTarget| {
+        let mut options = merge_options.write();
+        match target {
+            BenchmarkTarget::Copy => options.mode = MergeMode::Copy,
+            BenchmarkTarget::ReEncode(preset) => {
+                options.mode = MergeMode::ReEncode;
+                options.quality.preset = preset;
+            }
+        }
+        drop(options);
+        open.set(false);
+    };
+
+    let total = BenchmarkTarget::all().len();
+
+    rsx! {
+        AlertDialogRoot {
+            open: open(),
+            on_open_change: move |v: bool| {
+                if !v {
+                    open.set(false);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "估算耗时" }
+                AlertDialogDescription {
+                    "截取第一个文件中的一小段分别处理，按总时长估算，实际耗时会因内容而异"
+                }
+                div { class: "space-y-2 text-sm",
+                    if is_running() {
+                        p { class: "text-gray-500", "测速中 ({results.read().len()}/{total})..." }
+                    }
+                    if let Some(e) = error_message() {
+                        p { class: "text-red-500 break-all", "{e}" }
+                    }
+                    if !results.read().is_empty() {
+                        table { class: "w-full",
+                            tbody {
+                                for (target , estimate) in results.read().iter().cloned() {
+                                    tr { class: "border-t border-gray-700",
+                                        td { class: "py-1 pr-2", "{target.label()}" }
+                                        {
+                                            match estimate {
+                                                Ok(secs) => rsx! {
+                                                    td { class: "py-1 pr-2 font-mono", "约 {format_duration(secs)}" }
+                                                    td { class: "py-1 text-right",
+                                                        Button { variant: ButtonVariant::Ghost, onclick: move |_| apply(target), "使用" }
+                                                    }
+                                                },
+                                                Err(e) => rsx! {
+                                                    td { class: "py-1 text-red-500 break-all", colspan: "2", "{e}" }
+                                                },
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button {
+                        disabled: is_running() || files.read().is_empty(),
+                        onclick: start_benchmark,
+                        if is_running() {
+                            "测速中..."
+                        } else {
+                            "开始测速"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
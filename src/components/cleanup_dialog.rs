@@ -0,0 +1,323 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::components::confirm_dialog::{Confirmation, confirm, use_confirm};
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::config::{AppConfig, CleanupRules};
+use crate::utils::{
+    CleanupCandidate, PermissionChoice, cleanup_candidates, delete_files_elevated, format_bytes,
+    format_size, prompt_permission_denied, recycle_files, sha256_file,
+};
+use dioxus::prelude::*;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+// 清理建议对话框：按规则分析扫描结果，列出可删除的文件供逐个确认后批量处理
+#[component]
+pub fn CleanupDialog(
+    open: Signal<bool>,
+    files: Signal<Vec<Mp4FileInfo>>,
+    unreadable: Signal<Vec<(PathBuf, String)>>,
+    config: Signal<AppConfig>,
+) -> Element {
+    use_restore_focus(move || open());
+    let mut candidates: Signal<Option<Vec<CleanupCandidate>>> = use_signal(|| None);
+    // 勾选要处理的文件，分析后默认全选
+    let mut checked: Signal<HashSet<PathBuf>> = use_signal(HashSet::new);
+    let mut is_busy: Signal<bool> = use_signal(|| false);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+    let confirm_requests = use_confirm();
+
+    let mut update_rules = move |change: &dyn Fn(&mut CleanupRules)| {
+        change(&mut config.write().cleanup_rules);
+        if let Err(e) = config.read().save() {
+            message.set(Some(format!("无法保存设置: {}", e)));
+        }
+        // 规则变化后之前的结果不再准确
+        candidates.set(None);
+    };
+
+    let analyze = move |_| async move {
+        is_busy.set(true);
+        message.set(None);
+        let files = files();
+        let unreadable = unreadable();
+        let rules = config.read().cleanup_rules.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            cleanup_candidates(&files, &unreadable, &rules, SystemTime::now(), |path| {
+                sha256_file(path).ok()
+            })
+        })
+        .await;
+        match result {
+            Ok(found) => {
+                checked.set(found.iter().map(|c| c.path.clone()).collect());
+                candidates.set(Some(found));
+            }
+            Err(e) => message.set(Some(format!("分析失败: {}", e))),
+        }
+        is_busy.set(false);
+    };
+
+    // 处理勾选的文件，permanent 为 false 时移到回收站
+    let mut remove_checked = move |permanent: bool| {
+        let targets: Vec<PathBuf> = candidates
+            .read()
+            .iter()
+            .flatten()
+            .map(|c| c.path.clone())
+            .filter(|p| checked.read().contains(p))
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        spawn(async move {
+            if permanent {
+                let confirmation = Confirmation::new(
+                    "确认永久删除",
+                    format!(
+                        "确定要永久删除选中的 {} 个文件吗？\n此操作不可撤销。",
+                        targets.len()
+                    ),
+                )
+                .action("删除")
+                .destructive();
+                if !confirm(confirm_requests, confirmation).await {
+                    return;
+                }
+            }
+            is_busy.set(true);
+            let mut removed = Vec::new();
+            let mut failed = Vec::new();
+            let mut denied = Vec::new();
+            for path in targets {
+                if permanent {
+                    match std::fs::remove_file(&path) {
+                        Ok(()) => removed.push(path),
+                        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                            denied.push(path)
+                        }
+                        Err(e) => failed.push(format!("{}: {}", path.display(), e)),
+                    }
+                } else {
+                    match recycle_files(std::slice::from_ref(&path)).await {
+                        Ok(()) => removed.push(path),
+                        Err(e) => failed.push(format!("{}: {}", path.display(), e)),
+                    }
+                }
+            }
+            // 没有权限的文件询问是否以管理员身份重试
+            if !denied.is_empty() {
+                let mut reason = "没有权限，已跳过".to_string();
+                if prompt_permission_denied(confirm_requests, &denied, "删除").await
+                    == PermissionChoice::RetryElevated
+                {
+                    match delete_files_elevated(&denied).await {
+                        Ok(deleted) => {
+                            denied.retain(|p| !deleted.contains(p));
+                            removed.extend(deleted);
+                            reason = "以管理员身份删除仍失败".to_string();
+                        }
+                        Err(e) => reason = e.to_string(),
+                    }
+                }
+                failed.extend(
+                    denied
+                        .iter()
+                        .map(|p| format!("{}: {}", p.display(), reason)),
+                );
+            }
+
+            files.write().retain(|f| !removed.contains(&f.file_path));
+            unreadable.write().retain(|(p, _)| !removed.contains(p));
+            if let Some(list) = candidates.write().as_mut() {
+                list.retain(|c| !removed.contains(&c.path));
+            }
+            checked.write().retain(|p| !removed.contains(p));
+            let action = if permanent {
+                "删除"
+            } else {
+                "移到回收站"
+            };
+            message.set(Some(if failed.is_empty() {
+                format!("已{} {} 个文件", action, removed.len())
+            } else {
+                format!(
+                    "已{} {} 个文件，失败 {} 个：\n{}",
+                    action,
+                    removed.len(),
+                    failed.len(),
+                    failed.join("\n")
+                )
+            }));
+            is_busy.set(false);
+        });
+    };
+
+    let rules = config.read().cleanup_rules.clone();
+    let checked_size: u64 = candidates
+        .read()
+        .iter()
+        .flatten()
+        .filter(|c| checked.read().contains(&c.path))
+        .map(|c| c.size)
+        .sum();
+
+    rsx! {
+        AlertDialogRoot {
+            open: open(),
+            on_open_change: move |v: bool| {
+                if !v && !is_busy() {
+                    open.set(false);
+                    message.set(None);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "清理建议" }
+                AlertDialogDescription {
+                    "找出空文件、极短片段、无法解析的文件、重复文件和过期片段，确认后批量处理"
+                }
+                div { class: "space-y-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-24 text-gray-500", "最短时长" }
+                        input {
+                            r#type: "number",
+                            class: "w-20 px-2 py-1 border rounded text-sm",
+                            min: "0",
+                            step: "1",
+                            value: "{rules.min_duration_secs}",
+                            onchange: move |e| {
+                                if let Ok(v) = e.value().parse::<f64>()
+                                    && v >= 0.0
+                                {
+                                    update_rules(&|r| r.min_duration_secs = v);
+                                }
+                            },
+                        }
+                        span { class: "text-gray-500", "秒，更短的片段视为误录" }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-24 text-gray-500", "过期片段" }
+                        input {
+                            r#type: "number",
+                            class: "w-20 px-2 py-1 border rounded text-sm",
+                            min: "0",
+                            value: "{rules.stale_days}",
+                            onchange: move |e| {
+                                if let Ok(v) = e.value().parse::<u32>() {
+                                    update_rules(&|r| r.stale_days = v);
+                                }
+                            },
+                        }
+                        span { class: "text-gray-500", "天前，0 表示不检查" }
+                        input {
+                            class: "flex-1 border rounded px-1 text-sm font-mono",
+                            placeholder: "文件名匹配，如 *rec*，留空匹配全部",
+                            value: "{rules.stale_pattern}",
+                            onchange: move |e| {
+                                let pattern = e.value().trim().to_string();
+                                update_rules(&|r| r.stale_pattern = pattern.clone());
+                            },
+                        }
+                    }
+                    if let Some(list) = candidates.read().as_ref() {
+                        if list.is_empty() {
+                            p { class: "text-green-600", "没有需要清理的文件" }
+                        } else {
+                            div { class: "flex items-center gap-2 text-gray-600",
+                                span { "共 {list.len()} 个建议，已选 {checked.read().len()} 个（{format_size(Some(checked_size))}）" }
+                                button {
+                                    class: "text-blue-600 hover:underline",
+                                    onclick: move |_| {
+                                        let all = candidates
+                                            .read()
+                                            .iter()
+                                            .flatten()
+                                            .map(|c| c.path.clone())
+                                            .collect();
+                                        checked.set(all);
+                                    },
+                                    "全选"
+                                }
+                                button {
+                                    class: "text-blue-600 hover:underline",
+                                    onclick: move |_| checked.write().clear(),
+                                    "全不选"
+                                }
+                            }
+                            div { class: "max-h-64 overflow-auto border rounded divide-y",
+                                for candidate in list.iter().cloned() {
+                                    label { class: "flex items-center gap-2 px-2 py-1",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: checked.read().contains(&candidate.path),
+                                            onchange: {
+                                                let path = candidate.path.clone();
+                                                move |e: FormEvent| {
+                                                    if e.checked() {
+                                                        checked.write().insert(path.clone());
+                                                    } else {
+                                                        checked.write().remove(&path);
+                                                    }
+                                                }
+                                            },
+                                        }
+                                        span {
+                                            class: "flex-1 truncate",
+                                            title: "{candidate.path.display()}",
+                                            {candidate.path.file_name().unwrap_or_default().to_string_lossy().into_owned()}
+                                        }
+                                        span { class: "text-xs text-amber-600 truncate max-w-48",
+                                            title: candidate.reason.label(),
+                                            {candidate.reason.label()}
+                                        }
+                                        span {
+                                            class: "text-xs text-gray-500 w-16 text-right",
+                                            title: format_bytes(candidate.size),
+                                            {format_size(Some(candidate.size))}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(message) = message() {
+                        p { class: "text-gray-600 whitespace-pre-wrap break-all", "{message}" }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        disabled: is_busy(),
+                        onclick: analyze,
+                        if is_busy() {
+                            "处理中..."
+                        } else if candidates.read().is_some() {
+                            "重新分析"
+                        } else {
+                            "开始分析"
+                        }
+                    }
+                    if candidates.read().as_ref().is_some_and(|list| !list.is_empty()) {
+                        Button {
+                            disabled: is_busy() || checked.read().is_empty(),
+                            onclick: move |_| remove_checked(false),
+                            "移到回收站"
+                        }
+                        Button {
+                            variant: ButtonVariant::Destructive,
+                            disabled: is_busy() || checked.read().is_empty(),
+                            onclick: move |_| remove_checked(true),
+                            "永久删除"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use crate::MergeEvent;
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::Button;
+use crate::components::input::Input;
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::components::progress::{Progress, ProgressIndicator};
+use crate::ffmpeg::clip_export::{ClipExportOptions, ClipFormat, run_clip_export};
+use crate::utils::{format_size, parse_timestamp};
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+// 导出GIF/片段对话框，target 为 None 时关闭
+#[component]
+pub fn ClipExportDialog(target: Signal<Option<Mp4FileInfo>>) -> Element {
+    use_restore_focus(move || target.read().is_some());
+    let mut start_input: Signal<String> = use_signal(|| "0".to_string());
+    let mut duration_input: Signal<String> = use_signal(|| "5".to_string());
+    let mut width_input: Signal<String> = use_signal(|| "480".to_string());
+    let mut fps_input: Signal<String> = use_signal(|| "15".to_string());
+    let mut format: Signal<ClipFormat> = use_signal(|| ClipFormat::Gif);
+    let mut progress: Signal<f64> = use_signal(|| 0.0);
+    let mut is_exporting: Signal<bool> = use_signal(|| false);
+    let mut result_message: Signal<Option<String>> = use_signal(|| None);
+
+    // 根据输入构造导出参数，无效时返回错误说明
+    let build_options = move || -> Result<ClipExportOptions, String> {
+        let start = parse_timestamp(&start_input()).ok_or("开始时间格式不正确")?;
+        let duration = parse_timestamp(&duration_input())
+            .filter(|d| *d > 0.0)
+            .ok_or("时长必须大于 0")?;
+        let optional_u32 = |s: String| -> Result<Option<u32>, String> {
+            let s = s.trim().to_string();
+            if s.is_empty() {
+                Ok(None)
+            } else {
+                s.parse::<u32>()
+                    .map(Some)
+                    .map_err(|_| format!("无效的数字: {}", s))
+            }
+        };
+        Ok(ClipExportOptions {
+            start,
+            duration,
+            width: optional_u32(width_input())?,
+            fps: optional_u32(fps_input())?,
+            format: format(),
+        })
+    };
+
+    let estimated_size = target.read().as_ref().and_then(|info| {
+        build_options().ok().map(|options| {
+            options.estimate_size(
+                info.size,
+                info.duration.as_secs_f64(),
+                info.width,
+                info.height,
+            )
+        })
+    });
+
+    let start_export = move |_| async move {
+        let Some(info) = target() else {
+            return;
+        };
+        let options = match build_options() {
+            Ok(options) => options,
+            Err(e) => {
+                result_message.set(Some(e));
+                return;
+            }
+        };
+        let stem = info
+            .file_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "clip".to_string());
+        let mut dialog = rfd::AsyncFileDialog::new()
+            .set_title("保存片段")
+            .set_file_name(format!("{}_clip.{}", stem, options.format.extension()));
+        if let Some(parent) = info.file_path.parent() {
+            dialog = dialog.set_directory(parent);
+        }
+        let Some(handle) = dialog.save_file().await else {
+            return;
+        };
+        let output_path = handle.path().to_path_buf();
+
+        is_exporting.set(true);
+        progress.set(0.0);
+        result_message.set(None);
+        let (tx, mut rx) = futures::channel::mpsc::unbounded::<MergeEvent>();
+        let export = run_clip_export(info.file_path.clone(), output_path.clone(), options, tx);
+        let updates = async {
+            while let Some(event) = rx.next().await {
+                match event {
+                    MergeEvent::Progress(p) => progress.set(p.percent),
+                    MergeEvent::Status(_)
+                    | MergeEvent::Segments(_)
+                    | MergeEvent::Probing { .. } => {}
+                    MergeEvent::Error(e) => result_message.set(Some(format!("导出失败: {}", e))),
+                    MergeEvent::Success(_) => {
+                        progress.set(100.0);
+                        let size = std::fs::metadata(&output_path).map(|m| m.len()).ok();
+                        result_message.set(Some(format!(
+                            "已导出 {}，实际大小 {}",
+                            output_path.display(),
+                            format_size(size)
+                        )));
+                    }
+                }
+            }
+        };
+        futures::join!(export, updates);
+        is_exporting.set(false);
+    };
+
+    let file_name = target
+        .read()
+        .as_ref()
+        .map(|info| info.file_name.clone())
+        .unwrap_or_default();
+
+    rsx! {
+        AlertDialogRoot {
+            open: target.read().is_some(),
+            on_open_change: move |v: bool| {
+                if !v && !is_exporting() {
+                    target.set(None);
+                    result_message.set(None);
+                    progress.set(0.0);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "导出GIF/片段" }
+                AlertDialogDescription { "{file_name}" }
+                div { class: "space-y-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "格式" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            onchange: move |evt| {
+                                format.set(if evt.value() == "mp4" { ClipFormat::Mp4 } else { ClipFormat::Gif });
+                            },
+                            option { value: "gif", selected: format() == ClipFormat::Gif, "GIF 动图" }
+                            option { value: "mp4", selected: format() == ClipFormat::Mp4, "MP4 短片" }
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "开始时间" }
+                        Input {
+                            placeholder: "例如 1:30",
+                            value: "{start_input}",
+                            oninput: move |e: FormEvent| start_input.set(e.value()),
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "时长(秒)" }
+                        Input {
+                            value: "{duration_input}",
+                            oninput: move |e: FormEvent| duration_input.set(e.value()),
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "宽度" }
+                        Input {
+                            placeholder: "留空保持原尺寸",
+                            value: "{width_input}",
+                            oninput: move |e: FormEvent| width_input.set(e.value()),
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "帧率" }
+                        Input {
+                            placeholder: "留空保持原帧率",
+                            value: "{fps_input}",
+                            oninput: move |e: FormEvent| fps_input.set(e.value()),
+                        }
+                    }
+                    p { class: "text-gray-500",
+                        "预计大小: {format_size(estimated_size)}"
+                    }
+                    if is_exporting() || progress() > 0.0 {
+                        Progress { aria_label: "导出进度", value: progress(), ProgressIndicator {} }
+                    }
+                    if let Some(message) = result_message() {
+                        p { class: "text-gray-600 break-all", "{message}" }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button { disabled: is_exporting(), onclick: start_export,
+                        if is_exporting() {
+                            "导出中..."
+                        } else {
+                            "导出"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::MergeEvent;
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::components::progress::{Progress, ProgressIndicator};
+use crate::components::savings_report::SavingsReport;
+use crate::ffmpeg::compress::{CompressPlan, plan_compression, run_compress};
+use crate::history::{HistoryEntry, JobKind, SizeComparison, record_history};
+use crate::utils::with_stem_suffix;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+// 常见聊天软件的文件大小限制（MB）
+const QUICK_SIZES: [u32; 4] = [8, 25, 50, 100];
+
+// 压缩到目标大小对话框，target 为 None 时关闭
+#[component]
+pub fn CompressDialog(target: Signal<Option<Mp4FileInfo>>) -> Element {
+    use_restore_focus(move || target.read().is_some());
+    let mut target_mb: Signal<f64> = use_signal(|| 25.0);
+    let mut progress: Signal<f64> = use_signal(|| 0.0);
+    let mut is_running: Signal<bool> = use_signal(|| false);
+    let mut result_message: Signal<Option<String>> = use_signal(|| None);
+    // 画质警告需要用户再次确认，确认后保存在这里
+    let mut pending_plan: Signal<Option<CompressPlan>> = use_signal(|| None);
+    // 压缩前后的大小，成功后展示并写入历史记录
+    let mut size: Signal<Option<SizeComparison>> = use_signal(|| None);
+
+    let start_compress = move |_| async move {
+        let Some(info) = target() else {
+            return;
+        };
+        is_running.set(true);
+        result_message.set(None);
+        size.set(None);
+
+        let plan = match pending_plan.take() {
+            Some(plan) => plan,
+            None => {
+                let target_bytes = (target_mb() * 1024.0 * 1024.0) as u64;
+                match plan_compression(&info.file_path, target_bytes).await {
+                    Ok(plan) if plan.warning.is_some() => {
+                        result_message.set(plan.warning.clone());
+                        pending_plan.set(Some(plan));
+                        is_running.set(false);
+                        return;
+                    }
+                    Ok(plan) => plan,
+                    Err(e) => {
+                        result_message.set(Some(e.to_string()));
+                        is_running.set(false);
+                        return;
+                    }
+                }
+            }
+        };
+
+        let output_path = with_stem_suffix(&info.file_path, "_compressed.mp4", "video");
+
+        progress.set(0.0);
+        let mut succeeded = false;
+        let (tx, mut rx) = futures::channel::mpsc::unbounded::<MergeEvent>();
+        let compress = run_compress(info.file_path.clone(), output_path.clone(), plan, tx);
+        let updates = async {
+            while let Some(event) = rx.next().await {
+                match event {
+                    MergeEvent::Progress(p) => progress.set(p.percent),
+                    MergeEvent::Status(_)
+                    | MergeEvent::Segments(_)
+                    | MergeEvent::Probing { .. } => {}
+                    MergeEvent::Error(e) => result_message.set(Some(format!("压缩失败: {}", e))),
+                    MergeEvent::Success(message) => {
+                        succeeded = true;
+                        progress.set(100.0);
+                        result_message.set(Some(format!("已保存到 {}", message)));
+                    }
+                }
+            }
+        };
+        futures::join!(compress, updates);
+        if succeeded
+            && let Ok(comparison) =
+                SizeComparison::new(info.file_path.clone(), info.size, output_path)
+        {
+            record_history(HistoryEntry::size_report(
+                JobKind::Compress,
+                vec![comparison.clone()],
+            ));
+            size.set(Some(comparison));
+        }
+        is_running.set(false);
+    };
+
+    let file_name = target
+        .read()
+        .as_ref()
+        .map(|info| info.file_name.clone())
+        .unwrap_or_default();
+
+    rsx! {
+        AlertDialogRoot {
+            open: target.read().is_some(),
+            on_open_change: move |v: bool| {
+                if !v && !is_running() {
+                    target.set(None);
+                    result_message.set(None);
+                    pending_plan.set(None);
+                    size.set(None);
+                    progress.set(0.0);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "压缩到目标大小" }
+                AlertDialogDescription { "{file_name}" }
+                div { class: "space-y-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "目标大小" }
+                        input {
+                            r#type: "number",
+                            class: "w-24 px-2 py-1 border rounded text-sm",
+                            min: "1",
+                            step: "any",
+                            value: "{target_mb}",
+                            onchange: move |e| {
+                                if let Ok(v) = e.value().parse::<f64>()
+                                    && v > 0.0
+                                {
+                                    target_mb.set(v);
+                                    pending_plan.set(None);
+                                }
+                            },
+                        }
+                        span { class: "text-gray-500", "MB" }
+                        for size in QUICK_SIZES {
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                onclick: move |_| {
+                                    target_mb.set(size as f64);
+                                    pending_plan.set(None);
+                                },
+                                "{size}MB"
+                            }
+                        }
+                    }
+                    p { class: "text-gray-500", "使用两遍编码，结果保存为同目录下的 *_compressed.mp4" }
+                    if is_running() || progress() > 0.0 {
+                        Progress { aria_label: "压缩进度", value: progress(), ProgressIndicator {} }
+                    }
+                    if let Some(comparison) = size() {
+                        SavingsReport { sizes: vec![comparison] }
+                    }
+                    if let Some(message) = result_message() {
+                        p {
+                            class: if pending_plan.read().is_some() { "text-amber-600 break-all" } else { "text-gray-600 break-all" },
+                            "{message}"
+                        }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button { disabled: is_running(), onclick: start_compress,
+                        if is_running() {
+                            "处理中..."
+                        } else if pending_plan.read().is_some() {
+                            "仍然压缩"
+                        } else {
+                            "开始"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
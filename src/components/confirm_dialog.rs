@@ -0,0 +1,141 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogContent, AlertDialogDescription, AlertDialogRoot,
+    AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use dioxus::prelude::*;
+use tokio::sync::oneshot;
+
+/// 确认对话框的内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confirmation {
+    pub title: String,
+    /// 说明文字，可以包含换行
+    pub message: String,
+    /// 确认按钮上的文字
+    pub action: String,
+    /// 不可撤销的操作，确认按钮显示为红色
+    pub destructive: bool,
+    /// 第二个可选操作（如“跳过”），显示在取消与确认之间
+    pub alternative: Option<String>,
+}
+
+/// 确认对话框的回答
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfirmAnswer {
+    Confirmed,
+    /// 选择了 [`Confirmation::alternative`]
+    Alternative,
+    /// 点击取消或关闭对话框
+    Cancelled,
+}
+
+impl Confirmation {
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            action: "确定".to_string(),
+            destructive: false,
+            alternative: None,
+        }
+    }
+
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.action = action.into();
+        self
+    }
+
+    pub fn destructive(mut self) -> Self {
+        self.destructive = true;
+        self
+    }
+
+    pub fn alternative(mut self, alternative: impl Into<String>) -> Self {
+        self.alternative = Some(alternative.into());
+        self
+    }
+}
+
+/// 等待用户回答的确认请求
+#[derive(Debug)]
+pub struct ConfirmRequest {
+    confirmation: Confirmation,
+    reply: oneshot::Sender<ConfirmAnswer>,
+}
+
+/// 全局确认对话框的请求，在 `Index` 中通过 context 提供
+pub type ConfirmRequests = Signal<Option<ConfirmRequest>>;
+
+/// 获取全局确认对话框
+pub fn use_confirm() -> ConfirmRequests {
+    use_context::<ConfirmRequests>()
+}
+
+/// 显示确认对话框并等待回答，点击确认返回 true，取消或关闭返回 false
+pub async fn confirm(requests: ConfirmRequests, confirmation: Confirmation) -> bool {
+    ask(requests, confirmation).await == ConfirmAnswer::Confirmed
+}
+
+/// 显示确认对话框并等待回答，用于带有第二个可选操作的对话框
+pub async fn ask(mut requests: ConfirmRequests, confirmation: Confirmation) -> ConfirmAnswer {
+    let (reply, answer) = oneshot::channel();
+    // 同时只显示一个对话框，尚未回答的视为取消
+    if let Some(previous) = requests.write().replace(ConfirmRequest {
+        confirmation,
+        reply,
+    }) {
+        let _ = previous.reply.send(ConfirmAnswer::Cancelled);
+    }
+    answer.await.unwrap_or(ConfirmAnswer::Cancelled)
+}
+
+// 全局确认对话框，内容由 `confirm` 提供
+#[component]
+pub fn ConfirmDialog() -> Element {
+    let mut requests = use_confirm();
+    use_restore_focus(move || requests.read().is_some());
+
+    let mut answer = move |answer: ConfirmAnswer| {
+        if let Some(request) = requests.take() {
+            let _ = request.reply.send(answer);
+        }
+    };
+
+    let Some(confirmation) = requests.read().as_ref().map(|r| r.confirmation.clone()) else {
+        return rsx! {};
+    };
+
+    rsx! {
+        AlertDialogRoot {
+            open: true,
+            on_open_change: move |v: bool| {
+                if !v {
+                    answer(ConfirmAnswer::Cancelled);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "{confirmation.title}" }
+                AlertDialogDescription {
+                    span { class: "whitespace-pre-line", "{confirmation.message}" }
+                }
+                AlertDialogActions {
+                    Button { variant: ButtonVariant::Ghost, onclick: move |_| answer(ConfirmAnswer::Cancelled), "取消" }
+                    if let Some(alternative) = &confirmation.alternative {
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            onclick: move |_| answer(ConfirmAnswer::Alternative),
+                            "{alternative}"
+                        }
+                    }
+                    Button {
+                        variant: if confirmation.destructive { ButtonVariant::Destructive } else { ButtonVariant::Primary },
+                        onclick: move |_| answer(ConfirmAnswer::Confirmed),
+                        "{confirmation.action}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::utils::{open_path, take_pending_crash_report};
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+// 上次运行崩溃时，启动后提示用户查看或复制崩溃报告
+#[component]
+pub fn CrashReportDialog() -> Element {
+    let mut report: Signal<Option<PathBuf>> = use_signal(take_pending_crash_report);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    let open_report = move |_| {
+        if let Some(path) = report()
+            && let Err(e) = open_path(&path)
+        {
+            message.set(Some(format!("无法打开崩溃报告: {}", e)));
+        }
+    };
+
+    let copy_report = move |_| {
+        let Some(path) = report() else {
+            return;
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let text = serde_json::to_string(&content).unwrap_or_default();
+                document::eval(&format!("navigator.clipboard.writeText({})", text));
+                message.set(Some("已复制到剪贴板".to_string()));
+            }
+            Err(e) => message.set(Some(format!("无法读取崩溃报告: {}", e))),
+        }
+    };
+
+    let path_text = report()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+
+    rsx! {
+        AlertDialogRoot {
+            open: report.read().is_some(),
+            on_open_change: move |v: bool| {
+                if !v {
+                    report.set(None);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "程序上次意外退出" }
+                AlertDialogDescription {
+                    "已生成崩溃报告，反馈问题时请附上报告内容。"
+                }
+                p { class: "text-sm text-gray-500 break-all", "{path_text}" }
+                if let Some(message) = message() {
+                    p { class: "text-sm text-gray-600", "{message}" }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button { variant: ButtonVariant::Secondary, onclick: copy_report, "复制内容" }
+                    Button { onclick: open_report, "打开报告" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::button::{Button, ButtonVariant};
+use crate::ffmpeg::diagnostics::{Codec, Diagnostics, collect_diagnostics};
+use dioxus::prelude::*;
+
+// FFmpeg 环境诊断：版本、编解码器、硬件加速与 PATH 搜索位置
+#[component]
+pub fn DiagnosticsView() -> Element {
+    let mut diagnostics: Signal<Option<Diagnostics>> = use_signal(|| None);
+    let mut is_running: Signal<bool> = use_signal(|| false);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    let run = move |_| async move {
+        is_running.set(true);
+        message.set(None);
+        diagnostics.set(Some(collect_diagnostics().await));
+        is_running.set(false);
+    };
+
+    let copy_report = move |_| {
+        let Some(report) = diagnostics.read().as_ref().map(Diagnostics::report) else {
+            return;
+        };
+        let text = serde_json::to_string(&report).unwrap_or_default();
+        document::eval(&format!("navigator.clipboard.writeText({})", text));
+        message.set(Some("已复制到剪贴板".to_string()));
+    };
+
+    rsx! {
+        div { class: "space-y-2 text-sm",
+            div { class: "flex items-center gap-2",
+                h2 { class: "text-sm font-semibold", "FFmpeg 环境诊断" }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    disabled: is_running(),
+                    onclick: run,
+                    if is_running() {
+                        "检测中..."
+                    } else {
+                        "运行诊断"
+                    }
+                }
+                if diagnostics.read().is_some() {
+                    Button { variant: ButtonVariant::Ghost, onclick: copy_report, "复制诊断信息" }
+                }
+                if let Some(msg) = message() {
+                    span { class: "text-xs text-green-400", "{msg}" }
+                }
+            }
+            if let Some(d) = diagnostics() {
+                ToolRow { name: "ffmpeg", path: d.ffmpeg_path.clone(), version: d.ffmpeg_version.clone() }
+                ToolRow { name: "ffprobe", path: d.ffprobe_path.clone(), version: d.ffprobe_version.clone() }
+                p { class: "text-gray-400",
+                    "硬件加速："
+                    if d.hwaccels.is_empty() {
+                        "无"
+                    } else {
+                        {d.hwaccels.join(", ")}
+                    }
+                }
+                CodecList { title: "编码器", codecs: d.encoders.clone() }
+                CodecList { title: "解码器", codecs: d.decoders.clone() }
+                details {
+                    summary { class: "cursor-pointer text-gray-400", "PATH 搜索位置 ({d.path_entries.len()})" }
+                    ul { class: "text-xs font-mono space-y-0.5 mt-1",
+                        for entry in d.path_entries.iter() {
+                            li {
+                                class: if entry.has_ffmpeg { "text-green-400 break-all" } else { "text-gray-500 break-all" },
+                                "{entry.dir.display()}"
+                                if entry.has_ffmpeg {
+                                    " ← ffmpeg"
+                                }
+                                if entry.has_ffprobe {
+                                    " ← ffprobe"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ToolRow(
+    name: &'static str,
+    path: Option<std::path::PathBuf>,
+    version: Result<String, String>,
+) -> Element {
+    rsx! {
+        div {
+            p { class: "font-semibold", "{name}" }
+            {
+                match (path, version) {
+                    (Some(path), Ok(version)) => rsx! {
+                        p { class: "text-xs text-gray-400 break-all", "{path.display()}" }
+                        p { class: "text-xs text-gray-400 break-all", "{version}" }
+                    },
+                    (Some(path), Err(e)) => rsx! {
+                        p { class: "text-xs text-gray-400 break-all", "{path.display()}" }
+                        p { class: "text-xs text-red-400 break-all", "{e}" }
+                    },
+                    (None, _) => rsx! {
+                        p { class: "text-xs text-red-400", "未在 PATH 中找到" }
+                    },
+                }
+            }
+        }
+    }
+}
+
+// 视频/音频编解码器按类型分组，完整列表折叠显示
+#[component]
+fn CodecList(title: &'static str, codecs: Vec<Codec>) -> Element {
+    let video = codecs.iter().filter(|c| c.kind == 'V').count();
+    let audio = codecs.iter().filter(|c| c.kind == 'A').count();
+
+    rsx! {
+        details {
+            summary { class: "cursor-pointer text-gray-400",
+                "{title}：共 {codecs.len()} 个（视频 {video}，音频 {audio}）"
+            }
+            ul { class: "text-xs font-mono max-h-48 overflow-y-auto custom-scrollbar mt-1",
+                for codec in codecs.iter() {
+                    li { class: "text-gray-400",
+                        "{codec.kind} {codec.name}"
+                        span { class: "text-gray-600", " {codec.description}" }
+                    }
+                }
+            }
+        }
+    }
+}
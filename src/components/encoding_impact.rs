@@ -0,0 +1,110 @@
+use crate::ffmpeg::diagnostics::detect_hardware_encoders;
+use crate::ffmpeg::options::{MergeMode, MergeOptions};
+use crate::utils::{ResourceSampler, ResourceUsage};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// 采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// FFmpeg 的 CPU 占用超过该值（%）时提示可改用硬件编码
+const HIGH_CPU_PERCENT: f32 = 60.0;
+
+// 合并期间显示所用编码器与 CPU/GPU 占用，软件编码占用过高时提示改用显卡编码
+#[component]
+pub fn EncodingImpact(active: ReadSignal<bool>, merge_options: Signal<MergeOptions>) -> Element {
+    let mut usage: Signal<Option<ResourceUsage>> = use_signal(|| None);
+    // 开始合并时的选项，合并过程中修改设置不影响正在运行的任务
+    let mut job_options: Signal<Option<MergeOptions>> = use_signal(|| None);
+    let mut hint_message: Signal<Option<String>> = use_signal(|| None);
+
+    use_effect(move || {
+        if active() {
+            job_options.set(Some(merge_options.peek().clone()));
+            hint_message.set(None);
+        } else {
+            usage.set(None);
+        }
+    });
+
+    use_future(move || async move {
+        let mut sampler = ResourceSampler::new();
+        loop {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+            if *active.peek() {
+                usage.set(Some(sampler.sample().await));
+            }
+        }
+    });
+
+    let switch_to_hardware = move |_| {
+        spawn(async move {
+            match detect_hardware_encoders().await.first() {
+                Some(encoder) => {
+                    merge_options.write().quality.hardware = Some(*encoder);
+                    hint_message.set(Some(format!(
+                        "已改用 {}，下次合并生效；如编码失败请在高级选项中换回 CPU 编码",
+                        encoder.label()
+                    )));
+                }
+                None => hint_message.set(Some(
+                    "当前 FFmpeg 不支持硬件编码，可降低编码速度预设以减少占用".to_string(),
+                )),
+            }
+        });
+    };
+
+    if !active() {
+        return rsx! {};
+    }
+    let Some(options) = job_options() else {
+        return rsx! {};
+    };
+    let software_encoding =
+        options.mode == MergeMode::ReEncode && options.quality.hardware.is_none();
+    let high_cpu = usage().is_some_and(|u| u.ffmpeg_cpu >= HIGH_CPU_PERCENT);
+
+    rsx! {
+        div { class: "flex flex-wrap items-center gap-x-4 gap-y-1 text-xs text-gray-500",
+            span { "编码器：{encoder_label(&options)}" }
+            if let Some(u) = usage() {
+                span { "CPU {u.cpu:.0}%（FFmpeg {u.ffmpeg_cpu:.0}%）" }
+                if let Some(gpu) = u.gpu {
+                    span { "GPU {gpu:.0}%" }
+                }
+            } else {
+                span { "正在统计资源占用..." }
+            }
+            if software_encoding && high_cpu && hint_message().is_none() {
+                span {
+                    "软件编码会占满 CPU，风扇转速可能升高。"
+                    button {
+                        class: "text-blue-600 hover:underline",
+                        onclick: switch_to_hardware,
+                        "改用硬件编码"
+                    }
+                }
+            }
+            if let Some(msg) = hint_message() {
+                span { class: "text-purple-400", "{msg}" }
+            }
+        }
+    }
+}
+
+// 合并所用编码器的说明
+fn encoder_label(options: &MergeOptions) -> String {
+    match options.mode {
+        MergeMode::Copy if options.active_external_audio().is_none() => {
+            "不重新编码（直接复制，占用很低）".to_string()
+        }
+        MergeMode::Copy => "视频直接复制，音频 aac".to_string(),
+        MergeMode::ReEncode => match options.quality.hardware {
+            Some(hardware) => format!("{}（显卡编码，{}）", hardware.codec(), hardware.label()),
+            None => format!(
+                "libx264（CPU 软件编码，{}）",
+                options.quality.preset.label()
+            ),
+        },
+        MergeMode::AudioOnly(_) => format!("{}（只处理音频）", options.mode.label()),
+    }
+}
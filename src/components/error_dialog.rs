@@ -0,0 +1,158 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogContent, AlertDialogDescription, AlertDialogRoot,
+    AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::config::AppConfig;
+use crate::error::AppError;
+use crate::ffmpeg::error::{ErrorCode, Remedy};
+use crate::ffmpeg::locate::FFMPEG_DOWNLOAD_URL;
+use crate::ffmpeg::options::{MergeMode, MergeOptions};
+use crate::utils::{open_path, relaunch_elevated};
+use dioxus::prelude::*;
+use std::path::Path;
+
+/// 交给全局错误对话框显示的错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportedError {
+    pub code: ErrorCode,
+    /// 原始错误信息
+    pub message: String,
+    /// 出错的合并所用的选项，“改用重新编码”时修改
+    pub merge_options: Option<Signal<MergeOptions>>,
+}
+
+/// 全局错误对话框的内容，在 `Index` 中通过 context 提供
+pub type ErrorReports = Signal<Option<ReportedError>>;
+
+/// 获取全局错误对话框
+pub fn use_error_reports() -> ErrorReports {
+    use_context::<ErrorReports>()
+}
+
+/// 常见错误交给对话框显示并返回 true，无法识别的错误返回 false 由调用方自行提示
+pub fn report_error(
+    mut reports: ErrorReports,
+    error: &AppError,
+    merge_options: Option<Signal<MergeOptions>>,
+) -> bool {
+    match error.code() {
+        Some(code) => {
+            reports.set(Some(ReportedError {
+                code,
+                message: error.to_string(),
+                merge_options,
+            }));
+            true
+        }
+        None => false,
+    }
+}
+
+// 常见错误的对话框：错误码、原始信息、解决建议与一键补救
+#[component]
+pub fn ErrorDialog(config: Signal<AppConfig>, active_tab: Signal<Option<String>>) -> Element {
+    let mut reports = use_error_reports();
+    use_restore_focus(move || reports.read().is_some());
+    let mut show_details: Signal<bool> = use_signal(|| false);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    let mut close = move || {
+        reports.set(None);
+        show_details.set(false);
+        message.set(None);
+    };
+
+    let apply_remedy = move |remedy: Remedy| async move {
+        let merge_options = reports.peek().as_ref().and_then(|r| r.merge_options);
+        match remedy {
+            Remedy::OpenSettings => active_tab.set(Some("tab4".to_string())),
+            Remedy::DownloadFfmpeg => {
+                if let Err(e) = open_path(Path::new(FFMPEG_DOWNLOAD_URL)) {
+                    message.set(Some(format!("无法打开下载页面: {}", e)));
+                    return;
+                }
+            }
+            Remedy::SwitchToReEncode => {
+                // 对应工作区可能已经关闭
+                if let Some(mut options) = merge_options.and_then(|o| o.try_write().ok()) {
+                    options.mode = MergeMode::ReEncode;
+                }
+            }
+            Remedy::ChooseOutputDirectory => {
+                let Some(folder) = rfd::AsyncFileDialog::new()
+                    .set_title("选择输出目录")
+                    .pick_folder()
+                    .await
+                else {
+                    return;
+                };
+                if let Err(e) = config
+                    .write()
+                    .set_output_directory(folder.path().to_path_buf())
+                {
+                    message.set(Some(format!("无法保存输出目录设置: {}", e)));
+                    return;
+                }
+            }
+            Remedy::RunAsAdministrator => {
+                if let Err(e) = relaunch_elevated().await {
+                    message.set(Some(e.to_string()));
+                    return;
+                }
+            }
+        }
+        close();
+    };
+
+    let Some(report) = reports() else {
+        return rsx! {};
+    };
+    let code = report.code;
+    // 没有关联合并选项时无法切换合并方式，非 Windows 系统无法提权重新打开
+    let remedies: Vec<Remedy> = code
+        .remedies()
+        .iter()
+        .copied()
+        .filter(|r| *r != Remedy::SwitchToReEncode || report.merge_options.is_some())
+        .filter(|r| *r != Remedy::RunAsAdministrator || cfg!(target_os = "windows"))
+        .collect();
+
+    rsx! {
+        AlertDialogRoot {
+            open: true,
+            on_open_change: move |v: bool| {
+                if !v {
+                    close();
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "{code.title()}" }
+                AlertDialogDescription { "{code.suggestion()}" }
+                p { class: "text-xs text-gray-500 break-all",
+                    "错误码 {code.code()}：{report.message}"
+                }
+                if show_details() {
+                    p { class: "text-sm text-gray-500", "{code.details()}" }
+                }
+                if let Some(msg) = message() {
+                    p { class: "text-sm text-red-500 break-all", "{msg}" }
+                }
+                AlertDialogActions {
+                    Button { variant: ButtonVariant::Ghost, onclick: move |_| close(), "关闭" }
+                    if !show_details() {
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            onclick: move |_| show_details.set(true),
+                            "了解更多"
+                        }
+                    }
+                    for remedy in remedies {
+                        Button { onclick: move |_| apply_remedy(remedy), "{remedy.label()}" }
+                    }
+                }
+            }
+        }
+    }
+}
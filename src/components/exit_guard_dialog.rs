@@ -0,0 +1,88 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogContent, AlertDialogDescription, AlertDialogRoot,
+    AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::exit_guard::{exit_app, use_exit_prompt};
+use crate::ffmpeg::merge_mp4::{RUNNING_MERGES, cancel_all_merges};
+use crate::queue::QUEUE_PAUSED;
+use dioxus::prelude::*;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::time::sleep;
+
+// 中止合并后等待 FFmpeg 结束、未完成的输出被删除的最长时间
+const CANCEL_WAIT: Duration = Duration::from_secs(5);
+
+// 有任务执行时关闭窗口的询问：取消任务并退出、在托盘中后台继续，或返回
+#[component]
+pub fn ExitGuardDialog() -> Element {
+    let mut prompt = use_exit_prompt();
+    use_restore_focus(move || prompt());
+    let mut cancelling: Signal<bool> = use_signal(|| false);
+
+    let cancel_and_exit = move |_| {
+        cancelling.set(true);
+        spawn(async move {
+            // 先暂停队列，避免中止当前任务后开始下一个
+            QUEUE_PAUSED.store(true, Ordering::SeqCst);
+            cancel_all_merges();
+            let waited = tokio::time::Instant::now();
+            while RUNNING_MERGES.load(Ordering::SeqCst) > 0 && waited.elapsed() < CANCEL_WAIT {
+                sleep(Duration::from_millis(100)).await;
+            }
+            exit_app();
+        });
+    };
+
+    let run_in_background = move |_| {
+        prompt.set(false);
+        dioxus_desktop::window().window.set_visible(false);
+    };
+
+    if !prompt() {
+        return rsx! {};
+    }
+
+    rsx! {
+        AlertDialogRoot {
+            open: true,
+            on_open_change: move |v: bool| {
+                if !v && !cancelling() {
+                    prompt.set(false);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "任务仍在执行" }
+                AlertDialogDescription {
+                    "现在退出会中断 FFmpeg，未完成的输出文件将被删除。可以转到托盘在后台继续执行，完成后再退出。"
+                }
+                AlertDialogActions {
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        disabled: cancelling(),
+                        onclick: move |_| prompt.set(false),
+                        "返回"
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        disabled: cancelling(),
+                        onclick: run_in_background,
+                        "后台继续"
+                    }
+                    Button {
+                        variant: ButtonVariant::Destructive,
+                        disabled: cancelling(),
+                        onclick: cancel_and_exit,
+                        if cancelling() {
+                            "正在取消..."
+                        } else {
+                            "取消任务并退出"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
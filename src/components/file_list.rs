@@ -1,26 +1,166 @@
 use super::button::{Button, ButtonVariant};
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::drag::use_drag_payload;
+use crate::ffmpeg::options::{MergeMode, MergeOptions};
+use crate::ffmpeg::probe::{PROBE_CONCURRENCY, probe_duration_cached, probe_video_stream};
+use crate::ffmpeg::speed::{SPEED_CHOICES, speed_label};
+use crate::utils::{display_name, format_bytes, format_duration, format_size, parse_mp4_info};
 use dioxus::prelude::*;
+use futures_util::{StreamExt, stream};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 
 // 1. 提取子组件：文件列表区域
 #[component]
-pub fn FileList(files: Signal<Vec<PathBuf>>, on_remove: Callback<usize>) -> Element {
+pub fn FileList(
+    files: Signal<Vec<PathBuf>>,
+    selected: Signal<Option<usize>>,
+    /// 合并前检查发现已被移动或删除的文件
+    missing: ReadSignal<Vec<PathBuf>>,
+    on_remove: Callback<usize>,
+    on_relocate: Callback<usize>,
+    /// 打开剪切编辑
+    on_trim: Callback<usize>,
+    /// 从文件库拖入的文件
+    on_drop: Callback<Vec<PathBuf>>,
+    /// 各片段的播放速度保存在合并选项中
+    merge_options: Signal<MergeOptions>,
+) -> Element {
+    let mut drag_payload = use_drag_payload();
+    let mut drop_hover: Signal<bool> = use_signal(|| false);
+    // 后台读取各文件的分辨率、时长与编码
+    let infos = use_resource(move || {
+        let files = files();
+        async move {
+            tokio::task::spawn_blocking(move || {
+                files
+                    .into_iter()
+                    .filter_map(|file| {
+                        let info = parse_mp4_info(file.clone()).ok()?;
+                        Some((file, info))
+                    })
+                    .collect::<HashMap<PathBuf, Mp4FileInfo>>()
+            })
+            .await
+            .unwrap_or_default()
+        }
+    });
+    let infos = infos.read().clone().unwrap_or_default();
+    // 前面已出现过的文件，合并时这些片段会重复
+    let duplicates: HashSet<usize> = {
+        let mut seen = HashSet::new();
+        files
+            .read()
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| !seen.insert((*file).clone()))
+            .map(|(index, _)| index)
+            .collect()
+    };
+    // 与第一个文件比较，参数不同时直接合并很可能失败
+    let reference = files.read().first().and_then(|f| infos.get(f).cloned());
+    let is_reencode = merge_options.read().mode == MergeMode::ReEncode;
+
     rsx! {
-        div { class: "mt-2",
+        div {
+            class: if drop_hover() { "mt-2 rounded-lg ring-2 ring-blue-500" } else { "mt-2" },
+            ondragover: move |e| {
+                // 只接受文件库中拖出的文件
+                if drag_payload.peek().is_some() {
+                    e.prevent_default();
+                    drop_hover.set(true);
+                }
+            },
+            ondragleave: move |_| drop_hover.set(false),
+            ondrop: move |e| {
+                e.prevent_default();
+                drop_hover.set(false);
+                if let Some(paths) = drag_payload.take() {
+                    on_drop.call(paths);
+                }
+            },
             if !files.read().is_empty() {
-                div { class: "space-y-2 max-h-52 overflow-y-auto pr-2 custom-scrollbar",
+                div {
+                    class: "space-y-2 max-h-52 overflow-y-auto pr-2 custom-scrollbar",
+                    role: "list",
+                    aria_label: "合并列表",
                     for (index , file) in files.read().iter().cloned().enumerate() {
-                        div { class: "flex items-center justify-between py-1 px-2 rounded-lg border border-gray-600 hover:border-gray-500 transition-colors",
-                            div { class: "flex items-center gap-3 overflow-hidden",
+                        div {
+                            class: if missing.read().contains(&file) { "flex items-center justify-between py-1 px-2 rounded-lg border border-red-500 bg-red-500/10 transition-colors" } else if selected() == Some(index) { "flex items-center justify-between py-1 px-2 rounded-lg border border-blue-500 bg-blue-500/10 transition-colors" } else { "flex items-center justify-between py-1 px-2 rounded-lg border border-gray-600 hover:border-gray-500 transition-colors" },
+                            role: "listitem",
+                            onclick: move |_| selected.set(Some(index)),
+                            // 用按钮承载键盘焦点，Enter/空格触发的点击冒泡到整行
+                            button {
+                                r#type: "button",
+                                class: "flex items-center gap-3 overflow-hidden text-left",
+                                aria_pressed: selected() == Some(index),
                                 span { class: "text-gray-400 text-sm font-mono", "{index + 1}." }
                                 span { class: " truncate flex-1 max-w-100",
-                                    "{file.file_name().unwrap().to_string_lossy()}"
+                                    "{display_name(&file)}"
+                                }
+                                if duplicates.contains(&index) {
+                                    span {
+                                        class: "px-1.5 rounded text-xs shrink-0 bg-amber-500/20 text-amber-400",
+                                        title: "该文件在列表中出现了多次",
+                                        "重复"
+                                    }
+                                }
+                                if !merge_options.read().cuts_of(&file).is_empty() {
+                                    span {
+                                        class: "px-1.5 rounded text-xs shrink-0 bg-blue-500/20 text-blue-400",
+                                        title: "合并时会剪掉设置的区间",
+                                        {format!("已剪 {} 处", merge_options.read().cuts_of(&file).len())}
+                                    }
+                                }
+                                if missing.read().contains(&file) {
+                                    span { class: "text-xs text-red-400 shrink-0", "文件不存在" }
+                                } else if let Some(info) = infos.get(&file).cloned() {
+                                    FileChips {
+                                        info,
+                                        reference: if index > 0 { reference.clone() } else { None },
+                                    }
                                 }
                             }
-                            Button {
-                                variant: ButtonVariant::Destructive,
-                                onclick: move |_| on_remove.call(index),
-                                "删除"
+                            div { class: "flex gap-2 shrink-0",
+                                select {
+                                    class: "border rounded px-1 text-xs",
+                                    disabled: !is_reencode,
+                                    title: if is_reencode { "播放速度" } else { "播放速度仅在“重新编码”模式下生效" },
+                                    aria_label: "播放速度",
+                                    onclick: move |e| e.stop_propagation(),
+                                    onchange: {
+                                        let file = file.clone();
+                                        move |e: FormEvent| {
+                                            if let Ok(speed) = e.value().parse::<f64>() {
+                                                merge_options.write().set_speed(file.clone(), speed);
+                                            }
+                                        }
+                                    },
+                                    for speed in SPEED_CHOICES {
+                                        option {
+                                            value: "{speed}",
+                                            selected: merge_options.read().speed_of(&file) == speed,
+                                            {speed_label(speed)}
+                                        }
+                                    }
+                                }
+                                if missing.read().contains(&file) {
+                                    Button {
+                                        variant: ButtonVariant::Secondary,
+                                        onclick: move |_| on_relocate.call(index),
+                                        "重新定位"
+                                    }
+                                }
+                                Button {
+                                    variant: ButtonVariant::Ghost,
+                                    onclick: move |_| on_trim.call(index),
+                                    "剪切"
+                                }
+                                Button {
+                                    variant: ButtonVariant::Destructive,
+                                    onclick: move |_| on_remove.call(index),
+                                    "删除"
+                                }
                             }
                         }
                     }
@@ -28,7 +168,121 @@ pub fn FileList(files: Signal<Vec<PathBuf>>, on_remove: Callback<usize>) -> Elem
             } else {
                 div { class: "text-center py-8 border-2 border-dashed border-gray-600 rounded-lg",
                     p { class: "text-gray-500 text-lg", "尚未选择任何文件" }
-                    p { class: "text-gray-600 text-sm mt-1", "点击上方按钮添加MP4文件" }
+                    p { class: "text-gray-600 text-sm mt-1", "点击上方按钮添加MP4文件，或从文件库拖入" }
+                }
+            }
+        }
+    }
+}
+
+// 分辨率、时长、编码标签，与第一个文件不同的项标红
+#[component]
+fn FileChips(info: Mp4FileInfo, reference: Option<Mp4FileInfo>) -> Element {
+    let resolution_differs = reference
+        .as_ref()
+        .is_some_and(|r| (r.width, r.height) != (info.width, info.height));
+    let codec_differs = reference.as_ref().is_some_and(|r| r.codec != info.codec);
+    let chip = |differs: bool| {
+        if differs {
+            "px-1.5 rounded text-xs shrink-0 bg-red-500/20 text-red-400"
+        } else {
+            "px-1.5 rounded text-xs shrink-0 bg-gray-700 text-gray-300"
+        }
+    };
+    let hint = "与第一个文件不同，直接合并可能失败";
+
+    rsx! {
+        span {
+            class: chip(resolution_differs),
+            title: if resolution_differs { hint } else { "" },
+            "{info.width}x{info.height}"
+        }
+        span { class: chip(false), {format_duration(info.duration.as_secs_f64())} }
+        span {
+            class: chip(codec_differs),
+            title: if codec_differs { hint } else { "" },
+            "{info.codec}"
+        }
+    }
+}
+
+// 后台探测得到的汇总信息
+#[derive(Debug, Clone, PartialEq, Default)]
+struct ProbeSummary {
+    duration: f64,
+    /// 视频编码及对应的片段数
+    codecs: BTreeMap<String, usize>,
+    /// 无法读取的文件数
+    failed: usize,
+}
+
+// 合并列表汇总：片段数、总时长、总大小与编码组成，时长和编码在后台探测
+#[component]
+pub fn FileListSummary(files: ReadSignal<Vec<PathBuf>>) -> Element {
+    let summary = use_resource(move || {
+        let files = files();
+        async move {
+            let mut probes = stream::iter(files)
+                .map(|file| async move {
+                    let duration = probe_duration_cached(&file).await;
+                    let stream = probe_video_stream(&file).await;
+                    (duration, stream)
+                })
+                .buffer_unordered(PROBE_CONCURRENCY);
+            let mut summary = ProbeSummary::default();
+            while let Some((duration, stream)) = probes.next().await {
+                match (duration, stream) {
+                    (Ok(duration), Ok(stream)) => {
+                        summary.duration += duration;
+                        *summary.codecs.entry(stream.codec).or_default() += 1;
+                    }
+                    _ => summary.failed += 1,
+                }
+            }
+            summary
+        }
+    });
+
+    let count = files.read().len();
+    if count == 0 {
+        return rsx! {};
+    }
+    let total_size: u64 = files
+        .read()
+        .iter()
+        .filter_map(|f| std::fs::metadata(f).ok())
+        .map(|m| m.len())
+        .sum();
+
+    rsx! {
+        div { class: "flex flex-wrap items-center gap-x-4 gap-y-1 mt-2 text-xs text-gray-400",
+            span { "{count} 个片段" }
+            span { title: format_bytes(total_size), "总大小 {format_size(Some(total_size))}" }
+            {
+                match summary() {
+                    None => rsx! {
+                        span { class: "text-gray-500", "正在读取时长与编码..." }
+                    },
+                    Some(summary) => rsx! {
+                        span { "总时长 {format_duration(summary.duration)}" }
+                        span { class: if summary.codecs.len() > 1 { "text-amber-500" } else { "" },
+                            "编码 "
+                            {
+                                summary
+                                    .codecs
+                                    .iter()
+                                    .map(|(codec, n)| format!("{} ×{}", codec, n))
+                                    .collect::<Vec<_>>()
+                                    .join("、")
+                            }
+                            if summary.codecs.len() > 1 {
+                                "（编码不一致，直接合并可能失败）"
+                            }
+                        }
+                        if summary.failed > 0 {
+                            span { class: "text-red-400", "{summary.failed} 个文件无法读取" }
+                        }
+                    },
                 }
             }
         }
@@ -0,0 +1,116 @@
+use super::button::{Button, ButtonVariant};
+use crate::config::ScanRules;
+use crate::utils::{list_mp4_files, natural_cmp_path};
+use dioxus::prelude::*;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, PartialEq)]
+enum FolderSort {
+    Name,
+    Modified,
+}
+
+/// 按扫描规则列出目录下的 MP4 文件，按文件名排序
+pub fn list_mp4_in_directory(
+    dir: &Path,
+    rules: &ScanRules,
+) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut paths = list_mp4_files(dir, rules)?;
+    sort_paths(&mut paths, FolderSort::Name);
+    Ok(paths)
+}
+
+// 从文件夹导入：勾选要合并的文件，确认后加入合并列表
+#[component]
+pub fn FolderImport(
+    candidates: Signal<Vec<PathBuf>>,
+    on_confirm: Callback<Vec<PathBuf>>,
+) -> Element {
+    let mut sort: Signal<FolderSort> = use_signal(|| FolderSort::Name);
+    let mut deselected: Signal<HashSet<PathBuf>> = use_signal(Default::default);
+
+    let mut apply_sort = move |mode: FolderSort| {
+        sort.set(mode);
+        sort_paths(&mut candidates.write(), mode);
+    };
+
+    let confirm = move |_| {
+        let excluded = deselected.read().clone();
+        let chosen: Vec<PathBuf> = candidates
+            .read()
+            .iter()
+            .filter(|p| !excluded.contains(*p))
+            .cloned()
+            .collect();
+        deselected.write().clear();
+        candidates.write().clear();
+        on_confirm.call(chosen);
+    };
+
+    let cancel = move |_| {
+        deselected.write().clear();
+        candidates.write().clear();
+    };
+
+    let selected_count = candidates
+        .read()
+        .iter()
+        .filter(|p| !deselected.read().contains(*p))
+        .count();
+
+    rsx! {
+        div { class: "mt-2 p-2 rounded-lg border border-gray-600 space-y-2",
+            div { class: "flex items-center justify-between text-sm",
+                span { class: "text-gray-400", "文件夹中共 {candidates.read().len()} 个文件，已选 {selected_count} 个" }
+                div { class: "flex items-center gap-2",
+                    span { class: "text-gray-400", "排序:" }
+                    select {
+                        class: "border rounded px-2 py-1 text-sm",
+                        onchange: move |evt| {
+                            match evt.value().as_str() {
+                                "modified" => apply_sort(FolderSort::Modified),
+                                _ => apply_sort(FolderSort::Name),
+                            }
+                        },
+                        option { value: "name", selected: sort() == FolderSort::Name, "文件名" }
+                        option { value: "modified", selected: sort() == FolderSort::Modified, "修改日期" }
+                    }
+                }
+            }
+            div { class: "space-y-1 max-h-52 overflow-y-auto pr-2 custom-scrollbar",
+                for file in candidates.read().iter().cloned() {
+                    label { class: "flex items-center gap-2 py-1 px-2 text-sm",
+                        input {
+                            r#type: "checkbox",
+                            checked: !deselected.read().contains(&file),
+                            onchange: {
+                                let file = file.clone();
+                                move |_| {
+                                    let mut guard = deselected.write();
+                                    if !guard.remove(&file) {
+                                        guard.insert(file.clone());
+                                    }
+                                }
+                            },
+                        }
+                        span { class: "truncate", "{file.file_name().unwrap_or_default().to_string_lossy()}" }
+                    }
+                }
+            }
+            div { class: "flex justify-end gap-2",
+                Button { variant: ButtonVariant::Secondary, onclick: cancel, "取消" }
+                Button { disabled: selected_count == 0, onclick: confirm, "添加选中文件" }
+            }
+        }
+    }
+}
+
+fn sort_paths(paths: &mut [PathBuf], mode: FolderSort) {
+    match mode {
+        FolderSort::Name => paths.sort_by(|a, b| natural_cmp_path(a, b)),
+        FolderSort::Modified => {
+            paths.sort_by_key(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::MergeEvent;
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::components::input::Input;
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::components::progress::{Progress, ProgressIndicator};
+use crate::ffmpeg::frames::{FrameExtractOptions, FrameMode, ImageFormat, run_frame_extract};
+use crate::utils::parse_timestamp;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use std::path::PathBuf;
+
+// 截图对话框，target 为 None 时关闭
+#[component]
+pub fn FrameExtractDialog(target: Signal<Option<Mp4FileInfo>>) -> Element {
+    use_restore_focus(move || target.read().is_some());
+    let mut mode: Signal<String> = use_signal(|| "single".to_string());
+    let mut value_input: Signal<String> = use_signal(|| "0".to_string());
+    let mut format: Signal<ImageFormat> = use_signal(|| ImageFormat::Png);
+    // 未选择时保存到视频所在目录
+    let mut output_dir: Signal<Option<PathBuf>> = use_signal(|| None);
+    let mut progress: Signal<f64> = use_signal(|| 0.0);
+    let mut is_running: Signal<bool> = use_signal(|| false);
+    let mut result_message: Signal<Option<String>> = use_signal(|| None);
+
+    let build_options = move || -> Result<FrameExtractOptions, String> {
+        let value = value_input();
+        let mode = match mode().as_str() {
+            "interval" => FrameMode::Interval(
+                parse_timestamp(&value)
+                    .filter(|v| *v > 0.0)
+                    .ok_or("间隔必须大于 0")?,
+            ),
+            "scene" => FrameMode::Scene(
+                value
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|v| *v > 0.0 && *v < 1.0)
+                    .ok_or("阈值需在 0~1 之间")?,
+            ),
+            _ => FrameMode::Single(parse_timestamp(&value).ok_or("时间格式不正确")?),
+        };
+        Ok(FrameExtractOptions {
+            mode,
+            format: format(),
+        })
+    };
+
+    let pick_output_dir = move |_| async move {
+        if let Some(folder) = rfd::AsyncFileDialog::new()
+            .set_title("选择保存目录")
+            .pick_folder()
+            .await
+        {
+            output_dir.set(Some(folder.path().to_path_buf()));
+        }
+    };
+
+    let start_extract = move |_| async move {
+        let Some(info) = target() else {
+            return;
+        };
+        let options = match build_options() {
+            Ok(options) => options,
+            Err(e) => {
+                result_message.set(Some(e));
+                return;
+            }
+        };
+        let dir = output_dir().unwrap_or_else(|| {
+            info.file_path
+                .parent()
+                .map(PathBuf::from)
+                .unwrap_or_default()
+        });
+
+        is_running.set(true);
+        progress.set(0.0);
+        result_message.set(None);
+        let (tx, mut rx) = futures::channel::mpsc::unbounded::<MergeEvent>();
+        let extract = run_frame_extract(info.file_path.clone(), dir, options, tx);
+        let updates = async {
+            while let Some(event) = rx.next().await {
+                match event {
+                    MergeEvent::Progress(p) => progress.set(p.percent),
+                    MergeEvent::Status(_)
+                    | MergeEvent::Segments(_)
+                    | MergeEvent::Probing { .. } => {}
+                    MergeEvent::Error(e) => result_message.set(Some(format!("截图失败: {}", e))),
+                    MergeEvent::Success(message) => {
+                        progress.set(100.0);
+                        result_message.set(Some(message));
+                    }
+                }
+            }
+        };
+        futures::join!(extract, updates);
+        is_running.set(false);
+    };
+
+    let file_name = target
+        .read()
+        .as_ref()
+        .map(|info| info.file_name.clone())
+        .unwrap_or_default();
+    let value_label = match mode().as_str() {
+        "interval" => "间隔(秒)",
+        "scene" => "变化阈值",
+        _ => "时间点",
+    };
+
+    rsx! {
+        AlertDialogRoot {
+            open: target.read().is_some(),
+            on_open_change: move |v: bool| {
+                if !v && !is_running() {
+                    target.set(None);
+                    result_message.set(None);
+                    progress.set(0.0);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "截图/提取画面" }
+                AlertDialogDescription { "{file_name}" }
+                div { class: "space-y-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "方式" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            onchange: move |evt| {
+                                let value = evt.value();
+                                value_input.set(
+                                    match value.as_str() {
+                                        "interval" => "10",
+                                        "scene" => "0.4",
+                                        _ => "0",
+                                    }
+                                        .to_string(),
+                                );
+                                mode.set(value);
+                            },
+                            option { value: "single", selected: mode() == "single", "单帧（指定时间点）" }
+                            option { value: "interval", selected: mode() == "interval", "每隔 N 秒一帧" }
+                            option { value: "scene", selected: mode() == "scene", "场景切换缩略图" }
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "{value_label}" }
+                        Input {
+                            value: "{value_input}",
+                            oninput: move |e: FormEvent| value_input.set(e.value()),
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "格式" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            onchange: move |evt| {
+                                format.set(if evt.value() == "jpeg" { ImageFormat::Jpeg } else { ImageFormat::Png });
+                            },
+                            option { value: "png", selected: format() == ImageFormat::Png, "PNG" }
+                            option { value: "jpeg", selected: format() == ImageFormat::Jpeg, "JPEG" }
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "保存到" }
+                        span { class: "flex-1 text-gray-600 break-all",
+                            if let Some(dir) = output_dir() {
+                                "{dir.display()}"
+                            } else {
+                                "视频所在目录"
+                            }
+                        }
+                        Button { variant: ButtonVariant::Secondary, onclick: pick_output_dir, "选择目录" }
+                    }
+                    if is_running() || progress() > 0.0 {
+                        Progress { aria_label: "截图进度", value: progress(), ProgressIndicator {} }
+                    }
+                    if let Some(message) = result_message() {
+                        p { class: "text-gray-600 break-all", "{message}" }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button { disabled: is_running(), onclick: start_extract,
+                        if is_running() {
+                            "处理中..."
+                        } else {
+                            "开始"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
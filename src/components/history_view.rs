@@ -0,0 +1,289 @@
+use super::button::{Button, ButtonVariant};
+use crate::components::savings_report::SavingsReport;
+use crate::config::AppConfig;
+use crate::error::AppError;
+use crate::history::{
+    HistoryEntry, HistoryExportFormat, HistoryStats, JobKind, describe_savings, export_history,
+    load_history, save_history, total_sizes, verify_checksums,
+};
+use crate::queue::{MergeJob, use_job_queue};
+use crate::utils::{format_date, format_duration, format_relative_date};
+use dioxus::prelude::*;
+
+// 历史记录视图：按当时保存的选项重新运行，并可与当前默认选项对比
+#[component]
+pub fn HistoryView(config: Signal<AppConfig>) -> Element {
+    let mut queue = use_job_queue();
+    let mut history: Signal<Vec<HistoryEntry>> = use_signal(load_history);
+    let mut expanded: Signal<Option<usize>> = use_signal(|| None);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+    // 正在校验或已校验的记录及结果
+    let mut verifying: Signal<Option<usize>> = use_signal(|| None);
+    let mut verify_result: Signal<Option<(usize, Result<usize, Vec<String>>)>> =
+        use_signal(|| None);
+
+    let verify = move |index: usize, entry: HistoryEntry| async move {
+        verifying.set(Some(index));
+        verify_result.set(None);
+        let result = tokio::task::spawn_blocking(move || verify_checksums(&entry.checksums))
+            .await
+            .unwrap_or_else(|e| Err(vec![format!("校验线程异常: {}", e)]));
+        verify_result.set(Some((index, result)));
+        verifying.set(None);
+    };
+
+    let mut persist = move |entries: Vec<HistoryEntry>| {
+        if let Err(e) = save_history(&entries) {
+            message.set(Some(format!("保存历史记录失败: {}", e)));
+        }
+        history.set(entries);
+        expanded.set(None);
+        verify_result.set(None);
+    };
+
+    let rerun = move |entry: HistoryEntry| {
+        let name = entry.name();
+        queue.write().push(MergeJob::new(
+            name.clone(),
+            entry.files,
+            entry.output_path,
+            entry.options,
+        ));
+        message.set(Some(format!("已按原选项将“{}”加入队列", name)));
+    };
+
+    let export = move |format: HistoryExportFormat| async move {
+        let Some(handle) = rfd::AsyncFileDialog::new()
+            .set_title("导出历史记录")
+            .set_file_name(format!("merge-history.{}", format.extension()))
+            .save_file()
+            .await
+        else {
+            return;
+        };
+        let path = handle.path().to_path_buf();
+        let result = export_history(&history.peek(), format).and_then(|content| {
+            std::fs::write(&path, content).map_err(|e| AppError::io("写入导出文件失败", e))
+        });
+        match result {
+            Ok(()) => message.set(Some(format!("已导出到 {}", path.display()))),
+            Err(e) => message.set(Some(e.to_string())),
+        }
+    };
+
+    let stats = HistoryStats::new(&history.read());
+    let language = config.read().language;
+
+    rsx! {
+        div { class: "max-w-2xl mx-auto p-6 pt-2 space-y-3",
+            div { class: "flex items-center justify-between",
+                h2 { class: "text-xl font-semibold", "任务历史" }
+                div { class: "flex gap-2",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| {
+                            history.set(load_history());
+                            expanded.set(None);
+                        },
+                        "刷新"
+                    }
+                    for format in HistoryExportFormat::ALL {
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            disabled: history.read().is_empty(),
+                            onclick: move |_| export(format),
+                            "导出 {format.extension().to_uppercase()}"
+                        }
+                    }
+                    Button {
+                        variant: ButtonVariant::Destructive,
+                        disabled: history.read().is_empty(),
+                        onclick: move |_| persist(Vec::new()),
+                        "清空"
+                    }
+                }
+            }
+            if stats.jobs > 0 {
+                div { class: "flex flex-wrap gap-x-4 gap-y-1 text-xs text-gray-400",
+                    span { "共 {stats.jobs} 个任务，成功 {stats.succeeded} 个" }
+                    span { "处理视频 {stats.videos} 个" }
+                    span { "输出总时长 {format_duration(stats.duration_secs)}" }
+                    if let Some(speed) = stats.average_speed() {
+                        span { title: "输出时长与耗时之比", "平均速度 {speed:.1}x" }
+                    }
+                }
+            }
+            if let Some(msg) = message() {
+                p { class: "text-sm text-green-400", "{msg}" }
+            }
+            if history.read().is_empty() {
+                div { class: "text-center py-8 border-2 border-dashed border-gray-600 rounded-lg",
+                    p { class: "text-gray-500 text-lg", "暂无历史记录" }
+                    p { class: "text-gray-600 text-sm mt-1",
+                        "每次合并完成后会记录文件和使用的选项，压缩与转码会记录节省的空间"
+                    }
+                }
+            }
+            div { class: "space-y-2 max-h-[60vh] overflow-y-auto pr-2 custom-scrollbar",
+                for (index , entry) in history.read().iter().cloned().enumerate() {
+                    div { class: "py-2 px-3 rounded-lg border border-gray-600 space-y-2",
+                        div { class: "flex items-center justify-between gap-3",
+                            div { class: "overflow-hidden",
+                                p { class: "font-semibold truncate",
+                                    if entry.kind != JobKind::Merge {
+                                        span { class: "text-xs text-gray-400 mr-1", "[{entry.kind.label()}]" }
+                                    }
+                                    "{entry.name()}"
+                                }
+                                p { class: "text-xs text-gray-400 truncate",
+                                    // 无法解析的旧记录原样显示
+                                    if let Some(time) = entry.finished_time() {
+                                        span { title: format_date(Some(time), language),
+                                            {format_relative_date(Some(time), language)}
+                                        }
+                                    } else {
+                                        "{entry.finished_at}"
+                                    }
+                                    if entry.sizes.is_empty() {
+                                        " · {entry.files.len()} 个文件 → {entry.output_path.display()}"
+                                    } else {
+                                        {
+                                            let (original, output) = total_sizes(&entry.sizes);
+                                            format!(" · {}", describe_savings(original, output))
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "flex items-center gap-2 shrink-0",
+                                if entry.error.is_some() {
+                                    span { class: "text-red-500 text-sm", "失败" }
+                                } else {
+                                    span { class: "text-green-500 text-sm", "成功" }
+                                }
+                                Button {
+                                    variant: ButtonVariant::Ghost,
+                                    onclick: move |_| {
+                                        let current = *expanded.peek();
+                                        expanded.set(if current == Some(index) { None } else { Some(index) });
+                                    },
+                                    if entry.kind == JobKind::Merge {
+                                        "对比默认"
+                                    } else {
+                                        "明细"
+                                    }
+                                }
+                                if !entry.checksums.is_empty() {
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        disabled: verifying().is_some(),
+                                        title: "重新计算 SHA-256，确认文件未被改动",
+                                        onclick: {
+                                            let entry = entry.clone();
+                                            move |_| verify(index, entry.clone())
+                                        },
+                                        if verifying() == Some(index) {
+                                            "校验中..."
+                                        } else {
+                                            "校验"
+                                        }
+                                    }
+                                }
+                                // 压缩与转码记录只用于查看节省的空间，不能作为合并任务重新运行
+                                if entry.kind == JobKind::Merge {
+                                    Button {
+                                        variant: ButtonVariant::Secondary,
+                                        onclick: {
+                                            let entry = entry.clone();
+                                            move |_| rerun(entry.clone())
+                                        },
+                                        "重新运行"
+                                    }
+                                }
+                                Button {
+                                    variant: ButtonVariant::Destructive,
+                                    onclick: move |_| {
+                                        let mut entries = history();
+                                        entries.remove(index);
+                                        persist(entries);
+                                    },
+                                    "删除"
+                                }
+                            }
+                        }
+                        if let Some(e) = &entry.error {
+                            p { class: "text-xs text-red-400 break-all", "{e}" }
+                        }
+                        if let Some(log) = &entry.post_command_log {
+                            details { class: "text-xs text-gray-500",
+                                summary { "合并后命令输出" }
+                                pre { class: "whitespace-pre-wrap break-all font-mono", "{log}" }
+                            }
+                        }
+                        if let Some(output) = entry.checksums.first() {
+                            p { class: "text-xs text-gray-500 font-mono break-all", "SHA-256 {output.sha256}" }
+                        }
+                        {
+                            match verify_result().filter(|(i, _)| *i == index).map(|(_, r)| r) {
+                                Some(Ok(count)) => rsx! {
+                                    p { class: "text-xs text-green-400", "校验通过：{count} 个文件均未改动" }
+                                },
+                                Some(Err(problems)) => rsx! {
+                                    for problem in problems {
+                                        p { class: "text-xs text-red-400 break-all", "{problem}" }
+                                    }
+                                },
+                                None => rsx! {},
+                            }
+                        }
+                        if expanded() == Some(index) {
+                            if entry.kind == JobKind::Merge {
+                                OptionsDiff { entry: entry.clone(), config }
+                            } else {
+                                SavingsReport { sizes: entry.sizes.clone() }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 列出历史选项与当前默认选项不同的项
+#[component]
+fn OptionsDiff(entry: HistoryEntry, config: Signal<AppConfig>) -> Element {
+    let defaults = config.read().default_options.summary();
+    let rows: Vec<(&'static str, String, String)> = entry
+        .options
+        .summary()
+        .into_iter()
+        .zip(defaults)
+        .filter(|((_, used), (_, current))| used != current)
+        .map(|((label, used), (_, current))| (label, used, current))
+        .collect();
+
+    rsx! {
+        if rows.is_empty() {
+            p { class: "text-xs text-gray-400", "与当前默认选项相同" }
+        } else {
+            table { class: "w-full text-xs",
+                thead {
+                    tr { class: "text-gray-400 text-left",
+                        th { class: "py-1", "选项" }
+                        th { class: "py-1", "本次使用" }
+                        th { class: "py-1", "当前默认" }
+                    }
+                }
+                tbody {
+                    for (label , used , current) in rows {
+                        tr { class: "border-t border-gray-700",
+                            td { class: "py-1 pr-2 text-gray-400", "{label}" }
+                            td { class: "py-1 pr-2 text-yellow-400", "{used}" }
+                            td { class: "py-1", "{current}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::ffmpeg::integrity::IntegrityReport;
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+/// 文件的完整性检查状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityState {
+    Pending,
+    Checking,
+    Done(IntegrityReport),
+    /// 无法完成检查（如找不到 FFmpeg）
+    Failed(String),
+}
+
+impl IntegrityState {
+    /// 列表中文件名旁的标记，检查通过时不显示
+    pub fn icon(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            IntegrityState::Pending | IntegrityState::Checking => Some(("⏳", "正在检查完整性")),
+            IntegrityState::Done(report) if report.is_ok() => None,
+            IntegrityState::Done(_) => Some(("⚠️", "发现解码错误，点击查看")),
+            IntegrityState::Failed(_) => Some(("❔", "无法完成完整性检查，点击查看")),
+        }
+    }
+}
+
+// 完整性检查结果对话框，检查在后台进行，对话框可随时关闭再打开
+#[component]
+pub fn IntegrityDialog(
+    open: Signal<bool>,
+    results: Signal<Vec<(PathBuf, IntegrityState)>>,
+) -> Element {
+    use_restore_focus(move || open());
+    let total = results.read().len();
+    let checked = results
+        .read()
+        .iter()
+        .filter(|(_, s)| matches!(s, IntegrityState::Done(_) | IntegrityState::Failed(_)))
+        .count();
+    let damaged = results
+        .read()
+        .iter()
+        .filter(|(_, s)| matches!(s, IntegrityState::Done(r) if !r.is_ok()))
+        .count();
+
+    rsx! {
+        AlertDialogRoot {
+            open: open(),
+            on_open_change: move |v: bool| open.set(v),
+            AlertDialogContent {
+                AlertDialogTitle { "完整性检查" }
+                AlertDialogDescription {
+                    if checked < total {
+                        "正在检查 {checked}/{total}，可关闭窗口继续其他操作"
+                    } else if damaged == 0 {
+                        "{total} 个文件均未发现解码错误"
+                    } else {
+                        "{total} 个文件中有 {damaged} 个存在解码错误"
+                    }
+                }
+                div { class: "max-h-80 overflow-auto space-y-2 text-sm",
+                    for (path , state) in results.read().iter() {
+                        div { class: "border-b pb-1",
+                            p { class: "text-gray-900 break-all",
+                                {path.file_name().unwrap_or_default().to_string_lossy().into_owned()}
+                            }
+                            match state {
+                                IntegrityState::Pending => rsx! {
+                                    p { class: "text-gray-400", "等待检查" }
+                                },
+                                IntegrityState::Checking => rsx! {
+                                    p { class: "text-gray-500", "正在解码..." }
+                                },
+                                IntegrityState::Done(report) if report.is_ok() => rsx! {
+                                    p { class: "text-green-600", "未发现错误" }
+                                },
+                                IntegrityState::Done(report) => rsx! {
+                                    p { class: "text-red-600", "{report.error_count} 个解码错误" }
+                                    pre { class: "text-xs text-gray-600 whitespace-pre-wrap break-all bg-gray-50 p-1 rounded",
+                                        {report.errors.join("\n")}
+                                        if report.error_count > report.errors.len() {
+                                            {format!("\n……其余 {} 行已省略", report.error_count - report.errors.len())}
+                                        }
+                                    }
+                                },
+                                IntegrityState::Failed(message) => rsx! {
+                                    p { class: "text-amber-600 break-all", "检查失败: {message}" }
+                                },
+                            }
+                        }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                }
+            }
+        }
+    }
+}
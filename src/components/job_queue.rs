@@ -0,0 +1,248 @@
+use super::button::{Button, ButtonVariant};
+use super::confirm_dialog::{Confirmation, confirm, use_confirm};
+use super::progress::{Progress, ProgressIndicator};
+use super::upload_panel::UploadPanel;
+use crate::config::AppConfig;
+use crate::queue::{JobStatus, QUEUE_PAUSED, use_job_queue, use_queue_runner};
+use crate::utils::{PowerAction, format_duration};
+use chrono::{Local, NaiveDateTime, TimeZone};
+use dioxus::prelude::*;
+use std::sync::atomic::Ordering;
+
+// 任务队列视图：显示每个任务的状态与进度，并按顺序执行待处理任务
+#[component]
+pub fn JobQueueView(config: Signal<AppConfig>) -> Element {
+    let mut queue = use_job_queue();
+    let confirm_requests = use_confirm();
+    let runner = use_queue_runner();
+    let is_running = runner.is_running;
+    let summary = runner.summary;
+    let mut scheduled_at = runner.scheduled_at;
+    // datetime-local 输入框的值，形如 2024-01-01T23:00
+    let mut schedule_input: Signal<String> = use_signal(String::new);
+    let mut schedule_error: Signal<Option<String>> = use_signal(|| None);
+    let mut power_after_completion = runner.power_after_completion;
+    let mut power_error: Signal<Option<String>> = use_signal(|| None);
+
+    let start_queue = move |_| runner.start();
+
+    let set_power_action = move |evt: FormEvent| {
+        let action = match evt.value().as_str() {
+            "sleep" => PowerAction::Sleep,
+            _ => PowerAction::Shutdown,
+        };
+        config.write().power_action = action;
+        if let Err(e) = config.read().save() {
+            power_error.set(Some(format!("无法保存电源操作设置: {}", e)));
+        }
+    };
+
+    let schedule_queue = move |_| {
+        let at = NaiveDateTime::parse_from_str(&schedule_input(), "%Y-%m-%dT%H:%M")
+            .ok()
+            .and_then(|naive| Local.from_local_datetime(&naive).earliest());
+        match at {
+            Some(at) if at > Local::now() => {
+                scheduled_at.set(Some(at));
+                schedule_error.set(None);
+            }
+            Some(_) => schedule_error.set(Some("请选择一个将来的时间".to_string())),
+            None => schedule_error.set(Some("请选择开始时间".to_string())),
+        }
+    };
+
+    // 从队列中移除尚未开始的任务
+    let remove_job = move |id: u64, name: String| {
+        spawn(async move {
+            let confirmation =
+                Confirmation::new("移除任务", format!("确定要从队列中移除“{}”吗？", name))
+                    .action("移除")
+                    .destructive();
+            if confirm(confirm_requests, confirmation).await {
+                queue.write().retain(|j| j.id != id);
+            }
+        });
+    };
+
+    let clear_finished = move |_| {
+        queue
+            .write()
+            .retain(|j| matches!(j.status, JobStatus::Pending | JobStatus::Running));
+    };
+
+    let pending_count = queue
+        .read()
+        .iter()
+        .filter(|j| j.status == JobStatus::Pending)
+        .count();
+
+    let schedule_label = scheduled_at().map(|at| {
+        let mut label = format!("将于 {} 开始", at.format("%m-%d %H:%M"));
+        if let Some(secs) = (runner.countdown)() {
+            label.push_str(&format!("（剩余 {}）", format_duration(secs as f64)));
+        }
+        label
+    });
+
+    rsx! {
+        div { class: "max-w-2xl mx-auto p-6 pt-2 space-y-3",
+            div { class: "flex items-center justify-between",
+                h2 { class: "text-xl font-semibold", "任务队列" }
+                div { class: "flex gap-2",
+                    Button { variant: ButtonVariant::Secondary, onclick: clear_finished, "清除已完成" }
+                    if is_running() {
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            title: "当前任务完成后停止",
+                            onclick: move |_| QUEUE_PAUSED.store(true, Ordering::SeqCst),
+                            "暂停"
+                        }
+                    }
+                    Button {
+                        disabled: is_running() || pending_count == 0,
+                        onclick: start_queue,
+                        if is_running() {
+                            "执行中..."
+                        } else {
+                            "开始队列 ({pending_count})"
+                        }
+                    }
+                }
+            }
+            // 定时开始：到点后自动执行，窗口最小化到托盘时照常进行
+            if !is_running() && pending_count > 0 {
+                div { class: "flex items-center gap-2 text-sm",
+                    if let Some(label) = schedule_label {
+                        span { class: "text-purple-400", "{label}" }
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            onclick: move |_| scheduled_at.set(None),
+                            "取消定时"
+                        }
+                    } else {
+                        span { class: "text-gray-400", "定时开始" }
+                        input {
+                            r#type: "datetime-local",
+                            class: "border rounded px-2 py-1 text-sm",
+                            value: "{schedule_input}",
+                            oninput: move |e| schedule_input.set(e.value()),
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            onclick: schedule_queue,
+                            "设定"
+                        }
+                    }
+                    if let Some(e) = schedule_error() {
+                        span { class: "text-red-400", "{e}" }
+                    }
+                }
+            }
+            // 队列全部成功完成后关机/睡眠，适合过夜批量处理
+            div { class: "flex items-center gap-2 text-sm text-gray-400",
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: power_after_completion(),
+                        onchange: move |evt| power_after_completion.set(evt.checked()),
+                    }
+                    "队列完成后"
+                }
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    disabled: !power_after_completion(),
+                    onchange: set_power_action,
+                    option {
+                        value: "shutdown",
+                        selected: config().power_action == PowerAction::Shutdown,
+                        {PowerAction::Shutdown.label()}
+                    }
+                    option {
+                        value: "sleep",
+                        selected: config().power_action == PowerAction::Sleep,
+                        {PowerAction::Sleep.label()}
+                    }
+                }
+                if let Some(e) = power_error() {
+                    span { class: "text-red-400", "{e}" }
+                }
+            }
+            if let Some(s) = summary() {
+                div {
+                    class: if s.failed > 0 { "p-3 rounded-lg border border-red-500/50 text-sm space-y-1" } else { "p-3 rounded-lg border border-green-500/50 text-sm space-y-1" },
+                    p { class: "font-semibold",
+                        if s.stopped_on_error {
+                            "队列因任务失败而停止"
+                        } else if s.remaining > 0 {
+                            "队列已暂停"
+                        } else {
+                            "队列执行完毕"
+                        }
+                    }
+                    p { class: "text-gray-400",
+                        "成功 {s.done} 个，失败 {s.failed} 个，未执行 {s.remaining} 个"
+                    }
+                }
+            }
+            if queue.read().is_empty() {
+                div { class: "text-center py-8 border-2 border-dashed border-gray-600 rounded-lg",
+                    p { class: "text-gray-500 text-lg", "队列为空" }
+                    p { class: "text-gray-600 text-sm mt-1", "可在合并页或文件库中将任务加入队列" }
+                }
+            }
+            div { class: "space-y-2 max-h-[60vh] overflow-y-auto pr-2 custom-scrollbar",
+                for job in queue.read().iter().cloned() {
+                    div { class: "py-2 px-3 rounded-lg border border-gray-600 space-y-2",
+                        div { class: "flex items-center justify-between gap-3",
+                            div { class: "overflow-hidden",
+                                p { class: "font-semibold truncate", "{job.name}" }
+                                p { class: "text-xs text-gray-400 truncate",
+                                    "{job.files.len()} 个文件 → {job.output_path.display()}"
+                                }
+                            }
+                            {
+                                match &job.status {
+                                    JobStatus::Pending => rsx! {
+                                        Button {
+                                            variant: ButtonVariant::Destructive,
+                                            onclick: {
+                                                let name = job.name.clone();
+                                                move |_| remove_job(job.id, name.clone())
+                                            },
+                                            "移除"
+                                        }
+                                    },
+                                    JobStatus::Running => rsx! {
+                                        span { class: "text-purple-400 font-mono text-sm", "{job.progress:.1}%" }
+                                    },
+                                    JobStatus::Done => rsx! {
+                                        span { class: "text-green-500 text-sm", "已完成" }
+                                    },
+                                    JobStatus::Failed(_) => rsx! {
+                                        span { class: "text-red-500 text-sm", "失败" }
+                                    },
+                                }
+                            }
+                        }
+                        if job.status == JobStatus::Running {
+                            Progress { aria_label: "任务进度", value: job.progress, ProgressIndicator {} }
+                        }
+                        if let JobStatus::Failed(e) = &job.status {
+                            p { class: "text-xs text-red-400 break-all", "{e}" }
+                        }
+                        if job.retries > 0 {
+                            p { class: "text-xs text-amber-400 break-all",
+                                if job.status == JobStatus::Pending {
+                                    "{job.status_message}"
+                                } else {
+                                    "已自动重试 {job.retries} 次"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            UploadPanel {}
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::components::mp4_merger::Mp4Merger;
+use crate::config::AppConfig;
+use crate::session::use_session;
+use dioxus::prelude::*;
+
+// 多个合并工作区，各自拥有独立的文件列表与选项，共用全局队列和配置
+#[component]
+pub fn MergerWorkspaces(config: Signal<AppConfig>) -> Element {
+    // 工作区编号，只增不减，用作 key 以保持各自状态
+    let mut workspaces: Signal<Vec<u32>> = use_signal(|| vec![1]);
+    let mut next_id: Signal<u32> = use_signal(|| 2);
+    let mut active: Signal<u32> = use_signal(|| 1);
+
+    // 恢复上次会话时补齐对应编号的工作区
+    let session = use_session();
+    use_effect(move || {
+        let restored: Vec<u32> = session.restore.read().keys().copied().collect();
+        let Some(max) = restored.iter().max().copied() else {
+            return;
+        };
+        let mut list = workspaces.write();
+        for id in restored {
+            if !list.contains(&id) {
+                list.push(id);
+            }
+        }
+        list.sort();
+        if *next_id.peek() <= max {
+            next_id.set(max + 1);
+        }
+    });
+
+    let add_workspace = move |_| {
+        let id = next_id();
+        next_id += 1;
+        workspaces.write().push(id);
+        active.set(id);
+    };
+
+    let mut close_workspace = move |id: u32| {
+        let mut list = workspaces.write();
+        let Some(index) = list.iter().position(|w| *w == id) else {
+            return;
+        };
+        list.remove(index);
+        if active() == id
+            && let Some(next) = list.get(index.min(list.len().saturating_sub(1)))
+        {
+            active.set(*next);
+        }
+    };
+
+    rsx! {
+        div { class: "flex flex-col h-full",
+            if workspaces.read().len() > 1 {
+                div {
+                    class: "flex items-center gap-1 px-4 pt-2 border-b border-gray-700 text-sm",
+                    role: "tablist",
+                    for id in workspaces() {
+                        div {
+                            key: "{id}",
+                            class: if active() == id { "flex items-center gap-1 px-3 py-1 rounded-t border border-b-0 border-gray-600 bg-gray-700/40" } else { "flex items-center gap-1 px-3 py-1 rounded-t text-gray-400 hover:text-gray-200 cursor-pointer" },
+                            onclick: move |_| active.set(id),
+                            button {
+                                r#type: "button",
+                                role: "tab",
+                                aria_selected: active() == id,
+                                "工作区 {id}"
+                            }
+                            button {
+                                class: "text-gray-500 hover:text-red-400",
+                                title: "关闭工作区",
+                                aria_label: "关闭工作区 {id}",
+                                onclick: move |evt| {
+                                    evt.stop_propagation();
+                                    close_workspace(id);
+                                },
+                                "✕"
+                            }
+                        }
+                    }
+                    button {
+                        class: "px-2 py-1 text-gray-400 hover:text-gray-200",
+                        title: "新建工作区",
+                        onclick: add_workspace,
+                        "+"
+                    }
+                }
+            } else {
+                div { class: "flex justify-end px-4 pt-2 text-sm",
+                    button {
+                        class: "text-gray-400 hover:text-gray-200",
+                        title: "同时准备多个合并任务",
+                        onclick: add_workspace,
+                        "+ 新建工作区"
+                    }
+                }
+            }
+            // 非当前工作区只隐藏不卸载，保留各自的状态
+            for id in workspaces() {
+                div { key: "{id}", class: if active() == id { "flex-1" } else { "hidden" },
+                    Mp4Merger { config, active: active() == id, workspace_id: id }
+                }
+            }
+        }
+    }
+}
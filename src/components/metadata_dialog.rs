@@ -0,0 +1,120 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::Button;
+use crate::components::input::Input;
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::ffmpeg::metadata::{ClipMetadata, read_metadata, write_metadata};
+use dioxus::prelude::*;
+
+// 元数据编辑对话框，target 为 None 时关闭
+#[component]
+pub fn MetadataDialog(target: Signal<Option<Mp4FileInfo>>) -> Element {
+    use_restore_focus(move || target.read().is_some());
+    let mut metadata: Signal<ClipMetadata> = use_signal(ClipMetadata::default);
+    let mut is_loading: Signal<bool> = use_signal(|| false);
+    let mut is_saving: Signal<bool> = use_signal(|| false);
+    let mut result_message: Signal<Option<String>> = use_signal(|| None);
+
+    // 打开时读取当前标签
+    use_effect(move || {
+        let Some(info) = target() else {
+            return;
+        };
+        is_loading.set(true);
+        result_message.set(None);
+        spawn(async move {
+            match read_metadata(&info.file_path).await {
+                Ok(m) => metadata.set(m),
+                Err(e) => {
+                    metadata.set(ClipMetadata::default());
+                    result_message.set(Some(format!("读取元数据失败: {}", e)));
+                }
+            }
+            is_loading.set(false);
+        });
+    });
+
+    let save = move |_| async move {
+        let Some(info) = target() else {
+            return;
+        };
+        is_saving.set(true);
+        result_message.set(None);
+        match write_metadata(&info.file_path, &metadata()).await {
+            Ok(()) => result_message.set(Some("已保存".to_string())),
+            Err(e) => result_message.set(Some(e.to_string())),
+        }
+        is_saving.set(false);
+    };
+
+    let file_name = target
+        .read()
+        .as_ref()
+        .map(|info| info.file_name.clone())
+        .unwrap_or_default();
+    let current = metadata();
+
+    rsx! {
+        AlertDialogRoot {
+            open: target.read().is_some(),
+            on_open_change: move |v: bool| {
+                if !v && !is_saving() {
+                    target.set(None);
+                    result_message.set(None);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "编辑元数据" }
+                AlertDialogDescription { "{file_name}" }
+                div { class: "space-y-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "标题" }
+                        Input {
+                            value: "{current.title}",
+                            oninput: move |e: FormEvent| metadata.write().title = e.value(),
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "作者" }
+                        Input {
+                            value: "{current.artist}",
+                            oninput: move |e: FormEvent| metadata.write().artist = e.value(),
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "备注" }
+                        Input {
+                            value: "{current.comment}",
+                            oninput: move |e: FormEvent| metadata.write().comment = e.value(),
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "创建时间" }
+                        Input {
+                            placeholder: "2024-01-01T12:00:00Z",
+                            value: "{current.creation_time}",
+                            oninput: move |e: FormEvent| metadata.write().creation_time = e.value(),
+                        }
+                    }
+                    p { class: "text-gray-500", "留空的标签会被删除；保存时直接复制音视频流并替换原文件" }
+                    if let Some(message) = result_message() {
+                        p { class: "text-gray-600 break-all", "{message}" }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button { disabled: is_loading() || is_saving(), onclick: save,
+                        if is_saving() {
+                            "保存中..."
+                        } else {
+                            "保存"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
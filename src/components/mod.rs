@@ -1,14 +1,48 @@
 // AUTOGENERATED Components module
 pub mod about_footer;
+pub mod advanced_options;
 pub mod alert_dialog;
+pub mod benchmark_dialog;
 pub mod button;
+pub mod cleanup_dialog;
+pub mod clip_export_dialog;
+pub mod compress_dialog;
+pub mod confirm_dialog;
+pub mod crash_report_dialog;
+pub mod diagnostics_view;
+pub mod encoding_impact;
+pub mod error_dialog;
+pub mod exit_guard_dialog;
 pub mod file_list;
+pub mod folder_import;
+pub mod frame_extract_dialog;
+pub mod history_view;
 pub mod input;
+pub mod integrity_dialog;
+pub mod job_queue;
+pub mod merger_workspaces;
+pub mod metadata_dialog;
+pub mod mp4_card;
 pub mod mp4_info;
 pub mod mp4_info_loading;
 pub mod mp4_info_table;
 pub mod mp4_merger;
+pub mod normalize_plan_dialog;
+pub mod notification_center;
 pub mod output_settings;
+pub mod post_steps;
+pub mod power_countdown;
 pub mod progress;
+pub mod rotate_dialog;
+pub mod savings_report;
+pub mod session_groups;
+pub mod session_restore_dialog;
+pub mod settings_view;
+pub mod setup_wizard;
 pub mod tabs;
+pub mod timeline;
 pub mod toast;
+pub mod transcode_dialog;
+pub mod trim_dialog;
+pub mod upload_panel;
+pub mod upload_settings;
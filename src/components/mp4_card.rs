@@ -0,0 +1,76 @@
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::ffmpeg::thumbnail::thumbnail;
+use crate::utils::{format_bytes, format_duration, format_size};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use dioxus::prelude::*;
+
+// 网格视图中的一张卡片：缩略图、文件名、时长与大小，点击卡片切换选中
+#[component]
+pub fn Mp4Card(
+    info: Mp4FileInfo,
+    selected: bool,
+    on_toggle: EventHandler<()>,
+    on_open: EventHandler<()>,
+) -> Element {
+    let path = info.file_path.clone();
+    let duration = info.duration.as_secs_f64();
+    // 以 data URL 嵌入，webview 不能直接访问本地文件；卡片按路径作 key，文件不变时不需重新生成
+    let image = use_resource(move || {
+        let path = path.clone();
+        async move {
+            let cached = thumbnail(&path, duration).await.ok()?;
+            let bytes = tokio::fs::read(cached).await.ok()?;
+            Some(format!("data:image/jpeg;base64,{}", STANDARD.encode(bytes)))
+        }
+    });
+
+    rsx! {
+        div {
+            class: if selected { "rounded-lg border-2 border-blue-500 bg-blue-50 overflow-hidden cursor-pointer" } else { "rounded-lg border border-gray-200 overflow-hidden cursor-pointer hover:border-gray-400" },
+            title: "{info.file_path.display()}",
+            onclick: move |_| on_toggle.call(()),
+            div { class: "aspect-video bg-gray-800 flex items-center justify-center",
+                match image() {
+                    Some(Some(src)) => rsx! {
+                        img { class: "w-full h-full object-cover", src }
+                    },
+                    Some(None) => rsx! {
+                        span { class: "text-xs text-gray-400", "无法生成缩略图" }
+                    },
+                    None => rsx! {
+                        span { class: "text-xs text-gray-400", "加载中..." }
+                    },
+                }
+            }
+            div { class: "p-2 space-y-1",
+                div { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        class: "rounded border-gray-300 text-blue-600",
+                        aria_label: "选择 {info.file_name}",
+                        checked: selected,
+                        // 复选框与卡片点击是同一操作，避免触发两次
+                        onclick: move |e| {
+                            e.stop_propagation();
+                            on_toggle.call(());
+                        },
+                    }
+                    span { class: "text-sm text-gray-900 truncate", "{info.file_name}" }
+                }
+                div { class: "flex items-center justify-between text-xs text-gray-500",
+                    span { {format_duration(info.duration.as_secs_f64())} }
+                    span { title: format_bytes(info.size), {format_size(Some(info.size))} }
+                    button {
+                        class: "text-blue-600 hover:underline",
+                        onclick: move |e| {
+                            e.stop_propagation();
+                            on_open.call(());
+                        },
+                        "打开"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,36 +1,36 @@
-use crate::components::alert_dialog::{
-    AlertDialogAction, AlertDialogActions, AlertDialogCancel, AlertDialogContent,
-    AlertDialogDescription, AlertDialogRoot, AlertDialogTitle,
-};
 use crate::components::button::Button;
+use crate::components::cleanup_dialog::CleanupDialog;
 use crate::components::mp4_info_loading::Mp4InfoLoading;
 use crate::components::mp4_info_table::Mp4InfoTable;
+use crate::components::session_groups::SessionGroups;
 use crate::config::AppConfig;
-use crate::utils::parse_mp4_info;
+use crate::shortcuts::{ShortcutAction, shortcut_hint, use_shortcut};
+use crate::utils::{detect_sessions, display_name, list_mp4_files, parse_mp4_info};
 
 use dioxus::prelude::*;
-use rayon::prelude::*;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         Arc,
         atomic::{AtomicBool, Ordering},
     },
 };
 
+pub use crate::utils::Mp4FileInfo;
 use tokio::sync::mpsc;
-// MP4 文件信息结构
-#[derive(Debug, Clone)]
-pub struct Mp4FileInfo {
-    pub file_name: String,
-    pub size: u64,
-    pub modified: Option<std::time::SystemTime>,
-    pub width: u16,
-    pub height: u16,
-    pub codec: String,      // H.264 / H.265 / HEVC / AV1 等
-    pub duration: String,   // 秒
-    pub file_path: PathBuf, // 添加文件路径
+use tokio::time::{sleep, timeout};
+
+// 检查扫描的目录是否仍可访问的间隔
+const DIRECTORY_CHECK_INTERVAL: Duration = Duration::from_secs(3);
+// 网络共享断开时读取目录信息可能长时间不返回，超时视为不可用
+const DIRECTORY_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 目录是否仍可访问
+async fn directory_available(dir: &Path) -> bool {
+    timeout(DIRECTORY_CHECK_TIMEOUT, tokio::fs::metadata(dir))
+        .await
+        .is_ok_and(|meta| meta.is_ok_and(|m| m.is_dir()))
 }
 // 进度状态
 #[derive(Debug, Clone, Default)]
@@ -45,22 +45,32 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
     let mut selected_directory: Signal<Option<PathBuf>> =
         use_signal(|| config.read().get_query_directory());
     let mut files: Signal<Vec<Mp4FileInfo>> = use_signal(Vec::new);
-    let mut open = use_signal(|| false);
-    let mut confirmed = use_signal(|| false);
-    let file_name = use_signal(String::new); // 要删除文件的名称
     let mut is_loading: Signal<bool> = use_signal(|| false);
     let mut error_message: Signal<Option<String>> = use_signal(|| None);
     // 3. 添加取消扫描的功能
     let mut should_cancel = use_signal(|| Arc::new(AtomicBool::new(false)));
     // 新增：进度状态
     let mut progress: Signal<ScanProgress> = use_signal(ScanProgress::default);
+    // 自动分组得到的录制会话
+    let mut session_groups: Signal<Vec<Vec<Mp4FileInfo>>> = use_signal(Vec::new);
+    // 扫描时无法解析的文件及原因，用于清理建议
+    let mut unreadable_files: Signal<Vec<(PathBuf, String)>> = use_signal(Vec::new);
+    let mut cleanup_open: Signal<bool> = use_signal(|| false);
+    // 扫描的目录已断开（可移动磁盘被拔出、网络共享不可用），保留列表但只读
+    let mut directory_offline: Signal<bool> = use_signal(|| false);
+    // 收起常用目录一栏，窗口较矮时给文件列表留出空间
+    let mut toolbar_collapsed: Signal<bool> = use_signal(|| false);
+    // 切换了目录但还没有扫描；启动时恢复了上次的目录也算
+    let mut needs_scan: Signal<bool> = use_signal(|| selected_directory.peek().is_some());
 
     // 提取核心逻辑为无参闭包，避免重复代码
     let mut perform_scan = move || {
         // 开始时间
         let start = Instant::now();
         let dir = selected_directory.read().clone();
+        let scan_rules = config.peek().scan_rules.clone();
         let cancel_flag = Arc::new(AtomicBool::new(false));
+        needs_scan.set(false);
         should_cancel.set(cancel_flag.clone());
         spawn(async move {
             if let Some(directory) = dir {
@@ -78,24 +88,11 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
                 let cancel_flag_for_blocking = cancel_flag.clone();
                 let result = tokio::task::spawn_blocking(move || {
                     // 先收集所有 MP4 文件路径
-                    let mp4_paths: Vec<PathBuf> = match std::fs::read_dir(&directory) {
-                        Ok(entries) => entries
-                            .par_bridge()
-                            .filter_map(|entry| entry.ok())
-                            .map(|entry| entry.path())
-                            .filter(|path| {
-                                path.is_file()
-                                    && path
-                                        .extension()
-                                        .map(|ext| ext.eq_ignore_ascii_case("mp4"))
-                                        .unwrap_or(false)
-                            })
-                            .collect(),
-                        Err(e) => return Err(e),
-                    };
+                    let mp4_paths = list_mp4_files(&directory, &scan_rules)?;
 
                     let total = mp4_paths.len();
                     let mut mp4_files = Vec::with_capacity(total);
+                    let mut unreadable = Vec::new();
 
                     for (idx, path) in mp4_paths.into_iter().enumerate() {
                         // 检查是否取消
@@ -103,11 +100,7 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
                             break;
                         }
 
-                        let file_name = path
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("未知文件")
-                            .to_string();
+                        let file_name = display_name(&path);
 
                         // 创建进度更新
                         let progress_update = ScanProgress {
@@ -119,27 +112,30 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
                         let _ = futures::executor::block_on(async {
                             tx_clone.send(progress_update).await.ok()
                         });
-                        match parse_mp4_info(path) {
+                        match parse_mp4_info(path.clone()) {
                             Ok(info) => {
                                 // println!("解析到文件信息: {:?}", info);
                                 mp4_files.push(info);
                             }
                             Err(e) => {
                                 println!("解析文件信息失败: {} - {}", file_name, e);
+                                unreadable.push((path, e.to_string()));
                             }
                         }
                     }
 
-                    Ok(mp4_files)
+                    Ok((mp4_files, unreadable))
                 })
                 .await;
                 drop(tx);
 
                 match result {
-                    Ok(Ok(mp4_files)) => {
+                    Ok(Ok((mp4_files, unreadable))) => {
                         println!("扫描到 {} 个 MP4 文件", mp4_files.len(),);
                         println!("扫描耗时: {:.2} 秒", start.elapsed().as_secs_f64());
                         files.set(mp4_files);
+                        unreadable_files.set(unreadable);
+                        directory_offline.set(false);
                     }
                     Ok(Err(e)) => {
                         error_message.set(Some(format!("无法读取目录: {}", e)));
@@ -154,6 +150,57 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
         });
     };
 
+    // 恢复上次的目录后，开启了“切换时立即扫描”则直接扫描
+    use_hook(move || {
+        if *needs_scan.peek() && config.peek().scan_on_switch {
+            perform_scan();
+        }
+    });
+
+    // 切换目录后不再沿用上一个目录的断开状态
+    use_effect(move || {
+        selected_directory();
+        directory_offline.set(false);
+    });
+
+    // 定时检查目录是否仍可访问，断开后只提示一次，而不是每个操作都报错
+    use_future(move || async move {
+        loop {
+            sleep(DIRECTORY_CHECK_INTERVAL).await;
+            let Some(dir) = selected_directory.peek().clone() else {
+                continue;
+            };
+            if *directory_offline.peek() || files.peek().is_empty() || *is_loading.peek() {
+                continue;
+            }
+            if !directory_available(&dir).await {
+                directory_offline.set(true);
+            }
+        }
+    });
+
+    // 目录恢复后重新扫描，仍不可用时提示
+    let reconnect = move |_| async move {
+        let Some(dir) = selected_directory.peek().clone() else {
+            return;
+        };
+        if directory_available(&dir).await {
+            perform_scan();
+        } else {
+            error_message.set(Some(format!(
+                "仍无法访问 {}，请确认磁盘已连接或网络共享可用",
+                dir.display()
+            )));
+        }
+    };
+
+    // 快捷键重新扫描
+    use_shortcut(ShortcutAction::Rescan, move || {
+        if selected_directory.read().is_some() && !is_loading() {
+            perform_scan();
+        }
+    });
+
     // 给按钮用的处理器，接收事件但忽略
     let on_scan_click = move |_evt: Event<MouseData>| {
         perform_scan();
@@ -178,6 +225,34 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
             }
         }
     };
+    // 切换到固定的目录，按设置立即扫描，否则清空上一个目录的结果
+    let mut switch_directory = move |path: PathBuf| {
+        selected_directory.set(Some(path.clone()));
+        if let Err(e) = config.write().set_query_directory(path) {
+            error_message.set(Some(format!("无法保存输出目录设置: {}", e)));
+        }
+        if config.read().scan_on_switch {
+            perform_scan();
+        } else {
+            files.set(Vec::new());
+            unreadable_files.set(Vec::new());
+            session_groups.set(Vec::new());
+            needs_scan.set(true);
+        }
+    };
+    let toggle_pinned = move |_| {
+        let Some(dir) = selected_directory() else {
+            return;
+        };
+        if let Err(e) = config.write().toggle_pinned_directory(dir) {
+            error_message.set(Some(format!("无法保存设置: {}", e)));
+        }
+    };
+    let current_pinned = selected_directory
+        .read()
+        .as_ref()
+        .is_some_and(|dir| config.read().is_pinned(dir));
+
     // 5. 添加取消扫描的函数
     let cancel_scan = move || {
         should_cancel.read().store(true, Ordering::SeqCst);
@@ -185,7 +260,7 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
     };
     rsx! {
         div { class: "flex flex-col h-full p-2",
-            div { class: "flex flex-col  overflow-hidden",
+            div { class: "flex flex-col shrink-0",
                 // 顶部操作区域
                 div {
                     // 错误消息
@@ -199,9 +274,24 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
                         }
                     }
                 }
+                if directory_offline() {
+                    div {
+                        class: "mb-2 p-3 rounded-xl bg-amber-50 border border-amber-200 flex items-center gap-3",
+                        role: "status",
+                        p { class: "flex-1 text-sm text-amber-800",
+                            "目录已断开（可移动磁盘被拔出或网络共享不可用），列表中的信息可能已过期，修改文件的操作已停用。"
+                        }
+                        Button {
+                            class: "px-3 py-1 text-sm rounded-lg bg-amber-500 text-white hover:bg-amber-600 disabled:opacity-50",
+                            disabled: is_loading(),
+                            onclick: reconnect,
+                            "重新连接并刷新"
+                        }
+                    }
+                }
                 // 输出目录选择
-                div { class: "flex sm:flex-row gap-3",
-                    div { class: "flex-1 flex items-center gap-3 p-2 border border-black-300 rounded-xl ",
+                div { class: "flex flex-wrap gap-3",
+                    div { class: "flex-1 min-w-60 flex items-center gap-3 p-2 border border-black-300 rounded-xl ",
                         span { class: "text-gray-400 text-lg", "📂" }
                         div { class: "flex-1 min-w-0",
                             p { class: "text-sm sm:text-base text-gray-800 truncate",
@@ -232,6 +322,7 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
                     Button {
                         class: "bg-gradient-to-r from-green-600 px-2 to-emerald-600 hover:from-green-700 hover:to-emerald-700 text-white font-medium rounded-xl shadow-md hover:shadow-lg transition-all duration-300 transform hover:-translate-y-0.5 disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:transform-none flex items-center gap-2",
                         disabled: selected_directory.read().is_none() || is_loading(),
+                        title: shortcut_hint(&config.read(), ShortcutAction::Rescan),
                         onclick: on_scan_click,
 
                         if is_loading() {
@@ -240,50 +331,114 @@ pub fn Mp4Info(mut config: Signal<AppConfig>) -> Element {
                             "扫描目录"
                         }
                     }
+                    // 自动分组按钮
+                    Button {
+                        class: "bg-gradient-to-r from-purple-600 px-2 to-purple-700 hover:from-purple-700 hover:to-purple-800 text-white font-medium rounded-xl shadow-md hover:shadow-lg transition-all duration-300 transform hover:-translate-y-0.5 disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:transform-none flex items-center gap-2",
+                        disabled: files.read().is_empty() || is_loading(),
+                        onclick: move |_| {
+                            let groups = detect_sessions(&files.read());
+                            if groups.is_empty() {
+                                error_message.set(Some("未检测到可合并的录制会话".to_string()));
+                            }
+                            session_groups.set(groups);
+                        },
+                        "自动分组"
+                    }
+                    // 清理建议按钮
+                    Button {
+                        class: "bg-gradient-to-r from-orange-500 px-2 to-orange-600 hover:from-orange-600 hover:to-orange-700 text-white font-medium rounded-xl shadow-md hover:shadow-lg transition-all duration-300 transform hover:-translate-y-0.5 disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:transform-none flex items-center gap-2",
+                        disabled: (files.read().is_empty() && unreadable_files.read().is_empty()) || is_loading(),
+                        onclick: move |_| cleanup_open.set(true),
+                        "清理建议"
+                    }
+                    button {
+                        class: "px-2 text-sm text-gray-500 hover:text-gray-800",
+                        title: if toolbar_collapsed() { "展开常用目录" } else { "收起常用目录" },
+                        aria_expanded: !toolbar_collapsed(),
+                        onclick: move |_| toolbar_collapsed.toggle(),
+                        if toolbar_collapsed() {
+                            "▾"
+                        } else {
+                            "▴"
+                        }
+                    }
 
                 }
+                // 固定的常用目录
+                div {
+                    class: "flex flex-wrap items-center gap-2 mt-2 text-sm",
+                    hidden: toolbar_collapsed(),
+                    span { class: "text-gray-500", "常用目录" }
+                    for dir in config.read().pinned_directories.iter().cloned() {
+                        button {
+                            class: if selected_directory.read().as_ref() == Some(&dir) { "px-2 py-1 rounded-lg border border-blue-500 bg-blue-50 text-blue-700" } else { "px-2 py-1 rounded-lg border hover:bg-gray-100 disabled:opacity-50" },
+                            title: "{dir.display()}",
+                            disabled: is_loading(),
+                            onclick: {
+                                let dir = dir.clone();
+                                move |_| switch_directory(dir.clone())
+                            },
+                            "📁 {display_name(&dir)}"
+                        }
+                    }
+                    if config.read().pinned_directories.is_empty() {
+                        span { class: "text-gray-400", "固定后可一键切换" }
+                    }
+                    button {
+                        class: "px-2 py-1 rounded-lg border hover:bg-gray-100 disabled:opacity-50",
+                        disabled: selected_directory.read().is_none(),
+                        onclick: toggle_pinned,
+                        if current_pinned {
+                            "取消固定当前目录"
+                        } else {
+                            "📌 固定当前目录"
+                        }
+                    }
+                    label { class: "flex items-center gap-1 text-gray-600",
+                        input {
+                            r#type: "checkbox",
+                            checked: config.read().scan_on_switch,
+                            onchange: move |e| {
+                                config.write().scan_on_switch = e.checked();
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            },
+                        }
+                        "切换后自动扫描"
+                    }
+                }
 
             }
 
+            if !session_groups.read().is_empty() {
+                SessionGroups { groups: session_groups, config }
+            }
+
             // 文件列表
-            div { class: "mt-4 h-[calc(100%-60px)]",
+            div { class: "mt-4 flex-1 min-h-0",
                 if is_loading() {
                     Mp4InfoLoading { progress, cancel_scan }
                 } else if !files.read().is_empty() {
                     Mp4InfoTable {
                         files,
+                        directory: selected_directory,
+                        config,
                         error_message,
-                        open,
-                        file_name,
-                        confirmed,
+                        offline: directory_offline,
                     }
+                } else if needs_scan() {
+                    div { class: "text-center p-8 text-gray-500", "点击“扫描目录”查看该目录下的文件" }
                 } else if selected_directory.read().is_some() && !is_loading() {
                     div { class: "text-center p-8 text-gray-500", "该目录下没有找到MP4文件" }
                 }
             }
         }
-        AlertDialogRoot { open: open(), on_open_change: move |v| open.set(v),
-            AlertDialogContent {
-                AlertDialogTitle { "确定删除" }
-                AlertDialogDescription {
-                    {
-                        format!(
-                            "确定要永久删除文件 \"{}\" 吗？\n此操作不可撤销。",
-                            file_name,
-                        )
-                    }
-                }
-                AlertDialogActions {
-                    AlertDialogCancel { "取消" }
-                    AlertDialogAction { on_click: move |_| confirmed.set(true), "确定" }
-                }
-            }
+        CleanupDialog {
+            open: cleanup_open,
+            files,
+            unreadable: unreadable_files,
+            config,
         }
-        if confirmed() {
-            p { style: "color: var(--contrast-error-color); margin-top: 16px; font-weight: 600;",
-                "Item deleted!"
-            }
-        }
-
     }
 }
@@ -28,7 +28,7 @@ pub fn Mp4InfoLoading(progress: Signal<ScanProgress>, cancel_scan: Callback) ->
                                 "正在扫描文件..."
                             }
                             p {
-                                class: "text-sm text-gray-600 truncate w-[300px]",
+                                class: "text-sm text-gray-600 truncate w-full",
                                 title: "正在扫描: {progress.read().current_file}",
                                 "正在扫描: {progress.read().current_file}"
                             }
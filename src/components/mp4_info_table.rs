@@ -1,54 +1,128 @@
-use crate::utils::{format_date, format_size};
+use crate::utils::{
+    LockedChoice, PermissionChoice, delete_files_elevated, find_locked_files, format_bytes,
+    format_date, format_duration, format_relative_date, format_size, prompt_locked_files,
+    prompt_permission_denied,
+};
 use dioxus::prelude::*;
 use std::collections::HashSet;
 use std::ops::{AddAssign, SubAssign};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
+use crate::clipboard::copy_text;
 use crate::components::button::Button;
+use crate::components::clip_export_dialog::ClipExportDialog;
+use crate::components::compress_dialog::CompressDialog;
+use crate::components::confirm_dialog::{Confirmation, confirm, use_confirm};
+use crate::components::frame_extract_dialog::FrameExtractDialog;
+use crate::components::integrity_dialog::{IntegrityDialog, IntegrityState};
+use crate::components::metadata_dialog::MetadataDialog;
+use crate::components::mp4_card::Mp4Card;
 use crate::components::mp4_info::Mp4FileInfo;
-use crate::utils::parse_duration_to_seconds;
-
-#[derive(Clone, Copy, PartialEq)]
-enum SortBy {
-    Duration,
-}
+use crate::components::rotate_dialog::RotateDialog;
+use crate::components::transcode_dialog::TranscodeDialog;
+use crate::config::{AppConfig, ScannerColumn, ScannerLayout, ScannerSort, ScannerView};
+use crate::drag::use_drag_payload;
+use crate::ffmpeg::integrity::check_integrity;
+use crate::rename::notify_renamed;
+use crate::utils::{natural_cmp_path, open_path, rename_in_place, reveal_path};
 
 #[component]
 pub fn Mp4InfoTable(
     files: Signal<Vec<Mp4FileInfo>>,
+    /// 扫描的目录，视图状态按目录保存
+    directory: ReadSignal<Option<PathBuf>>,
+    config: Signal<AppConfig>,
     error_message: Signal<Option<String>>,
-    open: Signal<bool>,
-    file_name: Signal<String>,
-    confirmed: Signal<bool>,
+    /// 扫描的目录已断开（可移动磁盘被拔出、网络共享不可用），列表只读
+    offline: ReadSignal<bool>,
 ) -> Element {
     // 分页状态
     let mut current_page: Signal<usize> = use_signal(|| 1); // 从1开始
-    let mut page_size: Signal<usize> = use_signal(|| 20); // 默认每页20条
+    // 排序、每页数量、筛选与列设置
+    let mut view: Signal<ScannerView> = use_signal(|| {
+        directory
+            .peek()
+            .as_ref()
+            .map(|dir| config.peek().scanner_view(dir))
+            .unwrap_or_default()
+    });
     let mut select_all_page: Signal<bool> = use_signal(|| false);
-    let mut paginated_files: Signal<Vec<Mp4FileInfo>> = use_signal(Vec::new);
+    let confirm_requests = use_confirm();
     let mut deleting_files: Signal<HashSet<PathBuf>> = use_signal(Default::default); // 新增：跟踪正在删除的文件
-    let sort_by: Signal<SortBy> = use_signal(|| SortBy::Duration);
-    let sort_desc: Signal<bool> = use_signal(|| true); // 默认降序（新的在前）
     let mut selected_files: Signal<HashSet<PathBuf>> = use_signal(Default::default);
+    // 正在导出GIF/片段的文件
+    let mut clip_target: Signal<Option<Mp4FileInfo>> = use_signal(|| None);
+    // 正在旋转/矫正的文件
+    let mut rotate_target: Signal<Option<Mp4FileInfo>> = use_signal(|| None);
+    // 正在压缩的文件
+    let mut compress_target: Signal<Option<Mp4FileInfo>> = use_signal(|| None);
+    // 正在截图的文件
+    let mut frames_target: Signal<Option<Mp4FileInfo>> = use_signal(|| None);
+    // 正在编辑元数据的文件
+    let mut metadata_target: Signal<Option<Mp4FileInfo>> = use_signal(|| None);
+    // 正在批量转码的文件
+    let mut transcode_targets: Signal<Vec<Mp4FileInfo>> = use_signal(Vec::new);
+    // 完整性检查结果，按开始检查时的顺序排列
+    let mut integrity_results: Signal<Vec<(PathBuf, IntegrityState)>> = use_signal(Vec::new);
+    let mut integrity_open: Signal<bool> = use_signal(|| false);
+    // 正在原地重命名的文件
+    let mut renaming: Signal<Option<PathBuf>> = use_signal(|| None);
+    let mut drag_payload = use_drag_payload();
+
+    // 切换目录时恢复该目录上次的视图
+    use_effect(move || {
+        let restored = directory()
+            .map(|dir| config.peek().scanner_view(&dir))
+            .unwrap_or_default();
+        view.set(restored);
+        current_page.set(1);
+    });
 
-    let total_pages = {
-        let files_len = files.read().len();
-        let size = *page_size.read();
-        files_len.div_ceil(size)
+    let mut update_view = move |change: &dyn Fn(&mut ScannerView)| {
+        let mut next = view.peek().clone();
+        change(&mut next);
+        view.set(next.clone());
+        current_page.set(1);
+        // 视图变化后当前页的选择不再对应
+        selected_files.write().clear();
+        select_all_page.set(false);
+        if let Some(dir) = directory.peek().clone()
+            && let Err(e) = config.write().set_scanner_view(dir, next)
+        {
+            error_message.set(Some(format!("无法保存列表视图: {}", e)));
+        }
     };
+
+    // 筛选并排序后的文件
+    let visible_files = use_memo(move || {
+        let view = view.read();
+        let mut visible: Vec<Mp4FileInfo> = files
+            .read()
+            .iter()
+            .filter(|info| matches_filters(&view, info))
+            .cloned()
+            .collect();
+        sort_mp4_files(&mut visible, view.sort, view.sort_desc);
+        visible
+    });
+    // 扫描结果中出现过的编码格式，供筛选使用
+    let codecs = use_memo(move || {
+        let mut codecs: Vec<String> = files.read().iter().map(|f| f.codec.clone()).collect();
+        codecs.sort();
+        codecs.dedup();
+        codecs
+    });
+
+    let page_size = view.read().page_size.max(1);
+    let total_pages = visible_files.read().len().div_ceil(page_size);
     // 计算当前页的文件切片
-    let mut update_paginated_files = move || {
-        let all_files = files.read();
-        let page = *current_page.read();
-        let size = *page_size.read();
-        let start = (page - 1) * size;
+    let paginated_files = use_memo(move || {
+        let all_files = visible_files.read();
+        let size = view.read().page_size.max(1);
+        let start = ((current_page() - 1) * size).min(all_files.len());
         let end = (start + size).min(all_files.len());
-        paginated_files.set(all_files[start..end].to_vec());
-    };
-    // 使用use_effect在相关状态变化时更新
-    use_effect(move || {
-        update_paginated_files();
+        all_files[start..end].to_vec()
     });
     let mut go_next = {
         move || {
@@ -61,13 +135,6 @@ pub fn Mp4InfoTable(
         }
     };
 
-    let mut set_page_size = {
-        let mut current_page = current_page;
-        move |new_size: usize| {
-            page_size.set(new_size);
-            current_page.set(1); // 切换每页数量时回到第一页
-        }
-    };
     // 分页控制函数
     let mut go_to_page = {
         move |page: usize| {
@@ -89,166 +156,153 @@ pub fn Mp4InfoTable(
             }
         }
     };
-    // 2. 在组件中使用排序函数
-    let handle_sort = {
-        // 开始时间
-        let start = Instant::now();
-        let mut sort_by_clone = sort_by;
-        let mut sort_desc_clone = sort_desc;
-        let mut files_clone = files;
-        let mut current_page_clone = current_page; // 添加
-        move |field: SortBy| {
-            let current_field = *sort_by.read();
-            let current_desc = *sort_desc_clone.read();
-
-            if current_field == field {
-                sort_desc_clone.set(!current_desc);
-            } else {
-                sort_by_clone.set(field);
-                // 根据字段设置默认排序方向
-                match field {
-                    SortBy::Duration => sort_desc_clone.set(true), // 时长默认降序
-                }
-            }
+    let mut sort_by = move |sort: ScannerSort| update_view(&|v| v.toggle_sort(sort));
 
-            // 获取新的排序参数
-            let new_field = *sort_by_clone.read();
-            let new_desc = *sort_desc_clone.read();
-            current_page_clone.set(1);
-            // 对文件进行排序
-            let mut sorted_files = files_clone.read().clone();
-            sort_mp4_files(&mut sorted_files, new_field, new_desc);
-            println!("排序耗时: {:.2} 毫秒", start.elapsed().as_millis());
-            files_clone.set(sorted_files);
+    // 按列表中的顺序取出选中的文件，拖动未选中的行时只拖这一行
+    let drag_paths = move |path: PathBuf| -> Vec<PathBuf> {
+        let selected = selected_files.read();
+        if !selected.contains(&path) {
+            return vec![path];
         }
+        visible_files
+            .read()
+            .iter()
+            .filter(|f| selected.contains(&f.file_path))
+            .map(|f| f.file_path.clone())
+            .collect()
     };
-    let mut sort_by_duration = {
-        let mut handle_sort_clone = handle_sort;
-        move || handle_sort_clone(SortBy::Duration)
-    };
-
-    let open_file = {
-        // let error_message = error_message.clone();
-        move |path: PathBuf| {
-            // let mut error_message = error_message.clone();
-            spawn(async move {
-                // /select 参数：打开资源管理器并选中指定文件
-                let result = std::process::Command::new("explorer")
-                    .args(["/select,", &path.to_string_lossy()])
-                    .spawn();
 
-                if let Err(e) = result {
-                    error_message.set(Some(format!("无法打开资源管理器: {}", e)));
-                }
-            });
+    let mut commit_rename = move |path: PathBuf, new_name: String| {
+        renaming.set(None);
+        if offline() {
+            return;
         }
+        let target = match rename_in_place(&path, &new_name) {
+            Ok(target) if target == path => return,
+            Ok(target) => target,
+            Err(e) => {
+                error_message.set(Some(e.to_string()));
+                return;
+            }
+        };
+        if let Some(info) = files.write().iter_mut().find(|f| f.file_path == path) {
+            info.file_name = target
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            info.file_path = target.clone();
+        }
+        if selected_files.write().remove(&path) {
+            selected_files.write().insert(target.clone());
+        }
+        notify_renamed(path, target);
     };
 
-    // 删除文件（带确认对话框）
-    let delete_file = {
-        move |path: PathBuf| {
-            // let path_for_operations = path.clone();
-            // let mut files = files;
-            // let mut error_message = error_message;
-            // let mut deleting_files = deleting_files;
-            // let mut current_page = current_page; // 需要添加这个捕获
-            spawn(async move {
-                open.set(true);
+    // 在文件管理器中显示并选中文件
+    let open_file = move |path: PathBuf| {
+        if offline() {
+            return;
+        }
+        if let Err(e) = reveal_path(&path) {
+            error_message.set(Some(format!("无法打开文件管理器: {}", e)));
+        }
+    };
 
-                // 显示确认对话框
-                if deleting_files.read().contains(&path) {
-                    return;
-                }
-                // 添加到删除集合
-                deleting_files.write().insert(path.clone());
+    let open_folder = move |path: PathBuf| {
+        let folder = path.parent().map(Path::to_path_buf).unwrap_or(path);
+        if let Err(e) = open_path(&folder) {
+            error_message.set(Some(format!("无法打开所在文件夹: {}", e)));
+        }
+    };
 
-                // 显示确认对话框
-                let file_name_table = path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "未知文件".to_string());
-                file_name.set(file_name_table);
-                // let result = rfd::AsyncMessageDialog::new()
-                //     .set_title("确认删除")
-                //     .set_description(format!(
-                //         "确定要永久删除文件 \"{}\" 吗？\n此操作不可撤销。",
-                //         file_name
-                //     ))
-                //     .set_buttons(rfd::MessageButtons::OkCancel)
-                //     .show()
-                //     .await;
-                // println!("删除文件: {:?}, {:?}", path, confirmed);
-                // if result == rfd::MessageDialogResult::Ok {
-                //     // 开始时间
-                //     let start = Instant::now();
-                //     // 使用spawn_blocking执行文件系统操作
-                //     let delete_result =
-                //         tokio::task::spawn_blocking(move || std::fs::remove_file(&path)).await;
-
-                //     match delete_result {
-                //         Ok(Ok(_)) => {
-                //             let remaining_count = {
-                //                 let mut files_guard = files.write();
-                //                 if let Some(pos) = files_guard
-                //                     .iter()
-                //                     .position(|f| f.file_path == path_for_operations)
-                //                 {
-                //                     files_guard.remove(pos);
-                //                     println!("删除耗时: {:.2} 毫秒", start.elapsed().as_millis());
-                //                 }
-                //                 // 返回剩余数量，这样就不需要在持有锁的时候读取
-                //                 files_guard.len()
-                //             }; // 这里写锁被释放
-                //             // 现在可以安全地读取，不需要files_clone
-                //             let size = *page_size.read();
-                //             let new_total_pages = if remaining_count == 0 {
-                //                 1
-                //             } else {
-                //                 remaining_count.div_ceil(size)
-                //             };
-
-                //             let current = *current_page.read();
-                //             if current > new_total_pages {
-                //                 current_page.set(new_total_pages.max(1));
-                //             }
-                //         }
-                //         Ok(Err(e)) => {
-                //             error_message.set(Some(format!("删除失败: {}", e)));
-                //         }
-                //         Err(e) => {
-                //             error_message.set(Some(format!("任务失败: {}", e)));
-                //         }
-                //     }
-                // }
-
-                // // 无论结果如何，都从删除集合中移除
-                // deleting_files.write().remove(&path_for_operations);
-            });
+    let copy_to_clipboard = move |text: String| {
+        if let Err(e) = copy_text(&text) {
+            error_message.set(Some(e));
         }
     };
 
-    // 批量删除函数
-    let mut batch_delete = {
-        move || {
-            let selected = selected_files.read().clone();
+    // 在后台依次解码选中的文件，解码占用 CPU 较多，不并行
+    let mut check_integrity_selected = move || {
+        let selected = selected_files.read();
+        let paths: Vec<PathBuf> = visible_files
+            .read()
+            .iter()
+            .filter(|info| selected.contains(&info.file_path))
+            .map(|info| info.file_path.clone())
+            .collect();
+        integrity_results.set(
+            paths
+                .iter()
+                .map(|p| (p.clone(), IntegrityState::Pending))
+                .collect(),
+        );
+        integrity_open.set(true);
+        spawn(async move {
+            for (index, path) in paths.iter().enumerate() {
+                integrity_results.write()[index].1 = IntegrityState::Checking;
+                let state = match check_integrity(path).await {
+                    Ok(report) => IntegrityState::Done(report),
+                    Err(e) => IntegrityState::Failed(e.to_string()),
+                };
+                integrity_results.write()[index].1 = state;
+            }
+        });
+    };
+    let integrity_running = integrity_results
+        .read()
+        .iter()
+        .any(|(_, s)| matches!(s, IntegrityState::Pending | IntegrityState::Checking));
+
+    // 删除文件（带确认对话框），单个文件与批量删除共用
+    let mut delete_paths = {
+        move |selected: HashSet<PathBuf>| {
+            if offline() {
+                return;
+            }
             if selected.is_empty() {
                 error_message.set(Some("请先选择要删除的文件".to_string()));
                 return;
             }
+            if selected.iter().any(|p| deleting_files.read().contains(p)) {
+                return;
+            }
 
             spawn(async move {
                 // 显示确认对话框
-                let result = rfd::AsyncMessageDialog::new()
-                    .set_title("确认批量删除")
-                    .set_description(format!(
+                let description = match selected.iter().next() {
+                    Some(path) if selected.len() == 1 => format!(
+                        "确定要永久删除文件 \"{}\" 吗？\n此操作不可撤销。",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    _ => format!(
                         "确定要永久删除选中的 {} 个文件吗？\n此操作不可撤销。",
                         selected.len()
-                    ))
-                    .set_buttons(rfd::MessageButtons::OkCancel)
-                    .show()
-                    .await;
+                    ),
+                };
+                let confirmation = Confirmation::new("确认删除", description)
+                    .action("删除")
+                    .destructive();
+                if confirm(confirm_requests, confirmation).await {
+                    // 被播放器等程序占用的文件先提示，可重试或跳过
+                    let mut selected = selected;
+                    loop {
+                        let locked = find_locked_files(&selected);
+                        if locked.is_empty() {
+                            break;
+                        }
+                        match prompt_locked_files(confirm_requests, &locked, "删除", true).await {
+                            LockedChoice::Retry => continue,
+                            LockedChoice::Skip => {
+                                selected.retain(|p| !locked.iter().any(|l| l.path == *p));
+                                break;
+                            }
+                            LockedChoice::Cancel => return,
+                        }
+                    }
+                    if selected.is_empty() {
+                        return;
+                    }
 
-                if result == rfd::MessageDialogResult::Ok {
                     // 开始时间
                     let start = Instant::now();
 
@@ -259,6 +313,7 @@ pub fn Mp4InfoTable(
 
                     let mut success_count = 0;
                     let mut failed_files = Vec::new();
+                    let mut denied = Vec::new();
 
                     // 逐个删除文件
                     for path in &selected {
@@ -272,6 +327,9 @@ pub fn Mp4InfoTable(
                             Ok(Ok(_)) => {
                                 success_count += 1;
                             }
+                            Ok(Err(e)) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                                denied.push(path.clone());
+                            }
                             Ok(Err(e)) => {
                                 failed_files.push((path.display().to_string(), e.to_string()));
                             }
@@ -281,10 +339,31 @@ pub fn Mp4InfoTable(
                         }
                     }
 
+                    // 没有权限的文件询问是否以管理员身份重试
+                    if !denied.is_empty() {
+                        let mut reason = "没有权限，已跳过".to_string();
+                        if prompt_permission_denied(confirm_requests, &denied, "删除").await
+                            == PermissionChoice::RetryElevated
+                        {
+                            match delete_files_elevated(&denied).await {
+                                Ok(deleted) => {
+                                    success_count += deleted.len();
+                                    denied.retain(|p| !deleted.contains(p));
+                                    reason = "以管理员身份删除仍失败".to_string();
+                                }
+                                Err(e) => reason = e.to_string(),
+                            }
+                        }
+                        for path in &denied {
+                            failed_files.push((path.display().to_string(), reason.clone()));
+                        }
+                    }
+
                     // 从列表中移除已删除的文件
                     if success_count > 0 {
                         let mut files_guard = files.write();
-                        files_guard.retain(|f| !selected.contains(&f.file_path));
+                        files_guard
+                            .retain(|f| !selected.contains(&f.file_path) || f.file_path.exists());
                     }
 
                     // 显示结果
@@ -309,8 +388,8 @@ pub fn Mp4InfoTable(
                         )));
                     }
 
-                    // 清空选择
-                    selected_files.write().clear();
+                    // 已删除的文件移出选择
+                    selected_files.write().retain(|p| !selected.contains(p));
                     select_all_page.set(false);
 
                     // 从删除集合中移除
@@ -321,18 +400,21 @@ pub fn Mp4InfoTable(
             });
         }
     };
+    // 目录断开后只能查看，修改文件的操作都不可用
+    let read_only = offline();
+
     rsx! {
-        div { class: "grid grid-rows-[auto_1fr_auto] gap-2  overflow-hidden",
-            // 顶部统计和分页控制
-            // 顶部统计、批量操作和分页控制
-            div { class: "flex justify-between items-center",
+        div { class: "flex flex-col h-full gap-2 overflow-hidden",
+            // 顶部统计、批量操作和分页控制，窗口较窄时换行
+            div { class: "flex flex-wrap justify-between items-center gap-2",
                 // 左侧：批量操作按钮
-                div { class: "flex items-center gap-4",
+                div { class: "flex flex-wrap items-center gap-2",
                     // 批量删除按钮（当有选中文件时显示）
                     if !selected_files.read().is_empty() {
                         Button {
-                            class: "px-4 py-2 bg-red-500 text-white rounded-md hover:bg-red-600 transition-colors flex items-center gap-2",
-                            onclick: move |_| batch_delete(),
+                            class: "px-4 py-2 bg-red-500 text-white rounded-md hover:bg-red-600 transition-colors flex items-center gap-2 disabled:opacity-50",
+                            disabled: read_only,
+                            onclick: move |_| delete_paths(selected_files.read().clone()),
                             svg {
                                 class: "w-4 h-4",
                                 fill: "currentColor",
@@ -345,6 +427,32 @@ pub fn Mp4InfoTable(
                             }
                             "批量删除 ({selected_files.read().len()})"
                         }
+                        Button {
+                            class: "px-4 py-2 bg-violet-500 text-white rounded-md hover:bg-violet-600 transition-colors disabled:opacity-50",
+                            disabled: read_only,
+                            onclick: move |_| {
+                                // 按当前排序排列，处理顺序与列表一致
+                                let selected = selected_files.read();
+                                let targets = visible_files
+                                    .read()
+                                    .iter()
+                                    .filter(|info| selected.contains(&info.file_path))
+                                    .cloned()
+                                    .collect();
+                                transcode_targets.set(targets);
+                            },
+                            "批量转码 ({selected_files.read().len()})"
+                        }
+                        Button {
+                            class: "px-4 py-2 bg-emerald-500 text-white rounded-md hover:bg-emerald-600 transition-colors disabled:opacity-50",
+                            disabled: integrity_running || read_only,
+                            onclick: move |_| check_integrity_selected(),
+                            if integrity_running {
+                                "检查中..."
+                            } else {
+                                "检查完整性 ({selected_files.read().len()})"
+                            }
+                        }
                     } else {
                         div { class: "text-sm text-gray-500", "选择文件进行批量操作" }
                     }
@@ -353,6 +461,9 @@ pub fn Mp4InfoTable(
                 // 中间：统计信息
                 div { class: "text-sm text-gray-600",
                     span { "共 {files.len()} 个文件" }
+                    if view.read().is_filtered() {
+                        span { class: "ml-2", "筛选后 {visible_files.read().len()} 个" }
+                    }
                     if !selected_files.read().is_empty() {
                         span { class: "ml-2 text-blue-600",
                             "已选择 {selected_files.read().len()} 个"
@@ -360,195 +471,463 @@ pub fn Mp4InfoTable(
                     }
                 }
 
-                // 右侧：每页数量选择
+                // 右侧：显示方式与每页数量选择
                 div { class: "flex items-center gap-2",
+                    div { class: "flex rounded border overflow-hidden text-sm",
+                        for (layout , label) in [(ScannerLayout::Table, "列表"), (ScannerLayout::Grid, "网格")] {
+                            button {
+                                class: if view.read().layout == layout { "px-2 py-1 bg-blue-500 text-white" } else { "px-2 py-1 hover:bg-gray-100" },
+                                onclick: move |_| update_view(&|v| v.layout = layout),
+                                "{label}"
+                            }
+                        }
+                    }
                     span { class: "text-sm text-gray-600", "每页" }
                     select {
                         class: "border rounded px-2 py-1 text-sm bg-white",
+                        aria_label: "每页条数",
                         onchange: move |evt| {
                             if let Ok(size) = evt.value().parse::<usize>() {
-                                set_page_size(size);
-                                // 重置选择状态
-                                selected_files.write().clear();
-                                select_all_page.set(false);
+                                update_view(&|v| v.page_size = size);
                             }
                         },
-                        option { value: "10", selected: *page_size.read() == 10, "10" }
-                        option { value: "20", selected: *page_size.read() == 20, "20" }
-                        option { value: "50", selected: *page_size.read() == 50, "50" }
-                        option { value: "100", selected: *page_size.read() == 100, "100" }
+                        for size in PAGE_SIZES {
+                            option { value: "{size}", selected: page_size == size, "{size}" }
+                        }
                     }
                     span { class: "text-sm text-gray-600", "条" }
                 }
             }
 
-            div { class: "border border-gray-200 rounded-md overflow-auto h-[380]",
-                table { class: "w-full table-auto divide-y divide-gray-200 min-w-max",
-                    thead { class: "bg-gray-50 sticky top-0 z-10",
-                        tr {
-                            // 全选复选框
-                            th { class: "px-2 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider w-10",
+            // 筛选与列设置
+            div { class: "flex items-center gap-2 text-sm",
+                input {
+                    class: "border rounded px-2 py-1 text-sm",
+                    placeholder: "按文件名筛选",
+                    aria_label: "按文件名筛选",
+                    value: "{view.read().name_filter}",
+                    onchange: move |evt| {
+                        let filter = evt.value();
+                        update_view(&|v| v.name_filter = filter.clone());
+                    },
+                }
+                select {
+                    class: "border rounded px-2 py-1 text-sm bg-white",
+                    aria_label: "按编码筛选",
+                    onchange: move |evt| {
+                        let codec = Some(evt.value()).filter(|c| !c.is_empty());
+                        update_view(&|v| v.codec_filter = codec.clone());
+                    },
+                    option { value: "", selected: view.read().codec_filter.is_none(), "全部编码" }
+                    for codec in codecs.read().iter().cloned() {
+                        option {
+                            value: "{codec}",
+                            selected: view.read().codec_filter.as_ref() == Some(&codec),
+                            "{codec}"
+                        }
+                    }
+                }
+                if view.read().is_filtered() {
+                    Button {
+                        class: "px-2 py-1 text-xs border rounded hover:bg-gray-100",
+                        onclick: move |_| {
+                            update_view(&|v| {
+                                v.name_filter.clear();
+                                v.codec_filter = None;
+                            })
+                        },
+                        "清除筛选"
+                    }
+                }
+                details { class: "relative ml-auto",
+                    summary { class: "cursor-pointer text-gray-600", "显示列" }
+                    div { class: "absolute right-0 z-20 mt-1 p-2 space-y-1 bg-white border rounded shadow",
+                        for column in ScannerColumn::ALL {
+                            label { class: "flex items-center gap-2 whitespace-nowrap",
                                 input {
                                     r#type: "checkbox",
-                                    class: "rounded border-gray-300 text-blue-600 focus:ring-blue-500",
-                                    checked: select_all_page(),
+                                    checked: view.read().shows(column),
                                     onchange: move |evt| {
-                                        let is_checked = evt.value().parse::<bool>().unwrap_or(false);
-                                        select_all_page.set(is_checked);
-
-                                        let current_files: Vec<PathBuf> = paginated_files
-                                            .iter()
-                                            .map(|f| f.file_path.clone())
-                                            .collect();
-                                        let mut selected = selected_files.write();
-                                        if is_checked {
-                                            for path in current_files {
-                                                selected.insert(path);
-                                            }
-                                        } else {
-                                            for path in current_files {
-                                                selected.remove(&path);
+                                        let shown = evt.checked();
+                                        update_view(&|v| {
+                                            v.hidden_columns.retain(|c| *c != column);
+                                            if !shown {
+                                                v.hidden_columns.push(column);
                                             }
-                                        }
+                                        });
                                     },
                                 }
-                            }
-                            // 序号列
-                            th { class: "px-2 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap w-12",
-                                "序号"
-                            }
-                            th { class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap w-32",
-                                "文件名"
-                            }
-                            th { class: "px-4 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
-                                "分辨率"
-                            }
-                            th { class: "px-4 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
-                                "编码格式"
-                            }
-                            th {
-                                class: "px-4 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap flex",
-                                onclick: move |_| sort_by_duration(),
-                                span { "时长" }
-                                div { class: "ml-1 w-3 h-3",
-                                    if *sort_by.read() == SortBy::Duration {
-                                        if *sort_desc.read() {
-                                            span { "↓" }
-                                        } else {
-                                            span { "↑" }
-                                        }
-                                    } else {
-                                        span { class: "text-gray-300", "↕" }
-                                    }
-                                }
-                            }
-                            th { class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap w-1/4",
-                                "大小"
-                            }
-                            th { class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap w-1/4",
-                                "修改日期"
-                            }
-                            th { class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap w-64",
-                                "操作"
+                                {column.label()}
                             }
                         }
                     }
-                    tbody { class: "bg-white divide-y divide-gray-200",
-                        for (index , info) in paginated_files.iter().enumerate() {
-                            {
-                                let info_clone = info.clone();
-                                let file_path = info.file_path.clone();
-                                let is_selected = selected_files.read().contains(&file_path);
-                                rsx! {
-                                    tr { class: if selected_files.read().contains(&info_clone.file_path) { "bg-blue-50" } else { "" },
-                                        // 单行复选框
-                                        td { class: "px-2 py-4",
-                                            input {
-                                                r#type: "checkbox",
-                                                class: "rounded border-gray-300 text-blue-600 focus:ring-blue-500",
-                                                checked: is_selected,
-                                                onclick: {
-                                                    let path = file_path.clone();
-                                                    let mut selected = selected_files;
-                                                    let mut select_all_page = select_all_page;
+                }
+            }
 
-                                                    move |_| {
-                                                        let mut selected_guard = selected.write();
-                                                        if selected_guard.contains(&path) {
-                                                            selected_guard.remove(&path);
-                                                            select_all_page.set(false);
-                                                        } else {
-                                                            selected_guard.insert(path.clone());
-                                                        }
-                                                    }
-                                                },
+            if view.read().layout == ScannerLayout::Table {
+                div { class: "border border-gray-200 rounded-md overflow-auto flex-1 min-h-40",
+                    table { class: "w-full table-auto divide-y divide-gray-200 min-w-max",
+                        thead { class: "bg-gray-50 sticky top-0 z-10",
+                            tr {
+                                // 全选复选框
+                                th { class: "px-2 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider w-10",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "rounded border-gray-300 text-blue-600 focus:ring-blue-500",
+                                        aria_label: "选择本页全部文件",
+                                        checked: select_all_page(),
+                                        onchange: move |evt| {
+                                            let is_checked = evt.value().parse::<bool>().unwrap_or(false);
+                                            select_all_page.set(is_checked);
 
+                                            let current_files: Vec<PathBuf> = paginated_files
+                                                .iter()
+                                                .map(|f| f.file_path.clone())
+                                                .collect();
+                                            let mut selected = selected_files.write();
+                                            if is_checked {
+                                                for path in current_files {
+                                                    selected.insert(path);
+                                                }
+                                            } else {
+                                                for path in current_files {
+                                                    selected.remove(&path);
+                                                }
                                             }
+                                        },
+                                    }
+                                }
+                                // 序号列
+                                th { class: "px-2 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap w-12",
+                                    "序号"
+                                }
+                                th {
+                                    class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap min-w-40",
+                                    aria_sort: aria_sort(&view.read(), ScannerSort::Name),
+                                    button {
+                                        class: "cursor-pointer",
+                                        onclick: move |_| sort_by(ScannerSort::Name),
+                                        "文件名 {sort_indicator(&view.read(), ScannerSort::Name)}"
+                                    }
+                                }
+                                if view.read().shows(ScannerColumn::Resolution) {
+                                    th { class: "px-4 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
+                                        "分辨率"
+                                    }
+                                }
+                                if view.read().shows(ScannerColumn::Codec) {
+                                    th { class: "px-4 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
+                                        "编码格式"
+                                    }
+                                }
+                                if view.read().shows(ScannerColumn::Duration) {
+                                    th {
+                                        class: "px-4 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
+                                        aria_sort: aria_sort(&view.read(), ScannerSort::Duration),
+                                        button {
+                                            class: "cursor-pointer",
+                                            onclick: move |_| sort_by(ScannerSort::Duration),
+                                            "时长 {sort_indicator(&view.read(), ScannerSort::Duration)}"
                                         }
-                                        // 序号（计算当前页的序号）
-                                        td { class: "px-2 py-4 text-sm text-gray-500 text-center",
-                                            {format!("{}", (current_page() - 1) * page_size() + index + 1)}
+                                    }
+                                }
+                                if view.read().shows(ScannerColumn::Size) {
+                                    th {
+                                        class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
+                                        aria_sort: aria_sort(&view.read(), ScannerSort::Size),
+                                        button {
+                                            class: "cursor-pointer",
+                                            onclick: move |_| sort_by(ScannerSort::Size),
+                                            "大小 {sort_indicator(&view.read(), ScannerSort::Size)}"
                                         }
-                                        td {
-                                            class: "px-2 py-4 text-sm text-gray-900 truncate",
-                                            title: "{info.file_name}",
-                                            {info.file_name.clone()}
+                                    }
+                                }
+                                if view.read().shows(ScannerColumn::Modified) {
+                                    th {
+                                        class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
+                                        aria_sort: aria_sort(&view.read(), ScannerSort::Modified),
+                                        button {
+                                            class: "cursor-pointer",
+                                            onclick: move |_| sort_by(ScannerSort::Modified),
+                                            "修改日期 {sort_indicator(&view.read(), ScannerSort::Modified)}"
                                         }
-                                        td { class: "px-4 py-4 text-sm text-gray-500 whitespace-nowrap",
-                                            {
-                                                if info.width > 0 && info.height > 0 {
-                                                    format!("{}x{}", info.width, info.height)
-                                                } else {
-                                                    "未知".to_string()
+                                    }
+                                }
+                                th { class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider whitespace-nowrap",
+                                    "操作"
+                                }
+                            }
+                        }
+                        tbody { class: "bg-white divide-y divide-gray-200",
+                            for (index , info) in paginated_files.iter().enumerate() {
+                                {
+                                    let info_clone = info.clone();
+                                    let file_path = info.file_path.clone();
+                                    let is_selected = selected_files.read().contains(&file_path);
+                                    rsx! {
+                                        tr {
+                                            class: if read_only { "opacity-50" } else if selected_files.read().contains(&info_clone.file_path) { "bg-blue-50" } else { "" },
+                                            title: if read_only { "目录已断开，信息可能已过期" },
+                                            // 拖到合并列表：拖动已选中的行时带上全部选中文件
+                                            draggable: !read_only,
+                                            ondragstart: {
+                                                let path = file_path.clone();
+                                                move |_| drag_payload.set(Some(drag_paths(path.clone())))
+                                            },
+                                            ondragend: move |_| drag_payload.set(None),
+                                            // 单行复选框
+                                            td { class: "px-2 py-4",
+                                                input {
+                                                    r#type: "checkbox",
+                                                    class: "rounded border-gray-300 text-blue-600 focus:ring-blue-500",
+                                                    aria_label: "选择 {info.file_name}",
+                                                    checked: is_selected,
+                                                    onclick: {
+                                                        let path = file_path.clone();
+                                                        let mut selected = selected_files;
+                                                        let mut select_all_page = select_all_page;
+
+                                                        move |_| {
+                                                            let mut selected_guard = selected.write();
+                                                            if selected_guard.contains(&path) {
+                                                                selected_guard.remove(&path);
+                                                                select_all_page.set(false);
+                                                            } else {
+                                                                selected_guard.insert(path.clone());
+                                                            }
+                                                        }
+                                                    },
+
                                                 }
                                             }
-                                        }
-                                        td { class: "px-4 py-4 text-sm text-gray-500 whitespace-nowrap", {info.codec.clone()} }
-                                        td { class: "px-4 py-4 text-sm text-gray-500 whitespace-nowrap", {info.duration.clone()} }
-                                        td { class: "px-2 py-4 text-sm text-gray-500 whitespace-nowrap", {format_size(Some(info.size))} }
-                                        td {
-                                            class: "px-2 py-4 text-sm text-gray-500 truncate",
-                                            title: "{format_date(info.modified)}",
-                                            {format_date(info.modified)}
-                                        }
-                                        td { class: "flex gap-2",
-                                            Button {
-                                                class: "px-3 py-1 text-xs bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors",
-                                                onclick: {
+                                            // 序号（计算当前页的序号）
+                                            td { class: "px-2 py-4 text-sm text-gray-500 text-center",
+                                                {format!("{}", (current_page() - 1) * page_size + index + 1)}
+                                            }
+                                            td {
+                                                class: "px-2 py-4 text-sm text-gray-900 truncate max-w-xs",
+                                                title: "{info.file_name}（双击或按 F2 重命名）",
+                                                tabindex: 0,
+                                                ondoubleclick: {
                                                     let path = info.file_path.clone();
-                                                    move |_| open_file(path.clone())
+                                                    move |_| {
+                                                        if !read_only {
+                                                            renaming.set(Some(path.clone()));
+                                                        }
+                                                    }
                                                 },
-                                                "打开"
-                                            }
-
-                                            // 删除按钮
-                                            Button {
-                                                class: "px-3 py-1 text-xs bg-red-500 text-white rounded hover:bg-red-600 transition-colors",
-                                                onclick: {
+                                                onkeydown: {
                                                     let path = info.file_path.clone();
-                                                    move |_| delete_file(path.clone())
+                                                    move |e: KeyboardEvent| {
+                                                        if e.key() == Key::F2 && !read_only {
+                                                            renaming.set(Some(path.clone()));
+                                                        }
+                                                    }
                                                 },
-                                                "删除"
+                                                if renaming.read().as_ref() == Some(&info.file_path) {
+                                                    input {
+                                                        class: "w-full border rounded px-1 text-sm",
+                                                        value: "{info.file_name}",
+                                                        autofocus: true,
+                                                        onkeydown: move |e| {
+                                                            if e.key() == Key::Escape {
+                                                                renaming.set(None);
+                                                            }
+                                                        },
+                                                        // 回车或失去焦点时提交
+                                                        onchange: {
+                                                            let path = info.file_path.clone();
+                                                            move |e: FormEvent| commit_rename(path.clone(), e.value())
+                                                        },
+                                                    }
+                                                } else {
+                                                    if let Some((icon, hint)) = integrity_results
+                                                        .read()
+                                                        .iter()
+                                                        .find(|(p, _)| *p == info.file_path)
+                                                        .and_then(|(_, s)| s.icon())
+                                                    {
+                                                        button {
+                                                            class: "mr-1",
+                                                            title: hint,
+                                                            onclick: move |_| integrity_open.set(true),
+                                                            "{icon}"
+                                                        }
+                                                    }
+                                                    {info.file_name.clone()}
+                                                }
+                                            }
+                                            if view.read().shows(ScannerColumn::Resolution) {
+                                                td { class: "px-4 py-4 text-sm text-gray-500 whitespace-nowrap",
+                                                    {
+                                                        if info.width > 0 && info.height > 0 {
+                                                            format!("{}x{}", info.width, info.height)
+                                                        } else {
+                                                            "未知".to_string()
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            if view.read().shows(ScannerColumn::Codec) {
+                                                td { class: "px-4 py-4 text-sm text-gray-500 whitespace-nowrap", {info.codec.clone()} }
+                                            }
+                                            if view.read().shows(ScannerColumn::Duration) {
+                                                td { class: "px-4 py-4 text-sm text-gray-500 whitespace-nowrap", {format_duration(info.duration.as_secs_f64())} }
                                             }
+                                            if view.read().shows(ScannerColumn::Size) {
+                                                td { class: "px-2 py-4 text-sm text-gray-500 whitespace-nowrap", title: format_bytes(info.size), {format_size(Some(info.size))} }
+                                            }
+                                            if view.read().shows(ScannerColumn::Modified) {
+                                                td {
+                                                    class: "px-2 py-4 text-sm text-gray-500 truncate",
+                                                    title: "{format_date(info.modified, config.read().language)}",
+                                                    {format_relative_date(info.modified, config.read().language)}
+                                                }
+                                            }
+                                            td { class: "px-2 py-2",
+                                                div { class: "flex gap-2 whitespace-nowrap",
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let path = info.file_path.clone();
+                                                            move |_| open_file(path.clone())
+                                                        },
+                                                        "打开"
+                                                    }
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs border rounded hover:bg-gray-100 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let path = info.file_path.clone();
+                                                            move |_| open_folder(path.clone())
+                                                        },
+                                                        "打开所在文件夹"
+                                                    }
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs border rounded hover:bg-gray-100 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let path = info.file_path.display().to_string();
+                                                            move |_| copy_to_clipboard(path.clone())
+                                                        },
+                                                        "复制路径"
+                                                    }
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs border rounded hover:bg-gray-100 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let name = info.file_name.clone();
+                                                            move |_| copy_to_clipboard(name.clone())
+                                                        },
+                                                        "复制文件名"
+                                                    }
+
+                                                    // 删除按钮
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-red-500 text-white rounded hover:bg-red-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let path = info.file_path.clone();
+                                                            move |_| delete_paths(HashSet::from([path.clone()]))
+                                                        },
+                                                        "删除"
+                                                    }
 
-                                            // 转码占位（后续实现）
-                                            Button {
-                                                class: "px-3 py-1 text-xs bg-gray-300 text-gray-700 rounded cursor-not-allowed",
-                                                disabled: true,
-                                                "转码"
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-purple-500 text-white rounded hover:bg-purple-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let info = info.clone();
+                                                            move |_| clip_target.set(Some(info.clone()))
+                                                        },
+                                                        "导出GIF/片段"
+                                                    }
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-amber-500 text-white rounded hover:bg-amber-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let info = info.clone();
+                                                            move |_| rotate_target.set(Some(info.clone()))
+                                                        },
+                                                        "旋转/矫正"
+                                                    }
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-teal-500 text-white rounded hover:bg-teal-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let info = info.clone();
+                                                            move |_| compress_target.set(Some(info.clone()))
+                                                        },
+                                                        "压缩到目标大小"
+                                                    }
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-sky-500 text-white rounded hover:bg-sky-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let info = info.clone();
+                                                            move |_| frames_target.set(Some(info.clone()))
+                                                        },
+                                                        "截图"
+                                                    }
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-slate-500 text-white rounded hover:bg-slate-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let info = info.clone();
+                                                            move |_| metadata_target.set(Some(info.clone()))
+                                                        },
+                                                        "元数据"
+                                                    }
+
+                                                    Button {
+                                                        class: "px-3 py-1 text-xs bg-violet-500 text-white rounded hover:bg-violet-600 transition-colors disabled:opacity-50",
+                                                        disabled: read_only,
+                                                        onclick: {
+                                                            let info = info.clone();
+                                                            move |_| transcode_targets.set(vec![info.clone()])
+                                                        },
+                                                        "转码"
+                                                    }
+                                                }
                                             }
                                         }
                                     }
                                 }
                             }
-                        }
 
+                        }
+                    }
+                }
+            } else {
+                div { class: "grid grid-cols-[repeat(auto-fill,minmax(180px,1fr))] gap-3 overflow-auto flex-1 min-h-40 p-1",
+                    for info in paginated_files.iter().cloned() {
+                        Mp4Card {
+                            key: "{info.file_path.display()}",
+                            selected: selected_files.read().contains(&info.file_path),
+                            on_toggle: {
+                                let path = info.file_path.clone();
+                                move |_| {
+                                    let mut selected = selected_files.write();
+                                    if !selected.remove(&path) {
+                                        selected.insert(path.clone());
+                                    } else {
+                                        select_all_page.set(false);
+                                    }
+                                }
+                            },
+                            on_open: {
+                                let path = info.file_path.clone();
+                                move |_| open_file(path.clone())
+                            },
+                            info,
+                        }
                     }
                 }
             }
             // 分页控制器
             if total_pages > 1 {
-                div { class: "flex justify-center items-center gap-2 mt-2",
+                div { class: "flex flex-wrap justify-center items-center gap-2",
                     // 首页
                     Button {
                         class: "px-3 py-1 text-sm border rounded hover:bg-gray-100 disabled:opacity-50 disabled:cursor-not-allowed",
@@ -602,24 +981,58 @@ pub fn Mp4InfoTable(
             }
 
         }
+        ClipExportDialog { target: clip_target }
+        RotateDialog { target: rotate_target }
+        CompressDialog { target: compress_target }
+        FrameExtractDialog { target: frames_target }
+        MetadataDialog { target: metadata_target }
+        IntegrityDialog { open: integrity_open, results: integrity_results }
+        TranscodeDialog {
+            targets: transcode_targets,
+            on_recycled: move |recycled: Vec<PathBuf>| {
+                files.write().retain(|f| !recycled.contains(&f.file_path));
+                selected_files.write().retain(|p| !recycled.contains(p));
+            },
+        }
 
     }
 }
 
-// 排序函数
-// 1. 添加排序函数
-fn sort_mp4_files(files: &mut [Mp4FileInfo], field: SortBy, desc: bool) {
-    match field {
-        SortBy::Duration => {
-            files.sort_by(|a, b| {
-                // 需要解析时长字符串为秒数进行比较
-                let a_secs = parse_duration_to_seconds(&a.duration);
-                let b_secs = parse_duration_to_seconds(&b.duration);
-                a_secs
-                    .partial_cmp(&b_secs)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
-        }
+const PAGE_SIZES: [usize; 4] = [10, 20, 50, 100];
+
+fn matches_filters(view: &ScannerView, info: &Mp4FileInfo) -> bool {
+    let keyword = view.name_filter.trim().to_lowercase();
+    (keyword.is_empty() || info.file_name.to_lowercase().contains(&keyword))
+        && view
+            .codec_filter
+            .as_ref()
+            .is_none_or(|codec| *codec == info.codec)
+}
+
+// 列头上的排序标记
+fn sort_indicator(view: &ScannerView, sort: ScannerSort) -> &'static str {
+    match (view.sort == sort, view.sort_desc) {
+        (false, _) => "↕",
+        (true, true) => "↓",
+        (true, false) => "↑",
+    }
+}
+
+// 表头的 aria-sort，读屏软件据此读出当前排序
+fn aria_sort(view: &ScannerView, sort: ScannerSort) -> &'static str {
+    match (view.sort == sort, view.sort_desc) {
+        (false, _) => "none",
+        (true, true) => "descending",
+        (true, false) => "ascending",
+    }
+}
+
+fn sort_mp4_files(files: &mut [Mp4FileInfo], sort: ScannerSort, desc: bool) {
+    match sort {
+        ScannerSort::Name => files.sort_by(|a, b| natural_cmp_path(&a.file_path, &b.file_path)),
+        ScannerSort::Duration => files.sort_by_key(|f| f.duration),
+        ScannerSort::Size => files.sort_by_key(|f| f.size),
+        ScannerSort::Modified => files.sort_by_key(|f| f.modified),
     }
 
     if desc {
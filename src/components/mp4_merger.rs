@@ -1,50 +1,142 @@
-use super::button::Button;
-use super::file_list::FileList;
+use super::advanced_options::AdvancedOptions;
+use super::benchmark_dialog::BenchmarkDialog;
+use super::button::{Button, ButtonVariant};
+use super::confirm_dialog::{Confirmation, confirm, use_confirm};
+use super::encoding_impact::EncodingImpact;
+use super::error_dialog::{report_error, use_error_reports};
+use super::file_list::{FileList, FileListSummary};
+use super::folder_import::{FolderImport, list_mp4_in_directory};
 use super::progress::{Progress, ProgressIndicator};
+use super::upload_panel::UploadPanel;
 use dioxus::prelude::*;
 use dioxus_primitives::toast::{ToastOptions, use_toast};
 use futures_util::StreamExt;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::Duration;
 use tokio::time::sleep;
 
 use crate::MergeEvent;
+use crate::clipboard::use_paste;
+use crate::components::normalize_plan_dialog::NormalizePlanDialog;
 use crate::components::output_settings::OutputSettings;
-use crate::config::AppConfig;
-use crate::ffmpeg::merge_mp4::run_ffmpeg_merge;
+use crate::components::timeline::MergeTimeline;
+use crate::components::trim_dialog::TrimDialog;
+use crate::config::{AppConfig, DuplicatePolicy};
+use crate::error::AppError;
+use crate::ffmpeg::benchmark::is_large_job;
+use crate::ffmpeg::estimate::estimate_job;
+use crate::ffmpeg::merge_mp4::{AfterMerge, run_ffmpeg_merge};
+use crate::ffmpeg::normalize::{NormalizePlan, plan_normalization};
+use crate::ffmpeg::options::{MergeMode, MergeOptions, SourceAction};
+use crate::ffmpeg::phase::{MergePhase, MergeProgress, current_segment};
+use crate::ffmpeg::proxy::ensure_proxy;
+use crate::notifications::{Notification, NotificationAction, notify, use_notifications};
+use crate::playlist::{PLAYLIST_EXTENSIONS, import_playlist};
+use crate::project::{MergeProject, PROJECT_EXTENSION};
+use crate::queue::{MergeJob, use_job_queue};
+use crate::rename::use_renamed;
+use crate::session::use_session;
+use crate::shortcuts::{ShortcutAction, shortcut_hint, use_shortcut};
+use crate::upload::use_uploader;
+use crate::utils::{
+    LockedChoice, clear_taskbar_progress, find_locked_files, format_size, free_space,
+    natural_cmp_path, prompt_locked_files, record_log, set_taskbar_error, set_taskbar_progress,
+};
 #[component]
-pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
+pub fn Mp4Merger(
+    mut config: Signal<AppConfig>,
+    active: ReadSignal<bool>,
+    workspace_id: u32,
+) -> Element {
     let mut files: Signal<Vec<PathBuf>> = use_signal(Vec::new);
+    // 从文件夹导入时的候选文件
+    let mut folder_candidates: Signal<Vec<PathBuf>> = use_signal(Vec::new);
     let mut output_filename: Signal<String> = use_signal(String::new);
+    let mut merge_options: Signal<MergeOptions> =
+        use_signal(|| config.read().default_options.clone());
+    // 正在编辑剪切区间的片段
+    let mut trim_target: Signal<Option<PathBuf>> = use_signal(|| None);
     let mut progress: Signal<f64> = use_signal(|| 0.0);
+    // 当前阶段及阶段内进度
+    let mut phase_progress: Signal<Option<MergeProgress>> = use_signal(|| None);
+    // 各输入片段的累计边界，用于在进度条上标出片段
+    let mut segments: Signal<Vec<f64>> = use_signal(Vec::new);
     let mut is_merging: Signal<bool> = use_signal(|| false);
     let mut status_message: Signal<String> = use_signal(Default::default);
     let mut error_message: Signal<Option<String>> = use_signal(|| None);
     let mut success_message: Signal<Option<String>> = use_signal(|| None);
+    // 正在合并的输出文件，完成后可在通知中直接打开
+    let mut merge_output: Signal<Option<PathBuf>> = use_signal(|| None);
+    // 合并失败的原因，以及是否已由错误对话框显示
+    let mut merge_failure: Signal<Option<(String, bool)>> = use_signal(|| None);
+    // 完成后关机/睡眠
+    let mut normalize_plan: Signal<Option<NormalizePlan>> = use_signal(|| None);
+    // 文件列表中选中的行，可用快捷键移除
+    let mut selected_file: Signal<Option<usize>> = use_signal(|| None);
+    // 合并前检查发现已被移动或删除的文件
+    let mut missing_files: Signal<Vec<PathBuf>> = use_signal(Vec::new);
+    // 正在进行的合并任务，探测时长阶段可以取消
+    let mut merge_task: Signal<Option<Task>> = use_signal(|| None);
+    let mut benchmark_open: Signal<bool> = use_signal(|| false);
+    // 输入很大时提示先估算耗时再选择合并方式
+    let large_job = use_memo(move || is_large_job(&files.read()));
+    // 输出目录所在磁盘的可用空间，切换输出目录时刷新
+    let mut output_free_space: Signal<Option<u64>> = use_signal(|| None);
+    let output_dir = use_memo(move || config.read().output_directory_for(&files.read()));
+    use_effect(move || {
+        let dir = output_dir();
+        spawn(async move {
+            let free = tokio::task::spawn_blocking(move || free_space(&dir).ok())
+                .await
+                .ok()
+                .flatten();
+            output_free_space.set(free);
+        });
+    });
 
     let toast = use_toast();
+    let mut job_queue = use_job_queue();
+    let error_reports = use_error_reports();
+    let notifications = use_notifications();
+    let confirm_requests = use_confirm();
+    let uploader = use_uploader();
 
+    // 文件库中改名的文件在列表中同步为新路径
+    use_renamed(move |event| {
+        if !files.peek().contains(&event.from) {
+            return;
+        }
+        for file in files.write().iter_mut() {
+            if *file == event.from {
+                *file = event.to.clone();
+            }
+        }
+    });
+
+    // 能识别的常见错误用带解决建议的对话框，其他仍用提示条
     use_effect(move || {
-        if let Some(error) = error_message() {
-            toast.error(
-                "发生错误".to_string(),
-                ToastOptions::new()
-                    .description(error)
-                    .duration(Duration::from_secs(5))
-                    .permanent(false),
+        if let Some(error) = error_message()
+            && !report_error(
+                error_reports,
+                &AppError::Other(error.clone()),
+                Some(merge_options),
+            )
+        {
+            notify(
+                notifications,
+                Notification::error("发生错误", error).action(NotificationAction::ShowLog),
             );
         }
     });
 
     use_effect(move || {
         if let Some(success) = success_message() {
-            toast.success(
-                "合并成功!".to_string(),
-                ToastOptions::new()
-                    .description(success)
-                    .duration(Duration::from_secs(3))
-                    .permanent(false),
-            );
+            let mut notification = Notification::success("合并成功!", success);
+            if let Some(output) = merge_output.peek().clone() {
+                notification = notification.action(NotificationAction::OpenFile(output));
+            }
+            notify(notifications, notification);
         }
     });
 
@@ -57,19 +149,77 @@ pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
             && let Some(file_name) = first_file.file_name()
         {
             let mut name = file_name.to_string_lossy().to_string();
-            // Replace .mp4 with _merged.mp4
+            // Replace .mp4 with _merged.<ext>
             if name.ends_with(".mp4") {
                 name.truncate(name.len() - 4);
-                name.push_str("_merged.mp4");
-            } else {
-                name.push_str("_merged.mp4");
             }
+            name.push_str("_merged.");
+            name.push_str(merge_options.peek().mode.extension());
             output_filename.set(name);
         }
     });
 
-    let add_files = {
-        move |_| async move {
+    // 开启代理时在后台为新加入的高分辨率素材生成代理，已有代理的文件会直接跳过
+    use_effect(move || {
+        if !config.read().generate_proxies {
+            return;
+        }
+        let files = files();
+        spawn(async move {
+            for file in files {
+                if let Err(e) = ensure_proxy(&file).await {
+                    record_log(&format!("生成代理失败 {}: {}", file.display(), e));
+                }
+            }
+        });
+    });
+
+    // 向列表追加文件，已在列表中的文件按设置跳过或询问后重复添加
+    let append_files = move |new_files: Vec<PathBuf>| {
+        spawn(async move {
+            let mut seen: HashSet<PathBuf> = files.peek().iter().cloned().collect();
+            let unique: Vec<PathBuf> = new_files
+                .iter()
+                .filter(|f| seen.insert((*f).clone()))
+                .cloned()
+                .collect();
+            let duplicates = new_files.len() - unique.len();
+            if duplicates == 0 {
+                files.write().extend(new_files);
+                return;
+            }
+            match config.peek().duplicate_policy {
+                DuplicatePolicy::Skip => {
+                    files.write().extend(unique);
+                    toast.info(
+                        "已跳过重复文件".to_string(),
+                        ToastOptions::new()
+                            .description(format!("{} 个文件已在列表中", duplicates))
+                            .duration(Duration::from_secs(3))
+                            .permanent(false),
+                    );
+                }
+                DuplicatePolicy::Confirm => {
+                    let confirmation = Confirmation::new(
+                        "重复的文件",
+                        format!(
+                            "有 {} 个文件已在列表中，重复添加会让这些片段在结果中出现多次。\n是否仍然添加？",
+                            duplicates
+                        ),
+                    )
+                    .action("仍然添加");
+                    if confirm(confirm_requests, confirmation).await {
+                        files.write().extend(new_files);
+                    } else {
+                        files.write().extend(unique);
+                    }
+                }
+            }
+        });
+    };
+
+    let add_files = move || {
+        spawn(async move {
             let mut dialog = rfd::AsyncFileDialog::new()
                 .add_filter("MP4 Files", &["mp4"])
                 .set_title("选择MP4文件");
@@ -91,17 +241,110 @@ pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
                     }
                 }
 
-                files
-                    .write()
-                    .extend(result.into_iter().map(|f| f.path().to_path_buf()));
+                append_files(result.into_iter().map(|f| f.path().to_path_buf()).collect());
+            }
+        });
+    };
+
+    let add_folder = {
+        move |_| async move {
+            let mut dialog = rfd::AsyncFileDialog::new().set_title("选择包含MP4文件的文件夹");
+            if let Some(dir) = config().get_last_input_directory() {
+                dialog = dialog.set_directory(dir);
+            }
+
+            if let Some(result) = dialog.pick_folder().await {
+                let dir_path = result.path().to_path_buf();
+                match list_mp4_in_directory(&dir_path, &config.peek().scan_rules) {
+                    Ok(paths) if paths.is_empty() => {
+                        error_message.set(Some("该文件夹下没有找到MP4文件".to_string()));
+                    }
+                    Ok(paths) => folder_candidates.set(paths),
+                    Err(e) => {
+                        error_message.set(Some(format!("无法读取目录: {}", e)));
+                    }
+                }
+                if let Err(e) = config.write().set_last_input_directory(dir_path) {
+                    error_message.set(Some(format!("无法保存输入目录设置: {}", e)));
+                }
             }
         }
     };
 
-    let remove_file = move |index: usize| {
-        files.write().remove(index);
+    let mut remove_file = move |index: usize| {
+        let removed = files.write().remove(index);
+        missing_files.write().retain(|p| *p != removed);
+        selected_file.set(None);
+    };
+
+    // 为已丢失的文件重新选择位置，保持其在列表中的顺序
+    let relocate_file = move |index: usize| {
+        spawn(async move {
+            let Some(old) = files.peek().get(index).cloned() else {
+                return;
+            };
+            let mut dialog = rfd::AsyncFileDialog::new()
+                .add_filter("MP4 Files", &["mp4"])
+                .set_title(format!(
+                    "重新定位 {}",
+                    old.file_name().unwrap_or_default().to_string_lossy()
+                ));
+            if let Some(dir) = old.parent().filter(|dir| dir.is_dir()) {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(file) = dialog.pick_file().await
+                && files.peek().get(index) == Some(&old)
+            {
+                files.write()[index] = file.path().to_path_buf();
+                missing_files.write().retain(|p| *p != old);
+                if missing_files.peek().is_empty() {
+                    error_message.set(None);
+                }
+            }
+        });
     };
 
+    // Ctrl+V 粘贴文件路径或复制的文件，只接受存在的 MP4 文件
+    use_paste(move |paths| {
+        if !*active.peek() {
+            return;
+        }
+        let (valid, invalid): (Vec<PathBuf>, Vec<PathBuf>) = paths.into_iter().partition(|p| {
+            p.is_file()
+                && p.extension()
+                    .map(|ext| ext.eq_ignore_ascii_case("mp4"))
+                    .unwrap_or(false)
+        });
+        if !invalid.is_empty() {
+            notify(
+                notifications,
+                Notification::warning(
+                    "部分粘贴内容已忽略",
+                    format!("{} 项不是存在的 MP4 文件", invalid.len()),
+                ),
+            );
+        }
+        if !valid.is_empty() {
+            append_files(valid);
+        }
+    });
+
+    // 快捷键：添加文件 / 移除选中文件
+    // 快捷键只作用于当前显示的工作区
+    use_shortcut(ShortcutAction::AddFiles, move || {
+        if *active.peek() {
+            add_files();
+        }
+    });
+    use_shortcut(ShortcutAction::RemoveSelected, move || {
+        if *active.peek()
+            && let Some(index) = selected_file()
+            && index < files.read().len()
+        {
+            remove_file(index);
+        }
+    });
+
     let select_output_directory = {
         move |_| async move {
             if let Some(result) = rfd::AsyncFileDialog::new()
@@ -125,6 +368,140 @@ pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
         }
     };
 
+    // 从 M3U 播放列表或路径列表（文本/CSV）按顺序导入，逐行报告无法导入的条目
+    let import_list = move |_| async move {
+        let mut dialog = rfd::AsyncFileDialog::new()
+            .set_title("导入文件列表")
+            .add_filter("播放列表/路径列表", &PLAYLIST_EXTENSIONS);
+        if let Some(dir) = config().get_last_input_directory() {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(file) = dialog.pick_file().await else {
+            return;
+        };
+        let import = match import_playlist(file.path()) {
+            Ok(import) => import,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        if !import.errors.is_empty() {
+            let notice = Confirmation::new(
+                "部分条目无法导入",
+                format!(
+                    "已导入 {} 个文件，以下 {} 行被跳过：\n{}",
+                    import.files.len(),
+                    import.errors.len(),
+                    import.error_report()
+                ),
+            )
+            .action("知道了");
+            confirm(confirm_requests, notice).await;
+        }
+        if !import.files.is_empty() {
+            append_files(import.files);
+        }
+    };
+
+    // 保存/打开项目：文件顺序、合并选项与输出设置
+    let save_project = move |_| async move {
+        let mut dialog = rfd::AsyncFileDialog::new()
+            .set_title("保存项目")
+            .add_filter("合并项目", &[PROJECT_EXTENSION]);
+        let stem = output_filename
+            .peek()
+            .rsplit_once('.')
+            .map(|(stem, _)| stem.to_string())
+            .unwrap_or_else(|| output_filename.peek().clone());
+        if !stem.is_empty() {
+            dialog = dialog.set_file_name(format!("{}.{}", stem, PROJECT_EXTENSION));
+        }
+        let Some(file) = dialog.save_file().await else {
+            return;
+        };
+        let project = MergeProject::new(
+            files.peek().clone(),
+            output_filename.peek().clone(),
+            config.peek().output_directory.clone(),
+            merge_options.peek().clone(),
+        );
+        match project.save(file.path()) {
+            Ok(()) => toast.success(
+                "项目已保存".to_string(),
+                ToastOptions::new()
+                    .description(file.path().display().to_string())
+                    .duration(Duration::from_secs(3))
+                    .permanent(false),
+            ),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    // 套用项目或恢复的会话内容，不存在的文件标记出来等待重新定位
+    let apply_project = move |project: MergeProject| {
+        if let Some(dir) = project.output_directory
+            && let Err(e) = config.write().set_output_directory(dir)
+        {
+            error_message.set(Some(format!("无法保存输出目录设置: {}", e)));
+        }
+        let missing: Vec<PathBuf> = project
+            .files
+            .iter()
+            .filter(|f| !f.exists())
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            error_message.set(Some(format!(
+                "有 {} 个文件不存在，请重新定位或移除",
+                missing.len()
+            )));
+        }
+        missing_files.set(missing);
+        files.set(project.files);
+        output_filename.set(project.output_filename);
+        merge_options.set(project.options);
+        selected_file.set(None);
+    };
+
+    // 当前内容交给会话自动保存；恢复上次会话时取走属于本工作区的内容
+    let mut session = use_session();
+    use_effect(move || {
+        let draft = MergeProject::new(
+            files(),
+            output_filename(),
+            config.read().output_directory.clone(),
+            merge_options(),
+        );
+        session.drafts.write().insert(workspace_id, draft);
+    });
+    use_effect(move || {
+        if !session.restore.read().contains_key(&workspace_id) {
+            return;
+        }
+        if let Some(project) = session.restore.write().remove(&workspace_id) {
+            apply_project(project);
+        }
+    });
+    use_drop(move || {
+        session.drafts.write().remove(&workspace_id);
+    });
+
+    let open_project = move |_| async move {
+        let Some(file) = rfd::AsyncFileDialog::new()
+            .set_title("打开项目")
+            .add_filter("合并项目", &[PROJECT_EXTENSION])
+            .pick_file()
+            .await
+        else {
+            return;
+        };
+        match MergeProject::load(file.path()) {
+            Ok(project) => apply_project(project),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
     let clear_output_directory = {
         move |_| {
             config.write().output_directory = None;
@@ -143,32 +520,223 @@ pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
     };
 
     // ✅ 订阅接收端
-    use_coroutine(move |mut rx: UnboundedReceiver<MergeEvent>| async move {
+    let merge_tx = use_coroutine(move |mut rx: UnboundedReceiver<MergeEvent>| async move {
         while let Some(event) = rx.next().await {
             match event {
-                MergeEvent::Progress(p) => progress.set(p),
+                MergeEvent::Progress(p) => {
+                    progress.set(p.overall());
+                    phase_progress.set(Some(p));
+                    set_taskbar_progress(p.overall());
+                }
+                MergeEvent::Segments(boundaries) => segments.set(boundaries),
+                MergeEvent::Probing { index, total, name } => {
+                    status_message.set(format!("读取时长 {}/{}：{}", index, total, name))
+                }
                 MergeEvent::Status(s) => status_message.set(s),
                 MergeEvent::Error(e) => {
-                    error_message.set(Some(e));
+                    // 合并途中文件被移走时标记出来，可重新定位或移除后继续
+                    if let AppError::FileNotFound(path) = &e
+                        && !missing_files.peek().contains(path)
+                    {
+                        missing_files.write().push(path.clone());
+                    }
+                    let reported = report_error(error_reports, &e, Some(merge_options));
+                    merge_failure.set(Some((e.to_string(), reported)));
                     is_merging.set(false);
+                    set_taskbar_error();
                 }
 
                 MergeEvent::Success(msg) => {
                     progress.set(100.0);
+                    phase_progress.set(Some(MergeProgress::new(MergePhase::Finalizing, 100.0)));
+                    set_taskbar_progress(100.0);
                     status_message.set("合并完成!".to_string());
                     success_message.set(Some(msg));
+                    // 原文件已移走，清空列表避免再次使用旧路径
+                    if merge_options.peek().source_action != SourceAction::Keep {
+                        files.set(Vec::new());
+                    }
                     sleep(Duration::from_secs(2)).await;
                     is_merging.set(false);
+                    clear_taskbar_progress();
                 }
             }
         }
     });
 
-    let merge_files = {
-        move |_| {
+    // 合并前重新检查输入，标出已被移动或删除的文件
+    let mut check_missing = move || {
+        let missing: Vec<PathBuf> = files
+            .peek()
+            .iter()
+            .filter(|file| !file.is_file())
+            .cloned()
+            .collect();
+        let all_present = missing.is_empty();
+        if !all_present {
+            error_message.set(Some(format!(
+                "有 {} 个文件已被移动或删除，请重新定位或移除后再合并",
+                missing.len()
+            )));
+        }
+        missing_files.set(missing);
+        all_present
+    };
+
+    let mut start_merge = move || {
+        if !check_missing() {
+            return;
+        }
+        let output_path_final = config()
+            .output_directory_for(&files.peek())
+            .join(output_filename());
+        merge_output.set(Some(output_path_final.clone()));
+
+        is_merging.set(true);
+        progress.set(0.0);
+        phase_progress.set(None);
+        segments.set(Vec::new());
+        set_taskbar_progress(0.0);
+        status_message.set("正在检查FFmpeg环境...".to_string());
+        error_message.set(None);
+        let files_value = files();
+
+        let task = spawn(async move {
+            if output_path_final.is_file() {
+                let confirmation = Confirmation::new(
+                    "输出文件已存在",
+                    format!(
+                        "{}\n已存在，合并结果会覆盖它。确定要继续吗？",
+                        output_path_final.display()
+                    ),
+                )
+                .action("覆盖")
+                .destructive();
+                if !confirm(confirm_requests, confirmation).await {
+                    is_merging.set(false);
+                    status_message.set(String::new());
+                    clear_taskbar_progress();
+                    merge_task.set(None);
+                    return;
+                }
+            }
+            // 输出文件被播放器等程序占用时无法覆盖，先提示重试
+            loop {
+                let locked = find_locked_files(std::slice::from_ref(&output_path_final));
+                if locked.is_empty() {
+                    break;
+                }
+                if prompt_locked_files(confirm_requests, &locked, "覆盖", false).await
+                    != LockedChoice::Retry
+                {
+                    is_merging.set(false);
+                    status_message.set(String::new());
+                    clear_taskbar_progress();
+                    merge_task.set(None);
+                    return;
+                }
+            }
+            // 合并前重新读取可用空间，低于提醒阈值时先确认
+            let dir = config.peek().output_directory_for(&files.peek());
+            let free = tokio::task::spawn_blocking(move || free_space(&dir).ok())
+                .await
+                .ok()
+                .flatten();
+            output_free_space.set(free);
+            if let Some(free) = free
+                && config.peek().is_low_space(free)
+            {
+                let confirmation = Confirmation::new(
+                    "输出磁盘空间不足",
+                    format!(
+                        "输出目录所在磁盘仅剩 {}，低于 {} GB 的提醒阈值。\n确定要继续吗？",
+                        format_size(Some(free)),
+                        config.peek().low_space_gb
+                    ),
+                )
+                .action("继续");
+                if !confirm(confirm_requests, confirmation).await {
+                    is_merging.set(false);
+                    status_message.set(String::new());
+                    clear_taskbar_progress();
+                    merge_task.set(None);
+                    return;
+                }
+            }
+            // 预计输出很大或耗时很长时先确认，避免误启动数小时的编码
+            let guard = config.peek().job_guard;
+            if guard.enabled {
+                status_message.set("正在估算输出大小与耗时...".to_string());
+                let options = merge_options.peek().clone();
+                match estimate_job(&files_value, &options).await {
+                    Ok(estimate) => {
+                        let reasons = guard.exceeded(estimate.output_bytes, estimate.secs);
+                        if !reasons.is_empty() {
+                            let confirmation = Confirmation::new(
+                                "这是一个大任务",
+                                format!(
+                                    "{}（{}）。\n确定要开始吗？",
+                                    estimate.describe(),
+                                    reasons.join("，")
+                                ),
+                            )
+                            .action("开始");
+                            if !confirm(confirm_requests, confirmation).await {
+                                is_merging.set(false);
+                                status_message.set(String::new());
+                                clear_taskbar_progress();
+                                merge_task.set(None);
+                                return;
+                            }
+                        }
+                    }
+                    // 估算失败不影响合并，合并时会报告具体错误
+                    Err(e) => eprintln!("估算任务大小失败: {}", e),
+                }
+            }
+            let after = AfterMerge::from_config(&config.peek());
+            let merged = run_ffmpeg_merge(
+                files_value,
+                output_path_final,
+                merge_options(),
+                after,
+                merge_tx.tx(),
+            )
+            .await;
+            merge_task.set(None);
+            if let Some(path) = merged {
+                uploader.start_if_enabled(path);
+            }
+        });
+        merge_task.set(Some(task));
+    };
+
+    // 还在探测时长时直接丢弃合并任务：正在运行的探测进程随之结束，也没有写入任何文件
+    let cancel_merge = move |_| {
+        let can_cancel = phase_progress
+            .peek()
+            .is_none_or(|p| p.phase == MergePhase::Probing);
+        if !can_cancel {
+            return;
+        }
+        if let Some(task) = merge_task.take() {
+            task.cancel();
+        }
+        is_merging.set(false);
+        progress.set(0.0);
+        phase_progress.set(None);
+        segments.set(Vec::new());
+        status_message.set("已取消合并".to_string());
+        clear_taskbar_progress();
+    };
+
+    let mut merge_files = {
+        move || {
+            if is_merging() {
+                return;
+            }
             let files_value = files();
             let output_filename_value = output_filename();
-            let config_value = config();
 
             if files_value.is_empty() {
                 error_message.set(Some("请先选择要合并的MP4文件".to_string()));
@@ -180,22 +748,91 @@ pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
                 return;
             }
 
-            // Construct output path
-            let output_dir = config_value.get_output_directory();
-            let output_path_final = output_dir.join(&output_filename_value);
+            if !check_missing() {
+                return;
+            }
+
+            let options = merge_options();
+            match options.normalize {
+                // 重新编码且开启统一时，先探测输入并展示转换方案
+                Some(normalize) if options.mode == MergeMode::ReEncode => {
+                    status_message.set("正在分析输入文件...".to_string());
+                    error_message.set(None);
+                    spawn(async move {
+                        match plan_normalization(&files_value, &normalize).await {
+                            Ok(plan) => {
+                                status_message.set(String::new());
+                                normalize_plan.set(Some(plan));
+                            }
+                            Err(e) => {
+                                status_message.set(String::new());
+                                error_message.set(Some(e.to_string()));
+                            }
+                        }
+                    });
+                }
+                _ => start_merge(),
+            }
+        }
+    };
+
+    // 合并失败时发出可重试的通知；已由错误对话框显示的只记入通知中心
+    let retry_merge = use_callback(move |_| merge_files());
+    use_effect(move || {
+        if let Some((error, reported)) = merge_failure() {
+            let notification = Notification::error("合并失败", error)
+                .action(NotificationAction::Retry(retry_merge))
+                .action(NotificationAction::ShowLog);
+            notify(
+                notifications,
+                if reported {
+                    notification.silent()
+                } else {
+                    notification
+                },
+            );
+        }
+    });
 
-            is_merging.set(true);
-            progress.set(0.0);
-            status_message.set("正在检查FFmpeg环境...".to_string());
-            error_message.set(None);
-            let tx = use_coroutine_handle::<MergeEvent>();
-            let tx_for_task = tx;
+    use_shortcut(ShortcutAction::StartMerge, move || {
+        if *active.peek() {
+            merge_files();
+        }
+    });
+
+    let enqueue_files = {
+        move |_| {
             let files_value = files();
+            let output_filename_value = output_filename();
+
+            if files_value.is_empty() {
+                error_message.set(Some("请先选择要合并的MP4文件".to_string()));
+                return;
+            }
+
+            if output_filename_value.is_empty() {
+                error_message.set(Some("请输入输出文件名".to_string()));
+                return;
+            }
 
-            let output_path_final_clone = output_path_final.clone();
-            spawn(async move {
-                run_ffmpeg_merge(files_value, output_path_final_clone, tx_for_task).await;
-            });
+            let output_path = config()
+                .output_directory_for(&files_value)
+                .join(&output_filename_value);
+            job_queue.write().push(MergeJob::new(
+                output_filename_value,
+                files_value,
+                output_path,
+                merge_options(),
+            ));
+            files.write().clear();
+            output_filename.set(String::new());
+            toast.success(
+                "已加入队列".to_string(),
+                ToastOptions::new()
+                    .description("可在“队列”页开始执行")
+                    .duration(Duration::from_secs(3))
+                    .permanent(false),
+            );
         }
     };
 
@@ -208,11 +845,63 @@ pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
                         h2 { class: "text-xl font-semibold flex items-center gap-2",
                             "选择要合并的MP4文件"
                         }
-                        Button { onclick: add_files, "添加文件" }
+                        div { class: "flex flex-wrap justify-end gap-2",
+                            if files.read().len() > 1 {
+                                Button {
+                                    variant: ButtonVariant::Outline,
+                                    onclick: move |_| files.write().sort_by(|a, b| natural_cmp_path(a, b)),
+                                    "自然排序"
+                                }
+                            }
+                            Button { variant: ButtonVariant::Ghost, onclick: open_project, "打开项目" }
+                            if !files.read().is_empty() {
+                                Button { variant: ButtonVariant::Ghost, onclick: save_project, "保存项目" }
+                            }
+                            Button { variant: ButtonVariant::Ghost, onclick: import_list, "导入列表" }
+                            Button { variant: ButtonVariant::Secondary, onclick: add_folder, "从文件夹添加" }
+                            Button {
+                                title: shortcut_hint(&config.read(), ShortcutAction::AddFiles),
+                                onclick: move |_| add_files(),
+                                "添加文件"
+                            }
+                        }
+                    }
+
+                    if !folder_candidates.read().is_empty() {
+                        FolderImport {
+                            candidates: folder_candidates,
+                            on_confirm: move |chosen: Vec<PathBuf>| append_files(chosen),
+                        }
                     }
 
                     // 文件列表
-                    FileList { files, on_remove: remove_file }
+                    FileList {
+                        files,
+                        selected: selected_file,
+                        missing: missing_files,
+                        on_remove: remove_file,
+                        on_relocate: relocate_file,
+                        on_drop: move |paths: Vec<PathBuf>| append_files(paths),
+                        on_trim: move |index: usize| trim_target.set(files.peek().get(index).cloned()),
+                        merge_options,
+                    }
+                    FileListSummary { files }
+                    MergeTimeline { files, selected: selected_file, merge_options }
+                    if !missing_files.read().is_empty() && !is_merging() {
+                        div { class: "flex justify-end mt-2",
+                            Button {
+                                variant: ButtonVariant::Outline,
+                                onclick: move |_| {
+                                    let missing = missing_files.take();
+                                    files.write().retain(|f| !missing.contains(f));
+                                    selected_file.set(None);
+                                    error_message.set(None);
+                                    merge_files();
+                                },
+                                "移除缺失文件并继续合并"
+                            }
+                        }
+                    }
 
                 }
 
@@ -223,43 +912,157 @@ pub fn Mp4Merger(mut config: Signal<AppConfig>) -> Element {
                     }
                     OutputSettings {
                         output_filename,
+                        merge_options,
                         config,
+                        files,
+                        free_space: output_free_space,
                         on_select_dir: select_output_directory,
                         on_clear_dir: clear_output_directory,
                     }
+                    AdvancedOptions { merge_options, config, error_message }
 
                 }
 
                 // 合并按钮和状态区域
                 div { class: "p-6 pt-2",
-                    div { class: "flex justify-center mb-6",
-                        Button { disabled: is_merging(), onclick: merge_files,
+                    div { class: "flex justify-center gap-3 mb-6",
+                        Button {
+                            disabled: is_merging(),
+                            title: shortcut_hint(&config.read(), ShortcutAction::StartMerge),
+                            onclick: move |_| merge_files(),
                             if is_merging() {
                                 "合并中..."
                             } else {
                                 "开始合并"
                             }
                         }
+                        Button { variant: ButtonVariant::Secondary, onclick: enqueue_files, "加入队列" }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            disabled: files.read().is_empty() || is_merging(),
+                            onclick: move |_| benchmark_open.set(true),
+                            "估算耗时"
+                        }
+                    }
+                    if large_job() && !is_merging() {
+                        p { class: "text-center text-sm text-amber-500 mb-4",
+                            "文件较大，建议先“估算耗时”比较直接合并与重新编码"
+                        }
                     }
 
                     // 进度条
                     if is_merging() || progress() > 0.0 {
                         div { class: "space-y-3 w-full",
                             div { class: "flex justify-between items-center",
-                                span { class: " font-semibold", "合并进度" }
-                                span { class: "text-purple-400 font-mono", "{progress():.1}%" }
+                                span { class: " font-semibold",
+                                    "合并进度"
+                                    if let Some(p) = phase_progress() {
+                                        span { class: "ml-2 text-sm text-gray-400", "· {p.phase.label()}" }
+                                    }
+                                }
+                                div { class: "flex items-center gap-2",
+                                    if is_merging() && phase_progress().is_none_or(|p| p.phase == MergePhase::Probing) {
+                                        Button { variant: ButtonVariant::Ghost, onclick: cancel_merge, "取消" }
+                                    }
+                                    span { class: "text-purple-400 font-mono", "{progress():.1}%" }
+                                }
                             }
                             Progress {
-                                aria_label: "Progressbar Demo",
+                                aria_label: "合并进度",
                                 value: progress() as f64,
                                 ProgressIndicator {}
                             }
+                            EncodingImpact { active: is_merging, merge_options }
+                            // 探测时长阶段显示正在读取的文件，避免文件很多时看起来像卡住
+                            if is_merging() && phase_progress().is_none_or(|p| p.phase == MergePhase::Probing) {
+                                p { class: "text-xs text-gray-400 truncate", "{status_message}" }
+                            }
+                            if let Some(current) = phase_progress().filter(|_| segments.read().len() > 1) {
+                                SegmentMarkers { boundaries: segments(), progress: current }
+                            }
+                            // 各阶段进度
+                            if let Some(current) = phase_progress() {
+                                div { class: "grid grid-cols-5 gap-2",
+                                    for phase in MergePhase::ALL {
+                                        div { class: "space-y-1",
+                                            p {
+                                                class: if phase == current.phase { "text-xs text-purple-400" } else { "text-xs text-gray-500" },
+                                                "{phase.label()}"
+                                            }
+                                            Progress {
+                                                aria_label: phase.label(),
+                                                value: current.phase_percent(phase),
+                                                ProgressIndicator {}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
+                    UploadPanel {}
                 }
             }
 
         }
+        BenchmarkDialog { open: benchmark_open, files, merge_options }
+        TrimDialog { target: trim_target, merge_options }
+        NormalizePlanDialog {
+            plan: normalize_plan,
+            on_confirm: move |_| {
+                normalize_plan.set(None);
+                start_merge();
+            },
+        }
 
     }
 }
+
+// 按片段时长分段着色：已写入、正在写入、未开始
+#[component]
+fn SegmentMarkers(boundaries: Vec<f64>, progress: MergeProgress) -> Element {
+    let total = boundaries.len();
+    let current = match progress.phase {
+        MergePhase::Probing | MergePhase::PreparingList => None,
+        MergePhase::Concatenating => progress
+            .position
+            .map(|position| current_segment(&boundaries, position)),
+        MergePhase::Verifying | MergePhase::Finalizing => Some(total),
+    };
+    let writing_label = match (progress.phase, current) {
+        (MergePhase::Concatenating, Some(current)) => {
+            Some(format!("正在写入第 {}/{} 个片段", current + 1, total))
+        }
+        _ => None,
+    };
+    let mut start = 0.0;
+    let widths: Vec<f64> = boundaries
+        .iter()
+        .map(|end| {
+            let width = (end - start) * 100.0;
+            start = *end;
+            width
+        })
+        .collect();
+
+    rsx! {
+        div { class: "space-y-1",
+            div { class: "flex h-2 w-full gap-px rounded overflow-hidden",
+                for (index , width) in widths.into_iter().enumerate() {
+                    div {
+                        class: match current {
+                            Some(current) if index < current => "bg-green-500",
+                            Some(current) if index == current => "bg-purple-500 animate-pulse",
+                            _ => "bg-gray-600",
+                        },
+                        style: "width: {width}%",
+                        title: "第 {index + 1} 个片段",
+                    }
+                }
+            }
+            if let Some(label) = writing_label {
+                p { class: "text-xs text-gray-400", "{label}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogAction, AlertDialogActions, AlertDialogCancel, AlertDialogContent,
+    AlertDialogDescription, AlertDialogRoot, AlertDialogTitle,
+};
+use crate::ffmpeg::normalize::NormalizePlan;
+use dioxus::prelude::*;
+
+// 开始重新编码前展示分辨率/帧率统一方案，确认后才开始合并
+#[component]
+pub fn NormalizePlanDialog(plan: Signal<Option<NormalizePlan>>, on_confirm: Callback) -> Element {
+    use_restore_focus(move || plan.read().is_some());
+    let Some(current) = plan() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        AlertDialogRoot {
+            open: true,
+            on_open_change: move |v: bool| {
+                if !v {
+                    plan.set(None);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "统一分辨率/帧率" }
+                AlertDialogDescription {
+                    "所有片段将统一为 {current.width}x{current.height} @ {current.fps:.2} fps"
+                }
+                div { class: "max-h-60 overflow-y-auto text-sm space-y-1",
+                    for (path , info) in current.inputs.iter() {
+                        div { class: "flex justify-between gap-3",
+                            span { class: "truncate", "{path.file_name().unwrap_or_default().to_string_lossy()}" }
+                            if current.changes(info) {
+                                span { class: "whitespace-nowrap text-amber-500",
+                                    "{info.width}x{info.height} @ {info.fps:.2} → 转换"
+                                }
+                            } else {
+                                span { class: "whitespace-nowrap text-gray-500", "无需转换" }
+                            }
+                        }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "取消" }
+                    AlertDialogAction { on_click: move |_| on_confirm.call(()), "开始合并" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::components::button::{Button, ButtonVariant};
+use crate::notifications::{
+    Notification, NotificationAction, NotificationLevel, Notifications, dismiss_toast,
+    use_notifications,
+};
+use crate::utils::{open_path, record_log, write_recent_log};
+use dioxus::html::Key;
+use dioxus::prelude::*;
+
+// 执行通知上的操作，打开失败时只记日志
+fn run_action(action: &NotificationAction) {
+    let result = match action {
+        NotificationAction::OpenFile(path) => open_path(path),
+        NotificationAction::Retry(retry) => {
+            retry.call(());
+            Ok(())
+        }
+        NotificationAction::ShowLog => write_recent_log().and_then(|path| open_path(&path)),
+    };
+    if let Err(e) = result {
+        record_log(&format!("执行通知操作“{}”失败: {}", action.label(), e));
+    }
+}
+
+fn level_class(level: NotificationLevel) -> &'static str {
+    match level {
+        NotificationLevel::Success => "border-green-500/60",
+        NotificationLevel::Warning => "border-yellow-500/60",
+        NotificationLevel::Error => "border-red-500/60",
+    }
+}
+
+// 右下角带操作按钮的提示条
+#[component]
+pub fn NotificationToasts() -> Element {
+    let notifications = use_notifications();
+    let toasts: Vec<Notification> = notifications
+        .read()
+        .iter()
+        .filter(|n| n.toast)
+        .cloned()
+        .collect();
+    if toasts.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        // 读屏软件会读出新的提示
+        div {
+            class: "fixed bottom-4 right-4 z-50 w-80 space-y-2",
+            role: "status",
+            aria_live: "polite",
+            for notification in toasts {
+                NotificationCard {
+                    key: "{notification.id}",
+                    notifications,
+                    notification,
+                }
+            }
+        }
+    }
+}
+
+// 左下角的通知中心：列出最近的错误与警告，避免长任务期间错过提示
+#[component]
+pub fn NotificationCenter() -> Element {
+    let mut notifications = use_notifications();
+    let mut expanded: Signal<bool> = use_signal(|| false);
+    // 上次展开时看到的最新通知
+    let mut seen: Signal<u64> = use_signal(|| 0);
+
+    let recent: Vec<Notification> = notifications
+        .read()
+        .iter()
+        .rev()
+        .filter(|n| n.level != NotificationLevel::Success)
+        .cloned()
+        .collect();
+    let unread = recent.iter().filter(|n| n.id > seen()).count();
+    let latest = recent.first().map(|n| n.id).unwrap_or(0);
+
+    rsx! {
+        div { class: "fixed bottom-4 left-4 z-40 flex flex-col items-start gap-2",
+            if expanded() {
+                div {
+                    class: "w-96 max-h-96 overflow-y-auto p-3 space-y-2 rounded-lg border border-gray-600 bg-gray-800 shadow-lg custom-scrollbar",
+                    id: "notification-center",
+                    onkeydown: move |e: KeyboardEvent| {
+                        if e.key() == Key::Escape {
+                            expanded.set(false);
+                            // 面板移除后焦点回到通知按钮
+                            document::eval(
+                                "document.getElementById('notification-center-toggle')?.focus();",
+                            );
+                        }
+                    },
+                    div { class: "flex items-center justify-between",
+                        span { class: "text-sm font-semibold", "通知" }
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            disabled: recent.is_empty(),
+                            onclick: move |_| {
+                                notifications
+                                    .write()
+                                    .retain(|n| n.level == NotificationLevel::Success);
+                            },
+                            "清空"
+                        }
+                    }
+                    if recent.is_empty() {
+                        p { class: "text-xs text-gray-500", "没有错误或警告" }
+                    }
+                    for notification in recent {
+                        NotificationCard {
+                            key: "{notification.id}",
+                            notifications,
+                            notification,
+                        }
+                    }
+                }
+            }
+            button {
+                class: if unread > 0 { "px-3 py-1 rounded-full text-xs bg-red-600 text-white shadow" } else { "px-3 py-1 rounded-full text-xs bg-gray-700 text-gray-300 shadow hover:bg-gray-600" },
+                id: "notification-center-toggle",
+                aria_expanded: expanded(),
+                aria_controls: "notification-center",
+                onclick: move |_| {
+                    expanded.toggle();
+                    seen.set(latest);
+                },
+                if unread > 0 {
+                    "通知 ({unread})"
+                } else {
+                    "通知"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NotificationCard(notifications: Notifications, notification: Notification) -> Element {
+    let id = notification.id;
+    rsx! {
+        div { class: "p-2 rounded-lg border bg-gray-900 text-sm space-y-1 {level_class(notification.level)}",
+            div { class: "flex items-start justify-between gap-2",
+                span { class: "font-semibold", "{notification.title}" }
+                div { class: "flex items-center gap-2 shrink-0",
+                    span { class: "text-xs text-gray-500",
+                        {notification.time.format("%H:%M:%S").to_string()}
+                    }
+                    if notification.toast {
+                        button {
+                            class: "text-gray-400 hover:text-gray-200",
+                            title: "关闭",
+                            aria_label: "关闭通知",
+                            onclick: move |_| dismiss_toast(notifications, id),
+                            "×"
+                        }
+                    }
+                }
+            }
+            if !notification.message.is_empty() {
+                p { class: "text-xs text-gray-400 break-all whitespace-pre-line", "{notification.message}" }
+            }
+            if !notification.actions.is_empty() {
+                div { class: "flex flex-wrap gap-2",
+                    for action in notification.actions.iter().cloned() {
+                        button {
+                            class: "px-2 py-0.5 rounded text-xs bg-gray-700 text-gray-200 hover:bg-gray-600",
+                            onclick: move |_| run_action(&action),
+                            {action.label()}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,18 +2,72 @@ use super::button::Button;
 use super::input::Input;
 use crate::components::button::ButtonVariant;
 use crate::config::AppConfig;
+use crate::ffmpeg::options::{MergeMode, MergeOptions, SourceAction};
+use crate::utils::format_size;
 use dioxus::prelude::*;
+use std::path::PathBuf;
 
 // 2. 提取子组件：输出设置区域
 #[component]
 pub fn OutputSettings(
     output_filename: Signal<String>,
+    merge_options: Signal<MergeOptions>,
     config: Signal<AppConfig>,
+    /// 合并列表，用于匹配输出目录规则
+    files: ReadSignal<Vec<PathBuf>>,
+    /// 输出目录所在磁盘的可用空间（字节），无法读取时为 None
+    free_space: Signal<Option<u64>>,
     on_select_dir: Callback<MouseEvent>,
     on_clear_dir: Callback<MouseEvent>,
 ) -> Element {
+    // 切换输出类型时同步修改文件扩展名
+    let mut set_mode = move |mode: MergeMode| {
+        merge_options.write().mode = mode;
+        let name = output_filename();
+        if let Some((stem, _)) = name.rsplit_once('.') {
+            output_filename.set(format!("{}.{}", stem, mode.extension()));
+        }
+    };
+
+    let space_hint = free_space().map(|free| {
+        let low = config.read().is_low_space(free);
+        let text = if low {
+            format!(
+                "磁盘可用空间仅剩 {}，低于 {} GB 的提醒阈值",
+                format_size(Some(free)),
+                config.read().low_space_gb
+            )
+        } else {
+            format!("磁盘可用 {}", format_size(Some(free)))
+        };
+        (low, text)
+    });
+
     rsx! {
         div { class: "space-y-3",
+            div { class: "flex items-center gap-3",
+                span { class: "text-gray-400 text-sm", "类型:" }
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    onchange: move |evt| {
+                        if let Some(mode) = evt
+                            .value()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| MergeMode::ALL.get(i))
+                        {
+                            set_mode(*mode);
+                        }
+                    },
+                    for (index , mode) in MergeMode::ALL.iter().enumerate() {
+                        option {
+                            value: "{index}",
+                            selected: merge_options.read().mode == *mode,
+                            {mode.label()}
+                        }
+                    }
+                }
+            }
             div { class: "flex items-center gap-3",
                 span { class: "text-gray-400 text-sm", "文件名:" }
                 Input {
@@ -25,7 +79,12 @@ pub fn OutputSettings(
             div { class: "flex items-center gap-3",
                 span { class: "text-gray-400 text-sm", "目录:" }
                 span { class: "flex-1 text-gray-300 text-sm break-all",
-                    if let Some(dir) = config().output_directory.as_ref() {
+                    if let Some(route) = config.read().output_route_for(&files.read()) {
+                        "{route.output_dir.display()}"
+                        span { class: "text-xs text-gray-500 ml-1",
+                            "（规则：来自 {route.input_dir.display()}）"
+                        }
+                    } else if let Some(dir) = config().output_directory.as_ref() {
                         "{dir.display()}"
                     } else {
                         "使用默认目录"
@@ -34,6 +93,39 @@ pub fn OutputSettings(
                 Button { variant: ButtonVariant::Secondary, onclick: on_select_dir, "选择目录" }
                 Button { variant: ButtonVariant::Secondary, onclick: on_clear_dir, "清除" }
             }
+            if let Some((low, text)) = space_hint {
+                p { class: if low { "text-xs text-red-500 pl-10" } else { "text-xs text-gray-500 pl-10" },
+                    "{text}"
+                }
+            }
+            div { class: "flex items-center gap-3",
+                span { class: "text-gray-400 text-sm", "原文件:" }
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    onchange: move |evt| {
+                        if let Some(action) = evt
+                            .value()
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| SourceAction::ALL.get(i))
+                        {
+                            merge_options.write().source_action = *action;
+                        }
+                    },
+                    for (index , action) in SourceAction::ALL.iter().enumerate() {
+                        option {
+                            value: "{index}",
+                            selected: merge_options.read().source_action == *action,
+                            {action.label()}
+                        }
+                    }
+                }
+                if merge_options.read().source_action == SourceAction::Archive {
+                    span { class: "text-gray-500 text-xs",
+                        "合并成功后移到各自目录下的“{config.read().archive_folder}”文件夹"
+                    }
+                }
+            }
         }
     }
 }
@@ -0,0 +1,103 @@
+use crate::ffmpeg::options::MergeOptions;
+use crate::post_process::PostStep;
+use dioxus::prelude::*;
+
+// 合并后处理步骤的启用与排序，随预设一起保存
+#[component]
+pub fn PostStepsEditor(merge_options: Signal<MergeOptions>) -> Element {
+    let steps = merge_options.read().post_steps.clone();
+    let count = steps.len();
+
+    let mut move_step = move |index: usize, up: bool| {
+        let mut options = merge_options.write();
+        let target = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+        if let Some(target) = target.filter(|t| *t < options.post_steps.len()) {
+            options.post_steps.swap(index, target);
+        }
+    };
+
+    rsx! {
+        div { class: "space-y-2",
+            div { class: "flex items-center gap-2",
+                span { class: "text-gray-400", "合并后处理:" }
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    aria_label: "添加后处理步骤",
+                    onchange: move |evt| {
+                        let defaults = PostStep::defaults();
+                        if let Some(step) = evt.value().parse::<usize>().ok().and_then(|i| defaults.get(i)) {
+                            merge_options.write().post_steps.push(step.clone());
+                        }
+                    },
+                    option { value: "", selected: true, "添加步骤..." }
+                    for (index , step) in PostStep::defaults().iter().enumerate() {
+                        option { value: "{index}", "{step.kind_label()}" }
+                    }
+                }
+                if count == 0 {
+                    span { class: "text-xs text-gray-500", "未启用" }
+                }
+            }
+            ol { class: "space-y-1",
+                for (index , step) in steps.into_iter().enumerate() {
+                    li { key: "{index}", class: "flex items-center gap-2",
+                        span { class: "text-gray-400 w-5 text-right", "{index + 1}." }
+                        span { class: "w-32", "{step.kind_label()}" }
+                        match step {
+                            PostStep::MoveToArchive { folder } => rsx! {
+                                input {
+                                    class: "w-32 px-2 py-1 border rounded text-sm",
+                                    aria_label: "归档文件夹名",
+                                    value: "{folder}",
+                                    onchange: move |e: FormEvent| {
+                                        let name = e.value().trim().to_string();
+                                        if let Some(PostStep::MoveToArchive { folder }) = merge_options.write().post_steps.get_mut(index)
+                                            && !name.is_empty()
+                                        {
+                                            *folder = name;
+                                        }
+                                    },
+                                }
+                            },
+                            PostStep::Nfo | PostStep::JsonSidecar => rsx! {
+                                span { class: "text-xs text-gray-500", "保存在输出文件旁，含原文件、时长与选项；校验值需在设置中开启" }
+                            },
+                            PostStep::Thumbnail => rsx! {
+                                span { class: "text-xs text-gray-500", "保存在输出文件旁" }
+                            },
+                            PostStep::RunCommand { .. }
+                            | PostStep::ArchiveSources { .. }
+                            | PostStep::RecycleSources => rsx! {},
+                        }
+                        button {
+                            class: "px-1 text-gray-400 hover:text-gray-200 disabled:opacity-30",
+                            aria_label: "上移",
+                            disabled: index == 0,
+                            onclick: move |_| move_step(index, true),
+                            "↑"
+                        }
+                        button {
+                            class: "px-1 text-gray-400 hover:text-gray-200 disabled:opacity-30",
+                            aria_label: "下移",
+                            disabled: index + 1 == count,
+                            onclick: move |_| move_step(index, false),
+                            "↓"
+                        }
+                        button {
+                            class: "px-1 text-red-500 hover:underline",
+                            aria_label: "移除",
+                            onclick: move |_| {
+                                merge_options.write().post_steps.remove(index);
+                            },
+                            "×"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
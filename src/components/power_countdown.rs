@@ -0,0 +1,78 @@
+use crate::components::alert_dialog::{
+    AlertDialogAction, AlertDialogActions, AlertDialogCancel, AlertDialogContent,
+    AlertDialogDescription, AlertDialogRoot, AlertDialogTitle,
+};
+use crate::config::AppConfig;
+use crate::ffmpeg::merge_mp4::RUNNING_MERGES;
+use crate::notifications::{Notification, notify, use_notifications};
+use dioxus::prelude::*;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// 倒计时秒数
+pub const POWER_COUNTDOWN_SECS: u32 = 60;
+
+// 队列完成后关机/睡眠的倒计时对话框，countdown 为 None 时关闭
+#[component]
+pub fn PowerCountdown(countdown: Signal<Option<u32>>, config: Signal<AppConfig>) -> Element {
+    let notifications = use_notifications();
+    let action = config().power_action;
+
+    let mut execute_now = move || {
+        countdown.set(None);
+        let action = config.peek().power_action;
+        // 倒计时期间又开始了合并时不再执行
+        if RUNNING_MERGES.load(Ordering::SeqCst) > 0 {
+            notify(
+                notifications,
+                Notification::warning(format!("已取消{}", action.label()), "还有合并任务正在进行"),
+            );
+            return;
+        }
+        if let Err(e) = action.execute() {
+            notify(
+                notifications,
+                Notification::error(format!("无法执行{}", action.label()), e.to_string()),
+            );
+        }
+    };
+
+    use_future(move || async move {
+        loop {
+            sleep(Duration::from_secs(1)).await;
+            match countdown() {
+                Some(0) => execute_now(),
+                Some(remaining) => countdown.set(Some(remaining - 1)),
+                None => {}
+            }
+        }
+    });
+
+    rsx! {
+        AlertDialogRoot {
+            open: countdown().is_some(),
+            on_open_change: move |v: bool| {
+                if !v {
+                    countdown.set(None);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "即将{action.label()}" }
+                AlertDialogDescription {
+                    {
+                        format!(
+                            "队列中的任务已全部完成，系统将在 {} 秒后{}。",
+                            countdown().unwrap_or(0),
+                            action.label(),
+                        )
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { on_click: move |_| countdown.set(None), "取消" }
+                    AlertDialogAction { on_click: move |_| execute_now(), "立即{action.label()}" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::MergeEvent;
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::Button;
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::components::progress::{Progress, ProgressIndicator};
+use crate::ffmpeg::rotate::{RotateMethod, RotateOptions, run_rotate};
+use crate::utils::with_stem_suffix;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+const ROTATIONS: [(i32, &str); 4] = [
+    (90, "顺时针 90°"),
+    (270, "逆时针 90°"),
+    (180, "旋转 180°"),
+    (0, "保持方向（仅重写元数据/转正）"),
+];
+
+// 旋转/矫正对话框，target 为 None 时关闭
+#[component]
+pub fn RotateDialog(target: Signal<Option<Mp4FileInfo>>) -> Element {
+    use_restore_focus(move || target.read().is_some());
+    let mut clockwise: Signal<i32> = use_signal(|| 90);
+    let mut method: Signal<RotateMethod> = use_signal(|| RotateMethod::Metadata);
+    let mut progress: Signal<f64> = use_signal(|| 0.0);
+    let mut is_running: Signal<bool> = use_signal(|| false);
+    let mut result_message: Signal<Option<String>> = use_signal(|| None);
+
+    let start_rotate = move |_| async move {
+        let Some(info) = target() else {
+            return;
+        };
+        let output_path = with_stem_suffix(&info.file_path, "_rotated.mp4", "video");
+        let options = RotateOptions {
+            clockwise: clockwise(),
+            method: method(),
+        };
+
+        is_running.set(true);
+        progress.set(0.0);
+        result_message.set(None);
+        let (tx, mut rx) = futures::channel::mpsc::unbounded::<MergeEvent>();
+        let rotate = run_rotate(info.file_path.clone(), output_path, options, tx);
+        let updates = async {
+            while let Some(event) = rx.next().await {
+                match event {
+                    MergeEvent::Progress(p) => progress.set(p.percent),
+                    MergeEvent::Status(_)
+                    | MergeEvent::Segments(_)
+                    | MergeEvent::Probing { .. } => {}
+                    MergeEvent::Error(e) => result_message.set(Some(format!("处理失败: {}", e))),
+                    MergeEvent::Success(path) => {
+                        progress.set(100.0);
+                        result_message.set(Some(format!("已保存到 {}", path)));
+                    }
+                }
+            }
+        };
+        futures::join!(rotate, updates);
+        is_running.set(false);
+    };
+
+    let file_name = target
+        .read()
+        .as_ref()
+        .map(|info| info.file_name.clone())
+        .unwrap_or_default();
+
+    rsx! {
+        AlertDialogRoot {
+            open: target.read().is_some(),
+            on_open_change: move |v: bool| {
+                if !v && !is_running() {
+                    target.set(None);
+                    result_message.set(None);
+                    progress.set(0.0);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "旋转/矫正" }
+                AlertDialogDescription { "{file_name}" }
+                div { class: "space-y-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "旋转" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            onchange: move |evt| {
+                                if let Ok(v) = evt.value().parse::<i32>() {
+                                    clockwise.set(v);
+                                }
+                            },
+                            for (value , label) in ROTATIONS {
+                                option { value: "{value}", selected: clockwise() == value, "{label}" }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "方式" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            onchange: move |evt| {
+                                method.set(if evt.value() == "reencode" { RotateMethod::ReEncode } else { RotateMethod::Metadata });
+                            },
+                            option { value: "metadata", selected: method() == RotateMethod::Metadata,
+                                "无损（修改旋转元数据）"
+                            }
+                            option { value: "reencode", selected: method() == RotateMethod::ReEncode,
+                                "重新编码（写入画面）"
+                            }
+                        }
+                    }
+                    p { class: "text-gray-500", "结果保存为同目录下的 *_rotated.mp4" }
+                    if is_running() || progress() > 0.0 {
+                        Progress { aria_label: "处理进度", value: progress(), ProgressIndicator {} }
+                    }
+                    if let Some(message) = result_message() {
+                        p { class: "text-gray-600 break-all", "{message}" }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button { disabled: is_running(), onclick: start_rotate,
+                        if is_running() {
+                            "处理中..."
+                        } else {
+                            "开始"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
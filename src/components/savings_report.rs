@@ -0,0 +1,42 @@
+use crate::history::{SizeComparison, describe_savings, total_sizes};
+use crate::utils::{format_bytes, format_size};
+use dioxus::prelude::*;
+
+// 压缩/转码前后的大小对比：逐个文件与合计
+#[component]
+pub fn SavingsReport(sizes: Vec<SizeComparison>) -> Element {
+    let (original, output) = total_sizes(&sizes);
+
+    rsx! {
+        table { class: "w-full text-xs",
+            thead {
+                tr { class: "text-gray-400 text-left",
+                    th { class: "py-1", "文件" }
+                    th { class: "py-1 text-right", "原大小" }
+                    th { class: "py-1 text-right", "新大小" }
+                    th { class: "py-1 text-right", "变化" }
+                }
+            }
+            tbody {
+                for size in sizes.iter() {
+                    tr { class: "border-t border-gray-200",
+                        td { class: "py-1 pr-2 truncate max-w-48", title: "{size.output.display()}",
+                            {size.original.file_name().unwrap_or_default().to_string_lossy().into_owned()}
+                        }
+                        td { class: "py-1 text-right", title: format_bytes(size.original_size), {format_size(Some(size.original_size))} }
+                        td { class: "py-1 text-right", title: format_bytes(size.output_size), {format_size(Some(size.output_size))} }
+                        td { class: "py-1 text-right", {describe_savings(size.original_size, size.output_size)} }
+                    }
+                }
+                if sizes.len() > 1 {
+                    tr { class: "border-t border-gray-400 font-semibold",
+                        td { class: "py-1", "合计" }
+                        td { class: "py-1 text-right", title: format_bytes(original), {format_size(Some(original))} }
+                        td { class: "py-1 text-right", title: format_bytes(output), {format_size(Some(output))} }
+                        td { class: "py-1 text-right", {describe_savings(original, output)} }
+                    }
+                }
+            }
+        }
+    }
+}
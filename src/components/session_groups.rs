@@ -0,0 +1,84 @@
+use super::button::{Button, ButtonVariant};
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::config::AppConfig;
+use crate::ffmpeg::options::MergeOptions;
+use crate::queue::{MergeJob, use_job_queue};
+use crate::utils::{format_duration, with_stem_suffix};
+use dioxus::prelude::*;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// 自动分组结果：每个录制会话可一键加入任务队列
+#[component]
+pub fn SessionGroups(groups: Signal<Vec<Vec<Mp4FileInfo>>>, config: Signal<AppConfig>) -> Element {
+    let mut queue = use_job_queue();
+
+    let mut enqueue = move |index: usize| {
+        let Some(group) = groups.read().get(index).cloned() else {
+            return;
+        };
+        queue.write().push(session_job(&group, &config.read()));
+        groups.write().remove(index);
+    };
+
+    let enqueue_all = move |_| {
+        let all = std::mem::take(&mut *groups.write());
+        let config_value = config.read();
+        queue
+            .write()
+            .extend(all.iter().map(|group| session_job(group, &config_value)));
+    };
+
+    rsx! {
+        div { class: "mt-2 p-2 rounded-xl border border-gray-300 space-y-2",
+            div { class: "flex items-center justify-between",
+                span { class: "text-sm text-gray-600", "检测到 {groups.read().len()} 个录制会话" }
+                div { class: "flex gap-2",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| groups.write().clear(),
+                        "关闭"
+                    }
+                    Button { onclick: enqueue_all, "全部加入队列" }
+                }
+            }
+            div { class: "space-y-1 max-h-48 overflow-y-auto",
+                for (index , group) in groups.read().iter().enumerate() {
+                    div { class: "flex items-center justify-between gap-3 text-sm py-1 px-2 border-b border-gray-200",
+                        span { class: "truncate flex-1", title: "{group[0].file_path.display()}",
+                            "{group[0].file_name} 等 {group.len()} 个片段"
+                        }
+                        span { class: "text-gray-500 whitespace-nowrap",
+                            "{group[0].width}x{group[0].height} · {group_duration(group)}"
+                        }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            onclick: move |_| enqueue(index),
+                            "加入队列"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn group_duration(group: &[Mp4FileInfo]) -> String {
+    let total: Duration = group.iter().map(|f| f.duration).sum();
+    format_duration(total.as_secs_f64())
+}
+
+fn session_job(group: &[Mp4FileInfo], config: &AppConfig) -> MergeJob {
+    let first = &group[0];
+    let files: Vec<PathBuf> = group.iter().map(|f| f.file_path.clone()).collect();
+    let merged = with_stem_suffix(&first.file_path, "_merged.mp4", "session");
+    let output_path = config
+        .output_directory_for(&files)
+        .join(merged.file_name().unwrap_or_default());
+    MergeJob::new(
+        format!("{} 等 {} 个片段", first.file_name, group.len()),
+        files,
+        output_path,
+        MergeOptions::default(),
+    )
+}
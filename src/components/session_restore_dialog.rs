@@ -0,0 +1,40 @@
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogContent, AlertDialogDescription, AlertDialogRoot,
+    AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::queue::use_job_queue;
+use crate::session::use_session;
+use dioxus::prelude::*;
+
+// 上次意外退出时留有自动保存的会话，启动后询问是否恢复
+#[component]
+pub fn SessionRestoreDialog() -> Element {
+    let session = use_session();
+    let job_queue = use_job_queue();
+
+    let Some(snapshot) = session.recovered.read().clone() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        AlertDialogRoot { open: true,
+            AlertDialogContent {
+                AlertDialogTitle { "恢复上次的会话？" }
+                AlertDialogDescription { "程序上次没有正常退出，以下内容已自动保存：" }
+                ul { class: "text-sm text-gray-500 list-disc pl-5",
+                    if !snapshot.workspaces.is_empty() {
+                        li { "{snapshot.workspaces.len()} 个工作区，共 {snapshot.file_count()} 个文件" }
+                    }
+                    if !snapshot.queue.is_empty() {
+                        li { "{snapshot.queue.len()} 个未完成的队列任务" }
+                    }
+                }
+                AlertDialogActions {
+                    Button { variant: ButtonVariant::Ghost, onclick: move |_| session.discard(), "放弃" }
+                    Button { onclick: move |_| session.restore(job_queue), "恢复" }
+                }
+            }
+        }
+    }
+}
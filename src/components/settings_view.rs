@@ -0,0 +1,652 @@
+use super::button::{Button, ButtonVariant};
+use super::diagnostics_view::DiagnosticsView;
+use super::upload_settings::UploadSettingsView;
+use crate::api::generate_token;
+use crate::config::{AppConfig, DuplicatePolicy, ErrorPolicy, OutputRoute, SizeUnits, Theme};
+use crate::ffmpeg::proxy::clear_proxies;
+use crate::post_command::PLACEHOLDER_HELP;
+use crate::shortcuts::{ShortcutAction, format_key_event};
+use crate::utils::{format_size, portable_dir, set_size_units};
+use dioxus::prelude::*;
+
+// 设置页：常规选项与快捷键映射表
+#[component]
+pub fn SettingsView(config: Signal<AppConfig>) -> Element {
+    // 正在录制新快捷键的操作
+    let mut recording: Signal<Option<ShortcutAction>> = use_signal(|| None);
+    let mut error_message: Signal<Option<String>> = use_signal(|| None);
+    let mut proxy_message: Signal<Option<String>> = use_signal(|| None);
+
+    let mut apply_binding = move |action: ShortcutAction, binding: String| {
+        let conflict = ShortcutAction::ALL
+            .into_iter()
+            .find(|other| *other != action && config.read().shortcut(*other) == binding);
+        if let Some(other) = conflict {
+            error_message.set(Some(format!("{} 已被“{}”使用", binding, other.label())));
+            return;
+        }
+        error_message.set(None);
+        if let Err(e) = config.write().set_shortcut(action, binding) {
+            error_message.set(Some(format!("无法保存快捷键设置: {}", e)));
+        }
+    };
+
+    // 依次选择输入目录和输出目录，任一步取消则不添加
+    let add_route = move |_| async move {
+        let Some(input) = rfd::AsyncFileDialog::new()
+            .set_title("选择输入所在的目录")
+            .pick_folder()
+            .await
+        else {
+            return;
+        };
+        let Some(output) = rfd::AsyncFileDialog::new()
+            .set_title("选择该目录的输出目录")
+            .pick_folder()
+            .await
+        else {
+            return;
+        };
+        let route = OutputRoute {
+            input_dir: input.path().to_path_buf(),
+            output_dir: output.path().to_path_buf(),
+        };
+        config
+            .write()
+            .output_routes
+            .retain(|r| r.input_dir != route.input_dir);
+        config.write().output_routes.push(route);
+        if let Err(e) = config.read().save() {
+            error_message.set(Some(format!("无法保存设置: {}", e)));
+        }
+    };
+
+    let exclude_patterns = config.read().scan_rules.exclude_patterns.join(", ");
+
+    rsx! {
+        div { class: "p-6 space-y-3",
+            h2 { class: "text-sm font-semibold", "常规" }
+            if let Some(dir) = portable_dir() {
+                p { class: "text-xs text-gray-500 break-all", "便携模式：配置与数据保存在 {dir.display()}" }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                input {
+                    r#type: "checkbox",
+                    checked: config.read().minimize_to_tray,
+                    onchange: move |e| {
+                        config.write().minimize_to_tray = e.checked();
+                        if let Err(e) = config.read().save() {
+                            error_message.set(Some(format!("无法保存设置: {}", e)));
+                        }
+                    },
+                }
+                "有任务执行时，关闭窗口最小化到托盘"
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                input {
+                    r#type: "checkbox",
+                    checked: config.read().compute_checksums,
+                    onchange: move |e| {
+                        config.write().compute_checksums = e.checked();
+                        if let Err(e) = config.read().save() {
+                            error_message.set(Some(format!("无法保存设置: {}", e)));
+                        }
+                    },
+                }
+                "合并完成后计算输出与输入的 SHA-256，记录在历史中"
+            }
+            div { class: "flex items-center gap-2 text-sm",
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: config.read().job_guard.enabled,
+                        onchange: move |e| {
+                            config.write().job_guard.enabled = e.checked();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                    "预计输出超过"
+                }
+                input {
+                    r#type: "number",
+                    class: "w-16 border rounded px-1 text-sm",
+                    min: "1",
+                    value: "{config.read().job_guard.max_output_gb}",
+                    onchange: move |e| {
+                        if let Ok(gb) = e.value().parse::<f64>()
+                            && gb > 0.0
+                        {
+                            config.write().job_guard.max_output_gb = gb;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                }
+                "GB 或耗时超过"
+                input {
+                    r#type: "number",
+                    class: "w-16 border rounded px-1 text-sm",
+                    min: "0.5",
+                    step: "0.5",
+                    value: "{config.read().job_guard.max_hours}",
+                    onchange: move |e| {
+                        if let Ok(hours) = e.value().parse::<f64>()
+                            && hours > 0.0
+                        {
+                            config.write().job_guard.max_hours = hours;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                }
+                "小时时，开始前确认"
+            }
+            div { class: "space-y-1 text-sm",
+                label { class: "flex items-center gap-2",
+                    "合并后执行命令"
+                    input {
+                        class: "flex-1 border rounded px-1 text-sm font-mono",
+                        placeholder: "留空则不执行，例如 rclone copy {{output}} remote:videos",
+                        value: "{config.read().post_command}",
+                        onchange: move |e| {
+                            config.write().post_command = e.value();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                }
+                p { class: "text-xs text-gray-500", "{PLACEHOLDER_HELP}，输出记录在历史中，失败时作为警告提示" }
+            }
+            div { class: "space-y-1 text-sm",
+                div { class: "flex items-center gap-2",
+                    label { class: "flex items-center gap-2",
+                        input {
+                            r#type: "checkbox",
+                            checked: config.read().api.enabled,
+                            onchange: move |e| {
+                                {
+                                    let mut config = config.write();
+                                    config.api.enabled = e.checked();
+                                    if config.api.token.is_empty() {
+                                        match generate_token() {
+                                            Ok(token) => config.api.token = token,
+                                            Err(e) => error_message.set(Some(e)),
+                                        }
+                                    }
+                                }
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            },
+                        }
+                        "启用本机 HTTP 接口，端口"
+                    }
+                    input {
+                        r#type: "number",
+                        class: "w-20 border rounded px-1 text-sm",
+                        min: "1024",
+                        max: "65535",
+                        value: "{config.read().api.port}",
+                        onchange: move |e| {
+                            if let Ok(port) = e.value().parse::<u16>()
+                                && port >= 1024
+                            {
+                                config.write().api.port = port;
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            }
+                        },
+                    }
+                }
+                if config.read().api.enabled {
+                    div { class: "flex items-center gap-2 text-xs text-gray-500",
+                        span { class: "font-mono break-all", "令牌 {config.read().api.token}" }
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            onclick: move |_| {
+                                match generate_token() {
+                                    Ok(token) => config.write().api.token = token,
+                                    Err(e) => {
+                                        error_message.set(Some(e));
+                                        return;
+                                    }
+                                }
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            },
+                            "重新生成"
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "只监听 127.0.0.1。请求头携带 Authorization: Bearer 令牌，或在地址后加 ?token=令牌；"
+                        "GET /api/queue 查询队列，POST /api/jobs 提交任务（可指定预设名称，输出保存在输出目录），POST /api/queue/start 开始，"
+                        "WebSocket /api/events 推送进度"
+                    }
+                }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                "归档原文件到子文件夹"
+                input {
+                    class: "w-32 border rounded px-1 text-sm",
+                    value: "{config.read().archive_folder}",
+                    onchange: move |e| {
+                        // 只允许单层文件夹名，避免把原文件移到意外的位置
+                        let name = e.value().trim().to_string();
+                        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+                            error_message.set(Some("归档文件夹名不能为空或包含路径分隔符".to_string()));
+                            return;
+                        }
+                        config.write().archive_folder = name;
+                        if let Err(e) = config.read().save() {
+                            error_message.set(Some(format!("无法保存设置: {}", e)));
+                        }
+                    },
+                }
+            }
+            div { class: "space-y-1 text-sm",
+                h3 { class: "font-semibold", "窗口" }
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: config.read().window.start_maximized,
+                        onchange: move |e| {
+                            config.write().window.start_maximized = e.checked();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                    "启动时最大化"
+                }
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: config.read().window.remember_size,
+                        onchange: move |e| {
+                            config.write().window.remember_size = e.checked();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                    "记住窗口大小"
+                }
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: config.read().window.always_on_top,
+                        onchange: move |e| {
+                            config.write().window.always_on_top = e.checked();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                    "窗口置顶"
+                }
+                label { class: "flex items-center gap-2",
+                    "最小窗口大小"
+                    input {
+                        r#type: "number",
+                        class: "w-20 border rounded px-1 text-sm",
+                        min: "320",
+                        aria_label: "最小宽度",
+                        value: "{config.read().window.min_width}",
+                        onchange: move |e| {
+                            if let Ok(width) = e.value().parse::<f64>()
+                                && width >= 320.0
+                            {
+                                config.write().window.min_width = width;
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            }
+                        },
+                    }
+                    "×"
+                    input {
+                        r#type: "number",
+                        class: "w-20 border rounded px-1 text-sm",
+                        min: "240",
+                        aria_label: "最小高度",
+                        value: "{config.read().window.min_height}",
+                        onchange: move |e| {
+                            if let Ok(height) = e.value().parse::<f64>()
+                                && height >= 240.0
+                            {
+                                config.write().window.min_height = height;
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            }
+                        },
+                    }
+                    "像素"
+                }
+            }
+            div { class: "space-y-1 text-sm",
+                h3 { class: "font-semibold", "扫描规则" }
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: config.read().scan_rules.include_subfolders,
+                        onchange: move |e| {
+                            config.write().scan_rules.include_subfolders = e.checked();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                    "包含子文件夹"
+                }
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: config.read().scan_rules.ignore_hidden,
+                        onchange: move |e| {
+                            config.write().scan_rules.ignore_hidden = e.checked();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                    "忽略隐藏的文件和文件夹"
+                }
+                label { class: "flex items-center gap-2",
+                    "排除"
+                    input {
+                        class: "flex-1 border rounded px-1 text-sm font-mono",
+                        placeholder: "用逗号分隔，例如 *.tmp.mp4, node_modules",
+                        value: "{exclude_patterns}",
+                        onchange: move |e| {
+                            config.write().scan_rules.exclude_patterns = e
+                                .value()
+                                .split([',', '，'])
+                                .map(str::trim)
+                                .filter(|p| !p.is_empty())
+                                .map(str::to_string)
+                                .collect();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                }
+                label { class: "flex items-center gap-2",
+                    "跳过小于"
+                    input {
+                        r#type: "number",
+                        class: "w-16 border rounded px-1 text-sm",
+                        min: "0",
+                        value: "{config.read().scan_rules.min_size_mb}",
+                        onchange: move |e| {
+                            if let Ok(mb) = e.value().parse::<f64>()
+                                && mb >= 0.0
+                            {
+                                config.write().scan_rules.min_size_mb = mb;
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            }
+                        },
+                    }
+                    "MB 的文件（0 为不限制）"
+                }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                "输出磁盘可用空间低于"
+                input {
+                    r#type: "number",
+                    class: "w-16 border rounded px-1 text-sm",
+                    min: "0",
+                    value: "{config.read().low_space_gb}",
+                    onchange: move |e| {
+                        if let Ok(gb) = e.value().parse::<f64>()
+                            && gb >= 0.0
+                        {
+                            config.write().low_space_gb = gb;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                }
+                "GB 时提醒（0 为不提醒）"
+            }
+            div { class: "flex items-center gap-2 text-sm",
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: config.read().generate_proxies,
+                        onchange: move |e| {
+                            config.write().generate_proxies = e.checked();
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        },
+                    }
+                    "为 4K 等高分辨率素材在后台生成低分辨率代理，加快剪切分析与预览"
+                }
+                Button {
+                    variant: ButtonVariant::Ghost,
+                    onclick: move |_| match clear_proxies() {
+                        Ok(freed) => proxy_message.set(Some(format!("已清除代理文件，释放 {}", format_size(Some(freed))))),
+                        Err(e) => error_message.set(Some(format!("清除代理文件失败: {}", e))),
+                    },
+                    "清除代理"
+                }
+                if let Some(message) = proxy_message() {
+                    span { class: "text-xs text-gray-500", "{message}" }
+                }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                "重复添加同一文件时"
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    onchange: move |e| {
+                        if let Some(policy) = DuplicatePolicy::ALL
+                            .into_iter()
+                            .find(|p| format!("{:?}", p) == e.value())
+                        {
+                            config.write().duplicate_policy = policy;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                    for policy in DuplicatePolicy::ALL {
+                        option {
+                            value: "{policy:?}",
+                            selected: config.read().duplicate_policy == policy,
+                            {policy.label()}
+                        }
+                    }
+                }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                "队列中的任务失败时"
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    onchange: move |e| {
+                        if let Some(policy) = ErrorPolicy::ALL
+                            .into_iter()
+                            .find(|p| format!("{:?}", p) == e.value())
+                        {
+                            config.write().error_policy = policy;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                    for policy in ErrorPolicy::ALL {
+                        option {
+                            value: "{policy:?}",
+                            selected: config.read().error_policy == policy,
+                            {policy.label()}
+                        }
+                    }
+                }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                "文件被占用或网络中断导致失败时，自动重试"
+                input {
+                    r#type: "number",
+                    class: "w-16 border rounded px-1 text-sm",
+                    min: "0",
+                    max: "10",
+                    value: "{config.read().retry_policy.max_retries}",
+                    onchange: move |e| {
+                        if let Ok(count) = e.value().parse::<u32>()
+                            && count <= 10
+                        {
+                            config.write().retry_policy.max_retries = count;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                }
+                "次（间隔从 {config.read().retry_policy.base_delay_secs} 秒起逐次翻倍）"
+            }
+            UploadSettingsView { config }
+            label { class: "flex items-center gap-2 text-sm",
+                "主题"
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    onchange: move |e| {
+                        if let Some(theme) = Theme::ALL.into_iter().find(|t| t.as_str() == e.value()) {
+                            config.write().theme = theme;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                    for theme in Theme::ALL {
+                        option {
+                            value: theme.as_str(),
+                            selected: config.read().theme == theme,
+                            {theme.label()}
+                        }
+                    }
+                }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                "文件大小单位"
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    onchange: move |e| {
+                        if let Some(units) = SizeUnits::ALL.into_iter().find(|u| u.label() == e.value()) {
+                            // 先更新全局单位，随后的重新渲染才会用新单位
+                            set_size_units(units);
+                            config.write().size_units = units;
+                            if let Err(e) = config.read().save() {
+                                error_message.set(Some(format!("无法保存设置: {}", e)));
+                            }
+                        }
+                    },
+                    for units in SizeUnits::ALL {
+                        option {
+                            value: units.label(),
+                            selected: config.read().size_units == units,
+                            {units.label()}
+                        }
+                    }
+                }
+            }
+            h2 { class: "text-sm font-semibold", "输出目录规则" }
+            p { class: "text-xs text-gray-500",
+                "第一个输入文件位于规则的输入目录（含子文件夹）时，自动输出到对应目录；多条命中时取最具体的一条"
+            }
+            div { class: "space-y-1 text-sm",
+                for (index , route) in config.read().output_routes.iter().cloned().enumerate() {
+                    div { class: "flex items-center gap-2",
+                        span { class: "flex-1 break-all font-mono text-xs",
+                            "{route.input_dir.display()} → {route.output_dir.display()}"
+                        }
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            onclick: move |_| {
+                                config.write().output_routes.remove(index);
+                                if let Err(e) = config.read().save() {
+                                    error_message.set(Some(format!("无法保存设置: {}", e)));
+                                }
+                            },
+                            "删除"
+                        }
+                    }
+                }
+                Button { variant: ButtonVariant::Secondary, onclick: add_route, "添加规则" }
+            }
+            h2 { class: "text-sm font-semibold", "快捷键" }
+            table { class: "w-full text-sm",
+                thead {
+                    tr { class: "text-left text-gray-400",
+                        th { class: "py-1", "操作" }
+                        th { class: "py-1", "快捷键" }
+                        th { class: "py-1" }
+                    }
+                }
+                tbody {
+                    for action in ShortcutAction::ALL {
+                        tr { class: "border-t border-gray-700",
+                            td { class: "py-2", {action.label()} }
+                            td { class: "py-2",
+                                if recording() == Some(action) {
+                                    input {
+                                        class: "w-40 px-2 py-1 border rounded text-sm",
+                                        "data-shortcut-capture": "true",
+                                        readonly: true,
+                                        autofocus: true,
+                                        placeholder: "请按下新的组合键",
+                                        onkeydown: move |evt| {
+                                            evt.prevent_default();
+                                            if let Some(binding) = format_key_event(&evt.data()) {
+                                                if binding == "Escape" {
+                                                    recording.set(None);
+                                                    return;
+                                                }
+                                                apply_binding(action, binding);
+                                                recording.set(None);
+                                            }
+                                        },
+                                        onblur: move |_| recording.set(None),
+                                    }
+                                } else {
+                                    kbd { class: "px-2 py-1 rounded border border-gray-600 font-mono",
+                                        "{config.read().shortcut(action)}"
+                                    }
+                                }
+                            }
+                            td { class: "py-2 flex gap-2 justify-end",
+                                Button {
+                                    variant: ButtonVariant::Secondary,
+                                    onclick: move |_| recording.set(Some(action)),
+                                    "修改"
+                                }
+                                Button {
+                                    variant: ButtonVariant::Ghost,
+                                    onclick: move |_| apply_binding(action, action.default_binding().to_string()),
+                                    "恢复默认"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            p { class: "text-xs text-gray-500",
+                "点击“修改”后按下新的组合键，按 Esc 取消。在输入框中时，不带 Ctrl/Alt 的快捷键不会触发。"
+            }
+            if let Some(message) = error_message() {
+                p { class: "text-sm text-red-400", "{message}" }
+            }
+            DiagnosticsView {}
+        }
+    }
+}
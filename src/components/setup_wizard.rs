@@ -0,0 +1,198 @@
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogContent, AlertDialogDescription, AlertDialogRoot,
+    AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::config::{AppConfig, Language, Theme};
+use crate::ffmpeg::locate::{FFMPEG_DOWNLOAD_URL, add_ffmpeg_dir, check_ffmpeg_dir, find_ffmpeg};
+use crate::utils::open_path;
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+
+const STEPS: [&str; 4] = ["FFmpeg", "语言与主题", "输出目录", "导入设置"];
+
+// 首次启动向导：检查 FFmpeg、选择主题与默认输出目录，可导入已有设置
+#[component]
+pub fn SetupWizard(config: Signal<AppConfig>) -> Element {
+    let mut step: Signal<usize> = use_signal(|| 0);
+    let mut ffmpeg_path: Signal<Option<PathBuf>> = use_signal(find_ffmpeg);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    let select_ffmpeg_dir = move |_| async move {
+        let Some(folder) = rfd::AsyncFileDialog::new()
+            .set_title("选择 ffmpeg 所在的文件夹")
+            .pick_folder()
+            .await
+        else {
+            return;
+        };
+        let dir = folder.path().to_path_buf();
+        match check_ffmpeg_dir(&dir) {
+            Ok(()) => {
+                add_ffmpeg_dir(&dir);
+                config.write().ffmpeg_dir = Some(dir);
+                ffmpeg_path.set(find_ffmpeg());
+                message.set(None);
+            }
+            Err(e) => message.set(Some(e)),
+        }
+    };
+
+    let select_output_dir = move |_| async move {
+        if let Some(folder) = rfd::AsyncFileDialog::new()
+            .set_title("选择默认输出目录")
+            .pick_folder()
+            .await
+        {
+            config.write().output_directory = Some(folder.path().to_path_buf());
+        }
+    };
+
+    let mut finish = move || {
+        config.write().setup_completed = true;
+        if let Err(e) = config.read().save() {
+            eprintln!("保存配置失败: {}", e);
+        }
+    };
+
+    let import_settings = move |_| async move {
+        let Some(file) = rfd::AsyncFileDialog::new()
+            .set_title("选择要导入的配置文件")
+            .add_filter("配置文件", &["json"])
+            .pick_file()
+            .await
+        else {
+            return;
+        };
+        match AppConfig::import_from(file.path()) {
+            Ok(imported) => {
+                if let Some(dir) = &imported.ffmpeg_dir {
+                    add_ffmpeg_dir(dir);
+                }
+                config.set(imported);
+                finish();
+            }
+            Err(e) => message.set(Some(format!("导入失败: {}", e))),
+        }
+    };
+
+    let current = step();
+    let is_last = current + 1 == STEPS.len();
+
+    rsx! {
+        AlertDialogRoot { open: !config.read().setup_completed,
+            AlertDialogContent {
+                AlertDialogTitle { "欢迎使用 mp4文件合并" }
+                AlertDialogDescription { "第 {current + 1}/{STEPS.len()} 步：{STEPS[current]}" }
+                div { class: "space-y-3 text-sm",
+                    {
+                        match current {
+                            0 => rsx! {
+                                if let Some(path) = ffmpeg_path() {
+                                    p { class: "text-green-500 break-all", "已找到 FFmpeg：{path.display()}" }
+                                } else {
+                                    p { class: "text-amber-500",
+                                        "没有找到 FFmpeg。合并需要 ffmpeg 与 ffprobe，可下载后选择其所在文件夹"
+                                    }
+                                }
+                                div { class: "flex gap-2",
+                                    Button {
+                                        variant: ButtonVariant::Secondary,
+                                        onclick: move |_| ffmpeg_path.set(find_ffmpeg()),
+                                        "重新检测"
+                                    }
+                                    Button { variant: ButtonVariant::Secondary, onclick: select_ffmpeg_dir, "选择所在文件夹" }
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: move |_| {
+                                            if let Err(e) = open_path(Path::new(FFMPEG_DOWNLOAD_URL)) {
+                                                message.set(Some(format!("无法打开下载页面: {}", e)));
+                                            }
+                                        },
+                                        "打开下载页面"
+                                    }
+                                }
+                            },
+                            1 => rsx! {
+                                label { class: "flex items-center gap-2",
+                                    span { class: "w-16 text-gray-500", "语言" }
+                                    select {
+                                        class: "border rounded px-2 py-1 text-sm",
+                                        onchange: move |e| {
+                                            if let Some(language) = Language::ALL.into_iter().find(|l| l.label() == e.value()) {
+                                                config.write().language = language;
+                                            }
+                                        },
+                                        for language in Language::ALL {
+                                            option {
+                                                value: language.label(),
+                                                selected: config.read().language == language,
+                                                {language.label()}
+                                            }
+                                        }
+                                    }
+                                }
+                                label { class: "flex items-center gap-2",
+                                    span { class: "w-16 text-gray-500", "主题" }
+                                    select {
+                                        class: "border rounded px-2 py-1 text-sm",
+                                        onchange: move |e| {
+                                            if let Some(theme) = Theme::ALL.into_iter().find(|t| t.as_str() == e.value()) {
+                                                config.write().theme = theme;
+                                            }
+                                        },
+                                        for theme in Theme::ALL {
+                                            option {
+                                                value: theme.as_str(),
+                                                selected: config.read().theme == theme,
+                                                {theme.label()}
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            2 => rsx! {
+                                p { class: "text-gray-500 break-all",
+                                    "合并结果默认保存到："
+                                    {config.read().get_output_directory().display().to_string()}
+                                }
+                                Button { variant: ButtonVariant::Secondary, onclick: select_output_dir, "选择目录" }
+                            },
+                            _ => rsx! {
+                                p { class: "text-gray-500", "如果在其他电脑上用过本程序，可导入其 config.json 中的预设、快捷键等设置" }
+                                Button { variant: ButtonVariant::Secondary, onclick: import_settings, "从文件导入" }
+                            },
+                        }
+                    }
+                    if let Some(msg) = message() {
+                        p { class: "text-red-500 break-all", "{msg}" }
+                    }
+                }
+                AlertDialogActions {
+                    Button { variant: ButtonVariant::Ghost, onclick: move |_| finish(), "跳过" }
+                    if current > 0 {
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            onclick: move |_| {
+                                message.set(None);
+                                step -= 1;
+                            },
+                            "上一步"
+                        }
+                    }
+                    if is_last {
+                        Button { onclick: move |_| finish(), "完成" }
+                    } else {
+                        Button {
+                            onclick: move |_| {
+                                message.set(None);
+                                step += 1;
+                            },
+                            "下一步"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
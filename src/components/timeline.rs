@@ -0,0 +1,205 @@
+use crate::a11y::is_activation_key;
+use crate::ffmpeg::junction_preview::{JUNCTION_PREVIEW_SECS, junction_preview};
+use crate::ffmpeg::options::MergeOptions;
+use crate::ffmpeg::probe::{PROBE_CONCURRENCY, probe_duration_cached};
+use crate::utils::{format_duration, open_path};
+use dioxus::prelude::*;
+use futures_util::{StreamExt, stream};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// 合并计划时间轴：按成片中的时长比例显示各片段，点击选中，拖动调整顺序
+#[component]
+pub fn MergeTimeline(
+    files: Signal<Vec<PathBuf>>,
+    selected: Signal<Option<usize>>,
+    merge_options: ReadSignal<MergeOptions>,
+) -> Element {
+    let mut dragging: Signal<Option<usize>> = use_signal(|| None);
+    let mut drop_target: Signal<Option<usize>> = use_signal(|| None);
+    // 正在生成预览的衔接处（前一片段的序号）
+    let mut previewing: Signal<Option<usize>> = use_signal(|| None);
+    let mut preview_error: Signal<Option<String>> = use_signal(|| None);
+    // 片段与片头/片尾的原始时长，无法读取的记为 0
+    let durations = use_resource(move || {
+        let options = merge_options();
+        let paths: Vec<PathBuf> = options
+            .intro
+            .iter()
+            .chain(files().iter())
+            .chain(options.outro.iter())
+            .cloned()
+            .collect();
+        async move {
+            stream::iter(paths)
+                .map(|path| async move {
+                    let duration = probe_duration_cached(&path).await.unwrap_or(0.0);
+                    (path, duration)
+                })
+                .buffer_unordered(PROBE_CONCURRENCY)
+                .collect::<HashMap<PathBuf, f64>>()
+                .await
+        }
+    });
+
+    if files.read().is_empty() {
+        return rsx! {};
+    }
+    // 生成第 `index` 与 `index + 1` 个片段衔接处的预览并用系统播放器打开
+    let preview_junction = move |index: usize| {
+        spawn(async move {
+            let (Some(before), Some(after)) = (
+                files.peek().get(index).cloned(),
+                files.peek().get(index + 1).cloned(),
+            ) else {
+                return;
+            };
+            previewing.set(Some(index));
+            preview_error.set(None);
+            let options = merge_options.peek().clone();
+            match junction_preview(&before, &after, &options).await {
+                Ok(path) => {
+                    if let Err(e) = open_path(&path) {
+                        preview_error.set(Some(format!("无法打开预览: {}", e)));
+                    }
+                }
+                Err(e) => preview_error.set(Some(e.to_string())),
+            }
+            previewing.set(None);
+        });
+    };
+
+    let Some(durations) = durations() else {
+        return rsx! {
+            p { class: "mt-3 text-xs text-gray-500", "正在生成时间轴..." }
+        };
+    };
+    let options = merge_options.read().clone();
+    let raw = |path: &PathBuf| durations.get(path).copied().unwrap_or(0.0);
+    let clips: Vec<(usize, PathBuf, f64)> = files
+        .read()
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(index, file)| {
+            let duration = options.effective_duration(&file, raw(&file));
+            (index, file, duration)
+        })
+        .collect();
+    let intro = options.intro.as_ref().map(raw);
+    let outro = options.outro.as_ref().map(raw);
+    let card = options.active_title_cards().map(|c| c.duration);
+    let junctions = clips.len().saturating_sub(1);
+    let total = clips.iter().map(|(_, _, d)| d).sum::<f64>()
+        + intro.unwrap_or(0.0)
+        + outro.unwrap_or(0.0)
+        + card.unwrap_or(0.0) * clips.len() as f64;
+
+    rsx! {
+        div { class: "mt-3 space-y-1",
+            div { class: "flex justify-between text-xs text-gray-400",
+                span { "时间轴（拖动片段调整顺序）" }
+                span { "成片约 {format_duration(total)}" }
+            }
+            div { class: "flex h-14 gap-px rounded overflow-hidden select-none",
+                if let Some(duration) = intro {
+                    FixedBlock { label: "片头", duration }
+                }
+                for (index , file , duration) in clips {
+                    if let Some(duration) = card {
+                        FixedBlock { label: "标题", duration }
+                    }
+                    div {
+                        class: if drop_target() == Some(index) && dragging() != Some(index) { "flex flex-col justify-center min-w-8 px-1 overflow-hidden text-xs cursor-grab bg-blue-400 text-white ring-2 ring-inset ring-white" } else if selected() == Some(index) { "flex flex-col justify-center min-w-8 px-1 overflow-hidden text-xs cursor-grab bg-blue-600 text-white" } else { "flex flex-col justify-center min-w-8 px-1 overflow-hidden text-xs cursor-grab bg-gray-600 text-gray-100 hover:bg-gray-500" },
+                        style: "flex: {duration.max(0.5)} 1 0%",
+                        title: "{file.display()}",
+                        draggable: true,
+                        role: "button",
+                        tabindex: 0,
+                        aria_pressed: selected() == Some(index),
+                        onclick: move |_| selected.set(Some(index)),
+                        onkeydown: move |e: KeyboardEvent| {
+                            if is_activation_key(&e) {
+                                e.prevent_default();
+                                selected.set(Some(index));
+                            }
+                        },
+                        ondragstart: move |_| dragging.set(Some(index)),
+                        ondragend: move |_| {
+                            dragging.set(None);
+                            drop_target.set(None);
+                        },
+                        ondragover: move |e| {
+                            // 只接受时间轴内部的拖动，文件库拖入的文件交给合并列表处理
+                            if dragging.peek().is_some() {
+                                e.prevent_default();
+                                drop_target.set(Some(index));
+                            }
+                        },
+                        ondrop: move |e| {
+                            e.prevent_default();
+                            drop_target.set(None);
+                            if let Some(from) = dragging.take()
+                                && from != index
+                            {
+                                let mut list = files.write();
+                                let moved = list.remove(from);
+                                list.insert(index, moved);
+                                selected.set(Some(index));
+                            }
+                        },
+                        span { class: "truncate font-medium",
+                            {file.file_stem().unwrap_or_default().to_string_lossy().into_owned()}
+                        }
+                        span { class: "truncate opacity-80",
+                            {format_duration(duration)}
+                            if !options.cuts_of(&file).is_empty() {
+                                {format!(" · 已剪 {} 处", options.cuts_of(&file).len())}
+                            }
+                        }
+                    }
+                }
+                if let Some(duration) = outro {
+                    FixedBlock { label: "片尾", duration }
+                }
+            }
+            if junctions > 0 {
+                div { class: "flex flex-wrap items-center gap-x-2 gap-y-1 text-xs",
+                    span {
+                        class: "text-gray-400",
+                        title: format!("连续播放前一片段最后 {JUNCTION_PREVIEW_SECS} 秒和后一片段开头 {JUNCTION_PREVIEW_SECS} 秒"),
+                        "预览衔接"
+                    }
+                    for index in 0..junctions {
+                        button {
+                            class: "px-1.5 rounded bg-gray-700 text-gray-300 hover:bg-gray-600 disabled:opacity-50",
+                            disabled: previewing().is_some(),
+                            onclick: move |_| preview_junction(index),
+                            if previewing() == Some(index) {
+                                "生成中..."
+                            } else {
+                                "{index + 1}→{index + 2}"
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(error) = preview_error() {
+                p { class: "text-xs text-red-400 break-all", "{error}" }
+            }
+        }
+    }
+}
+
+// 片头、片尾与标题卡，位置固定，不参与拖动
+#[component]
+fn FixedBlock(label: &'static str, duration: f64) -> Element {
+    rsx! {
+        div {
+            class: "flex flex-col justify-center min-w-6 px-1 overflow-hidden text-xs bg-gray-800 text-gray-400",
+            style: "flex: {duration.max(0.5)} 1 0%",
+            title: "{label} {format_duration(duration)}",
+            span { class: "truncate", "{label}" }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::MergeEvent;
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::Button;
+use crate::components::mp4_info::Mp4FileInfo;
+use crate::components::progress::{Progress, ProgressIndicator};
+use crate::components::savings_report::SavingsReport;
+use crate::ffmpeg::encoder::EncoderPreset;
+use crate::ffmpeg::transcode::{
+    TranscodeCodec, TranscodeOptions, run_transcode, transcode_output_path,
+};
+use crate::history::{HistoryEntry, JobKind, SizeComparison, record_history};
+use crate::utils::recycle_files;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use std::path::PathBuf;
+
+const RESOLUTIONS: [(Option<u32>, &str); 4] = [
+    (None, "保持原分辨率"),
+    (Some(1080), "不超过 1080p"),
+    (Some(720), "不超过 720p"),
+    (Some(480), "不超过 480p"),
+];
+
+// 批量转码对话框，所有文件共用同一组设置并依次处理，targets 为空时关闭。
+// 原文件移到回收站后通过 on_recycled 通知列表移除
+#[component]
+pub fn TranscodeDialog(
+    targets: Signal<Vec<Mp4FileInfo>>,
+    on_recycled: EventHandler<Vec<PathBuf>>,
+) -> Element {
+    use_restore_focus(move || !targets.read().is_empty());
+    let mut options: Signal<TranscodeOptions> = use_signal(TranscodeOptions::default);
+    // 正在处理第几个文件
+    let mut current: Signal<Option<usize>> = use_signal(|| None);
+    let mut file_progress: Signal<f64> = use_signal(|| 0.0);
+    let mut finished: Signal<usize> = use_signal(|| 0);
+    let mut is_running: Signal<bool> = use_signal(|| false);
+    // 每个文件的结果
+    let mut results: Signal<Vec<String>> = use_signal(Vec::new);
+    // 成功转码的文件前后大小，完成后展示并写入历史记录
+    let mut sizes: Signal<Vec<SizeComparison>> = use_signal(Vec::new);
+
+    let start_transcode = move |_| async move {
+        let files = targets();
+        let options = options();
+        is_running.set(true);
+        finished.set(0);
+        results.set(Vec::new());
+        sizes.set(Vec::new());
+        let mut recycled = Vec::new();
+
+        for (index, info) in files.iter().enumerate() {
+            current.set(Some(index));
+            file_progress.set(0.0);
+            let output_path = transcode_output_path(&info.file_path, options.codec);
+            let mut outcome: Result<String, String> = Err("未完成".to_string());
+            let (tx, mut rx) = futures::channel::mpsc::unbounded::<MergeEvent>();
+            let transcode = run_transcode(info.file_path.clone(), output_path, options, tx);
+            let updates = async {
+                while let Some(event) = rx.next().await {
+                    match event {
+                        MergeEvent::Progress(p) => file_progress.set(p.percent),
+                        MergeEvent::Status(_)
+                        | MergeEvent::Segments(_)
+                        | MergeEvent::Probing { .. } => {}
+                        MergeEvent::Error(e) => outcome = Err(e.to_string()),
+                        MergeEvent::Success(path) => outcome = Ok(path),
+                    }
+                }
+            };
+            futures::join!(transcode, updates);
+
+            // 原文件可能随后移到回收站，先记录大小
+            if let Ok(path) = &outcome
+                && let Ok(size) =
+                    SizeComparison::new(info.file_path.clone(), info.size, PathBuf::from(path))
+            {
+                sizes.write().push(size);
+            }
+            let line = match outcome {
+                Ok(path) if options.recycle_originals => {
+                    match recycle_files(std::slice::from_ref(&info.file_path)).await {
+                        Ok(()) => {
+                            recycled.push(info.file_path.clone());
+                            format!("{}：已保存到 {}，原文件已移到回收站", info.file_name, path)
+                        }
+                        Err(e) => format!("{}：已保存到 {}，但{}", info.file_name, path, e),
+                    }
+                }
+                Ok(path) => format!("{}：已保存到 {}", info.file_name, path),
+                Err(e) => format!("{}：转码失败: {}", info.file_name, e),
+            };
+            results.write().push(line);
+            *finished.write() += 1;
+        }
+
+        current.set(None);
+        is_running.set(false);
+        if !sizes.read().is_empty() {
+            record_history(HistoryEntry::size_report(JobKind::Transcode, sizes()));
+        }
+        if !recycled.is_empty() {
+            on_recycled.call(recycled);
+        }
+    };
+
+    let total = targets.read().len();
+    let overall = if total == 0 {
+        0.0
+    } else {
+        let running = if current().is_some() {
+            file_progress() / 100.0
+        } else {
+            0.0
+        };
+        (finished() as f64 + running) / total as f64 * 100.0
+    };
+    let current_name = current()
+        .and_then(|i| targets.read().get(i).map(|info| info.file_name.clone()))
+        .unwrap_or_default();
+    let codec = options.read().codec;
+
+    rsx! {
+        AlertDialogRoot {
+            open: total > 0,
+            on_open_change: move |v: bool| {
+                if !v && !is_running() {
+                    targets.set(Vec::new());
+                    results.set(Vec::new());
+                    sizes.set(Vec::new());
+                    finished.set(0);
+                    file_progress.set(0.0);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "批量转码" }
+                AlertDialogDescription { "共 {total} 个文件，使用相同的设置依次处理" }
+                div { class: "space-y-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "编码" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            disabled: is_running(),
+                            onchange: move |evt| {
+                                let codec = if evt.value() == "h265" { TranscodeCodec::H265 } else { TranscodeCodec::H264 };
+                                let mut options = options.write();
+                                options.codec = codec;
+                                options.crf = codec.default_crf();
+                            },
+                            for c in TranscodeCodec::ALL {
+                                option {
+                                    value: if c == TranscodeCodec::H265 { "h265" } else { "h264" },
+                                    selected: codec == c,
+                                    "{c.label()}"
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "CRF" }
+                        input {
+                            r#type: "number",
+                            class: "w-20 px-2 py-1 border rounded text-sm",
+                            min: "0",
+                            max: "51",
+                            disabled: is_running(),
+                            value: "{options.read().crf}",
+                            onchange: move |e| {
+                                if let Ok(v) = e.value().parse::<u8>()
+                                    && v <= 51
+                                {
+                                    options.write().crf = v;
+                                }
+                            },
+                        }
+                        span { class: "text-gray-500", "越小画质越高，默认 {codec.default_crf()}" }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "速度" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            disabled: is_running(),
+                            onchange: move |evt| {
+                                if let Some(preset) = EncoderPreset::ALL
+                                    .into_iter()
+                                    .find(|p| p.as_str() == evt.value())
+                                {
+                                    options.write().preset = preset;
+                                }
+                            },
+                            for preset in EncoderPreset::ALL {
+                                option {
+                                    value: preset.as_str(),
+                                    selected: options.read().preset == preset,
+                                    "{preset.label()}"
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
+                        span { class: "w-20 text-gray-500", "分辨率" }
+                        select {
+                            class: "border rounded px-2 py-1 text-sm",
+                            disabled: is_running(),
+                            onchange: move |evt| options.write().max_height = evt.value().parse().ok(),
+                            for (height , label) in RESOLUTIONS {
+                                option {
+                                    value: height.map(|h| h.to_string()).unwrap_or_default(),
+                                    selected: options.read().max_height == height,
+                                    "{label}"
+                                }
+                            }
+                        }
+                    }
+                    label { class: "flex items-center gap-2",
+                        input {
+                            r#type: "checkbox",
+                            disabled: is_running(),
+                            checked: options.read().recycle_originals,
+                            onchange: move |e| options.write().recycle_originals = e.checked(),
+                        }
+                        "成功后将原文件移到回收站"
+                    }
+                    p { class: "text-gray-500",
+                        "结果保存为同目录下的 *_h264.mp4 / *_h265.mp4，音频直接复制"
+                    }
+                    if is_running() || finished() > 0 {
+                        if is_running() {
+                            p { class: "text-gray-600 truncate",
+                                "正在处理 {finished() + 1}/{total}：{current_name}"
+                            }
+                            Progress {
+                                aria_label: "当前文件进度",
+                                value: file_progress(),
+                                ProgressIndicator {}
+                            }
+                        }
+                        p { class: "text-gray-600", "总进度 {finished()}/{total}" }
+                        Progress { aria_label: "总进度", value: overall, ProgressIndicator {} }
+                    }
+                    if !is_running() && !sizes.read().is_empty() {
+                        SavingsReport { sizes: sizes() }
+                    }
+                    if !results.read().is_empty() {
+                        div { class: "max-h-40 overflow-auto space-y-1",
+                            for line in results.read().iter() {
+                                p { class: "text-gray-600 break-all", "{line}" }
+                            }
+                        }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "关闭" }
+                    Button { disabled: is_running(), onclick: start_transcode,
+                        if is_running() {
+                            "处理中..."
+                        } else {
+                            "开始"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
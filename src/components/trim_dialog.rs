@@ -0,0 +1,304 @@
+use crate::a11y::use_restore_focus;
+use crate::components::alert_dialog::{
+    AlertDialogActions, AlertDialogCancel, AlertDialogContent, AlertDialogDescription,
+    AlertDialogRoot, AlertDialogTitle,
+};
+use crate::components::button::{Button, ButtonVariant};
+use crate::components::input::Input;
+use crate::ffmpeg::dead_air::{CutRange, DEFAULT_MIN_DEAD_AIR_SECS, DeadAir, detect_dead_air};
+use crate::ffmpeg::options::MergeOptions;
+use crate::ffmpeg::probe::probe_duration_cached;
+use crate::ffmpeg::proxy::preview_source;
+use crate::ffmpeg::waveform::waveform_peaks;
+use crate::utils::parse_timestamp;
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+// 波形区域的宽高（像素），每根竖线占 2 像素
+const WAVEFORM_WIDTH: f64 = 480.0;
+const WAVEFORM_HEIGHT: f64 = 64.0;
+
+// 片段剪切编辑：手动添加要剪掉的区间，或分析静音/黑场后按建议剪掉，target 为 None 时关闭
+#[component]
+pub fn TrimDialog(target: Signal<Option<PathBuf>>, merge_options: Signal<MergeOptions>) -> Element {
+    use_restore_focus(move || target.read().is_some());
+    let mut start_input: Signal<String> = use_signal(String::new);
+    let mut end_input: Signal<String> = use_signal(String::new);
+    let mut min_secs_input: Signal<String> = use_signal(|| DEFAULT_MIN_DEAD_AIR_SECS.to_string());
+    let mut suggestions: Signal<Option<Vec<DeadAir>>> = use_signal(|| None);
+    let mut is_analyzing: Signal<bool> = use_signal(|| false);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    // 打开时读取时长和波形，有代理时从代理解码
+    let waveform = use_resource(move || {
+        let file = target();
+        async move {
+            let source = preview_source(&file?);
+            let duration = probe_duration_cached(&source).await;
+            let peaks = waveform_peaks(&source, (WAVEFORM_WIDTH / 2.0) as usize).await;
+            Some(match (duration, peaks) {
+                (Ok(duration), Ok(peaks)) => Ok((duration, peaks)),
+                (Err(e), _) | (_, Err(e)) => Err(e.to_string()),
+            })
+        }
+    });
+
+    let mut add_cuts = move |ranges: Vec<CutRange>| {
+        let Some(file) = target() else {
+            return;
+        };
+        let mut cuts = merge_options.peek().cuts_of(&file).to_vec();
+        for range in ranges {
+            if !cuts.contains(&range) {
+                cuts.push(range);
+            }
+        }
+        cuts.sort_by(|a, b| a.start.total_cmp(&b.start));
+        merge_options.write().set_cuts(file, cuts);
+    };
+
+    let add_manual = move |_| {
+        let start = parse_timestamp(&start_input()).unwrap_or(0.0);
+        match parse_timestamp(&end_input()) {
+            Some(end) if end > start => {
+                add_cuts(vec![CutRange { start, end }]);
+                start_input.set(String::new());
+                end_input.set(String::new());
+                message.set(None);
+            }
+            _ => message.set(Some("结束时间必须晚于开始时间".to_string())),
+        }
+    };
+
+    let analyze = move |_| async move {
+        let Some(file) = target() else {
+            return;
+        };
+        let min_secs = min_secs_input()
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|v| *v > 0.0)
+            .unwrap_or(DEFAULT_MIN_DEAD_AIR_SECS);
+        is_analyzing.set(true);
+        message.set(None);
+        // 有代理时分析代理，解码快得多，检测到的时间与原文件一致
+        match detect_dead_air(&preview_source(&file), min_secs).await {
+            Ok(found) => suggestions.set(Some(found)),
+            Err(e) => message.set(Some(format!("分析失败: {}", e))),
+        }
+        is_analyzing.set(false);
+    };
+
+    let Some(file) = target() else {
+        return rsx! {};
+    };
+    let cuts = merge_options.read().cuts_of(&file).to_vec();
+    let pending: Vec<CutRange> = suggestions
+        .read()
+        .iter()
+        .flatten()
+        .map(|s| s.range)
+        .filter(|r| !cuts.contains(r))
+        .collect();
+    let pending_count = pending.len();
+
+    rsx! {
+        AlertDialogRoot {
+            open: true,
+            on_open_change: move |v: bool| {
+                if !v && !is_analyzing() {
+                    target.set(None);
+                    suggestions.set(None);
+                    message.set(None);
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "剪切片段" }
+                AlertDialogDescription {
+                    {file.file_name().unwrap_or_default().to_string_lossy().into_owned()}
+                }
+                div { class: "space-y-3 text-sm",
+                    match waveform().flatten() {
+                        None => rsx! {
+                            p { class: "text-gray-400", "正在读取波形..." }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-red-600 break-all", "无法读取波形: {e}" }
+                        },
+                        Some(Ok((_, peaks))) if peaks.is_empty() => rsx! {
+                            p { class: "text-gray-400", "该片段没有音轨" }
+                        },
+                        Some(Ok((duration, peaks))) => rsx! {
+                            div { class: "space-y-1",
+                                div {
+                                    class: "relative bg-gray-900 rounded cursor-crosshair",
+                                    style: "width: {WAVEFORM_WIDTH}px; height: {WAVEFORM_HEIGHT}px",
+                                    role: "img",
+                                    aria_label: "音频波形，点击选取开始和结束时间，也可以在下方直接输入",
+                                    // 第一次点击填入开始时间，第二次填入结束时间
+                                    onclick: move |e: MouseEvent| {
+                                        let at = (e.element_coordinates().x / WAVEFORM_WIDTH * duration)
+                                            .clamp(0.0, duration);
+                                        if start_input.peek().trim().is_empty() || !end_input.peek().trim().is_empty() {
+                                            start_input.set(format_time(at));
+                                            end_input.set(String::new());
+                                        } else {
+                                            end_input.set(format_time(at));
+                                        }
+                                    },
+                                    svg {
+                                        class: "absolute inset-0 pointer-events-none",
+                                        width: "{WAVEFORM_WIDTH}",
+                                        height: "{WAVEFORM_HEIGHT}",
+                                        for (i , peak) in peaks.iter().copied().enumerate() {
+                                            rect {
+                                                x: "{i * 2}",
+                                                y: "{(1.0 - peak as f64) * WAVEFORM_HEIGHT / 2.0}",
+                                                width: "1",
+                                                height: "{(peak as f64 * WAVEFORM_HEIGHT).max(1.0)}",
+                                                fill: "#60a5fa",
+                                            }
+                                        }
+                                        for cut in cuts.iter() {
+                                            rect {
+                                                x: "{cut.start / duration * WAVEFORM_WIDTH}",
+                                                y: "0",
+                                                width: "{cut.duration() / duration * WAVEFORM_WIDTH}",
+                                                height: "{WAVEFORM_HEIGHT}",
+                                                fill: "#ef4444",
+                                                fill_opacity: "0.35",
+                                            }
+                                        }
+                                        for at in [parse_timestamp(&start_input()), parse_timestamp(&end_input())].into_iter().flatten() {
+                                            line {
+                                                x1: "{at / duration * WAVEFORM_WIDTH}",
+                                                x2: "{at / duration * WAVEFORM_WIDTH}",
+                                                y1: "0",
+                                                y2: "{WAVEFORM_HEIGHT}",
+                                                stroke: "#facc15",
+                                            }
+                                        }
+                                    }
+                                }
+                                p { class: "text-xs text-gray-500",
+                                    "点击波形设置开始/结束时间，红色为已剪掉的区间"
+                                }
+                            }
+                        },
+                    }
+                    div { class: "space-y-1",
+                        p { class: "text-gray-500", "要剪掉的区间" }
+                        if cuts.is_empty() {
+                            p { class: "text-gray-400", "未设置，合并时使用完整片段" }
+                        }
+                        for cut in cuts.iter().copied() {
+                            div { class: "flex items-center gap-2",
+                                span { class: "flex-1 font-mono", {describe_range(&cut)} }
+                                button {
+                                    class: "text-red-500 hover:underline",
+                                    onclick: {
+                                        let file = file.clone();
+                                        move |_| {
+                                            let mut cuts = merge_options.peek().cuts_of(&file).to_vec();
+                                            cuts.retain(|c| *c != cut);
+                                            merge_options.write().set_cuts(file.clone(), cuts);
+                                        }
+                                    },
+                                    "移除"
+                                }
+                            }
+                        }
+                        div { class: "flex items-center gap-2",
+                            Input {
+                                placeholder: "开始，如 0:05",
+                                aria_label: "开始时间",
+                                value: "{start_input}",
+                                oninput: move |e: FormEvent| start_input.set(e.value()),
+                            }
+                            Input {
+                                placeholder: "结束，如 0:12.5",
+                                aria_label: "结束时间",
+                                value: "{end_input}",
+                                oninput: move |e: FormEvent| end_input.set(e.value()),
+                            }
+                            Button { variant: ButtonVariant::Secondary, onclick: add_manual, "添加" }
+                        }
+                    }
+                    div { class: "space-y-1",
+                        div { class: "flex items-center gap-2",
+                            span { class: "text-gray-500", "静音/黑场至少" }
+                            input {
+                                r#type: "number",
+                                class: "w-16 px-2 py-1 border rounded text-sm",
+                                min: "0.5",
+                                step: "0.5",
+                                aria_label: "静音/黑场最短时长（秒）",
+                                value: "{min_secs_input}",
+                                oninput: move |e| min_secs_input.set(e.value()),
+                            }
+                            span { class: "text-gray-500", "秒" }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                disabled: is_analyzing(),
+                                onclick: analyze,
+                                if is_analyzing() {
+                                    "正在分析..."
+                                } else {
+                                    "分析静音/黑场"
+                                }
+                            }
+                        }
+                        if let Some(found) = suggestions.read().as_ref() {
+                            if found.is_empty() {
+                                p { class: "text-green-600", "没有发现较长的静音或黑场" }
+                            } else {
+                                div { class: "max-h-48 overflow-auto border rounded divide-y",
+                                    for suggestion in found.iter().copied() {
+                                        div { class: "flex items-center gap-2 px-2 py-1",
+                                            span { class: "w-10 text-amber-600", {suggestion.kind.label()} }
+                                            span { class: "flex-1 font-mono", {describe_range(&suggestion.range)} }
+                                            if cuts.contains(&suggestion.range) {
+                                                span { class: "text-gray-400", "已剪掉" }
+                                            } else {
+                                                button {
+                                                    class: "text-blue-600 hover:underline",
+                                                    onclick: move |_| add_cuts(vec![suggestion.range]),
+                                                    "剪掉"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(message) = message() {
+                        p { class: "text-red-600 break-all", "{message}" }
+                    }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "完成" }
+                    if pending_count > 0 {
+                        Button { onclick: move |_| add_cuts(pending.clone()), "全部剪掉 ({pending_count})" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 如 `00:12.5 – 00:20.0（7.5 秒）`
+fn describe_range(range: &CutRange) -> String {
+    format!(
+        "{} – {}（{:.1} 秒）",
+        format_time(range.start),
+        format_time(range.end),
+        range.duration()
+    )
+}
+
+fn format_time(secs: f64) -> String {
+    let minutes = (secs / 60.0).floor();
+    format!("{:02}:{:04.1}", minutes as u32, secs - minutes * 60.0)
+}
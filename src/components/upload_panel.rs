@@ -0,0 +1,89 @@
+use super::button::{Button, ButtonVariant};
+use super::progress::{Progress, ProgressIndicator};
+use crate::clipboard::copy_text;
+use crate::upload::{UploadStatus, use_uploader};
+use crate::utils::{display_name, format_bytes};
+use dioxus::prelude::*;
+
+// 合并输出的上传进度，失败的可重试，完成的可再次复制链接
+#[component]
+pub fn UploadPanel() -> Element {
+    let uploader = use_uploader();
+    let mut copy_error: Signal<Option<String>> = use_signal(|| None);
+
+    if uploader.tasks.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "space-y-2",
+            h3 { class: "text-sm font-semibold text-gray-400", "上传" }
+            for task in uploader.tasks.read().iter().cloned() {
+                div { key: "{task.id}", class: "py-2 px-3 rounded-lg border border-gray-600 space-y-2 text-sm",
+                    div { class: "flex items-center justify-between gap-3",
+                        p { class: "font-semibold truncate", title: "{task.path.display()}",
+                            "{display_name(&task.path)}"
+                        }
+                        match &task.status {
+                            UploadStatus::Running | UploadStatus::Retrying(_) => rsx! {
+                                span { class: "text-purple-400 font-mono shrink-0",
+                                    "{format_bytes(task.sent)} / {format_bytes(task.total)}"
+                                }
+                            },
+                            UploadStatus::Done(link) => rsx! {
+                                div { class: "flex items-center gap-2 shrink-0",
+                                    span { class: "text-green-500", "已上传" }
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: {
+                                            let link = link.clone();
+                                            move |_| copy_error.set(copy_text(&link).err())
+                                        },
+                                        "复制链接"
+                                    }
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: move |_| uploader.dismiss(task.id),
+                                        "移除"
+                                    }
+                                }
+                            },
+                            UploadStatus::Failed(_) => rsx! {
+                                div { class: "flex items-center gap-2 shrink-0",
+                                    Button {
+                                        variant: ButtonVariant::Secondary,
+                                        onclick: move |_| uploader.retry(task.id),
+                                        "重试"
+                                    }
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: move |_| uploader.dismiss(task.id),
+                                        "移除"
+                                    }
+                                }
+                            },
+                        }
+                    }
+                    if matches!(task.status, UploadStatus::Running | UploadStatus::Retrying(_)) {
+                        Progress { aria_label: "上传进度", value: task.percent(), ProgressIndicator {} }
+                    }
+                    match &task.status {
+                        UploadStatus::Retrying(message) => rsx! {
+                            p { class: "text-xs text-amber-400 break-all", "{message}" }
+                        },
+                        UploadStatus::Done(link) => rsx! {
+                            p { class: "text-xs text-gray-400 break-all select-text", "{link}" }
+                        },
+                        UploadStatus::Failed(e) => rsx! {
+                            p { class: "text-xs text-red-400 break-all", "{e}" }
+                        },
+                        UploadStatus::Running => rsx! {},
+                    }
+                }
+            }
+            if let Some(e) = copy_error() {
+                p { class: "text-xs text-red-400", "{e}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::config::{AppConfig, UploadKind, UploadSettings};
+use dioxus::prelude::*;
+
+// 合并成功后自动上传的目标：WebDAV 目录或 S3 兼容存储桶
+#[component]
+pub fn UploadSettingsView(config: Signal<AppConfig>) -> Element {
+    let error_message: Signal<Option<String>> = use_signal(|| None);
+    let upload = config.read().upload.clone();
+    let s3 = upload.kind == UploadKind::S3;
+
+    rsx! {
+        div { class: "space-y-1 text-sm",
+            h3 { class: "font-semibold", "上传" }
+            label { class: "flex items-center gap-2",
+                input {
+                    r#type: "checkbox",
+                    checked: upload.enabled,
+                    onchange: move |e| update_upload(config, error_message, |u| u.enabled = e.checked()),
+                }
+                "合并成功后上传输出文件到"
+                select {
+                    class: "border rounded px-2 py-1 text-sm",
+                    onchange: move |e| {
+                        if let Some(kind) = UploadKind::ALL.into_iter().find(|k| format!("{:?}", k) == e.value()) {
+                            update_upload(config, error_message, |u| u.kind = kind);
+                        }
+                    },
+                    for kind in UploadKind::ALL {
+                        option { value: "{kind:?}", selected: upload.kind == kind, {kind.label()} }
+                    }
+                }
+            }
+            if upload.enabled {
+                div { class: "grid grid-cols-[auto_1fr] items-center gap-x-2 gap-y-1 max-w-xl",
+                    span { if s3 { "服务地址" } else { "上传目录" } }
+                    input {
+                        class: "border rounded px-1 text-sm font-mono",
+                        placeholder: if s3 { "https://s3.us-east-1.amazonaws.com" } else { "https://dav.example.com/videos" },
+                        value: "{upload.url}",
+                        onchange: move |e| update_upload(config, error_message, |u| u.url = e.value().trim().to_string()),
+                    }
+                    if s3 {
+                        span { "存储桶" }
+                        input {
+                            class: "border rounded px-1 text-sm font-mono",
+                            value: "{upload.bucket}",
+                            onchange: move |e| update_upload(config, error_message, |u| u.bucket = e.value().trim().to_string()),
+                        }
+                        span { "区域" }
+                        input {
+                            class: "border rounded px-1 text-sm font-mono",
+                            placeholder: "us-east-1",
+                            value: "{upload.region}",
+                            onchange: move |e| update_upload(config, error_message, |u| u.region = e.value().trim().to_string()),
+                        }
+                    }
+                    span { if s3 { "Access Key" } else { "用户名" } }
+                    input {
+                        class: "border rounded px-1 text-sm font-mono",
+                        value: "{upload.username}",
+                        onchange: move |e| update_upload(config, error_message, |u| u.username = e.value().trim().to_string()),
+                    }
+                    span { if s3 { "Secret Key" } else { "密码" } }
+                    input {
+                        r#type: "password",
+                        class: "border rounded px-1 text-sm font-mono",
+                        value: "{upload.password}",
+                        onchange: move |e| update_upload(config, error_message, |u| u.password = e.value()),
+                    }
+                    span { "访问地址" }
+                    input {
+                        class: "border rounded px-1 text-sm font-mono",
+                        placeholder: "可选，如 https://cdn.example.com/videos",
+                        value: "{upload.public_url}",
+                        onchange: move |e| update_upload(config, error_message, |u| u.public_url = e.value().trim().to_string()),
+                    }
+                }
+                label { class: "flex items-center gap-2",
+                    input {
+                        r#type: "checkbox",
+                        checked: upload.copy_link,
+                        onchange: move |e| update_upload(config, error_message, |u| u.copy_link = e.checked()),
+                    }
+                    "上传完成后复制链接到剪贴板"
+                }
+                p { class: "text-xs text-gray-500",
+                    "文件以原文件名上传，已存在时覆盖；链接为访问地址加文件名，未填写访问地址时使用上传地址。"
+                    "网络中断或服务器繁忙时按上方的自动重试次数重试，进度显示在合并页与队列页"
+                }
+            }
+            if let Some(e) = error_message() {
+                p { class: "text-xs text-red-400", "{e}" }
+            }
+        }
+    }
+}
+
+// 修改上传设置并保存
+fn update_upload(
+    mut config: Signal<AppConfig>,
+    mut error_message: Signal<Option<String>>,
+    update: impl FnOnce(&mut UploadSettings),
+) {
+    update(&mut config.write().upload);
+    if let Err(e) = config.read().save() {
+        error_message.set(Some(format!("无法保存设置: {}", e)));
+    }
+}
@@ -0,0 +1,16 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+/// 正在从文件库拖动的文件。webview 的拖放数据只能放文本，路径改放在共享状态里，
+/// 松开时由放置目标取走
+pub type DragPayload = Signal<Option<Vec<PathBuf>>>;
+
+/// 提供 [`DragPayload`] 上下文，应在根组件调用一次
+pub fn use_drag_provider() -> DragPayload {
+    use_context_provider(|| Signal::new(None))
+}
+
+/// 获取拖动中的文件
+pub fn use_drag_payload() -> DragPayload {
+    use_context::<DragPayload>()
+}
@@ -0,0 +1,56 @@
+use crate::config::AppConfig;
+use crate::ffmpeg::merge_mp4::RUNNING_MERGES;
+use crate::queue::{JobQueue, JobStatus};
+use crate::session::clear_session;
+use dioxus::prelude::*;
+use dioxus_desktop::WindowCloseBehaviour;
+use dioxus_desktop::tao::event::{Event, WindowEvent};
+use std::sync::atomic::Ordering;
+
+/// 有任务执行时关闭窗口弹出的询问，在 `Index` 中通过 context 提供
+pub type ExitPrompt = Signal<bool>;
+
+/// 获取退出询问
+pub fn use_exit_prompt() -> ExitPrompt {
+    use_context::<ExitPrompt>()
+}
+
+/// 是否有合并正在进行（合并页或队列）
+pub fn jobs_running(queue: JobQueue) -> bool {
+    RUNNING_MERGES.load(Ordering::SeqCst) > 0
+        || queue.peek().iter().any(|j| j.status == JobStatus::Running)
+}
+
+/// 提供 [`ExitPrompt`]：有任务执行时关闭窗口只隐藏（见托盘），未开启“最小化到托盘”时
+/// 重新显示窗口并询问如何处理，避免直接结束 FFmpeg 留下损坏的输出。应在根组件调用一次
+pub fn use_exit_guard(config: Signal<AppConfig>, queue: JobQueue) -> ExitPrompt {
+    let mut prompt = use_context_provider(|| Signal::new(false));
+
+    dioxus_desktop::use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } = event
+            && jobs_running(queue)
+            && !config.peek().minimize_to_tray
+        {
+            prompt.set(true);
+            // 关闭请求处理完后窗口才被隐藏，稍后再显示
+            spawn(async move {
+                let desktop = dioxus_desktop::window();
+                desktop.window.set_visible(true);
+                desktop.window.set_focus();
+            });
+        }
+    });
+
+    prompt
+}
+
+/// 真正退出程序
+pub fn exit_app() {
+    clear_session();
+    let desktop = dioxus_desktop::window();
+    desktop.set_close_behavior(WindowCloseBehaviour::LastWindowExitsApp);
+    desktop.close();
+}
@@ -1 +0,0 @@
-pub mod merge_mp4;
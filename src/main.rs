@@ -3,30 +3,72 @@ use dioxus_desktop::launch::launch_virtual_dom;
 use dioxus_desktop::tao::event_loop::EventLoop;
 use dioxus_desktop::{Config, tao::window::WindowBuilder};
 use dioxus_desktop::{LogicalPosition, LogicalSize};
+mod a11y;
+mod api;
+mod clipboard;
 mod components;
-mod config;
-mod ffmpeg;
+mod drag;
+mod exit_guard;
+mod notifications;
+mod playlist;
+mod project;
+mod queue;
+mod rename;
+mod session;
+mod shortcuts;
+mod tray;
+mod upload;
 mod utils;
-use crate::components::mp4_merger::Mp4Merger;
+mod window_state;
+use crate::clipboard::use_paste_listener;
+use crate::components::merger_workspaces::MergerWorkspaces;
 use crate::components::tabs::*;
 use crate::config::AppConfig;
+use crate::drag::use_drag_provider;
+use crate::exit_guard::use_exit_guard;
+use crate::notifications::use_notifications_provider;
+use crate::queue::{JobQueue, MergeJob, use_queue_scheduler};
+use crate::rename::use_rename_provider;
+use crate::session::use_session_autosave;
+use crate::shortcuts::use_shortcut_listener;
+use crate::upload::use_uploader_provider;
+use crate::utils::set_size_units;
+use crate::window_state::use_window_state;
 use components::about_footer::AboutFooter;
+use components::confirm_dialog::{ConfirmDialog, ConfirmRequests};
+use components::crash_report_dialog::CrashReportDialog;
+use components::error_dialog::{ErrorDialog, ErrorReports};
+use components::exit_guard_dialog::ExitGuardDialog;
+use components::history_view::HistoryView;
+use components::job_queue::JobQueueView;
 use components::mp4_info::Mp4Info;
+use components::notification_center::{NotificationCenter, NotificationToasts};
+use components::power_countdown::PowerCountdown;
+use components::session_restore_dialog::SessionRestoreDialog;
+use components::settings_view::SettingsView;
+use components::setup_wizard::SetupWizard;
 use components::toast::ToastProvider;
+use merge_mp4_core::{MergeEvent, config, error, ffmpeg, history, post_command, post_process};
 const FAVICON: Asset = asset!("/assets/favicon.ico");
 const MAIN_CSS: Asset = asset!("/assets/main.css");
 const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
+// 各标签页的值，与 `TabTrigger` 一致
+const TABS: [&str; 5] = ["tab1", "tab2", "tab3", "tab5", "tab4"];
 
-#[derive(Clone, Debug)]
-enum MergeEvent {
-    Progress(f64),
-    Status(String),
-    Error(String),
-    Success(String),
-}
 fn main() {
-    let window_width = 900.0;
-    let window_height = 700.0;
+    utils::install_panic_hook();
+    ffmpeg::workspace::clean_stale_workspaces();
+    ffmpeg::checkpoint::clean_stale_checkpoints();
+    let saved_config = AppConfig::load().ok();
+    // 向导中指定过 FFmpeg 目录时加入 PATH
+    if let Some(dir) = saved_config
+        .as_ref()
+        .and_then(|config| config.ffmpeg_dir.as_ref())
+    {
+        ffmpeg::locate::add_ffmpeg_dir(dir);
+    }
+    let window_settings = saved_config.map(|config| config.window).unwrap_or_default();
+
     let event_loop = EventLoop::new();
     let monitor = event_loop.primary_monitor().unwrap();
     let monitor_size = monitor.size();
@@ -34,6 +76,9 @@ fn main() {
     // 🔥 核心：把显示器物理尺寸转成逻辑尺寸
     let monitor_width_logical = monitor_size.width as f64 / scale_factor;
     let monitor_height_logical = monitor_size.height as f64 / scale_factor;
+    // 125%/150% 缩放的小屏上默认大小可能超出屏幕
+    let (window_width, window_height) =
+        window_settings.initial_size(monitor_width_logical, monitor_height_logical);
 
     // 计算居中（现在都是逻辑像素）
     let x = (monitor_width_logical - window_width) / 2.0;
@@ -41,9 +86,14 @@ fn main() {
 
     // println!("当前显示器尺寸: {:?}", size);
     let window_builder = WindowBuilder::new()
-        .with_always_on_top(false) // 不放在最顶层
+        .with_always_on_top(window_settings.always_on_top)
+        .with_maximized(window_settings.start_maximized)
         .with_title("mp4文件合并")
         .with_inner_size(LogicalSize::new(window_width, window_height))
+        .with_min_inner_size(LogicalSize::new(
+            window_settings.min_width.min(window_width),
+            window_settings.min_height.min(window_height),
+        ))
         .with_position(LogicalPosition::new(x, y));
     let virtual_dom = VirtualDom::new(App);
     let platform_config = Config::new().with_window(window_builder);
@@ -71,7 +121,7 @@ fn Layout() -> Element {
     }
     rsx! {
         main { class: "h-screen flex flex-col",
-            div { class: "flex-1", Outlet::<Route> {} }
+            div { class: "flex-1 min-h-0", Outlet::<Route> {} }
             AboutFooter { author: "{author}", version: "{version}" }
 
         }
@@ -79,28 +129,102 @@ fn Layout() -> Element {
 }
 #[component]
 fn Index() -> Element {
-    let config: Signal<AppConfig> = use_signal(|| {
-        AppConfig::load().unwrap_or_else(|e| {
+    let mut config: Signal<AppConfig> = use_signal(|| {
+        let config = AppConfig::load().unwrap_or_else(|e| {
             eprintln!("Failed to load config: {}", e);
             AppConfig::default()
-        })
+        });
+        set_size_units(config.size_units);
+        config
+    });
+
+    // 全局任务队列，合并页与文件库共用
+    let job_queue: JobQueue = use_signal(Vec::<MergeJob>::new);
+    use_context_provider(|| job_queue);
+    use_session_autosave(job_queue);
+    // 带操作按钮的提示条与通知中心
+    let notifications = use_notifications_provider();
+    // 合并成功后上传到 WebDAV/S3，合并页与队列共用
+    let uploader = use_uploader_provider(config, notifications);
+    let queue_runner = use_queue_scheduler(config, job_queue, uploader);
+    api::use_api_server(config, job_queue);
+    // 常见错误统一用带解决建议的对话框显示
+    let error_reports: ErrorReports = use_signal(|| None);
+    use_context_provider(|| error_reports);
+    // 需要用户确认的操作统一用同一个对话框询问
+    let confirm_requests: ConfirmRequests = use_signal(|| None);
+    use_context_provider(|| confirm_requests);
+    // 文件库中改名的文件同步到各合并列表
+    use_rename_provider();
+    // 从文件库拖到合并列表的文件
+    let drag_payload = use_drag_provider();
+
+    // 启动时回到上次所在的标签页，快捷键触发时切换到对应的标签页
+    let mut active_tab: Signal<Option<String>> = use_signal(|| {
+        let last = config.peek().last_tab.clone();
+        Some(
+            last.filter(|t| TABS.contains(&t.as_str()))
+                .unwrap_or_else(|| "tab1".to_string()),
+        )
+    });
+    let shortcut_events = use_shortcut_listener(config);
+    use_paste_listener();
+    tray::use_tray(job_queue);
+    use_exit_guard(config, job_queue);
+    use_window_state(config);
+    use_effect(move || {
+        if let Some(event) = shortcut_events() {
+            active_tab.set(Some(event.action.tab().to_string()));
+        }
+    });
+    use_effect(move || {
+        let tab = active_tab();
+        if tab.is_some() && config.peek().last_tab != tab {
+            config.write().last_tab = tab;
+            if let Err(e) = config.read().save() {
+                eprintln!("保存当前标签页失败: {}", e);
+            }
+        }
+    });
+
+    // 按设置切换浅色/深色主题
+    use_effect(move || {
+        let theme = config.read().theme;
+        document::eval(&format!(
+            "document.documentElement.dataset.theme = '{}';",
+            theme.as_str()
+        ));
     });
 
-    println!("config{:?}", config);
     rsx! {
 
         Tabs {
+            value: active_tab,
+            on_value_change: move |value: String| active_tab.set(Some(value)),
             default_value: "tab1".to_string(),
             horizontal: true,
             class: "h-full",
             TabList {
-                TabTrigger { value: "tab1".to_string(), index: 0usize, "合并" }
+                TabTrigger { value: "tab1".to_string(), index: 0usize,
+                    // 从文件库拖动文件经过时切换到合并页，便于放到列表中
+                    span {
+                        ondragenter: move |_| {
+                            if drag_payload.peek().is_some() {
+                                active_tab.set(Some("tab1".to_string()));
+                            }
+                        },
+                        "合并"
+                    }
+                }
                 TabTrigger { value: "tab2".to_string(), index: 1usize, "文件库" }
+                TabTrigger { value: "tab3".to_string(), index: 2usize, "队列" }
+                TabTrigger { value: "tab5".to_string(), index: 3usize, "历史" }
+                TabTrigger { value: "tab4".to_string(), index: 4usize, "设置" }
             }
             TabContent { index: 0usize, value: "tab1".to_string(), class: "flex-1 ",
 
                 ToastProvider {
-                    Mp4Merger { config }
+                    MergerWorkspaces { config }
                 }
 
             }
@@ -110,9 +234,37 @@ fn Index() -> Element {
                 value: "tab2".to_string(),
                 Mp4Info { config }
             }
+            TabContent {
+                index: 2usize,
+                class: "tabs-content flex-1",
+                value: "tab3".to_string(),
+                JobQueueView { config }
+            }
+            TabContent {
+                index: 3usize,
+                class: "tabs-content flex-1",
+                value: "tab5".to_string(),
+                HistoryView { config }
+            }
+            TabContent {
+                index: 4usize,
+                class: "tabs-content flex-1",
+                value: "tab4".to_string(),
+                SettingsView { config }
+            }
 
         }
 
+        CrashReportDialog {}
+        SessionRestoreDialog {}
+        ErrorDialog { config, active_tab }
+        ConfirmDialog {}
+        ExitGuardDialog {}
+        PowerCountdown { countdown: queue_runner.power_countdown, config }
+        NotificationToasts {}
+        NotificationCenter {}
+        SetupWizard { config }
+
         // 错误消息（固定在底部）
     }
 }
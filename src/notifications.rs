@@ -0,0 +1,132 @@
+use chrono::{DateTime, Local};
+use dioxus::prelude::*;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// 通知中心保留的条数，更早的丢弃
+const NOTIFICATION_CAPACITY: usize = 50;
+/// 带操作按钮的提示条显示时长
+const TOAST_DURATION: Duration = Duration::from_secs(8);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 通知级别，错误与警告会保留在通知中心
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationLevel {
+    Success,
+    Warning,
+    Error,
+}
+
+/// 通知上的操作按钮
+#[derive(Clone, PartialEq)]
+pub enum NotificationAction {
+    /// 用系统默认程序打开文件
+    OpenFile(PathBuf),
+    /// 重新执行失败的操作
+    Retry(Callback<()>),
+    /// 打开最近的日志
+    ShowLog,
+}
+
+impl NotificationAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationAction::OpenFile(_) => "打开文件",
+            NotificationAction::Retry(_) => "重试",
+            NotificationAction::ShowLog => "查看日志",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Notification {
+    pub id: u64,
+    pub level: NotificationLevel,
+    pub title: String,
+    pub message: String,
+    pub time: DateTime<Local>,
+    pub actions: Vec<NotificationAction>,
+    /// 仍以提示条显示，到时后只留在通知中心
+    pub toast: bool,
+}
+
+impl Notification {
+    fn new(level: NotificationLevel, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            id: 0,
+            level,
+            title: title.into(),
+            message: message.into(),
+            time: Local::now(),
+            actions: Vec::new(),
+            toast: true,
+        }
+    }
+
+    pub fn success(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Success, title, message)
+    }
+
+    pub fn warning(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Warning, title, message)
+    }
+
+    pub fn error(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Error, title, message)
+    }
+
+    pub fn action(mut self, action: NotificationAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// 只记入通知中心，不弹出提示条（例如已由对话框显示的错误）
+    pub fn silent(mut self) -> Self {
+        self.toast = false;
+        self
+    }
+}
+
+/// 最近的通知，新的在后
+pub type Notifications = Signal<Vec<Notification>>;
+
+/// 提供 [`Notifications`] 上下文，应在根组件调用一次
+pub fn use_notifications_provider() -> Notifications {
+    use_context_provider(|| Signal::new(Vec::new()))
+}
+
+/// 获取通知列表
+pub fn use_notifications() -> Notifications {
+    use_context::<Notifications>()
+}
+
+/// 发出通知；提示条到时后自动收起，错误与警告仍留在通知中心
+pub fn notify(mut notifications: Notifications, mut notification: Notification) {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    notification.id = id;
+    let toast = notification.toast;
+    {
+        let mut list = notifications.write();
+        list.push(notification);
+        let overflow = list.len().saturating_sub(NOTIFICATION_CAPACITY);
+        list.drain(..overflow);
+    }
+    if toast {
+        // 发出通知的组件可能先被卸载，计时挂在根作用域上
+        spawn_forever(async move {
+            tokio::time::sleep(TOAST_DURATION).await;
+            dismiss_toast(notifications, id);
+        });
+    }
+}
+
+/// 收起提示条，成功通知不进入通知中心，直接移除
+pub fn dismiss_toast(mut notifications: Notifications, id: u64) {
+    let mut list = notifications.write();
+    list.retain(|n| n.id != id || n.level != NotificationLevel::Success);
+    if let Some(n) = list.iter_mut().find(|n| n.id == id) {
+        n.toast = false;
+    }
+}
@@ -0,0 +1,124 @@
+use crate::clipboard::parse_path_line;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 可导入的列表文件扩展名
+pub const PLAYLIST_EXTENSIONS: [&str; 5] = ["m3u", "m3u8", "csv", "txt", "lst"];
+
+/// 列表中无法导入的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistError {
+    /// 从 1 开始的行号
+    pub line: usize,
+    pub message: String,
+}
+
+/// 导入结果：按列表顺序的有效文件与逐行错误
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlaylistImport {
+    pub files: Vec<PathBuf>,
+    pub errors: Vec<PlaylistError>,
+}
+
+impl PlaylistImport {
+    /// 错误说明，每行形如 "第 3 行：文件不存在 a.mp4"
+    pub fn error_report(&self) -> String {
+        self.errors
+            .iter()
+            .map(|e| format!("第 {} 行：{}", e.line, e.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 读取 M3U 播放列表或每行一个路径的文本/CSV 文件
+pub fn import_playlist(path: &Path) -> Result<PlaylistImport, String> {
+    let bytes = fs::read(path).map_err(|e| format!("无法读取列表文件: {}", e))?;
+    let content = String::from_utf8_lossy(&bytes);
+    let is_csv = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("csv"))
+        .unwrap_or(false);
+    let base = path.parent().unwrap_or(Path::new(""));
+    Ok(parse_playlist(&content, is_csv, base))
+}
+
+/// 解析列表内容：# 开头的行（含 M3U 的 #EXTINF）忽略，相对路径按列表所在目录解析。
+/// CSV 取每行第一列，首行是表头时跳过
+pub fn parse_playlist(content: &str, is_csv: bool, base: &Path) -> PlaylistImport {
+    let mut import = PlaylistImport::default();
+    for (index, line) in content.trim_start_matches('\u{feff}').lines().enumerate() {
+        let line_no = index + 1;
+        let text = if is_csv { first_csv_field(line) } else { line };
+        if is_csv && index == 0 && is_csv_header(text) {
+            continue;
+        }
+        let Some(path) = parse_path_line(text) else {
+            continue;
+        };
+        let path = if path.is_relative() {
+            base.join(path)
+        } else {
+            path
+        };
+        let is_mp4 = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("mp4"))
+            .unwrap_or(false);
+        let message = if !path.is_file() {
+            format!("文件不存在 {}", path.display())
+        } else if !is_mp4 {
+            format!("不是 MP4 文件 {}", path.display())
+        } else {
+            import.files.push(path);
+            continue;
+        };
+        import.errors.push(PlaylistError {
+            line: line_no,
+            message,
+        });
+    }
+    import
+}
+
+// CSV 第一列，支持用双引号包住含逗号的路径
+fn first_csv_field(line: &str) -> &str {
+    let line = line.trim();
+    if let Some(rest) = line.strip_prefix('"') {
+        return rest.split_once('"').map(|(field, _)| field).unwrap_or(rest);
+    }
+    line.split([',', ';', '\t']).next().unwrap_or("")
+}
+
+fn is_csv_header(field: &str) -> bool {
+    ["path", "file", "filename", "路径", "文件", "文件名"]
+        .iter()
+        .any(|header| field.trim().eq_ignore_ascii_case(header))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playlist_lines_are_mapped_with_errors() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        fs::write(dir.join("a.mp4"), b"").unwrap();
+        fs::write(dir.join("b, c.mp4"), b"").unwrap();
+        fs::write(dir.join("notes.txt"), b"").unwrap();
+
+        let m3u = "#EXTM3U\n#EXTINF:10,A\na.mp4\n\nmissing.mp4\nnotes.txt\n";
+        let import = parse_playlist(m3u, false, dir);
+        assert_eq!(import.files, vec![dir.join("a.mp4")]);
+        assert_eq!(
+            import.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![5, 6]
+        );
+
+        let csv = "path,title\n\"b, c.mp4\",第二段\na.mp4,第一段\n";
+        let import = parse_playlist(csv, true, dir);
+        assert_eq!(import.files, vec![dir.join("b, c.mp4"), dir.join("a.mp4")]);
+        assert!(import.errors.is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::ffmpeg::options::MergeOptions;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 项目文件扩展名
+pub const PROJECT_EXTENSION: &str = "m4mproj";
+
+const PROJECT_VERSION: u32 = 1;
+
+/// 保存下来的合并会话：文件顺序、合并选项（含各片段速度与剪切）与输出设置
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MergeProject {
+    pub version: u32,
+    /// 与项目文件同目录（或其子目录）的文件存为相对路径，便于连同素材一起拷到其他电脑
+    pub files: Vec<PathBuf>,
+    pub output_filename: String,
+    pub output_directory: Option<PathBuf>,
+    pub options: MergeOptions,
+}
+
+impl MergeProject {
+    pub fn new(
+        files: Vec<PathBuf>,
+        output_filename: String,
+        output_directory: Option<PathBuf>,
+        options: MergeOptions,
+    ) -> Self {
+        Self {
+            version: PROJECT_VERSION,
+            files,
+            output_filename,
+            output_directory,
+            options,
+        }
+    }
+
+    /// 写入项目文件
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let base = path.parent().unwrap_or(Path::new(""));
+        let mut project = self.clone();
+        project.files = project
+            .files
+            .iter()
+            .map(|file| relative_to(file, base))
+            .collect();
+        project.options.clip_speeds = project
+            .options
+            .clip_speeds
+            .iter()
+            .map(|(file, speed)| (relative_to(file, base), *speed))
+            .collect();
+        project.options.clip_cuts = project
+            .options
+            .clip_cuts
+            .iter()
+            .map(|(file, cuts)| (relative_to(file, base), cuts.clone()))
+            .collect();
+        let content =
+            serde_json::to_string_pretty(&project).map_err(|e| format!("无法序列化项目: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("无法写入项目文件: {}", e))
+    }
+
+    /// 读取项目文件，相对路径按项目文件所在目录还原
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("无法读取项目文件: {}", e))?;
+        let mut project: Self =
+            serde_json::from_str(&content).map_err(|e| format!("项目文件格式错误: {}", e))?;
+        if project.version > PROJECT_VERSION {
+            return Err(format!(
+                "项目文件由更新版本的程序创建（版本 {}），请先升级",
+                project.version
+            ));
+        }
+        let base = path.parent().unwrap_or(Path::new(""));
+        let absolute = |file: PathBuf| {
+            if file.is_relative() {
+                base.join(file)
+            } else {
+                file
+            }
+        };
+        project.files = project.files.into_iter().map(absolute).collect();
+        project.options.clip_speeds = std::mem::take(&mut project.options.clip_speeds)
+            .into_iter()
+            .map(|(file, speed)| (absolute(file), speed))
+            .collect();
+        project.options.clip_cuts = std::mem::take(&mut project.options.clip_cuts)
+            .into_iter()
+            .map(|(file, cuts)| (absolute(file), cuts))
+            .collect();
+        Ok(project)
+    }
+}
+
+// base 下的文件返回相对路径，其他保持原样
+fn relative_to(file: &Path, base: &Path) -> PathBuf {
+    match file.strip_prefix(base) {
+        Ok(relative) if !base.as_os_str().is_empty() => relative.to_path_buf(),
+        _ => file.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_round_trips_with_relative_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let path = dir.join(format!("session.{}", PROJECT_EXTENSION));
+        let outside = std::env::temp_dir().join("elsewhere.mp4");
+        let mut options = MergeOptions::default();
+        options.set_speed(dir.join("clips").join("a.mp4"), 2.0);
+        let project = MergeProject::new(
+            vec![dir.join("clips").join("a.mp4"), outside.clone()],
+            "out.mp4".to_string(),
+            None,
+            options,
+        );
+        project.save(&path).unwrap();
+
+        let saved: MergeProject =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.files[0], Path::new("clips").join("a.mp4"));
+        assert_eq!(saved.files[1], outside);
+        assert_eq!(
+            saved.options.speed_of(&Path::new("clips").join("a.mp4")),
+            2.0
+        );
+        assert_eq!(MergeProject::load(&path).unwrap(), project);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::MergeEvent;
+use crate::components::power_countdown::POWER_COUNTDOWN_SECS;
+use crate::config::{AppConfig, ErrorPolicy, RetryPolicy};
+use crate::ffmpeg::merge_mp4::{AfterMerge, RUNNING_MERGES, run_ffmpeg_merge};
+use crate::upload::Uploader;
+use chrono::{DateTime, Local};
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+pub use merge_mp4_core::queue::{JobStatus, MergeJob, QUEUE_PAUSED, QueueSummary};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// 全局任务队列，在 `Index` 中通过 context 提供
+pub type JobQueue = Signal<Vec<MergeJob>>;
+
+/// 获取全局任务队列
+pub fn use_job_queue() -> JobQueue {
+    use_context::<JobQueue>()
+}
+
+/// 队列的执行状态，在 `Index` 中通过 context 提供，切换标签页或最小化到托盘时不受影响
+#[derive(Clone, Copy)]
+pub struct QueueRunner {
+    pub is_running: Signal<bool>,
+    /// 上一次执行结束后的汇总
+    pub summary: Signal<Option<QueueSummary>>,
+    /// 定时开始的时间
+    pub scheduled_at: Signal<Option<DateTime<Local>>>,
+    /// 距定时开始的剩余秒数，每秒刷新
+    pub countdown: Signal<Option<i64>>,
+    /// 队列全部成功完成后执行电源操作
+    pub power_after_completion: Signal<bool>,
+    /// 电源操作倒计时的剩余秒数，None 时不显示倒计时
+    pub power_countdown: Signal<Option<u32>>,
+    queue: JobQueue,
+    config: Signal<AppConfig>,
+    uploader: Uploader,
+}
+
+impl QueueRunner {
+    /// 开始执行待处理的任务，已在执行时忽略
+    pub fn start(mut self) {
+        if *self.is_running.peek() {
+            return;
+        }
+        self.is_running.set(true);
+        self.summary.set(None);
+        self.scheduled_at.set(None);
+        self.countdown.set(None);
+        QUEUE_PAUSED.store(false, Ordering::SeqCst);
+        let policy = self.config.peek().error_policy;
+        let retry = self.config.peek().retry_policy;
+        let after = AfterMerge::from_config(&self.config.peek());
+        // 在根作用域执行，不随发起的组件卸载而中止
+        spawn_forever(async move {
+            let summary = run_pending_jobs(self.queue, policy, retry, after, self.uploader).await;
+            // 只在队列全部成功且没有其他合并（其他工作区的直接合并）仍在运行时关机/睡眠
+            let drained = summary.done > 0 && summary.failed == 0 && summary.remaining == 0;
+            if drained
+                && *self.power_after_completion.peek()
+                && RUNNING_MERGES.load(Ordering::SeqCst) == 0
+            {
+                self.power_countdown.set(Some(POWER_COUNTDOWN_SECS));
+            }
+            self.summary.set(Some(summary));
+            self.is_running.set(false);
+        });
+    }
+}
+
+/// 获取队列执行状态
+pub fn use_queue_runner() -> QueueRunner {
+    use_context::<QueueRunner>()
+}
+
+/// 提供 [`QueueRunner`] 并运行定时器：到达定时时间后自动开始队列，应在根组件调用一次
+pub fn use_queue_scheduler(
+    config: Signal<AppConfig>,
+    queue: JobQueue,
+    uploader: Uploader,
+) -> QueueRunner {
+    let runner = use_context_provider(|| QueueRunner {
+        is_running: Signal::new(false),
+        summary: Signal::new(None),
+        scheduled_at: Signal::new(None),
+        countdown: Signal::new(None),
+        power_after_completion: Signal::new(false),
+        power_countdown: Signal::new(None),
+        queue,
+        config,
+        uploader,
+    });
+
+    use_future(move || async move {
+        let mut countdown = runner.countdown;
+        loop {
+            let scheduled_at = *runner.scheduled_at.peek();
+            match scheduled_at {
+                Some(at) => {
+                    let remaining = (at - Local::now()).num_seconds();
+                    if remaining <= 0 {
+                        runner.start();
+                    } else if *countdown.peek() != Some(remaining) {
+                        countdown.set(Some(remaining));
+                    }
+                }
+                None if countdown.peek().is_some() => countdown.set(None),
+                None => {}
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+    });
+
+    runner
+}
+
+/// 依次执行队列中所有待处理的任务（暂停时提前结束）。暂时性错误按 `retry` 等待后重试，
+/// 其余失败按 `policy` 继续或停止，成功的任务按 `after` 做收尾，并在开启上传时交给 `uploader`
+pub async fn run_pending_jobs(
+    mut queue: JobQueue,
+    policy: ErrorPolicy,
+    retry: RetryPolicy,
+    after: AfterMerge,
+    uploader: Uploader,
+) -> QueueSummary {
+    let mut summary = QueueSummary::default();
+    loop {
+        if QUEUE_PAUSED.load(Ordering::SeqCst) || summary.stopped_on_error {
+            break;
+        }
+        let next = queue
+            .read()
+            .iter()
+            .find(|j| j.status == JobStatus::Pending)
+            .cloned();
+        let Some(job) = next else {
+            break;
+        };
+        update_job(queue, job.id, |j| j.status = JobStatus::Running);
+
+        // 每个任务使用独立的事件通道，避免进度串到下一个任务
+        let (tx, mut rx) = futures::channel::mpsc::unbounded::<MergeEvent>();
+        let mut transient = false;
+        let merge = run_ffmpeg_merge(
+            job.files.clone(),
+            job.output_path.clone(),
+            job.options.clone(),
+            after.clone(),
+            tx,
+        );
+        let updates = async {
+            while let Some(event) = rx.next().await {
+                if let MergeEvent::Error(e) = &event {
+                    transient = e.is_transient();
+                }
+                update_job(queue, job.id, |j| match event {
+                    MergeEvent::Progress(p) => j.progress = p.overall(),
+                    MergeEvent::Status(s) => j.status_message = s,
+                    MergeEvent::Segments(_) => {}
+                    MergeEvent::Probing { index, total, name } => {
+                        j.status_message = format!("读取时长 {}/{}：{}", index, total, name)
+                    }
+                    MergeEvent::Error(e) => j.status = JobStatus::Failed(e.to_string()),
+                    MergeEvent::Success(msg) => {
+                        j.progress = 100.0;
+                        j.status = JobStatus::Done;
+                        j.status_message = msg;
+                    }
+                });
+            }
+        };
+        let (merged, _) = futures::join!(merge, updates);
+        if let Some(path) = merged {
+            uploader.start_if_enabled(path);
+        }
+
+        if transient && job.retries < retry.max_retries {
+            let attempt = job.retries + 1;
+            let delay = retry.delay(attempt);
+            update_job(queue, job.id, |j| {
+                if let JobStatus::Failed(e) = &j.status {
+                    j.status_message =
+                        format!("{}，{} 秒后第 {} 次重试", e, delay.as_secs(), attempt);
+                }
+                j.status = JobStatus::Pending;
+                j.progress = 0.0;
+                j.retries = attempt;
+            });
+            sleep(delay).await;
+            continue;
+        }
+
+        // 合并函数异常结束而未发送结果时，按失败处理
+        let mut queue_guard = queue.write();
+        if let Some(j) = queue_guard.iter_mut().find(|j| j.id == job.id) {
+            if j.status == JobStatus::Running {
+                j.status = JobStatus::Failed("任务意外中止".to_string());
+            }
+            match j.status {
+                JobStatus::Done => summary.done += 1,
+                JobStatus::Failed(_) => {
+                    summary.failed += 1;
+                    summary.stopped_on_error = policy == ErrorPolicy::Stop;
+                }
+                _ => {}
+            }
+        }
+    }
+    summary.remaining = queue
+        .peek()
+        .iter()
+        .filter(|j| j.status == JobStatus::Pending)
+        .count();
+    summary
+}
+
+fn update_job(mut queue: JobQueue, id: u64, f: impl FnOnce(&mut MergeJob)) {
+    if let Some(job) = queue.write().iter_mut().find(|j| j.id == id) {
+        f(job);
+    }
+}
@@ -0,0 +1,38 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+/// 文件在文件库中被重命名，`seq` 用于区分连续的多次重命名
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenameEvent {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub seq: u64,
+}
+
+pub type RenameEvents = Signal<Option<RenameEvent>>;
+
+/// 提供 [`RenameEvents`] 上下文，应在根组件调用一次
+pub fn use_rename_provider() -> RenameEvents {
+    use_context_provider(|| Signal::new(None))
+}
+
+/// 通知其他页面文件已改名
+pub fn notify_renamed(from: PathBuf, to: PathBuf) {
+    let mut events = consume_context::<RenameEvents>();
+    let seq = events.peek().as_ref().map_or(1, |e| e.seq + 1);
+    events.set(Some(RenameEvent { from, to, seq }));
+}
+
+/// 在组件中响应文件改名，挂载前发生的改名不再处理
+pub fn use_renamed(mut handler: impl FnMut(&RenameEvent) + 'static) {
+    let events = use_context::<RenameEvents>();
+    let mut handled = use_signal(|| events.peek().as_ref().map(|e| e.seq));
+    use_effect(move || {
+        if let Some(event) = events()
+            && Some(event.seq) != *handled.peek()
+        {
+            handled.set(Some(event.seq));
+            handler(&event);
+        }
+    });
+}
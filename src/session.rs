@@ -0,0 +1,182 @@
+//! 会话自动保存：定时保存各工作区的文件列表、选项与未完成的队列任务，
+//! 正常退出时删除，下次启动仍存在说明上次意外退出，可提示恢复
+use crate::exit_guard::jobs_running;
+use crate::ffmpeg::options::MergeOptions;
+use crate::project::MergeProject;
+use crate::queue::{JobQueue, JobStatus, MergeJob};
+use crate::utils::app_data_dir;
+use dioxus::prelude::*;
+use dioxus_desktop::tao::event::{Event, WindowEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 队列中尚未完成的任务
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QueuedJob {
+    pub name: String,
+    pub files: Vec<PathBuf>,
+    pub output_path: PathBuf,
+    pub options: MergeOptions,
+}
+
+/// 自动保存的会话内容
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct SessionSnapshot {
+    pub workspaces: Vec<MergeProject>,
+    pub queue: Vec<QueuedJob>,
+}
+
+impl SessionSnapshot {
+    pub fn is_empty(&self) -> bool {
+        self.workspaces.is_empty() && self.queue.is_empty()
+    }
+
+    /// 各工作区的文件总数
+    pub fn file_count(&self) -> usize {
+        self.workspaces.iter().map(|w| w.files.len()).sum()
+    }
+}
+
+/// 会话状态，在 `Index` 中通过 context 提供
+#[derive(Clone, Copy)]
+pub struct SessionState {
+    /// 各工作区当前的内容，按工作区编号
+    pub drafts: Signal<BTreeMap<u32, MergeProject>>,
+    /// 等待对应工作区取走并套用的恢复内容
+    pub restore: Signal<BTreeMap<u32, MergeProject>>,
+    /// 上次意外退出时留下的会话，用户选择恢复或放弃前不覆盖
+    pub recovered: Signal<Option<SessionSnapshot>>,
+}
+
+impl SessionState {
+    /// 恢复上次的会话：工作区内容交给各工作区，未完成的任务重新加入队列
+    pub fn restore(mut self, mut queue: JobQueue) {
+        let Some(snapshot) = self.recovered.take() else {
+            return;
+        };
+        self.restore.set(
+            (1..)
+                .zip(snapshot.workspaces)
+                .collect::<BTreeMap<u32, MergeProject>>(),
+        );
+        queue.write().extend(
+            snapshot
+                .queue
+                .into_iter()
+                .map(|job| MergeJob::new(job.name, job.files, job.output_path, job.options)),
+        );
+    }
+
+    /// 放弃上次的会话
+    pub fn discard(mut self) {
+        self.recovered.set(None);
+        clear_session();
+    }
+}
+
+/// 获取会话状态
+pub fn use_session() -> SessionState {
+    use_context::<SessionState>()
+}
+
+/// 提供 [`SessionState`] 并定时保存会话，应在根组件调用一次
+pub fn use_session_autosave(queue: JobQueue) -> SessionState {
+    let state = use_context_provider(|| SessionState {
+        drafts: Signal::new(BTreeMap::new()),
+        restore: Signal::new(BTreeMap::new()),
+        recovered: Signal::new(load_session().filter(|s| !s.is_empty())),
+    });
+
+    use_future(move || async move {
+        let mut last = None;
+        loop {
+            sleep(AUTOSAVE_INTERVAL).await;
+            if state.recovered.peek().is_some() {
+                continue;
+            }
+            let snapshot = SessionSnapshot {
+                workspaces: state
+                    .drafts
+                    .peek()
+                    .values()
+                    .filter(|w| !w.files.is_empty())
+                    .cloned()
+                    .collect(),
+                queue: queue
+                    .peek()
+                    .iter()
+                    .filter(|j| matches!(j.status, JobStatus::Pending | JobStatus::Running))
+                    .map(|j| QueuedJob {
+                        name: j.name.clone(),
+                        files: j.files.clone(),
+                        output_path: j.output_path.clone(),
+                        options: j.options.clone(),
+                    })
+                    .collect(),
+            };
+            if last.as_ref() == Some(&snapshot) {
+                continue;
+            }
+            let result = if snapshot.is_empty() {
+                clear_session();
+                Ok(())
+            } else {
+                save_session(&snapshot)
+            };
+            match result {
+                Ok(()) => last = Some(snapshot),
+                Err(e) => eprintln!("自动保存会话失败: {}", e),
+            }
+        }
+    });
+
+    // 关闭窗口会真正退出时（没有任务在执行）视为正常退出
+    dioxus_desktop::use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } = event
+            && !jobs_running(queue)
+        {
+            clear_session();
+        }
+    });
+
+    state
+}
+
+/// 正常退出时删除自动保存的会话
+pub fn clear_session() {
+    if let Ok(path) = session_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn load_session() -> Option<SessionSnapshot> {
+    let content = fs::read_to_string(session_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_session(snapshot: &SessionSnapshot) -> Result<(), io::Error> {
+    let path = session_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    // 先写临时文件再替换，避免写到一半时崩溃留下损坏的会话
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)
+}
+
+fn session_path() -> Result<PathBuf, io::Error> {
+    Ok(app_data_dir()?.join("session.json"))
+}
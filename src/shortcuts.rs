@@ -0,0 +1,113 @@
+use crate::config::AppConfig;
+use dioxus::html::Key;
+use dioxus::prelude::*;
+pub use merge_mp4_core::shortcuts::ShortcutAction;
+
+/// 一次快捷键触发，`seq` 用于区分连续触发的同一操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShortcutEvent {
+    pub action: ShortcutAction,
+    pub seq: u64,
+}
+
+pub type ShortcutEvents = Signal<Option<ShortcutEvent>>;
+
+/// 把按键格式化为 "Ctrl+Shift+K" 形式，只按下修饰键时返回 None。
+/// 需要与 [`LISTENER_JS`] 中的格式保持一致。
+pub fn format_key_event(evt: &KeyboardData) -> Option<String> {
+    let key = match evt.key() {
+        Key::Control | Key::Shift | Key::Alt | Key::Meta | Key::Unidentified => return None,
+        Key::Character(s) if s == " " => "Space".to_string(),
+        Key::Character(s) => s.to_uppercase(),
+        key => key.to_string(),
+    };
+    let modifiers = evt.modifiers();
+    let mut combo = String::new();
+    if modifiers.ctrl() || modifiers.meta() {
+        combo.push_str("Ctrl+");
+    }
+    if modifiers.alt() {
+        combo.push_str("Alt+");
+    }
+    if modifiers.shift() {
+        combo.push_str("Shift+");
+    }
+    combo.push_str(&key);
+    Some(combo)
+}
+
+// 在 document 上监听按键：只拦截已绑定的组合键，输入框内的无修饰键按键留给输入框本身
+const LISTENER_JS: &str = r#"
+let bound = new Set();
+(async () => {
+    while (true) {
+        bound = new Set(await dioxus.recv());
+    }
+})();
+document.addEventListener('keydown', (e) => {
+    if (['Control', 'Shift', 'Alt', 'Meta'].includes(e.key)) return;
+    const t = e.target;
+    if (t && t.closest && t.closest('[data-shortcut-capture]')) return;
+    let key = e.key.length === 1 ? e.key.toUpperCase() : e.key;
+    if (key === ' ') key = 'Space';
+    const plain = !(e.ctrlKey || e.metaKey || e.altKey) && !/^F\d+$/.test(key);
+    const editable = t && (t.isContentEditable || ['INPUT', 'TEXTAREA', 'SELECT'].includes(t.tagName));
+    if (plain && editable) return;
+    const combo = (e.ctrlKey || e.metaKey ? 'Ctrl+' : '') + (e.altKey ? 'Alt+' : '') + (e.shiftKey ? 'Shift+' : '') + key;
+    if (!bound.has(combo)) return;
+    e.preventDefault();
+    dioxus.send(combo);
+});
+"#;
+
+/// 安装全局按键监听并提供 [`ShortcutEvents`] 上下文，应在根组件调用一次
+pub fn use_shortcut_listener(config: Signal<AppConfig>) -> ShortcutEvents {
+    let mut events: ShortcutEvents = use_context_provider(|| Signal::new(None));
+    let listener = use_hook(|| document::eval(LISTENER_JS));
+
+    // 绑定变化时同步给页面脚本
+    use_effect(move || {
+        let bindings: Vec<String> = ShortcutAction::ALL
+            .iter()
+            .map(|action| config.read().shortcut(*action))
+            .collect();
+        let _ = listener.send(bindings);
+    });
+
+    use_future(move || async move {
+        let mut listener = listener;
+        let mut seq = 0;
+        while let Ok(combo) = listener.recv::<String>().await {
+            let action = ShortcutAction::ALL
+                .into_iter()
+                .find(|action| config.peek().shortcut(*action) == combo);
+            if let Some(action) = action {
+                seq += 1;
+                events.set(Some(ShortcutEvent { action, seq }));
+            }
+        }
+    });
+
+    events
+}
+
+/// 在组件中响应某个快捷键操作
+pub fn use_shortcut(action: ShortcutAction, mut handler: impl FnMut() + 'static) {
+    let events = use_context::<ShortcutEvents>();
+    // 挂载前已发生的触发不再处理
+    let mut handled = use_signal(|| events.peek().map(|e| e.seq));
+    use_effect(move || {
+        if let Some(event) = events()
+            && event.action == action
+            && Some(event.seq) != *handled.peek()
+        {
+            handled.set(Some(event.seq));
+            handler();
+        }
+    });
+}
+
+/// 按钮提示文字，附带当前绑定的快捷键
+pub fn shortcut_hint(config: &AppConfig, action: ShortcutAction) -> String {
+    format!("{} ({})", action.label(), config.shortcut(action))
+}
@@ -0,0 +1,117 @@
+use crate::exit_guard::{exit_app, jobs_running};
+use crate::ffmpeg::merge_mp4::RUNNING_MERGES;
+use crate::queue::{JobQueue, JobStatus, QUEUE_PAUSED};
+use dioxus::prelude::*;
+use dioxus_desktop::WindowCloseBehaviour;
+use std::rc::Rc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::time::sleep;
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::{Icon, TrayIconBuilder};
+
+const ICON_SIZE: u32 = 32;
+
+/// 创建托盘图标：提示文字显示队列状态，菜单提供暂停全部、打开窗口和退出。
+/// 有任务在执行时关闭窗口只隐藏不退出，见 [`use_exit_guard`](crate::exit_guard::use_exit_guard)。
+pub fn use_tray(queue: JobQueue) {
+    let tray = use_hook(|| {
+        let open_item = MenuItem::new("打开窗口", true, None);
+        let pause_item = MenuItem::new("暂停全部", true, None);
+        let quit_item = MenuItem::new("退出", true, None);
+        let menu = Menu::new();
+        let _ = menu.append_items(&[
+            &open_item,
+            &pause_item,
+            &PredefinedMenuItem::separator(),
+            &quit_item,
+        ]);
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip("mp4文件合并")
+            .with_icon(tray_icon_image())
+            .build()
+            .map_err(|e| eprintln!("创建托盘图标失败: {}", e))
+            .ok();
+        Rc::new((icon, open_item, pause_item, quit_item))
+    });
+
+    let handler_tray = tray.clone();
+    dioxus_desktop::use_tray_menu_event_handler(move |event: &MenuEvent| {
+        let (_, open_item, pause_item, quit_item) = &*handler_tray;
+        let desktop = dioxus_desktop::window();
+        if event.id() == open_item.id() {
+            desktop.window.set_visible(true);
+            desktop.window.set_minimized(false);
+            desktop.window.set_focus();
+        } else if event.id() == pause_item.id() {
+            QUEUE_PAUSED.store(true, Ordering::SeqCst);
+        } else if event.id() == quit_item.id() {
+            exit_app();
+        }
+    });
+
+    // 任务状态不全是信号（直接合并使用原子计数），这里定时刷新
+    use_future(move || {
+        let tray = tray.clone();
+        async move {
+            let mut hides = None;
+            let mut last_tooltip = String::new();
+            loop {
+                let running = RUNNING_MERGES.load(Ordering::SeqCst);
+                let pending = queue
+                    .peek()
+                    .iter()
+                    .filter(|j| j.status == JobStatus::Pending)
+                    .count();
+
+                let tooltip = if running == 0 && pending == 0 {
+                    "mp4文件合并 - 空闲".to_string()
+                } else if QUEUE_PAUSED.load(Ordering::SeqCst) {
+                    format!(
+                        "mp4文件合并 - 执行中 {}，队列已暂停（等待 {}）",
+                        running, pending
+                    )
+                } else {
+                    format!("mp4文件合并 - 执行中 {}，等待 {}", running, pending)
+                };
+                if tooltip != last_tooltip
+                    && let Some(icon) = &tray.0
+                {
+                    let _ = icon.set_tooltip(Some(&tooltip));
+                    last_tooltip = tooltip;
+                }
+
+                let hide = jobs_running(queue);
+                if hides != Some(hide) {
+                    dioxus_desktop::window().set_close_behavior(if hide {
+                        WindowCloseBehaviour::LastWindowHides
+                    } else {
+                        WindowCloseBehaviour::LastWindowExitsApp
+                    });
+                    hides = Some(hide);
+                }
+
+                sleep(Duration::from_secs(1)).await;
+            }
+        }
+    });
+}
+
+// 程序生成的托盘图标：蓝底白色播放三角
+fn tray_icon_image() -> Icon {
+    let size = ICON_SIZE as i32;
+    let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            // 三角形顶点 (10,8) (10,24) (24,16)
+            let in_triangle = (10..=24).contains(&x) && (y - 16).abs() * 14 <= (24 - x) * 8;
+            if in_triangle {
+                rgba.extend_from_slice(&[255, 255, 255, 255]);
+            } else {
+                rgba.extend_from_slice(&[37, 99, 235, 255]);
+            }
+        }
+    }
+    Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).expect("托盘图标尺寸错误")
+}
@@ -0,0 +1,191 @@
+use crate::clipboard::copy_text;
+use crate::config::AppConfig;
+use crate::notifications::{Notification, Notifications, notify};
+use crate::utils::display_name;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use merge_mp4_core::upload::{UploadEvent, upload_file};
+use std::path::PathBuf;
+
+/// 上传任务的状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadStatus {
+    Running,
+    /// 等待自动重试，内容为上一次失败的原因
+    Retrying(String),
+    /// 已完成，内容为访问链接
+    Done(String),
+    Failed(String),
+}
+
+/// 一个合并输出的上传
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadTask {
+    pub id: u64,
+    pub path: PathBuf,
+    pub sent: u64,
+    pub total: u64,
+    pub status: UploadStatus,
+}
+
+impl UploadTask {
+    /// 上传进度（%）
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.sent as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// 合并成功后的上传，在 `Index` 中通过 context 提供，切换标签页或关闭合并页时不受影响
+#[derive(Clone, Copy)]
+pub struct Uploader {
+    pub tasks: Signal<Vec<UploadTask>>,
+    next_id: Signal<u64>,
+    config: Signal<AppConfig>,
+    notifications: Notifications,
+}
+
+impl Uploader {
+    /// 设置中开启了上传时上传 `path`
+    pub fn start_if_enabled(self, path: PathBuf) {
+        if self.config.peek().upload.enabled {
+            self.start(path);
+        }
+    }
+
+    /// 添加上传任务并立即开始
+    pub fn start(mut self, path: PathBuf) {
+        let id = *self.next_id.peek();
+        self.next_id.set(id + 1);
+        self.tasks.write().push(UploadTask {
+            id,
+            path: path.clone(),
+            sent: 0,
+            total: 0,
+            status: UploadStatus::Running,
+        });
+        self.run(id, path);
+    }
+
+    /// 重新上传失败的任务
+    pub fn retry(self, id: u64) {
+        let path = self
+            .tasks
+            .peek()
+            .iter()
+            .find(|t| t.id == id && matches!(t.status, UploadStatus::Failed(_)))
+            .map(|t| t.path.clone());
+        if let Some(path) = path {
+            self.update(id, |t| {
+                t.sent = 0;
+                t.status = UploadStatus::Running;
+            });
+            self.run(id, path);
+        }
+    }
+
+    /// 移除已结束的任务
+    pub fn dismiss(mut self, id: u64) {
+        self.tasks.write().retain(|t| {
+            t.id != id || matches!(t.status, UploadStatus::Running | UploadStatus::Retrying(_))
+        });
+    }
+
+    fn run(self, id: u64, path: PathBuf) {
+        let settings = self.config.peek().upload.clone();
+        let retry = self.config.peek().retry_policy;
+        // 在根作用域执行，不随发起的组件卸载而中止
+        spawn_forever(async move {
+            // 上传在后台线程报告进度，经通道转到界面线程
+            let (tx, mut rx) = futures::channel::mpsc::unbounded::<UploadEvent>();
+            let upload = upload_file(&settings, &retry, &path, move |event| {
+                let _ = tx.unbounded_send(event);
+            });
+            futures::pin_mut!(upload);
+            // 只在进度变化超过 0.1% 时刷新界面
+            let mut shown = None;
+            let result = loop {
+                tokio::select! {
+                    result = &mut upload => break result,
+                    Some(event) = rx.next() => match event {
+                        UploadEvent::Progress { sent, total } => {
+                            let permille = (sent * 1000).checked_div(total);
+                            if shown != Some(permille) {
+                                shown = Some(permille);
+                                self.update(id, |t| {
+                                    t.sent = sent;
+                                    t.total = total;
+                                    t.status = UploadStatus::Running;
+                                });
+                            }
+                        }
+                        UploadEvent::Retrying { attempt, delay_secs, error } => {
+                            shown = None;
+                            self.update(id, |t| {
+                                t.status = UploadStatus::Retrying(format!(
+                                    "{}，{} 秒后第 {} 次重试",
+                                    error, delay_secs, attempt
+                                ))
+                            });
+                        }
+                    },
+                }
+            };
+
+            let name = display_name(&path);
+            match result {
+                Ok(link) => {
+                    self.update(id, |t| {
+                        t.sent = t.total;
+                        t.status = UploadStatus::Done(link.clone());
+                    });
+                    let mut message = link.clone();
+                    if settings.copy_link {
+                        match copy_text(&link) {
+                            Ok(()) => message.push_str("（链接已复制）"),
+                            Err(e) => message.push_str(&format!("（{}）", e)),
+                        }
+                    }
+                    notify(
+                        self.notifications,
+                        Notification::success(format!("{} 上传完成", name), message),
+                    );
+                }
+                Err(e) => {
+                    self.update(id, |t| t.status = UploadStatus::Failed(e.to_string()));
+                    notify(
+                        self.notifications,
+                        Notification::error(
+                            format!("{} 上传失败", name),
+                            format!("{}，可在队列页重试", e),
+                        ),
+                    );
+                }
+            }
+        });
+    }
+
+    fn update(mut self, id: u64, f: impl FnOnce(&mut UploadTask)) {
+        if let Some(task) = self.tasks.write().iter_mut().find(|t| t.id == id) {
+            f(task);
+        }
+    }
+}
+
+/// 提供 [`Uploader`]，应在根组件调用一次
+pub fn use_uploader_provider(config: Signal<AppConfig>, notifications: Notifications) -> Uploader {
+    use_context_provider(|| Uploader {
+        tasks: Signal::new(Vec::new()),
+        next_id: Signal::new(1),
+        config,
+        notifications,
+    })
+}
+
+/// 获取上传任务
+pub fn use_uploader() -> Uploader {
+    use_context::<Uploader>()
+}
@@ -0,0 +1,41 @@
+//! 文件被占用时的提示对话框，检测逻辑见 `merge_mp4_core::utils::find_locked_files`
+use crate::components::confirm_dialog::{ConfirmAnswer, ConfirmRequests, Confirmation, ask};
+use merge_mp4_core::utils::LockedFile;
+
+/// 遇到被占用的文件时用户的选择
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockedChoice {
+    Retry,
+    Skip,
+    Cancel,
+}
+
+/// 提示哪些文件被占用，询问重试、跳过还是取消。`allow_skip` 为 false 时只提供重试与取消
+pub async fn prompt_locked_files(
+    requests: ConfirmRequests,
+    locked: &[LockedFile],
+    action: &str,
+    allow_skip: bool,
+) -> LockedChoice {
+    let list = locked
+        .iter()
+        .map(LockedFile::describe)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut confirmation = Confirmation::new(
+        "文件正在使用中",
+        format!(
+            "以下文件正被其他程序使用，无法{}：\n{}\n\n请关闭相关程序后重试。",
+            action, list
+        ),
+    )
+    .action("重试");
+    if allow_skip {
+        confirmation = confirmation.alternative("跳过这些文件");
+    }
+    match ask(requests, confirmation).await {
+        ConfirmAnswer::Confirmed => LockedChoice::Retry,
+        ConfirmAnswer::Alternative => LockedChoice::Skip,
+        ConfirmAnswer::Cancelled => LockedChoice::Cancel,
+    }
+}
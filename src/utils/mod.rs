@@ -1,6 +1,9 @@
-mod duration;
-mod format_size;
-mod mp4;
-pub use duration::{format_date, format_duration, parse_duration_to_seconds};
-pub use format_size::format_size;
-pub use mp4::parse_mp4_info;
+mod file_lock;
+mod permission;
+mod resource_usage;
+mod taskbar;
+pub use file_lock::{LockedChoice, prompt_locked_files};
+pub use merge_mp4_core::utils::*;
+pub use permission::{PermissionChoice, prompt_permission_denied};
+pub use resource_usage::{ResourceSampler, ResourceUsage};
+pub use taskbar::{clear_taskbar_progress, set_taskbar_error, set_taskbar_progress};
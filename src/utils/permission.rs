@@ -0,0 +1,37 @@
+//! 没有权限操作文件时的提示对话框，提权操作见 `merge_mp4_core::utils::delete_files_elevated`
+use crate::components::confirm_dialog::{ConfirmAnswer, ConfirmRequests, Confirmation, ask};
+use merge_mp4_core::utils::display_name;
+use std::path::PathBuf;
+
+/// 遇到没有权限的文件时用户的选择
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermissionChoice {
+    RetryElevated,
+    Skip,
+}
+
+/// 提示哪些文件因权限不足无法处理，询问以管理员身份重试还是跳过，取消也视为跳过
+pub async fn prompt_permission_denied(
+    requests: ConfirmRequests,
+    files: &[PathBuf],
+    action: &str,
+) -> PermissionChoice {
+    let list = files
+        .iter()
+        .map(|f| display_name(f))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let confirmation = Confirmation::new(
+        "没有权限",
+        format!(
+            "没有权限{}以下文件：\n{}\n\n文件可能是只读的，或位于受保护的目录中。",
+            action, list
+        ),
+    )
+    .action("以管理员身份重试")
+    .alternative("跳过这些文件");
+    match ask(requests, confirmation).await {
+        ConfirmAnswer::Confirmed => PermissionChoice::RetryElevated,
+        ConfirmAnswer::Alternative | ConfirmAnswer::Cancelled => PermissionChoice::Skip,
+    }
+}
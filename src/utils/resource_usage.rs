@@ -0,0 +1,77 @@
+//! 编码期间的 CPU/GPU 占用采样
+use sysinfo::{ProcessesToUpdate, System};
+
+/// 一次采样的结果，均为百分比
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ResourceUsage {
+    /// 整机 CPU 占用
+    pub cpu: f32,
+    /// 所有 FFmpeg 进程合计的 CPU 占用，按核数折算到 0~100
+    pub ffmpeg_cpu: f32,
+    /// 显卡占用，目前只能通过 nvidia-smi 读取 NVIDIA 显卡
+    pub gpu: Option<f32>,
+}
+
+/// 持续采样 CPU 占用，两次采样的间隔即统计区间
+pub struct ResourceSampler {
+    system: System,
+}
+
+impl ResourceSampler {
+    pub fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu_usage();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        Self { system }
+    }
+
+    /// 统计自上次采样以来的占用
+    pub async fn sample(&mut self) -> ResourceUsage {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_processes(ProcessesToUpdate::All, true);
+        let cores = self.system.cpus().len().max(1) as f32;
+        let ffmpeg_cpu: f32 = self
+            .system
+            .processes()
+            .values()
+            .filter(|p| {
+                let name = p.name().to_string_lossy();
+                name == "ffmpeg" || name == "ffmpeg.exe"
+            })
+            .map(|p| p.cpu_usage())
+            .sum();
+        ResourceUsage {
+            cpu: self.system.global_cpu_usage(),
+            ffmpeg_cpu: (ffmpeg_cpu / cores).min(100.0),
+            gpu: nvidia_gpu_usage().await,
+        }
+    }
+}
+
+impl Default for ResourceSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 没有 NVIDIA 显卡或未安装驱动时 nvidia-smi 不存在，返回 None
+async fn nvidia_gpu_usage() -> Option<f32> {
+    let output = tokio::process::Command::new("nvidia-smi")
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .args([
+            "--query-gpu=utilization.gpu",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
@@ -0,0 +1,28 @@
+use dioxus_desktop::tao::window::{ProgressBarState, ProgressState};
+
+/// 在任务栏按钮（Windows）或 Dock 图标（macOS）上显示进度，取值 0~100
+pub fn set_taskbar_progress(progress: f64) {
+    update_taskbar(
+        ProgressState::Normal,
+        Some(progress.clamp(0.0, 100.0) as u64),
+    );
+}
+
+/// 将任务栏进度标记为出错状态（红色）
+pub fn set_taskbar_error() {
+    update_taskbar(ProgressState::Error, Some(100));
+}
+
+/// 清除任务栏上的进度显示
+pub fn clear_taskbar_progress() {
+    update_taskbar(ProgressState::None, None);
+}
+
+fn update_taskbar(state: ProgressState, progress: Option<u64>) {
+    let desktop = dioxus_desktop::window();
+    desktop.window.set_progress_bar(ProgressBarState {
+        state: Some(state),
+        progress,
+        desktop_filename: None,
+    });
+}
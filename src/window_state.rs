@@ -0,0 +1,49 @@
+use crate::config::AppConfig;
+use dioxus::prelude::*;
+use dioxus_desktop::LogicalSize;
+use dioxus_desktop::tao::event::{Event, WindowEvent};
+
+/// 把设置中的窗口行为应用到主窗口，并在关闭时记下窗口大小。应在根组件调用一次
+pub fn use_window_state(config: Signal<AppConfig>) {
+    // 设置页修改置顶与最小尺寸后立即生效，启动时的值已由 `WindowBuilder` 应用
+    use_effect(move || {
+        let settings = config.read().window;
+        let window = &dioxus_desktop::window().window;
+        window.set_always_on_top(settings.always_on_top);
+        window.set_min_inner_size(Some(LogicalSize::new(
+            settings.min_width,
+            settings.min_height,
+        )));
+    });
+
+    dioxus_desktop::use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } = event
+        {
+            save_window_size(config);
+        }
+    });
+}
+
+// 最大化或最小化时的大小不是用户调整的结果，不记录
+fn save_window_size(mut config: Signal<AppConfig>) {
+    if !config.peek().window.remember_size {
+        return;
+    }
+    let window = &dioxus_desktop::window().window;
+    let size = window.inner_size();
+    if window.is_maximized() || size.width == 0 || size.height == 0 {
+        return;
+    }
+    let size = size.to_logical::<f64>(window.scale_factor());
+    let last_size = Some((size.width.round(), size.height.round()));
+    if config.peek().window.last_size == last_size {
+        return;
+    }
+    config.write().window.last_size = last_size;
+    if let Err(e) = config.read().save() {
+        eprintln!("保存窗口大小失败: {}", e);
+    }
+}